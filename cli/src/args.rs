@@ -0,0 +1,77 @@
+//! Minimal command-line argument parsing.
+
+use crate::Failure;
+
+/// The remaining arguments of a subcommand.
+#[derive(Debug)]
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    /// Wrap the given arguments.
+    pub fn new<I: IntoIterator<Item = String>>(args: I) -> Self {
+        Self {
+            args: args.into_iter().collect(),
+        }
+    }
+
+    /// Remove a flag from the arguments, returning whether it was given.
+    pub fn flag(&mut self, names: &[&str]) -> bool {
+        let len = self.args.len();
+        self.args.retain(|arg| !names.contains(&arg.as_str()));
+        self.args.len() != len
+    }
+
+    /// Remove an option and its value from the arguments.
+    pub fn value(&mut self, names: &[&str]) -> Result<Option<String>, Failure> {
+        let index = match self.args.iter().position(|a| names.contains(&a.as_str())) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        if index + 1 >= self.args.len() {
+            return Err(Failure::Usage(format!("missing value for `{}`", names[0])));
+        }
+        let value = self.args.remove(index + 1);
+        self.args.remove(index);
+        Ok(Some(value))
+    }
+
+    /// Get the remaining positional arguments, rejecting unknown options.
+    pub fn positional(self) -> Result<Vec<String>, Failure> {
+        if let Some(arg) = self.args.iter().find(|a| a.starts_with('-') && *a != "-") {
+            return Err(Failure::Usage(format!("unknown option `{}`", arg)));
+        }
+        if self.args.is_empty() {
+            return Err(Failure::Usage("no input file given".to_string()));
+        }
+        Ok(self.args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parse() {
+        let mut a = args(&["--check", "a.obo", "--to", "json", "b.obo"]);
+        assert!(a.flag(&["--check"]));
+        assert!(!a.flag(&["-i"]));
+        assert_eq!(a.value(&["--to"]).unwrap(), Some("json".to_string()));
+        assert_eq!(a.positional().unwrap(), vec!["a.obo", "b.obo"]);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(args(&["--to"]).value(&["--to"]).is_err());
+        assert!(args(&["--nope", "a.obo"]).positional().is_err());
+        assert!(args(&[]).positional().is_err());
+    }
+}
@@ -0,0 +1,51 @@
+//! The `check` subcommand.
+
+use fastobo::diagnostic::Severity;
+
+use crate::args::Args;
+use crate::Failure;
+use crate::EXIT_CHECK;
+use crate::EXIT_OK;
+
+pub const USAGE: &str = "\
+Usage: obo check [OPTIONS] <FILE>...
+
+Check OBO documents for syntax and semantic errors.
+
+Options:
+    --deny-warnings   exit with an error code if any warning is reported
+    -q, --quiet       only print the summary for each file
+";
+
+pub fn run(mut args: Args) -> Result<i32, Failure> {
+    let deny_warnings = args.flag(&["--deny-warnings"]);
+    let quiet = args.flag(&["-q", "--quiet"]);
+    let files = args.positional()?;
+
+    let mut code = EXIT_OK;
+    for path in &files {
+        let text = crate::read(path)?;
+        let (_, diagnostics) = fastobo_lint::lint(&text);
+        if !quiet {
+            for diagnostic in &diagnostics {
+                eprintln!(
+                    "{}",
+                    diagnostic.clone().with_path(path.as_str()).render(&text)
+                );
+            }
+        }
+
+        let errors = diagnostics
+            .iter()
+            .filter(|d| d.severity() == Severity::Error)
+            .count();
+        let warnings = diagnostics.len() - errors;
+        if errors + warnings > 0 {
+            eprintln!("{}: {} error(s), {} warning(s)", path, errors, warnings);
+        }
+        if errors > 0 || (deny_warnings && warnings > 0) {
+            code = EXIT_CHECK;
+        }
+    }
+    Ok(code)
+}
@@ -0,0 +1,77 @@
+//! The `convert` subcommand.
+
+use std::io::Write;
+
+use fastobo::owl::rdf::RdfFormat;
+
+use crate::args::Args;
+use crate::Failure;
+use crate::EXIT_OK;
+
+pub const USAGE: &str = "\
+Usage: obo convert --to <FORMAT> [OPTIONS] <FILE>
+
+Convert an OBO document to another format.
+
+Options:
+    --to <FORMAT>       the output format, either `json` or `ttl`
+    -o, --output <OUT>  write the document to a file instead of printing it
+
+The `json` format produces an OBO Graphs document: frames become nodes with
+their metadata, `is_a`, `instance_of`, `inverse_of` and `relationship`
+clauses become edges, and `intersection_of`, `equivalent_to`, `domain`,
+`range`, `holds_over_chain` and `equivalent_to_chain` clauses become graph
+axioms. Other clauses (such as `union_of`, `disjoint_from` or relation
+properties) are skipped with a warning.
+";
+
+/// A format an OBO document can be converted to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    /// The OBO Graphs JSON format.
+    Json,
+    /// OWL2 serialized as RDF Turtle.
+    Turtle,
+}
+
+pub fn run(mut args: Args) -> Result<i32, Failure> {
+    let format = match args.value(&["--to"])?.as_deref() {
+        Some("json") => Format::Json,
+        Some("ttl") | Some("turtle") => Format::Turtle,
+        Some(other) => return Err(Failure::Usage(format!("unknown format `{}`", other))),
+        None => return Err(Failure::Usage("missing `--to` option".to_string())),
+    };
+    let output = args.value(&["-o", "--output"])?;
+    let files = args.positional()?;
+    if files.len() > 1 {
+        return Err(Failure::Usage("expected a single input file".to_string()));
+    }
+
+    let doc = crate::parse(&files[0])?;
+    let mut buffer = Vec::new();
+    match format {
+        Format::Json => {
+            let conversion = crate::json::to_json(&doc);
+            if !conversion.skipped.is_empty() {
+                let tags: Vec<String> = conversion.skipped.into_iter().collect();
+                eprintln!("warning: skipped unsupported clauses: {}", tags.join(", "));
+            }
+            serde_json::to_writer_pretty(&mut buffer, &conversion.json).map_err(|e| {
+                Failure::Error(format!("could not convert `{}` to JSON: {}", files[0], e))
+            })?;
+            buffer.push(b'\n');
+        }
+        Format::Turtle => fastobo::owl::rdf::to_writer(&mut buffer, &doc, RdfFormat::Turtle)
+            .map_err(|e| {
+                Failure::Error(format!("could not convert `{}` to Turtle: {}", files[0], e))
+            })?,
+    }
+
+    match output {
+        Some(path) => crate::write(&path, &buffer)?,
+        None => std::io::stdout()
+            .write_all(&buffer)
+            .map_err(|e| Failure::Error(format!("could not write output: {}", e)))?,
+    }
+    Ok(EXIT_OK)
+}
@@ -0,0 +1,103 @@
+//! The `extract` subcommand.
+
+use fastobo::ast::*;
+
+use crate::args::Args;
+use crate::Failure;
+use crate::EXIT_OK;
+
+pub const USAGE: &str = "\
+Usage: obo extract --subset <SUBSET> [OPTIONS] <FILE>
+
+Extract the frames declared in a subset into a new document.
+
+Options:
+    --subset <SUBSET>   the identifier of the subset to extract
+    -o, --output <OUT>  write the document to a file instead of printing it
+";
+
+/// Check whether an entity frame is declared in the given subset.
+fn in_subset(frame: &EntityFrame, subset: &SubsetIdent) -> bool {
+    match frame {
+        EntityFrame::Term(term) => term
+            .clauses()
+            .iter()
+            .any(|line| matches!(line.as_inner(), TermClause::Subset(s) if s.as_ref() == subset)),
+        EntityFrame::Typedef(typedef) => typedef.clauses().iter().any(
+            |line| matches!(line.as_inner(), TypedefClause::Subset(s) if s.as_ref() == subset),
+        ),
+        EntityFrame::Instance(instance) => instance.clauses().iter().any(
+            |line| matches!(line.as_inner(), InstanceClause::Subset(s) if s.as_ref() == subset),
+        ),
+    }
+}
+
+/// Extract the frames of a subset, keeping the header of the document.
+pub fn extract(mut doc: OboDoc, subset: &SubsetIdent) -> OboDoc {
+    doc.entities_mut().retain(|frame| in_subset(frame, subset));
+    doc
+}
+
+pub fn run(mut args: Args) -> Result<i32, Failure> {
+    let subset: SubsetIdent = match args.value(&["--subset"])? {
+        Some(subset) => subset
+            .parse()
+            .map_err(|_| Failure::Usage(format!("invalid subset identifier `{}`", subset)))?,
+        None => return Err(Failure::Usage("missing `--subset` option".to_string())),
+    };
+    let output = args.value(&["-o", "--output"])?;
+    let files = args.positional()?;
+    if files.len() > 1 {
+        return Err(Failure::Usage("expected a single input file".to_string()));
+    }
+
+    let doc = extract(crate::parse(&files[0])?, &subset);
+    match output {
+        Some(path) => crate::write(&path, doc.to_string())?,
+        None => print!("{}", doc),
+    }
+    Ok(EXIT_OK)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn extract() {
+        let doc: OboDoc = concat!(
+            "subsetdef: slim \"slim\"\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:001\n",
+            "subset: slim\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:002\n",
+            "\n",
+            "[Typedef]\n",
+            "id: part_of\n",
+            "subset: slim\n",
+        )
+        .parse()
+        .unwrap();
+        let subset: SubsetIdent = "slim".parse().unwrap();
+        let extracted = super::extract(doc, &subset);
+        assert_eq!(
+            extracted.to_string(),
+            concat!(
+                "subsetdef: slim \"slim\"\n",
+                "\n",
+                "[Term]\n",
+                "id: TST:001\n",
+                "subset: slim\n",
+                "\n",
+                "[Typedef]\n",
+                "id: part_of\n",
+                "subset: slim\n",
+            )
+        );
+    }
+}
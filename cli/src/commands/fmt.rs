@@ -0,0 +1,103 @@
+//! The `fmt` subcommand.
+
+use fastobo::ast::OboDoc;
+use fastobo::semantics::Orderable;
+use fastobo::writer::Serializer;
+use fastobo::writer::WriterOptions;
+
+use crate::args::Args;
+use crate::Failure;
+use crate::EXIT_CHECK;
+use crate::EXIT_OK;
+
+pub const USAGE: &str = "\
+Usage: obo fmt [OPTIONS] <FILE>...
+
+Rewrite OBO documents in canonical order and formatting.
+
+Options:
+    --check            exit with an error code if a file is not formatted
+    -i, --in-place     rewrite the files instead of printing them
+";
+
+/// Serialize a document in canonical order and formatting.
+pub fn format(mut doc: OboDoc) -> Result<String, Failure> {
+    doc.sort();
+    let mut serializer = Serializer::with_options(Vec::new(), WriterOptions::canonical());
+    serializer
+        .write_doc(&doc)
+        .map_err(|e| Failure::Error(e.to_string()))?;
+    String::from_utf8(serializer.into_inner())
+        .map_err(|e| Failure::Error(format!("invalid UTF-8 in output: {}", e)))
+}
+
+pub fn run(mut args: Args) -> Result<i32, Failure> {
+    let check = args.flag(&["--check"]);
+    let in_place = args.flag(&["-i", "--in-place"]);
+    let files = args.positional()?;
+
+    let mut code = EXIT_OK;
+    for path in &files {
+        let text = crate::read(path)?;
+        let doc = text
+            .parse()
+            .map_err(|e| Failure::Error(format!("could not parse `{}`: {}", path, e)))?;
+        let formatted = format(doc)?;
+        if check {
+            if formatted != text {
+                println!("{}: not formatted", path);
+                code = EXIT_CHECK;
+            }
+        } else if in_place {
+            if formatted != text {
+                crate::write(path, &formatted)?;
+            }
+        } else {
+            print!("{}", formatted);
+        }
+    }
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn format() {
+        let doc = concat!(
+            "format-version: 1.4\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:002\n",
+            "is_a: TST:001\n",
+            "name: second\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:001\n",
+            "name: first\n",
+        );
+        let formatted = super::format(doc.parse().unwrap()).unwrap();
+        assert_eq!(
+            formatted,
+            concat!(
+                "format-version: 1.4\n",
+                "\n",
+                "[Term]\n",
+                "id: TST:001\n",
+                "name: first\n",
+                "\n",
+                "[Term]\n",
+                "id: TST:002\n",
+                "name: second\n",
+                "is_a: TST:001 ! first\n",
+                "\n",
+            )
+        );
+        assert_eq!(
+            super::format(formatted.parse().unwrap()).unwrap(),
+            formatted
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! The subcommands of the command-line interface.
+
+mod check;
+mod convert;
+mod extract;
+mod fmt;
+mod stats;
+
+use crate::args::Args;
+use crate::Failure;
+
+/// A subcommand, with its usage message.
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub run: fn(Args) -> Result<i32, Failure>,
+}
+
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "fmt",
+        usage: fmt::USAGE,
+        run: fmt::run,
+    },
+    Command {
+        name: "check",
+        usage: check::USAGE,
+        run: check::run,
+    },
+    Command {
+        name: "extract",
+        usage: extract::USAGE,
+        run: extract::run,
+    },
+    Command {
+        name: "stats",
+        usage: stats::USAGE,
+        run: stats::run,
+    },
+    Command {
+        name: "convert",
+        usage: convert::USAGE,
+        run: convert::run,
+    },
+];
+
+/// Find the subcommand with the given name.
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
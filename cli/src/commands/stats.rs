@@ -0,0 +1,159 @@
+//! The `stats` subcommand.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use fastobo::ast::*;
+
+use crate::args::Args;
+use crate::Failure;
+use crate::EXIT_OK;
+
+pub const USAGE: &str = "\
+Usage: obo stats <FILE>...
+
+Print the number of frames, obsolete entities and cross-references
+grouped by prefix in OBO documents.
+";
+
+/// Statistics about the entities of an OBO document.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub terms: usize,
+    pub typedefs: usize,
+    pub instances: usize,
+    pub obsoletes: usize,
+    pub xrefs: BTreeMap<String, usize>,
+}
+
+impl Stats {
+    fn add_xref(&mut self, xref: &Xref) {
+        let prefix = match xref.id() {
+            Ident::Prefixed(id) => id.prefix().to_string(),
+            Ident::Unprefixed(_) => "(unprefixed)".to_string(),
+            Ident::Url(_) => "(url)".to_string(),
+        };
+        *self.xrefs.entry(prefix).or_insert(0) += 1;
+    }
+
+    /// Collect statistics about the given document.
+    pub fn from_doc(doc: &OboDoc) -> Self {
+        let mut stats = Stats::default();
+        for frame in doc.entities() {
+            let mut obsolete = false;
+            match frame {
+                EntityFrame::Term(term) => {
+                    stats.terms += 1;
+                    for line in term.clauses() {
+                        match line.as_inner() {
+                            TermClause::IsObsolete(b) => obsolete |= *b,
+                            TermClause::Xref(xref) => stats.add_xref(xref),
+                            _ => (),
+                        }
+                    }
+                }
+                EntityFrame::Typedef(typedef) => {
+                    stats.typedefs += 1;
+                    for line in typedef.clauses() {
+                        match line.as_inner() {
+                            TypedefClause::IsObsolete(b) => obsolete |= *b,
+                            TypedefClause::Xref(xref) => stats.add_xref(xref),
+                            _ => (),
+                        }
+                    }
+                }
+                EntityFrame::Instance(instance) => {
+                    stats.instances += 1;
+                    for line in instance.clauses() {
+                        match line.as_inner() {
+                            InstanceClause::IsObsolete(b) => obsolete |= *b,
+                            InstanceClause::Xref(xref) => stats.add_xref(xref),
+                            _ => (),
+                        }
+                    }
+                }
+            }
+            if obsolete {
+                stats.obsoletes += 1;
+            }
+        }
+        stats
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        writeln!(f, "terms:      {}", self.terms)?;
+        writeln!(f, "typedefs:   {}", self.typedefs)?;
+        writeln!(f, "instances:  {}", self.instances)?;
+        writeln!(f, "obsoletes:  {}", self.obsoletes)?;
+        writeln!(f, "xrefs:      {}", self.xrefs.values().sum::<usize>())?;
+        for (prefix, count) in &self.xrefs {
+            writeln!(f, "  {}: {}", prefix, count)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn run(args: Args) -> Result<i32, Failure> {
+    let files = args.positional()?;
+    for (i, path) in files.iter().enumerate() {
+        let stats = Stats::from_doc(&crate::parse(path)?);
+        if files.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{}:", path);
+        }
+        print!("{}", stats);
+    }
+    Ok(EXIT_OK)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn from_doc() {
+        let doc: OboDoc = concat!(
+            "[Term]\n",
+            "id: TST:001\n",
+            "xref: PMID:1\n",
+            "xref: PMID:2\n",
+            "xref: Wikipedia:Test\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:002\n",
+            "is_obsolete: true\n",
+            "\n",
+            "[Typedef]\n",
+            "id: part_of\n",
+            "xref: BFO:0000050\n",
+        )
+        .parse()
+        .unwrap();
+        let stats = Stats::from_doc(&doc);
+        assert_eq!(stats.terms, 2);
+        assert_eq!(stats.typedefs, 1);
+        assert_eq!(stats.instances, 0);
+        assert_eq!(stats.obsoletes, 1);
+        assert_eq!(
+            stats.to_string(),
+            concat!(
+                "terms:      2\n",
+                "typedefs:   1\n",
+                "instances:  0\n",
+                "obsoletes:  1\n",
+                "xrefs:      4\n",
+                "  BFO: 1\n",
+                "  PMID: 2\n",
+                "  Wikipedia: 1\n",
+            )
+        );
+    }
+}
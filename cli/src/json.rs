@@ -0,0 +1,495 @@
+//! Serialization of OBO documents to the OBO Graphs JSON format.
+//!
+//! Entity frames are converted to nodes with their metadata, `is_a`,
+//! `instance_of`, `inverse_of` and `relationship` clauses to edges, and
+//! `intersection_of`, `equivalent_to`, `domain`, `range`, `holds_over_chain`
+//! and `equivalent_to_chain` clauses to the corresponding graph axioms.
+//! Clauses without an OBO Graphs counterpart (such as `union_of`,
+//! `disjoint_from` or the relation properties of typedefs) are skipped,
+//! and their tags are reported to the caller.
+
+use std::collections::BTreeSet;
+
+use fastobo::ast::*;
+use fastobo::owl::Translator;
+use fastobo::semantics::Identified;
+use fastobo::semantics::OboClause;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+const OBO_IN_OWL: &str = "http://www.geneontology.org/formats/oboInOwl#";
+const REPLACED_BY: &str = "http://purl.obolibrary.org/obo/IAO_0100001";
+
+/// The result of the conversion of an OBO document.
+pub struct Conversion {
+    /// The OBO Graphs JSON document.
+    pub json: Value,
+    /// The tags of the clauses that could not be converted.
+    pub skipped: BTreeSet<String>,
+}
+
+/// The metadata of a node, collected from the clauses of a frame.
+#[derive(Default)]
+struct Meta {
+    definition: Option<Value>,
+    comments: Vec<Value>,
+    subsets: Vec<Value>,
+    xrefs: Vec<Value>,
+    synonyms: Vec<Value>,
+    basic_property_values: Vec<Value>,
+    deprecated: bool,
+}
+
+impl Meta {
+    fn definition(&mut self, def: &Definition) {
+        let xrefs: Vec<String> = def.xrefs().iter().map(|x| x.id().to_string()).collect();
+        self.definition = Some(json!({ "val": def.text().as_str(), "xrefs": xrefs }));
+    }
+
+    fn synonym(&mut self, syn: &Synonym) {
+        let pred = match syn.scope() {
+            SynonymScope::Exact => "hasExactSynonym",
+            SynonymScope::Broad => "hasBroadSynonym",
+            SynonymScope::Narrow => "hasNarrowSynonym",
+            SynonymScope::Related => "hasRelatedSynonym",
+        };
+        let xrefs: Vec<String> = syn.xrefs().iter().map(|x| x.id().to_string()).collect();
+        let mut synonym = json!({
+            "pred": pred,
+            "val": syn.description().as_str(),
+            "xrefs": xrefs,
+        });
+        if let Some(ty) = syn.ty() {
+            synonym["synonymType"] = Value::String(ty.to_string());
+        }
+        self.synonyms.push(synonym);
+    }
+
+    fn xref(&mut self, xref: &Xref) {
+        self.xrefs.push(json!({ "val": xref.id().to_string() }));
+    }
+
+    fn basic<P: ToString, V: ToString>(&mut self, pred: P, val: V) {
+        self.basic_property_values
+            .push(json!({ "pred": pred.to_string(), "val": val.to_string() }));
+    }
+
+    fn into_json(self) -> Option<Value> {
+        let mut fields = Map::new();
+        if let Some(def) = self.definition {
+            fields.insert("definition".to_string(), def);
+        }
+        let lists = vec![
+            ("comments", self.comments),
+            ("subsets", self.subsets),
+            ("xrefs", self.xrefs),
+            ("synonyms", self.synonyms),
+            ("basicPropertyValues", self.basic_property_values),
+        ];
+        for (key, values) in lists {
+            if !values.is_empty() {
+                fields.insert(key.to_string(), Value::Array(values));
+            }
+        }
+        if self.deprecated {
+            fields.insert("deprecated".to_string(), Value::Bool(true));
+        }
+        match fields.is_empty() {
+            true => None,
+            false => Some(Value::Object(fields)),
+        }
+    }
+}
+
+/// A graph being built from the entity frames of a document.
+struct Graph<'a> {
+    tr: &'a Translator,
+    nodes: Vec<Value>,
+    edges: Vec<Value>,
+    equivalent_nodes_sets: Vec<Value>,
+    logical_definition_axioms: Vec<Value>,
+    domain_range_axioms: Vec<Value>,
+    property_chain_axioms: Vec<Value>,
+    skipped: BTreeSet<String>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(tr: &'a Translator) -> Self {
+        Self {
+            tr,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            equivalent_nodes_sets: Vec::new(),
+            logical_definition_axioms: Vec::new(),
+            domain_range_axioms: Vec::new(),
+            property_chain_axioms: Vec::new(),
+            skipped: BTreeSet::new(),
+        }
+    }
+
+    fn iri(&self, id: &Ident) -> String {
+        self.tr.iri(id).to_string()
+    }
+
+    fn edge<P: Into<Value>>(&mut self, sub: &Ident, pred: P, obj: &Ident) {
+        let edge = json!({ "sub": self.iri(sub), "pred": pred.into(), "obj": self.iri(obj) });
+        self.edges.push(edge);
+    }
+
+    fn equivalent(&mut self, x: &Ident, y: &Ident) {
+        let ids = vec![self.iri(x), self.iri(y)];
+        self.equivalent_nodes_sets.push(json!({ "nodeIds": ids }));
+    }
+
+    fn property_value(&self, meta: &mut Meta, pv: &PropertyValue) {
+        match pv {
+            PropertyValue::Resource(pv) => {
+                meta.basic(self.iri(pv.property().as_ref()), self.iri(pv.target()))
+            }
+            PropertyValue::Literal(pv) => {
+                meta.basic(self.iri(pv.property().as_ref()), pv.literal().as_str())
+            }
+        }
+    }
+
+    fn skip<C: OboClause>(&mut self, clause: &C) {
+        self.skipped.insert(clause.tag().to_string());
+    }
+
+    fn term(&mut self, frame: &TermFrame) -> (&'static str, Option<String>, Meta) {
+        let id = frame.id().as_inner().as_ref();
+        let mut meta = Meta::default();
+        let mut label = None;
+        let mut genus = Vec::new();
+        let mut restrictions = Vec::new();
+        for line in frame.clauses() {
+            match line.as_inner() {
+                TermClause::Name(name) => label = Some(name.as_str().to_string()),
+                TermClause::Namespace(ns) => meta.basic(
+                    format!("{}hasOBONamespace", OBO_IN_OWL),
+                    ns.as_ref().to_string(),
+                ),
+                TermClause::AltId(alt) => {
+                    meta.basic(format!("{}hasAlternativeId", OBO_IN_OWL), alt)
+                }
+                TermClause::Def(def) => meta.definition(def),
+                TermClause::Comment(c) => meta.comments.push(Value::from(c.as_str())),
+                TermClause::Subset(s) => meta.subsets.push(self.iri(s.as_ref().as_ref()).into()),
+                TermClause::Synonym(syn) => meta.synonym(syn),
+                TermClause::Xref(xref) => meta.xref(xref),
+                TermClause::PropertyValue(pv) => self.property_value(&mut meta, pv),
+                TermClause::CreatedBy(by) => {
+                    meta.basic(format!("{}created_by", OBO_IN_OWL), by.as_str())
+                }
+                TermClause::CreationDate(date) => {
+                    meta.basic(format!("{}creation_date", OBO_IN_OWL), date)
+                }
+                TermClause::IsObsolete(b) => meta.deprecated |= *b,
+                TermClause::ReplacedBy(by) => {
+                    meta.basic(REPLACED_BY, self.iri(by.as_ref().as_ref()))
+                }
+                TermClause::Consider(c) => meta.basic(
+                    format!("{}consider", OBO_IN_OWL),
+                    self.iri(c.as_ref().as_ref()),
+                ),
+                TermClause::IsA(sup) => self.edge(id, "is_a", sup.as_ref().as_ref()),
+                TermClause::Relationship(rel, obj) => {
+                    let pred = self.iri(rel.as_ref().as_ref());
+                    self.edge(id, pred, obj.as_ref().as_ref())
+                }
+                TermClause::IntersectionOf(None, cls) => {
+                    genus.push(self.iri(cls.as_ref().as_ref()))
+                }
+                TermClause::IntersectionOf(Some(rel), cls) => restrictions.push(json!({
+                    "propertyId": self.iri(rel.as_ref().as_ref()),
+                    "fillerId": self.iri(cls.as_ref().as_ref()),
+                })),
+                TermClause::EquivalentTo(cls) => self.equivalent(id, cls.as_ref().as_ref()),
+                other => self.skip(other),
+            }
+        }
+        if !genus.is_empty() || !restrictions.is_empty() {
+            self.logical_definition_axioms.push(json!({
+                "definedClassId": self.iri(id),
+                "genusIds": genus,
+                "restrictions": restrictions,
+            }));
+        }
+        ("CLASS", label, meta)
+    }
+
+    fn typedef(&mut self, frame: &TypedefFrame) -> (&'static str, Option<String>, Meta) {
+        let id = frame.id().as_inner().as_ref();
+        let mut meta = Meta::default();
+        let mut label = None;
+        let mut domains = Vec::new();
+        let mut ranges = Vec::new();
+        for line in frame.clauses() {
+            match line.as_inner() {
+                TypedefClause::Name(name) => label = Some(name.as_str().to_string()),
+                TypedefClause::Namespace(ns) => meta.basic(
+                    format!("{}hasOBONamespace", OBO_IN_OWL),
+                    ns.as_ref().to_string(),
+                ),
+                TypedefClause::AltId(alt) => {
+                    meta.basic(format!("{}hasAlternativeId", OBO_IN_OWL), alt)
+                }
+                TypedefClause::Def(def) => meta.definition(def),
+                TypedefClause::Comment(c) => meta.comments.push(Value::from(c.as_str())),
+                TypedefClause::Subset(s) => meta.subsets.push(self.iri(s.as_ref().as_ref()).into()),
+                TypedefClause::Synonym(syn) => meta.synonym(syn),
+                TypedefClause::Xref(xref) => meta.xref(xref),
+                TypedefClause::PropertyValue(pv) => self.property_value(&mut meta, pv),
+                TypedefClause::CreatedBy(by) => {
+                    meta.basic(format!("{}created_by", OBO_IN_OWL), by.as_str())
+                }
+                TypedefClause::CreationDate(date) => {
+                    meta.basic(format!("{}creation_date", OBO_IN_OWL), date)
+                }
+                TypedefClause::IsObsolete(b) => meta.deprecated |= *b,
+                TypedefClause::ReplacedBy(by) => {
+                    meta.basic(REPLACED_BY, self.iri(by.as_ref().as_ref()))
+                }
+                TypedefClause::Consider(c) => {
+                    meta.basic(format!("{}consider", OBO_IN_OWL), self.iri(c))
+                }
+                TypedefClause::IsA(sup) => self.edge(id, "subPropertyOf", sup.as_ref().as_ref()),
+                TypedefClause::InverseOf(inv) => self.edge(id, "inverseOf", inv.as_ref().as_ref()),
+                TypedefClause::Relationship(rel, obj) => {
+                    let pred = self.iri(rel.as_ref().as_ref());
+                    self.edge(id, pred, obj.as_ref().as_ref())
+                }
+                TypedefClause::EquivalentTo(rel) => self.equivalent(id, rel.as_ref().as_ref()),
+                TypedefClause::Domain(cls) => domains.push(self.iri(cls.as_ref().as_ref())),
+                TypedefClause::Range(cls) => ranges.push(self.iri(cls.as_ref().as_ref())),
+                TypedefClause::HoldsOverChain(r1, r2)
+                | TypedefClause::EquivalentToChain(r1, r2) => {
+                    let chain = vec![
+                        self.iri(r1.as_ref().as_ref()),
+                        self.iri(r2.as_ref().as_ref()),
+                    ];
+                    self.property_chain_axioms.push(json!({
+                        "predicateId": self.iri(id),
+                        "chainPredicateIds": chain,
+                    }));
+                }
+                other => self.skip(other),
+            }
+        }
+        if !domains.is_empty() || !ranges.is_empty() {
+            self.domain_range_axioms.push(json!({
+                "predicateId": self.iri(id),
+                "domainClassIds": domains,
+                "rangeClassIds": ranges,
+            }));
+        }
+        ("PROPERTY", label, meta)
+    }
+
+    fn instance(&mut self, frame: &InstanceFrame) -> (&'static str, Option<String>, Meta) {
+        let id = frame.id().as_inner().as_ref();
+        let mut meta = Meta::default();
+        let mut label = None;
+        for line in frame.clauses() {
+            match line.as_inner() {
+                InstanceClause::Name(name) => label = Some(name.as_str().to_string()),
+                InstanceClause::Namespace(ns) => meta.basic(
+                    format!("{}hasOBONamespace", OBO_IN_OWL),
+                    ns.as_ref().to_string(),
+                ),
+                InstanceClause::AltId(alt) => {
+                    meta.basic(format!("{}hasAlternativeId", OBO_IN_OWL), alt)
+                }
+                InstanceClause::Def(def) => meta.definition(def),
+                InstanceClause::Comment(c) => meta.comments.push(Value::from(c.as_str())),
+                InstanceClause::Subset(s) => {
+                    meta.subsets.push(self.iri(s.as_ref().as_ref()).into())
+                }
+                InstanceClause::Synonym(syn) => meta.synonym(syn),
+                InstanceClause::Xref(xref) => meta.xref(xref),
+                InstanceClause::PropertyValue(pv) => self.property_value(&mut meta, pv),
+                InstanceClause::CreatedBy(by) => {
+                    meta.basic(format!("{}created_by", OBO_IN_OWL), by.as_str())
+                }
+                InstanceClause::CreationDate(date) => {
+                    meta.basic(format!("{}creation_date", OBO_IN_OWL), date)
+                }
+                InstanceClause::IsObsolete(b) => meta.deprecated |= *b,
+                InstanceClause::ReplacedBy(by) => {
+                    meta.basic(REPLACED_BY, self.iri(by.as_ref().as_ref()))
+                }
+                InstanceClause::Consider(c) => {
+                    meta.basic(format!("{}consider", OBO_IN_OWL), self.iri(c))
+                }
+                InstanceClause::InstanceOf(cls) => self.edge(id, "type", cls.as_ref().as_ref()),
+                InstanceClause::Relationship(rel, obj) => {
+                    let pred = self.iri(rel.as_ref().as_ref());
+                    self.edge(id, pred, obj)
+                }
+                other => self.skip(other),
+            }
+        }
+        ("INDIVIDUAL", label, meta)
+    }
+
+    /// Add the node, edges and axioms of a single entity frame.
+    fn entity(&mut self, frame: &EntityFrame) {
+        let (ty, label, meta) = match frame {
+            EntityFrame::Term(term) => self.term(term),
+            EntityFrame::Typedef(typedef) => self.typedef(typedef),
+            EntityFrame::Instance(instance) => self.instance(instance),
+        };
+        let mut node = json!({ "id": self.iri(frame.as_id()), "type": ty });
+        if let Some(label) = label {
+            node["lbl"] = Value::String(label);
+        }
+        if let Some(meta) = meta.into_json() {
+            node["meta"] = meta;
+        }
+        self.nodes.push(node);
+    }
+}
+
+/// Convert an OBO document into an OBO Graphs JSON document.
+pub fn to_json(doc: &OboDoc) -> Conversion {
+    let tr = Translator::new(doc.header());
+    let mut graph = Graph::new(&tr);
+    for frame in doc.entities() {
+        graph.entity(frame);
+    }
+
+    let mut json = json!({
+        "nodes": graph.nodes,
+        "edges": graph.edges,
+        "equivalentNodesSets": graph.equivalent_nodes_sets,
+        "logicalDefinitionAxioms": graph.logical_definition_axioms,
+        "domainRangeAxioms": graph.domain_range_axioms,
+        "propertyChainAxioms": graph.property_chain_axioms,
+    });
+    if let Some(iri) = tr.ontology_iri() {
+        json["id"] = Value::String(iri.to_string());
+    }
+    Conversion {
+        json: json!({ "graphs": [json] }),
+        skipped: graph.skipped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn convert(text: &str) -> Conversion {
+        super::to_json(&OboDoc::from_str(text).unwrap())
+    }
+
+    #[test]
+    fn to_json() {
+        let conversion = convert(concat!(
+            "ontology: tst\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:001\n",
+            "name: test \"item\"\n",
+            "def: \"A test.\" [PMID:1]\n",
+            "is_a: TST:002\n",
+            "is_obsolete: true\n",
+        ));
+        assert!(conversion.skipped.is_empty());
+        assert_eq!(
+            conversion.json,
+            json!({
+                "graphs": [{
+                    "id": "http://purl.obolibrary.org/obo/tst.owl",
+                    "nodes": [{
+                        "id": "http://purl.obolibrary.org/obo/TST_001",
+                        "lbl": "test \"item\"",
+                        "type": "CLASS",
+                        "meta": {
+                            "definition": { "val": "A test.", "xrefs": ["PMID:1"] },
+                            "deprecated": true,
+                        },
+                    }],
+                    "edges": [{
+                        "sub": "http://purl.obolibrary.org/obo/TST_001",
+                        "pred": "is_a",
+                        "obj": "http://purl.obolibrary.org/obo/TST_002",
+                    }],
+                    "equivalentNodesSets": [],
+                    "logicalDefinitionAxioms": [],
+                    "domainRangeAxioms": [],
+                    "propertyChainAxioms": [],
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn axioms() {
+        let conversion = convert(concat!(
+            "[Term]\n",
+            "id: TST:001\n",
+            "intersection_of: TST:002\n",
+            "intersection_of: part_of TST:003\n",
+            "equivalent_to: TST:004\n",
+            "property_value: IAO:0000114 IAO:0000122\n",
+            "\n",
+            "[Typedef]\n",
+            "id: part_of\n",
+            "domain: TST:002\n",
+            "range: TST:003\n",
+            "holds_over_chain: part_of part_of\n",
+        ));
+        assert!(conversion.skipped.is_empty());
+
+        let graph = &conversion.json["graphs"][0];
+        let obo = "http://purl.obolibrary.org/obo/";
+        let axiom = &graph["logicalDefinitionAxioms"][0];
+        assert_eq!(axiom["definedClassId"], json!(format!("{}TST_001", obo)));
+        assert_eq!(axiom["genusIds"], json!([format!("{}TST_002", obo)]));
+        assert_eq!(
+            axiom["restrictions"][0]["fillerId"],
+            json!(format!("{}TST_003", obo))
+        );
+        assert_eq!(
+            graph["equivalentNodesSets"][0]["nodeIds"][1],
+            json!(format!("{}TST_004", obo))
+        );
+        assert_eq!(
+            graph["nodes"][0]["meta"]["basicPropertyValues"][0]["val"],
+            json!(format!("{}IAO_0000122", obo))
+        );
+        assert_eq!(
+            graph["domainRangeAxioms"][0]["domainClassIds"],
+            json!([format!("{}TST_002", obo)])
+        );
+        assert_eq!(
+            graph["propertyChainAxioms"][0]["chainPredicateIds"]
+                .as_array()
+                .map(Vec::len),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn skipped() {
+        let conversion = convert(concat!(
+            "[Term]\n",
+            "id: TST:001\n",
+            "union_of: TST:002\n",
+            "union_of: TST:003\n",
+            "disjoint_from: TST:004\n",
+            "\n",
+            "[Typedef]\n",
+            "id: part_of\n",
+            "is_transitive: true\n",
+        ));
+        let skipped: Vec<String> = conversion.skipped.into_iter().collect();
+        assert_eq!(skipped, vec!["disjoint_from", "is_transitive", "union_of"]);
+    }
+}
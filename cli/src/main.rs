@@ -0,0 +1,123 @@
+//! Command-line toolkit for OBO documents, built on top of [`fastobo`].
+//!
+//! [`fastobo`]: https://docs.rs/fastobo
+
+extern crate fastobo;
+extern crate fastobo_lint;
+
+#[cfg(test)]
+extern crate pretty_assertions;
+
+mod args;
+mod commands;
+mod json;
+
+use std::process::exit;
+
+use fastobo::ast::OboDoc;
+
+use self::args::Args;
+
+/// The exit code used when the command succeeded.
+const EXIT_OK: i32 = 0;
+/// The exit code used when a check failed, e.g. a file is not formatted.
+const EXIT_CHECK: i32 = 1;
+/// The exit code used when a file could not be read, parsed or written.
+const EXIT_ERROR: i32 = 2;
+/// The exit code used when the command line is invalid.
+const EXIT_USAGE: i32 = 64;
+
+const USAGE: &str = "\
+Usage: obo <COMMAND> [OPTIONS] <FILE>...
+
+Format, check, extract and convert OBO documents.
+
+Commands:
+    fmt        rewrite documents in canonical order and formatting
+    check      check documents for syntax and semantic errors
+    extract    extract the frames of a subset into a new document
+    stats      print statistics about documents
+    convert    convert a document to OBO Graphs JSON or OWL Turtle
+
+Options:
+    -h, --help       print this help message
+    -V, --version    print the version number
+
+Run `obo <COMMAND> --help` for the options of each command.
+
+Exit codes:
+    0    the command succeeded
+    1    a check failed
+    2    a file could not be read, parsed or written
+    64   the command line is invalid
+";
+
+/// The reason a command failed.
+#[derive(Debug)]
+pub enum Failure {
+    /// The command line is invalid.
+    Usage(String),
+    /// A file could not be read, parsed or written.
+    Error(String),
+}
+
+/// Read the contents of a file.
+pub fn read(path: &str) -> Result<String, Failure> {
+    std::fs::read_to_string(path)
+        .map_err(|e| Failure::Error(format!("could not read `{}`: {}", path, e)))
+}
+
+/// Write the contents of a file.
+pub fn write<C: AsRef<[u8]>>(path: &str, contents: C) -> Result<(), Failure> {
+    std::fs::write(path, contents)
+        .map_err(|e| Failure::Error(format!("could not write `{}`: {}", path, e)))
+}
+
+/// Read and parse an OBO document.
+pub fn parse(path: &str) -> Result<OboDoc, Failure> {
+    let text = read(path)?;
+    text.parse()
+        .map_err(|e| Failure::Error(format!("could not parse `{}`: {}", path, e)))
+}
+
+fn run(mut args: Vec<String>) -> Result<i32, Failure> {
+    if args.is_empty() {
+        return Err(Failure::Usage("no command given".to_string()));
+    }
+    let command = args.remove(0);
+    let help = args.iter().any(|a| a == "-h" || a == "--help");
+    let args = Args::new(args);
+    match command.as_str() {
+        "-h" | "--help" => {
+            print!("{}", USAGE);
+            Ok(EXIT_OK)
+        }
+        "-V" | "--version" => {
+            println!("obo {}", env!("CARGO_PKG_VERSION"));
+            Ok(EXIT_OK)
+        }
+        name => match commands::find(name) {
+            Some(command) if help => {
+                print!("{}", command.usage);
+                Ok(EXIT_OK)
+            }
+            Some(command) => (command.run)(args),
+            None => Err(Failure::Usage(format!("unknown command `{}`", name))),
+        },
+    }
+}
+
+fn main() {
+    let code = match run(std::env::args().skip(1).collect()) {
+        Ok(code) => code,
+        Err(Failure::Usage(msg)) => {
+            eprintln!("error: {}\n\n{}", msg, USAGE);
+            EXIT_USAGE
+        }
+        Err(Failure::Error(msg)) => {
+            eprintln!("error: {}", msg);
+            EXIT_ERROR
+        }
+    };
+    exit(code)
+}
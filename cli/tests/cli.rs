@@ -0,0 +1,67 @@
+extern crate fastobo;
+extern crate pretty_assertions;
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+use fastobo::owl::rdf::RdfFormat;
+use pretty_assertions::assert_eq;
+
+fn data(name: &str) -> String {
+    let mut p = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    p.push("tests");
+    p.push("data");
+    p.push(name);
+    p.to_string_lossy().into_owned()
+}
+
+fn obo(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_obo"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn exit_ok() {
+    let output = obo(&["check", &data("valid.obo")]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn exit_check() {
+    let output = obo(&["check", &data("invalid.obo")]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("1 error(s)"), "{}", stderr);
+}
+
+#[test]
+fn exit_error() {
+    let path = data("missing.obo");
+    let output = obo(&["convert", "--to", "ttl", &path]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(&path), "{}", stderr);
+}
+
+#[test]
+fn exit_usage() {
+    let output = obo(&["frobnicate", &data("valid.obo")]);
+    assert_eq!(output.status.code(), Some(64));
+    let output = obo(&["convert", "--to", "xml", &data("valid.obo")]);
+    assert_eq!(output.status.code(), Some(64));
+}
+
+#[test]
+fn convert_turtle() {
+    let path = data("valid.obo");
+    let output = obo(&["convert", "--to", "ttl", &path]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let doc = fastobo::from_file(&path).unwrap();
+    let mut expected = Vec::new();
+    fastobo::owl::rdf::to_writer(&mut expected, &doc, RdfFormat::Turtle).unwrap();
+    assert_eq!(output.stdout, expected);
+}
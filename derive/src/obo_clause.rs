@@ -16,6 +16,7 @@ use crate::utils::is_option;
 pub struct OboClauseVariant {
     ident: Ident,
     fields: Fields<Type>,
+    #[allow(dead_code)]
     attrs: Vec<syn::Attribute>,
     #[darling(default)]
     tag: Option<syn::Lit>,
@@ -59,7 +60,7 @@ impl OboClauseVariant {
         }
     }
 
-    pub fn cardinality(&self) -> Cow<syn::Path> {
+    pub fn cardinality(&self) -> Cow<'_, syn::Path> {
         match &self.cardinality {
             Some(s) => Cow::Borrowed(s),
             None => Cow::Owned(parse_quote! { Any }),
@@ -82,7 +83,7 @@ impl OboClauseVariant {
             vec![parse_quote! {
                 #id( #(ref #c1,)* ) => write!(f, #fmt_string, #(#c2,)*),
             }]
-        } else if let Some(idx) = self.fields.iter().position(|ty| is_option(ty)) {
+        } else if let Some(idx) = self.fields.iter().position(is_option) {
             // If the variant contain an option, we need two arms: one where the
             // value is `Some` and the other when the value is `None`.
             // NB: limited to a single `Option` per variant.
@@ -15,39 +15,44 @@ use std::str::FromStr;
 use std::string::ToString;
 
 use fastobo::ast::*;
-use fastobo::visit::Visit;
+use fastobo::visit::VisitContext;
+use fastobo::visit::VisitControl;
+use fastobo::visit::VisitFlow;
 use isbn::Isbn;
 use isbn::IsbnError;
 
 #[derive(Default)]
 struct IsbnChecker<'a> {
     valid: HashSet<&'a PrefixedIdent>,
-    invalid: HashMap<&'a PrefixedIdent, IsbnError>,
+    invalid: HashMap<&'a PrefixedIdent, (Option<&'a Ident>, Option<&'a str>, IsbnError)>,
 }
 
-impl<'a> Visit<'a> for IsbnChecker<'a> {
-    fn visit_prefixed_ident(&mut self, id: &'a PrefixedIdent) {
+impl<'a> VisitControl<'a> for IsbnChecker<'a> {
+    type Break = ();
+
+    fn visit_prefixed_ident(&mut self, ctx: &VisitContext<'a>, id: &'a PrefixedIdent) -> VisitFlow {
         if id.prefix() == "ISBN" {
             if let Err(e) = Isbn::from_str(id.local()) {
-                self.invalid.insert(id, e);
+                self.invalid.insert(id, (ctx.entity(), ctx.clause(), e));
             } else {
                 self.valid.insert(id);
             }
         }
+        VisitFlow::Continue
     }
 }
 
 fn main() {
     for path in std::env::args().skip(1) {
         // Parse the document
-        let mut doc = match fastobo::from_file(&path) {
+        let doc = match fastobo::from_file(&path) {
             Ok(doc) => doc,
             Err(e) => panic!("{} could not be parsed:\n{}", path, e),
         };
 
         // Collect all ISBNs, valid and invalid;
         let mut checker = IsbnChecker::default();
-        checker.visit_doc(&mut doc);
+        checker.walk_doc(&doc);
 
         // Report the invalid ISBNs
         println!(
@@ -55,8 +60,16 @@ fn main() {
             checker.valid.len(),
             checker.invalid.len(),
         );
-        for (id, err) in checker.invalid.iter() {
-            println!("- {:<16}\t{:?}", id.to_string(), err)
+        for (id, (entity, tag, err)) in checker.invalid.iter() {
+            let frame = entity.map_or_else(|| String::from("header"), ToString::to_string);
+            let tag = tag.unwrap_or_default();
+            println!(
+                "- {:<16}\t{:?}\t({}: in {})",
+                id.to_string(),
+                err,
+                tag,
+                frame
+            )
         }
     }
 }
@@ -0,0 +1,474 @@
+//! Semantic checks run on a parsed OBO document.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::ops::Range;
+
+use fastobo::ast::*;
+use fastobo::diagnostic::code;
+use fastobo::diagnostic::locate_clauses;
+use fastobo::diagnostic::locate_frame;
+use fastobo::diagnostic::Diagnostic;
+use fastobo::error::CardinalityError;
+use fastobo::semantics::cmp_entities;
+use fastobo::semantics::Cardinality;
+use fastobo::semantics::Identified;
+use fastobo::semantics::OboClause;
+use fastobo::semantics::Orderable;
+
+/// Run all the checks on a document, returning diagnostics in source order.
+pub fn check(doc: &OboDoc, source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    cardinality(doc, source, &mut diagnostics);
+    references(doc, source, &mut diagnostics);
+    subsets(doc, source, &mut diagnostics);
+    identifiers(doc, source, &mut diagnostics);
+    ordering(doc, source, &mut diagnostics);
+    diagnostics.sort_by_key(|d| d.labels().first().map(|l| l.range().start));
+    diagnostics
+}
+
+// ---------------------------------------------------------------------------
+
+/// Attach a label to a diagnostic, if the problem could be located.
+fn labelled<R>(diagnostic: Diagnostic, ranges: R, message: &str) -> Diagnostic
+where
+    R: IntoIterator<Item = Range<usize>>,
+{
+    ranges
+        .into_iter()
+        .fold(diagnostic, |d, range| d.with_label(range, message))
+}
+
+/// Locate a value in the clauses of a frame, or of the header frame.
+///
+/// The range covers `needle` as a whitespace-delimited token in the first
+/// clause with the given tag where it appears.
+fn locate_value(
+    source: &str,
+    frame: Option<&Ident>,
+    tag: &str,
+    needle: &str,
+) -> Option<Range<usize>> {
+    let start = match frame {
+        Some(id) => locate_frame(source, id)?.start,
+        None => 0,
+    };
+    locate_clauses(source, start, tag)
+        .into_iter()
+        .find_map(|range| {
+            // NB: skip the colon following the tag.
+            let value = range.end + 1;
+            let rest = &source[value..];
+            let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+            find_token(line, needle).map(|offset| value + offset)
+        })
+        .map(|start| start..start + needle.len())
+}
+
+/// Find `needle` in `haystack` as a whitespace-delimited token.
+fn find_token(haystack: &str, needle: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(offset) = haystack[from..].find(needle) {
+        let start = from + offset;
+        let end = start + needle.len();
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[end..].chars().next();
+        let delimited = |c: Option<char>| {
+            c.into_iter()
+                .all(|c| c.is_whitespace() || "[]{},\"!".contains(c))
+        };
+        if delimited(before) && delimited(after) {
+            return Some(start);
+        }
+        from = end;
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+
+/// Count the clauses of a frame with the same tag, in order of appearance.
+fn count_tags<'a, C: OboClause + 'a, I>(clauses: I) -> Vec<(&'a str, Cardinality, usize)>
+where
+    I: IntoIterator<Item = &'a C>,
+{
+    let mut counts: Vec<(&str, Cardinality, usize)> = Vec::new();
+    for clause in clauses {
+        match counts.iter_mut().find(|(tag, _, _)| *tag == clause.tag()) {
+            Some(entry) => entry.2 += 1,
+            None => counts.push((clause.tag(), clause.cardinality(), 1)),
+        }
+    }
+    counts
+}
+
+/// Get the diagnostic code of a cardinality error.
+fn cardinality_code(error: &CardinalityError) -> &'static str {
+    match error {
+        CardinalityError::MissingClause { .. } => code::MISSING_CLAUSE,
+        CardinalityError::DuplicateClauses { .. } => code::DUPLICATE_CLAUSES,
+        CardinalityError::SingleClause { .. } => code::SINGLE_CLAUSE,
+    }
+}
+
+/// Check that clauses appear as many times as the specification allows.
+pub fn cardinality(doc: &OboDoc, source: &str, out: &mut Vec<Diagnostic>) {
+    for (tag, cardinality, n) in count_tags(doc.header().iter()) {
+        if let Some(err) = cardinality.to_error(n, tag) {
+            let diag =
+                Diagnostic::error(cardinality_code(&err), format!("header frame has {}", err))
+                    .with_help(format!("remove all but one `{}` clause", tag));
+            out.push(labelled(
+                diag,
+                locate_clauses(source, 0, tag),
+                "declared here",
+            ));
+        }
+    }
+
+    let default_namespace = doc
+        .header()
+        .iter()
+        .any(|c| matches!(c, HeaderClause::DefaultNamespace(_)));
+
+    for entity in doc.entities() {
+        let counts = match entity {
+            EntityFrame::Term(f) => count_tags(f.clauses().iter().map(Line::as_inner)),
+            EntityFrame::Typedef(f) => count_tags(f.clauses().iter().map(Line::as_inner)),
+            EntityFrame::Instance(f) => count_tags(f.clauses().iter().map(Line::as_inner)),
+        };
+        let frame = locate_frame(source, entity.as_id());
+        let id = entity.as_id().to_string();
+
+        for (tag, cardinality, n) in counts {
+            if let Some(err) = cardinality.to_error(n, tag) {
+                let help = match cardinality {
+                    Cardinality::NotOne => format!("add another `{}` clause, or remove it", tag),
+                    _ => format!("remove all but one `{}` clause", tag),
+                };
+                let code = cardinality_code(&err);
+                let diag =
+                    Diagnostic::error(code, format!("frame `{}` has {}", id, err)).with_help(help);
+                let clauses = frame
+                    .iter()
+                    .flat_map(|range| locate_clauses(source, range.start, tag));
+                out.push(labelled(diag, clauses, "declared here"));
+            }
+        }
+
+        // NB: the namespace may be inherited from the `default-namespace`.
+        let namespaced = match entity {
+            EntityFrame::Term(f) => f.iter().any(|l| l.as_inner().tag() == "namespace"),
+            EntityFrame::Typedef(f) => f.iter().any(|l| l.as_inner().tag() == "namespace"),
+            EntityFrame::Instance(f) => f.iter().any(|l| l.as_inner().tag() == "namespace"),
+        };
+        if !namespaced && !default_namespace {
+            let diag = Diagnostic::error(
+                code::MISSING_CLAUSE,
+                format!("frame `{}` is missing a namespace clause", id),
+            )
+            .with_help("add a `namespace` clause, or a `default-namespace` to the header");
+            out.push(labelled(diag, frame.clone(), "in this frame"));
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Get the identifiers referenced by a term clause.
+fn term_references(clause: &TermClause) -> Vec<&Ident> {
+    use self::TermClause::*;
+    match clause {
+        IsA(id) | UnionOf(id) | EquivalentTo(id) | DisjointFrom(id) | ReplacedBy(id)
+        | Consider(id) => vec![id.as_ref().as_ref()],
+        IntersectionOf(Some(rel), id) | Relationship(rel, id) => {
+            vec![rel.as_ref().as_ref(), id.as_ref().as_ref()]
+        }
+        IntersectionOf(None, id) => vec![id.as_ref().as_ref()],
+        _ => Vec::new(),
+    }
+}
+
+/// Get the identifiers referenced by a typedef clause.
+fn typedef_references(clause: &TypedefClause) -> Vec<&Ident> {
+    use self::TypedefClause::*;
+    match clause {
+        Domain(id) | Range(id) => vec![id.as_ref().as_ref()],
+        IsA(id) | IntersectionOf(id) | UnionOf(id) | EquivalentTo(id) | DisjointFrom(id)
+        | InverseOf(id) | TransitiveOver(id) | DisjointOver(id) | ReplacedBy(id) => {
+            vec![id.as_ref().as_ref()]
+        }
+        HoldsOverChain(r1, r2) | EquivalentToChain(r1, r2) | Relationship(r1, r2) => {
+            vec![r1.as_ref().as_ref(), r2.as_ref().as_ref()]
+        }
+        Consider(id) => vec![id.as_ref()],
+        _ => Vec::new(),
+    }
+}
+
+/// Get the identifiers referenced by an instance clause.
+fn instance_references(clause: &InstanceClause) -> Vec<&Ident> {
+    use self::InstanceClause::*;
+    match clause {
+        InstanceOf(id) => vec![id.as_ref().as_ref()],
+        Relationship(rel, id) => vec![rel.as_ref().as_ref(), id.as_ref()],
+        ReplacedBy(id) => vec![id.as_ref().as_ref()],
+        Consider(id) => vec![id.as_ref()],
+        _ => Vec::new(),
+    }
+}
+
+/// Check that referenced entities are declared in the document.
+///
+/// References to entities declared in imported ontologies cannot be
+/// resolved, so dangling references are only reported as warnings.
+pub fn references(doc: &OboDoc, source: &str, out: &mut Vec<Diagnostic>) {
+    let declared: HashSet<&Ident> = doc.entities().iter().map(|e| e.as_id()).collect();
+
+    for entity in doc.entities() {
+        let references: Vec<(&str, &Ident)> = match entity {
+            EntityFrame::Term(f) => f
+                .iter()
+                .flat_map(|l| term_references(l).into_iter().map(move |id| (l.tag(), id)))
+                .collect(),
+            EntityFrame::Typedef(f) => f
+                .iter()
+                .flat_map(|l| {
+                    typedef_references(l)
+                        .into_iter()
+                        .map(move |id| (l.tag(), id))
+                })
+                .collect(),
+            EntityFrame::Instance(f) => f
+                .iter()
+                .flat_map(|l| {
+                    instance_references(l)
+                        .into_iter()
+                        .map(move |id| (l.tag(), id))
+                })
+                .collect(),
+        };
+
+        for (tag, id) in references {
+            if !declared.contains(id) {
+                let id = id.to_string();
+                let diag = Diagnostic::warning(
+                    code::DANGLING_REFERENCE,
+                    format!("reference to undeclared entity `{}`", id),
+                )
+                .with_help(format!(
+                    "declare `{}` in this document, or import an ontology declaring it",
+                    id
+                ));
+                let range = locate_value(source, Some(entity.as_id()), tag, &id);
+                out.push(labelled(diag, range, "not declared in this document"));
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Check that subsets are declared with a `subsetdef` header clause.
+pub fn subsets(doc: &OboDoc, source: &str, out: &mut Vec<Diagnostic>) {
+    let declared: HashSet<&SubsetIdent> = doc
+        .header()
+        .iter()
+        .filter_map(|c| match c {
+            HeaderClause::Subsetdef(id, _) => Some(id.as_ref()),
+            _ => None,
+        })
+        .collect();
+
+    for entity in doc.entities() {
+        let subsets: Vec<&SubsetIdent> = match entity {
+            EntityFrame::Term(f) => f
+                .iter()
+                .filter_map(|l| match l.as_inner() {
+                    TermClause::Subset(s) => Some(s.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+            EntityFrame::Typedef(f) => f
+                .iter()
+                .filter_map(|l| match l.as_inner() {
+                    TypedefClause::Subset(s) => Some(s.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+            EntityFrame::Instance(f) => f
+                .iter()
+                .filter_map(|l| match l.as_inner() {
+                    InstanceClause::Subset(s) => Some(s.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+        };
+
+        for subset in subsets.into_iter().filter(|s| !declared.contains(s)) {
+            let subset = subset.to_string();
+            let diag = Diagnostic::error(
+                code::UNDECLARED_SUBSET,
+                format!("undeclared subset `{}`", subset),
+            )
+            .with_help(format!(
+                "add `subsetdef: {} \"...\"` to the header frame",
+                subset
+            ));
+            let range = locate_value(source, Some(entity.as_id()), "subset", &subset);
+            out.push(labelled(diag, range, "not declared in the header"));
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Check that the prefixed identifiers of entity frames are canonical.
+pub fn identifiers(doc: &OboDoc, source: &str, out: &mut Vec<Diagnostic>) {
+    for entity in doc.entities() {
+        if let Ident::Prefixed(id) = entity.as_id() {
+            if !id.is_canonical() {
+                let diag = Diagnostic::warning(
+                    code::NON_CANONICAL_IDENT,
+                    format!("identifier `{}` is not canonical", id),
+                )
+                .with_help("canonical identifiers are written `IDSPACE:` followed by digits");
+                let range = locate_frame(source, entity.as_id());
+                out.push(labelled(diag, range, "not canonical"));
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Check that frames and clauses are in serialization order.
+pub fn ordering(doc: &OboDoc, source: &str, out: &mut Vec<Diagnostic>) {
+    let help = "run with `--fix` to sort the document";
+
+    if !doc.header().is_sorted() {
+        out.push(
+            Diagnostic::warning(
+                code::UNSORTED,
+                "header clauses are not in serialization order",
+            )
+            .with_help(help),
+        );
+    }
+
+    let entities = doc.entities();
+    for (i, entity) in entities.iter().enumerate() {
+        let frame = locate_frame(source, entity.as_id());
+        let id = entity.as_id().to_string();
+        if i > 0 && cmp_entities(&entities[i - 1], entity) == Ordering::Greater {
+            let diag =
+                Diagnostic::warning(code::UNSORTED, format!("frame `{}` is out of order", id))
+                    .with_help(help);
+            out.push(labelled(diag, frame.clone(), "in this frame"));
+        }
+        if !entity.is_sorted() {
+            let diag = Diagnostic::warning(
+                code::UNSORTED,
+                format!("clauses of frame `{}` are not in serialization order", id),
+            )
+            .with_help(help);
+            out.push(labelled(diag, frame, "in this frame"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn codes(text: &str) -> Vec<(&'static str, Option<usize>)> {
+        let doc = OboDoc::from_str(text).unwrap();
+        check(&doc, text)
+            .into_iter()
+            .map(|d| {
+                let line = |l: &fastobo::diagnostic::Label| {
+                    text[..l.range().start].matches('\n').count() + 1
+                };
+                (d.code(), d.labels().first().map(line))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        let text = concat!(
+            "subsetdef: slim \"slim\"\n",
+            "default-namespace: test\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:001\n",
+            "subset: slim\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:002\n",
+            "is_a: TST:001\n",
+        );
+        assert_eq!(codes(text), Vec::new());
+    }
+
+    #[test]
+    fn problems() {
+        let text = concat!(
+            "default-namespace: test\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:002\n",
+            "name: deuxieme\n",
+            "name: second\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:one\n",
+            "is_a: TST:003\n",
+            "subset: slim\n",
+        );
+        assert_eq!(
+            codes(text),
+            vec![
+                (code::DUPLICATE_CLAUSES, Some(5)),
+                (code::NON_CANONICAL_IDENT, Some(9)),
+                (code::UNSORTED, Some(9)),
+                (code::DANGLING_REFERENCE, Some(10)),
+                (code::UNDECLARED_SUBSET, Some(11)),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_namespace() {
+        let text = "[Term]\nid: TST:001\n";
+        assert_eq!(codes(text), vec![(code::MISSING_CLAUSE, Some(2))]);
+    }
+
+    #[test]
+    fn cardinality_codes() {
+        let text = concat!(
+            "format-version: 1.2\n",
+            "format-version: 1.4\n",
+            "default-namespace: test\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:001\n",
+            "union_of: TST:002\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:002\n",
+        );
+        assert_eq!(
+            codes(text),
+            vec![
+                (code::DUPLICATE_CLAUSES, Some(1)),
+                (code::SINGLE_CLAUSE, Some(7))
+            ]
+        );
+    }
+}
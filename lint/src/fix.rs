@@ -0,0 +1,122 @@
+//! Automatic fixes applied through the syntax tree.
+
+use std::collections::HashSet;
+
+use fastobo::ast::*;
+use fastobo::diagnostic::code;
+use fastobo::diagnostic::Diagnostic;
+use fastobo::semantics::Orderable;
+
+/// Check whether the problem reported by a diagnostic is fixed by [`fix`].
+///
+/// [`fix`]: ./fn.fix.html
+pub fn is_fixable(diagnostic: &Diagnostic) -> bool {
+    matches!(diagnostic.code(), code::UNDECLARED_SUBSET | code::UNSORTED)
+}
+
+/// Declare the subsets used in entity frames but missing from the header.
+///
+/// Returns the number of `subsetdef` clauses added to the header.
+pub fn declare_subsets(doc: &mut OboDoc) -> usize {
+    let mut declared: HashSet<SubsetIdent> = doc
+        .header()
+        .iter()
+        .filter_map(|c| match c {
+            HeaderClause::Subsetdef(id, _) => Some(id.as_ref().clone()),
+            _ => None,
+        })
+        .collect();
+
+    let mut missing = Vec::new();
+    for entity in doc.entities() {
+        let subsets: Vec<&SubsetIdent> = match entity {
+            EntityFrame::Term(f) => f
+                .iter()
+                .filter_map(|l| match l.as_inner() {
+                    TermClause::Subset(s) => Some(s.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+            EntityFrame::Typedef(f) => f
+                .iter()
+                .filter_map(|l| match l.as_inner() {
+                    TypedefClause::Subset(s) => Some(s.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+            EntityFrame::Instance(f) => f
+                .iter()
+                .filter_map(|l| match l.as_inner() {
+                    InstanceClause::Subset(s) => Some(s.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+        };
+        for subset in subsets {
+            if declared.insert(subset.clone()) {
+                missing.push(subset.clone());
+            }
+        }
+    }
+
+    let n = missing.len();
+    for subset in missing {
+        let desc = QuotedString::new(subset.to_string());
+        doc.header_mut()
+            .push(HeaderClause::Subsetdef(Box::new(subset), Box::new(desc)));
+    }
+    n
+}
+
+/// Apply all the automatic fixes to a document.
+///
+/// Missing subset declarations are added to the header, and the document
+/// is then sorted in serialization order.
+pub fn fix(doc: &mut OboDoc) {
+    declare_subsets(doc);
+    doc.sort();
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn fix() {
+        let mut doc = OboDoc::from_str(concat!(
+            "default-namespace: test\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:002\n",
+            "subset: slim\n",
+            "\n",
+            "[Term]\n",
+            "id: TST:001\n",
+            "is_a: TST:002\n",
+            "subset: slim\n",
+        ))
+        .unwrap();
+
+        super::fix(&mut doc);
+        assert_eq!(
+            doc.to_string(),
+            concat!(
+                "subsetdef: slim \"slim\"\n",
+                "default-namespace: test\n",
+                "\n",
+                "[Term]\n",
+                "id: TST:001\n",
+                "subset: slim\n",
+                "is_a: TST:002\n",
+                "\n",
+                "[Term]\n",
+                "id: TST:002\n",
+                "subset: slim\n",
+            )
+        );
+    }
+}
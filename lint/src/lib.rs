@@ -0,0 +1,78 @@
+//! A linter for OBO documents, built on top of [`fastobo`].
+//!
+//! The linter parses an OBO document, then runs semantic checks on the
+//! syntax tree and reports problems as [`Diagnostic`]s, which can be
+//! rendered in the style of `rustc` with an excerpt of the source text.
+//! Some problems can be fixed automatically by rewriting the document
+//! through the syntax tree, see the [`fix`] module.
+//!
+//! [`fastobo`]: https://docs.rs/fastobo
+//! [`Diagnostic`]: https://docs.rs/fastobo/latest/fastobo/diagnostic/struct.Diagnostic.html
+//! [`fix`]: ./fix/index.html
+
+extern crate fastobo;
+
+pub mod checks;
+pub mod fix;
+
+use std::str::FromStr;
+
+use fastobo::ast::OboDoc;
+use fastobo::diagnostic::Diagnostic;
+
+/// Parse and lint an OBO document.
+///
+/// Returns the parsed document if the source text is syntactically valid,
+/// and the diagnostics found in the document, labelled with byte ranges
+/// of the source text.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo_lint;
+/// let (doc, diagnostics) = fastobo_lint::lint("[Term]\nid: TST:001\nnamespace: test\n");
+/// assert!(doc.is_some());
+/// assert!(diagnostics.is_empty());
+/// ```
+pub fn lint(text: &str) -> (Option<OboDoc>, Vec<Diagnostic>) {
+    match OboDoc::from_str(text) {
+        Ok(doc) => {
+            let diagnostics = checks::check(&doc, text);
+            (Some(doc), diagnostics)
+        }
+        Err(error) => (None, vec![Diagnostic::from(&error)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn lint_syntax_error() {
+        let text = "[Term]\nid: TST:001\nis_a TST:002\n";
+        let (doc, diagnostics) = lint(text);
+        assert!(doc.is_none());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code(),
+            fastobo::diagnostic::code::PARSER_ERROR
+        );
+        let range = diagnostics[0].labels()[0].range();
+        assert_eq!(text[..range.start].matches('\n').count(), 2);
+    }
+
+    #[test]
+    fn lint_syntax_error_non_ascii() {
+        let text = "[Term]\nid: TST:001\ndef: \"éééééééééé\" [PMID:1\n";
+        let (_, diagnostics) = lint(text);
+        assert_eq!(diagnostics.len(), 1);
+        let range = diagnostics[0].labels()[0].range();
+        assert!(text.is_char_boundary(range.start));
+        assert!(text.is_char_boundary(range.end));
+
+        let rendered = diagnostics[0].clone().with_path("test.obo").render(text);
+        assert!(rendered.contains(" --> test.obo:3:26\n"), "{}", rendered);
+    }
+}
@@ -0,0 +1,150 @@
+//! Command-line interface of the OBO linter.
+
+extern crate fastobo;
+extern crate fastobo_lint;
+
+use std::path::PathBuf;
+use std::process::exit;
+
+use fastobo::diagnostic::Diagnostic;
+use fastobo::diagnostic::Severity;
+
+/// The exit code used when no error was found.
+const EXIT_OK: i32 = 0;
+/// The exit code used when errors (or denied warnings) were found.
+const EXIT_LINT: i32 = 1;
+/// The exit code used when a file could not be read or written.
+const EXIT_IO: i32 = 2;
+/// The exit code used when the command line is invalid.
+const EXIT_USAGE: i32 = 64;
+
+const USAGE: &str = "\
+Usage: fastobo-lint [OPTIONS] <FILE>...
+
+Check OBO documents for syntax and semantic errors.
+
+Options:
+    --fix             rewrite the files, fixing problems when possible
+    --deny-warnings   exit with an error code if any warning is reported
+    -q, --quiet       only print the summary for each file
+    -h, --help        print this help message
+    -V, --version     print the version number
+
+Exit codes:
+    0    no error was found
+    1    errors were found, or warnings with `--deny-warnings`
+    2    a file could not be read or written
+    64   the command line is invalid
+";
+
+#[derive(Debug, Default)]
+struct Args {
+    fix: bool,
+    deny_warnings: bool,
+    quiet: bool,
+    files: Vec<PathBuf>,
+}
+
+impl Args {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        for arg in args {
+            match arg.as_str() {
+                "--fix" => parsed.fix = true,
+                "--deny-warnings" => parsed.deny_warnings = true,
+                "-q" | "--quiet" => parsed.quiet = true,
+                "-h" | "--help" => {
+                    print!("{}", USAGE);
+                    exit(EXIT_OK);
+                }
+                "-V" | "--version" => {
+                    println!("fastobo-lint {}", env!("CARGO_PKG_VERSION"));
+                    exit(EXIT_OK);
+                }
+                flag if flag.starts_with('-') && flag != "-" => {
+                    return Err(format!("unknown option `{}`", flag));
+                }
+                file => parsed.files.push(PathBuf::from(file)),
+            }
+        }
+        if parsed.files.is_empty() {
+            return Err("no input file given".to_string());
+        }
+        Ok(parsed)
+    }
+}
+
+/// Print diagnostics and a summary, returning whether the file failed.
+fn report(args: &Args, path: &str, text: &str, diagnostics: &[Diagnostic]) -> bool {
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity() == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    if !args.quiet {
+        for diagnostic in diagnostics {
+            eprintln!("{}", diagnostic.clone().with_path(path).render(text));
+        }
+    }
+
+    let fixable = diagnostics
+        .iter()
+        .filter(|d| fastobo_lint::fix::is_fixable(d))
+        .count();
+    if errors + warnings > 0 {
+        eprint!("{}: {} error(s), {} warning(s)", path, errors, warnings);
+        if fixable > 0 && !args.fix {
+            eprint!(", {} fixable with `--fix`", fixable);
+        }
+        eprintln!();
+    }
+
+    errors > 0 || (args.deny_warnings && warnings > 0)
+}
+
+fn main() {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("error: {}\n\n{}", msg, USAGE);
+            exit(EXIT_USAGE);
+        }
+    };
+
+    let mut code = EXIT_OK;
+    for file in &args.files {
+        let path = file.display().to_string();
+        let mut text = match std::fs::read_to_string(file) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("error: could not read `{}`: {}", path, e);
+                code = EXIT_IO;
+                continue;
+            }
+        };
+
+        let (doc, mut diagnostics) = fastobo_lint::lint(&text);
+        if args.fix {
+            if let Some(mut doc) = doc {
+                fastobo_lint::fix::fix(&mut doc);
+                let fixed = doc.to_string();
+                if fixed != text {
+                    if let Err(e) = std::fs::write(file, &fixed) {
+                        eprintln!("error: could not write `{}`: {}", path, e);
+                        code = EXIT_IO;
+                        continue;
+                    }
+                    diagnostics = fastobo_lint::lint(&fixed).1;
+                    text = fixed;
+                }
+            }
+        }
+
+        if report(&args, &path, &text, &diagnostics) && code == EXIT_OK {
+            code = EXIT_LINT;
+        }
+    }
+
+    exit(code)
+}
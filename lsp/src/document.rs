@@ -0,0 +1,432 @@
+//! Analysis of an open OBO document.
+
+use std::collections::HashMap;
+use std::ops::Range as LineRange;
+
+use fastobo::ast::EntityFrame;
+use fastobo::ast::OboDoc;
+use fastobo::diagnostic::locate_frames;
+use fastobo::diagnostic::Severity;
+use fastobo::semantics::Identified;
+use lsp_types::CompletionItem;
+use lsp_types::CompletionItemKind;
+use lsp_types::Diagnostic;
+use lsp_types::DiagnosticSeverity;
+use lsp_types::DocumentSymbol;
+use lsp_types::MarkupContent;
+use lsp_types::MarkupKind;
+use lsp_types::NumberOrString;
+use lsp_types::Position;
+use lsp_types::Range;
+use lsp_types::SymbolKind;
+
+/// The tags of `[Typedef]` clauses whose values are relations.
+const RELATION_TAGS: &[&str] = &[
+    "is_a",
+    "intersection_of",
+    "union_of",
+    "equivalent_to",
+    "disjoint_from",
+    "inverse_of",
+    "transitive_over",
+    "equivalent_to_chain",
+    "disjoint_over",
+    "holds_over_chain",
+    "replaced_by",
+];
+
+/// The kind of an entity frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameKind {
+    Term,
+    Typedef,
+    Instance,
+}
+
+impl FrameKind {
+    fn from_header(line: &str) -> Option<Self> {
+        match line.trim() {
+            "[Term]" => Some(FrameKind::Term),
+            "[Typedef]" => Some(FrameKind::Typedef),
+            "[Instance]" => Some(FrameKind::Instance),
+            _ => None,
+        }
+    }
+
+    fn symbol_kind(self) -> SymbolKind {
+        match self {
+            FrameKind::Term => SymbolKind::CLASS,
+            FrameKind::Typedef => SymbolKind::PROPERTY,
+            FrameKind::Instance => SymbolKind::OBJECT,
+        }
+    }
+
+    fn completion_kind(self) -> CompletionItemKind {
+        match self {
+            FrameKind::Term => CompletionItemKind::CLASS,
+            FrameKind::Typedef => CompletionItemKind::PROPERTY,
+            FrameKind::Instance => CompletionItemKind::VALUE,
+        }
+    }
+}
+
+/// The location of an entity frame in the source text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct FrameLines {
+    kind: FrameKind,
+    id: String,
+    lines: LineRange<usize>,
+    id_line: usize,
+}
+
+/// An open OBO document, with the results of its analysis.
+///
+/// The syntax tree does not record source positions, so frames are located
+/// in the source text with `fastobo::diagnostic::locate_frames`, while names
+/// and definitions are taken from the syntax tree when the document parses
+/// successfully.
+#[derive(Debug)]
+pub struct Document {
+    lines: Vec<String>,
+    doc: Option<OboDoc>,
+    entities: HashMap<String, usize>,
+    frames: Vec<FrameLines>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Document {
+    /// Parse and analyze the given source text.
+    pub fn new(text: &str) -> Self {
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        let starts = line_starts(text);
+        let (doc, lints) = fastobo_lint::lint(text);
+
+        let diagnostics = lints
+            .iter()
+            .map(|lint| {
+                let range = match lint.labels().first() {
+                    Some(label) => Range::new(
+                        text_position(text, &starts, label.range().start),
+                        text_position(text, &starts, label.range().end),
+                    ),
+                    None => Range::default(),
+                };
+                let severity = match lint.severity() {
+                    Severity::Error => DiagnosticSeverity::ERROR,
+                    Severity::Warning => DiagnosticSeverity::WARNING,
+                    Severity::Note => DiagnosticSeverity::INFORMATION,
+                };
+                Diagnostic {
+                    range,
+                    severity: Some(severity),
+                    code: Some(NumberOrString::String(lint.code().to_string())),
+                    source: Some("fastobo".to_string()),
+                    message: lint.message().to_string(),
+                    ..Diagnostic::default()
+                }
+            })
+            .collect();
+
+        let entities = doc
+            .iter()
+            .flat_map(|doc| doc.entities().iter().enumerate())
+            .map(|(i, frame)| (frame.as_id().to_string(), i))
+            .collect();
+
+        Self {
+            frames: scan_frames(text, &starts),
+            lines,
+            doc,
+            entities,
+            diagnostics,
+        }
+    }
+
+    /// Get the diagnostics found in the document.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Get the entity frame with the given identifier, if the document parsed.
+    fn entity(&self, id: &str) -> Option<&EntityFrame> {
+        let index = *self.entities.get(id)?;
+        self.doc.as_ref().map(|doc| &doc.entities()[index])
+    }
+
+    /// Get the frame containing the given line.
+    fn frame_at(&self, line: usize) -> Option<&FrameLines> {
+        self.frames.iter().find(|f| f.lines.contains(&line))
+    }
+
+    /// Get the identifier under the cursor, with its range.
+    fn word_at(&self, position: Position) -> Option<(&str, Range)> {
+        let line = self.lines.get(position.line as usize)?;
+        let offset = byte_offset(line, position.character);
+        let delimiter = |c: char| c.is_whitespace() || "[]{},\"!".contains(c);
+        let start = line[..offset]
+            .rfind(delimiter)
+            .map(|i| i + line[i..].chars().next().unwrap().len_utf8())
+            .unwrap_or(0);
+        let end = line[offset..]
+            .find(delimiter)
+            .map(|i| offset + i)
+            .unwrap_or_else(|| line.len());
+        let word = &line[start..end];
+        if word.is_empty() || word.ends_with(':') {
+            return None;
+        }
+        let line_no = position.line as usize;
+        Some((
+            word,
+            Range::new(
+                lsp_position(line, line_no, start),
+                lsp_position(line, line_no, end),
+            ),
+        ))
+    }
+
+    /// Get the range of the opening line of the frame declaring the identifier under the cursor.
+    pub fn definition(&self, position: Position) -> Option<Range> {
+        let (word, _) = self.word_at(position)?;
+        let frame = self.frames.iter().find(|f| f.id == word)?;
+        let line = &self.lines[frame.lines.start];
+        Some(Range::new(
+            lsp_position(line, frame.lines.start, 0),
+            lsp_position(line, frame.lines.start, line.len()),
+        ))
+    }
+
+    /// Get the name and definition of the entity under the cursor.
+    pub fn hover(&self, position: Position) -> Option<(MarkupContent, Range)> {
+        let (word, range) = self.word_at(position)?;
+        let entity = self.entity(word)?;
+        let mut value = match entity.name() {
+            Ok(name) => format!("**{}** (`{}`)", name.as_str(), word),
+            Err(_) => format!("`{}`", word),
+        };
+        if let Ok(def) = entity.definition() {
+            value.push_str("\n\n");
+            value.push_str(def.text().as_str());
+        }
+        let content = MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        };
+        Some((content, range))
+    }
+
+    /// Get the identifiers that can be written at the cursor.
+    ///
+    /// Relations are proposed for the first argument of `relationship`
+    /// clauses and for the relation-valued clauses of `[Typedef]` frames,
+    /// classes and instances everywhere else.
+    pub fn completions(&self, position: Position) -> Vec<CompletionItem> {
+        let line = match self.lines.get(position.line as usize) {
+            Some(line) => line,
+            None => return Vec::new(),
+        };
+        let before = &line[..byte_offset(line, position.character)];
+        let (tag, value) = match before.trim_start().split_once(':') {
+            Some((tag, value)) => (tag, value.trim_start()),
+            None => return Vec::new(),
+        };
+
+        let in_typedef = self
+            .frame_at(position.line as usize)
+            .map(|f| f.kind == FrameKind::Typedef)
+            .unwrap_or(false);
+        let relations = (tag == "relationship" && !value.contains(char::is_whitespace))
+            || (in_typedef && RELATION_TAGS.contains(&tag));
+        let wanted = |kind: FrameKind| (kind == FrameKind::Typedef) == relations;
+
+        self.frames
+            .iter()
+            .filter(|frame| wanted(frame.kind))
+            .map(|frame| CompletionItem {
+                label: frame.id.clone(),
+                kind: Some(frame.kind.completion_kind()),
+                detail: self
+                    .entity(&frame.id)
+                    .and_then(|e| e.name().ok())
+                    .map(|name| name.as_str().to_string()),
+                ..CompletionItem::default()
+            })
+            .collect()
+    }
+
+    /// Get a symbol for each entity frame of the document.
+    #[allow(deprecated)]
+    pub fn symbols(&self) -> Vec<DocumentSymbol> {
+        self.frames
+            .iter()
+            .map(|frame| {
+                let last = frame.lines.end - 1;
+                let range = Range::new(
+                    Position::new(frame.lines.start as u32, 0),
+                    lsp_position(&self.lines[last], last, self.lines[last].len()),
+                );
+                let id_line = &self.lines[frame.id_line];
+                let selection_range = Range::new(
+                    Position::new(frame.id_line as u32, 0),
+                    lsp_position(id_line, frame.id_line, id_line.len()),
+                );
+                DocumentSymbol {
+                    name: frame.id.clone(),
+                    detail: self
+                        .entity(&frame.id)
+                        .and_then(|e| e.name().ok())
+                        .map(|name| name.as_str().to_string()),
+                    kind: frame.kind.symbol_kind(),
+                    tags: None,
+                    deprecated: None,
+                    range,
+                    selection_range,
+                    children: None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Locate the entity frames of a source text.
+fn scan_frames(text: &str, starts: &[usize]) -> Vec<FrameLines> {
+    locate_frames(text)
+        .into_iter()
+        .filter_map(|frame| {
+            let range = frame.range();
+            Some(FrameLines {
+                kind: FrameKind::from_header(&text[frame.header()])?,
+                id: text[frame.id()].to_string(),
+                lines: line_index(starts, range.start)..line_index(starts, range.end) + 1,
+                id_line: line_index(starts, frame.id().start),
+            })
+        })
+        .collect()
+}
+
+/// Get the byte offsets where the lines of a source text start.
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// Get the index of the line containing a byte offset.
+fn line_index(starts: &[usize], offset: usize) -> usize {
+    starts.partition_point(|&start| start <= offset) - 1
+}
+
+/// Convert a UTF-16 column into a byte offset within a line.
+fn byte_offset(line: &str, character: u32) -> usize {
+    let mut units = 0;
+    for (offset, c) in line.char_indices() {
+        if units >= character as usize {
+            return offset;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+/// Convert a byte offset within a line into a LSP position.
+///
+/// Offsets past the end of the line or inside a character are moved back
+/// to the closest character boundary.
+fn lsp_position(line: &str, index: usize, offset: usize) -> Position {
+    let mut offset = offset.min(line.len());
+    while !line.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let character = line[..offset].encode_utf16().count();
+    Position::new(index as u32, character as u32)
+}
+
+/// Convert a byte offset within the document into a LSP position.
+fn text_position(text: &str, starts: &[usize], offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let index = line_index(starts, offset);
+    lsp_position(&text[starts[index]..], index, offset - starts[index])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const TEXT: &str = concat!(
+        "default-namespace: test\n",
+        "\n",
+        "[Term]\n",
+        "id: TST:001\n",
+        "name: première\n",
+        "def: \"The first term.\" []\n",
+        "\n",
+        "[Term]\n",
+        "id: TST:002\n",
+        "is_a: TST:001 ! première\n",
+        "relationship: part_of TST:001\n",
+        "\n",
+        "[Typedef]\n",
+        "id: part_of\n",
+        "is_a: \n",
+    );
+
+    #[test]
+    fn word_at() {
+        let doc = Document::new(TEXT);
+        let (word, range) = doc.word_at(Position::new(9, 8)).unwrap();
+        assert_eq!(word, "TST:001");
+        assert_eq!(range, Range::new(Position::new(9, 6), Position::new(9, 13)));
+        assert!(doc.word_at(Position::new(9, 2)).is_none());
+        assert!(doc.word_at(Position::new(1, 0)).is_none());
+    }
+
+    #[test]
+    fn utf16_positions() {
+        let line = "name: première";
+        assert_eq!(lsp_position(line, 0, line.len()), Position::new(0, 14));
+        assert_eq!(byte_offset(line, 14), line.len());
+        assert_eq!(byte_offset(line, 11), 11);
+        assert_eq!(byte_offset(line, 12), 13);
+    }
+
+    #[test]
+    fn non_ascii_syntax_error() {
+        let text = "[Term]\nid: TST:001\ndef: \"éééééééééé\" [PMID:1\n";
+        let doc = Document::new(text);
+        assert_eq!(doc.diagnostics().len(), 1);
+        assert_eq!(doc.diagnostics()[0].range.start, Position::new(2, 25));
+        assert_eq!(lsp_position("é", 0, 1), Position::new(0, 0));
+    }
+
+    #[test]
+    fn scan_frames() {
+        let doc = Document::new(TEXT);
+        let ids: Vec<_> = doc
+            .frames
+            .iter()
+            .map(|f| (f.id.as_str(), f.lines.clone()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![("TST:001", 2..6), ("TST:002", 7..11), ("part_of", 12..15)]
+        );
+    }
+
+    #[test]
+    fn completions() {
+        let doc = Document::new(TEXT);
+        let labels = |line, character| {
+            doc.completions(Position::new(line, character))
+                .into_iter()
+                .map(|item| item.label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(9, 6), vec!["TST:001", "TST:002"]);
+        assert_eq!(labels(10, 14), vec!["part_of"]);
+        assert_eq!(labels(10, 22), vec!["TST:001", "TST:002"]);
+        assert_eq!(labels(14, 6), vec!["part_of"]);
+        assert_eq!(labels(1, 0), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,29 @@
+//! A Language Server Protocol implementation for OBO documents.
+//!
+//! The server is built on top of [`fastobo`] and [`fastobo-lint`], and
+//! provides the following features to editors:
+//!
+//! * diagnostics for syntax errors and semantic problems,
+//! * go-to-definition from a referenced identifier to its frame,
+//! * hover showing the name and definition of an entity,
+//! * completion of class and relation identifiers,
+//! * a document symbol for each entity frame.
+//!
+//! [`fastobo`]: https://docs.rs/fastobo
+//! [`fastobo-lint`]: https://docs.rs/fastobo-lint
+
+extern crate fastobo;
+extern crate fastobo_lint;
+extern crate lsp_server;
+extern crate lsp_types;
+extern crate serde;
+extern crate serde_json;
+
+#[cfg(test)]
+extern crate pretty_assertions;
+
+pub mod document;
+pub mod server;
+
+pub use self::server::capabilities;
+pub use self::server::run;
@@ -0,0 +1,13 @@
+//! Language server for OBO documents, communicating over standard streams.
+
+extern crate fastobo_lsp;
+extern crate lsp_server;
+
+use lsp_server::Connection;
+
+fn main() -> Result<(), fastobo_lsp::server::ServerError> {
+    let (connection, threads) = Connection::stdio();
+    fastobo_lsp::run(connection)?;
+    threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,467 @@
+//! The language server, handling messages from the client.
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use lsp_server::Connection;
+use lsp_server::ErrorCode;
+use lsp_server::Message;
+use lsp_server::Notification;
+use lsp_server::Request;
+use lsp_server::Response;
+use lsp_types::notification::DidChangeTextDocument;
+use lsp_types::notification::DidCloseTextDocument;
+use lsp_types::notification::DidOpenTextDocument;
+use lsp_types::notification::LogMessage;
+use lsp_types::notification::Notification as _;
+use lsp_types::notification::PublishDiagnostics;
+use lsp_types::request::Completion;
+use lsp_types::request::DocumentSymbolRequest;
+use lsp_types::request::GotoDefinition;
+use lsp_types::request::HoverRequest;
+use lsp_types::request::Request as _;
+use lsp_types::CompletionOptions;
+use lsp_types::CompletionParams;
+use lsp_types::CompletionResponse;
+use lsp_types::DidChangeTextDocumentParams;
+use lsp_types::DidCloseTextDocumentParams;
+use lsp_types::DidOpenTextDocumentParams;
+use lsp_types::DocumentSymbolParams;
+use lsp_types::DocumentSymbolResponse;
+use lsp_types::GotoDefinitionParams;
+use lsp_types::GotoDefinitionResponse;
+use lsp_types::Hover;
+use lsp_types::HoverContents;
+use lsp_types::HoverParams;
+use lsp_types::HoverProviderCapability;
+use lsp_types::Location;
+use lsp_types::LogMessageParams;
+use lsp_types::MessageType;
+use lsp_types::OneOf;
+use lsp_types::PublishDiagnosticsParams;
+use lsp_types::ServerCapabilities;
+use lsp_types::TextDocumentSyncCapability;
+use lsp_types::TextDocumentSyncKind;
+use lsp_types::Url;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::document::Document;
+
+/// The error type of the language server.
+pub type ServerError = Box<dyn Error + Send + Sync>;
+
+/// Get the capabilities advertised by the language server.
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string(), " ".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    }
+}
+
+/// Run the language server on the given connection until it is shut down.
+pub fn run(connection: Connection) -> Result<(), ServerError> {
+    let capabilities = serde_json::to_value(capabilities())?;
+    connection.initialize(capabilities)?;
+
+    let mut server = Server::default();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let response = server.handle_request(request);
+                connection.sender.send(response.into())?;
+            }
+            Message::Notification(notification) => {
+                // NB: a malformed notification must not stop the server, so
+                // the error is logged to the client instead.
+                let method = notification.method.clone();
+                let reply = match server.handle_notification(notification) {
+                    Ok(diagnostics) => diagnostics,
+                    Err(e) => {
+                        let params = LogMessageParams {
+                            typ: MessageType::ERROR,
+                            message: format!("invalid `{}` notification: {}", method, e),
+                        };
+                        Some(Notification::new(LogMessage::METHOD.to_string(), params))
+                    }
+                };
+                if let Some(reply) = reply {
+                    connection.sender.send(reply.into())?;
+                }
+            }
+            Message::Response(_) => (),
+        }
+    }
+    Ok(())
+}
+
+/// The state of the language server: the documents opened by the client.
+#[derive(Debug, Default)]
+struct Server {
+    documents: HashMap<Url, Document>,
+}
+
+impl Server {
+    fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            GotoDefinition::METHOD => self.dispatch(request, Self::definition),
+            HoverRequest::METHOD => self.dispatch(request, Self::hover),
+            Completion::METHOD => self.dispatch(request, Self::completion),
+            DocumentSymbolRequest::METHOD => self.dispatch(request, Self::symbols),
+            method => {
+                let message = format!("unsupported method `{}`", method);
+                return Response::new_err(id, ErrorCode::MethodNotFound as i32, message);
+            }
+        };
+        match result {
+            Ok(value) => Response::new_ok(id, value),
+            Err(e) => Response::new_err(id, ErrorCode::InvalidParams as i32, e.to_string()),
+        }
+    }
+
+    fn dispatch<P, R, F>(&self, request: Request, handler: F) -> Result<Value, serde_json::Error>
+    where
+        P: DeserializeOwned,
+        R: Serialize,
+        F: Fn(&Self, P) -> Option<R>,
+    {
+        let params = serde_json::from_value(request.params)?;
+        serde_json::to_value(handler(self, params))
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<Option<Notification>, ServerError> {
+        let (uri, version) = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = Document::new(&params.text_document.text);
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), document);
+                (uri, Some(params.text_document.version))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents
+                        .insert(uri.clone(), Document::new(&change.text));
+                }
+                (uri, Some(params.text_document.version))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                (params.text_document.uri, None)
+            }
+            _ => return Ok(None),
+        };
+
+        let diagnostics = self
+            .documents
+            .get(&uri)
+            .map(|doc| doc.diagnostics().to_vec())
+            .unwrap_or_default();
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, version);
+        Ok(Some(Notification::new(
+            PublishDiagnostics::METHOD.to_string(),
+            params,
+        )))
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let range = self.documents.get(&uri)?.definition(position.position)?;
+        Some(GotoDefinitionResponse::Scalar(Location::new(uri, range)))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let (content, range) = document.hover(position.position)?;
+        Some(Hover {
+            contents: HoverContents::Markup(content),
+            range: Some(range),
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let document = self.documents.get(&position.text_document.uri)?;
+        Some(CompletionResponse::Array(
+            document.completions(position.position),
+        ))
+    }
+
+    fn symbols(&self, params: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+        let document = self.documents.get(&params.text_document.uri)?;
+        Some(DocumentSymbolResponse::Nested(document.symbols()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::thread::JoinHandle;
+
+    use lsp_server::RequestId;
+    use lsp_types::*;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use fastobo::diagnostic::code;
+
+    const TEXT: &str = concat!(
+        "format-version: 1.4\n",
+        "default-namespace: test\n",
+        "\n",
+        "[Term]\n",
+        "id: TST:001\n",
+        "name: first\n",
+        "def: \"The first term.\" []\n",
+        "\n",
+        "[Term]\n",
+        "id: TST:002\n",
+        "name: second\n",
+        "is_a: TST:001\n",
+        "relationship: part_of TST:003\n",
+        "\n",
+        "[Typedef]\n",
+        "id: part_of\n",
+    );
+
+    /// A language client running the server in another thread.
+    struct Client {
+        connection: Connection,
+        server: Option<JoinHandle<()>>,
+        next_id: i32,
+    }
+
+    impl Client {
+        fn new() -> Self {
+            let (server, connection) = Connection::memory();
+            let server = std::thread::spawn(move || run(server).unwrap());
+            let mut client = Self {
+                connection,
+                server: Some(server),
+                next_id: 0,
+            };
+            let result: InitializeResult =
+                client.request::<request::Initialize>(InitializeParams::default());
+            assert_eq!(result.capabilities, capabilities());
+            client.notify::<notification::Initialized>(InitializedParams {});
+            client
+        }
+
+        fn request<R: request::Request>(&mut self, params: R::Params) -> R::Result {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), R::METHOD.to_string(), params);
+            self.connection.sender.send(request.into()).unwrap();
+            match self.connection.receiver.recv().unwrap() {
+                Message::Response(response) => {
+                    assert_eq!(response.id, id);
+                    serde_json::from_value(response.result.unwrap()).unwrap()
+                }
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+
+        fn notify<N: notification::Notification>(&mut self, params: N::Params) {
+            let notification = Notification::new(N::METHOD.to_string(), params);
+            self.connection.sender.send(notification.into()).unwrap();
+        }
+
+        fn open(&mut self, uri: &Url, text: &str) -> PublishDiagnosticsParams {
+            self.notify::<notification::DidOpenTextDocument>(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    "obo".to_string(),
+                    1,
+                    text.to_string(),
+                ),
+            });
+            match self.connection.receiver.recv().unwrap() {
+                Message::Notification(n) if n.method == PublishDiagnostics::METHOD => {
+                    serde_json::from_value(n.params).unwrap()
+                }
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+
+        fn position(uri: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+            TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                Position::new(line, character),
+            )
+        }
+    }
+
+    impl Drop for Client {
+        fn drop(&mut self) {
+            self.request::<request::Shutdown>(());
+            self.notify::<notification::Exit>(());
+            if let Some(server) = self.server.take() {
+                server.join().unwrap();
+            }
+        }
+    }
+
+    fn uri() -> Url {
+        Url::parse("file:///tmp/test.obo").unwrap()
+    }
+
+    #[test]
+    fn diagnostics() {
+        let mut client = Client::new();
+        let published = client.open(&uri(), TEXT);
+        assert_eq!(published.version, Some(1));
+        let messages: Vec<_> = published
+            .diagnostics
+            .iter()
+            .map(|d| (d.range.start.line, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![(12, "reference to undeclared entity `TST:003`")]
+        );
+
+        let published = client.open(&uri(), "[Term]\nid TST:001\n");
+        assert_eq!(published.diagnostics.len(), 1);
+        let diagnostic = &published.diagnostics[0];
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String(code::MISSING_ID.to_string()))
+        );
+        assert_eq!(diagnostic.range.start.line, 1);
+    }
+
+    #[test]
+    fn invalid_notification() {
+        let mut client = Client::new();
+        let notification = Notification::new(
+            notification::DidOpenTextDocument::METHOD.to_string(),
+            serde_json::json!({ "textDocument": null }),
+        );
+        client.connection.sender.send(notification.into()).unwrap();
+        match client.connection.receiver.recv().unwrap() {
+            Message::Notification(n) if n.method == LogMessage::METHOD => {
+                let params: LogMessageParams = serde_json::from_value(n.params).unwrap();
+                assert_eq!(params.typ, MessageType::ERROR);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        let published = client.open(&uri(), TEXT);
+        assert_eq!(published.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn goto_definition() {
+        let mut client = Client::new();
+        client.open(&uri(), TEXT);
+        let response = client.request::<request::GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: Client::position(&uri(), 11, 8),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        let expected = Location::new(uri(), Range::new(Position::new(3, 0), Position::new(3, 6)));
+        assert_eq!(response, Some(GotoDefinitionResponse::Scalar(expected)));
+
+        let response = client.request::<request::GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: Client::position(&uri(), 12, 25),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        assert_eq!(response, None);
+    }
+
+    #[test]
+    fn hover() {
+        let mut client = Client::new();
+        client.open(&uri(), TEXT);
+        let response = client.request::<request::HoverRequest>(HoverParams {
+            text_document_position_params: Client::position(&uri(), 11, 8),
+            work_done_progress_params: Default::default(),
+        });
+        let expected = Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "**first** (`TST:001`)\n\nThe first term.".to_string(),
+            }),
+            range: Some(Range::new(Position::new(11, 6), Position::new(11, 13))),
+        };
+        assert_eq!(response, Some(expected));
+    }
+
+    #[test]
+    fn completion() {
+        let mut client = Client::new();
+        client.open(&uri(), TEXT);
+        let response = client.request::<request::Completion>(CompletionParams {
+            text_document_position: Client::position(&uri(), 12, 14),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        });
+        let items = match response {
+            Some(CompletionResponse::Array(items)) => items,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "part_of");
+        assert_eq!(items[0].kind, Some(CompletionItemKind::PROPERTY));
+    }
+
+    #[test]
+    fn document_symbols() {
+        let mut client = Client::new();
+        client.open(&uri(), TEXT);
+        let response = client.request::<request::DocumentSymbolRequest>(DocumentSymbolParams {
+            text_document: TextDocumentIdentifier::new(uri()),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        let symbols = match response {
+            Some(DocumentSymbolResponse::Nested(symbols)) => symbols,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        let summary: Vec<_> = symbols
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.detail.as_deref(),
+                    s.kind,
+                    s.range.start.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("TST:001", Some("first"), SymbolKind::CLASS, 3),
+                ("TST:002", Some("second"), SymbolKind::CLASS, 8),
+                ("part_of", None, SymbolKind::PROPERTY, 14),
+            ]
+        );
+    }
+}
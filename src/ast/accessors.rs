@@ -0,0 +1,195 @@
+//! Macros generating typed accessors for the clauses of entity frames.
+//!
+//! Each macro expands to inherent methods, and is meant to be invoked in an
+//! `impl` block of a frame type with a `clauses: Vec<Line<Clause>>` field.
+//! The kind of accessors generated follows the cardinality of the clause:
+//!
+//! * `single_clause!` for clauses appearing at most once (or exactly once),
+//!   with a getter, a setter replacing the existing clause, and optionally
+//!   a method to remove it;
+//! * `flag_clause!` for boolean clauses appearing at most once;
+//! * `many_clause!` and `pair_clause!` for clauses appearing any number of
+//!   times, with an iterator getter, and methods to add or remove values;
+//! * `not_one_clause!` for clauses that cannot appear exactly once, with an
+//!   iterator getter and a setter replacing all the clauses at once.
+
+use std::mem::discriminant;
+
+use super::Line;
+
+/// Replace the first line with the same clause variant, removing the others.
+///
+/// The replaced line is rebuilt from the new clause, so the qualifiers and
+/// comment describing the previous value are dropped. If no line has the
+/// same variant, a new line is added at the end of the frame.
+pub(crate) fn set_clause<C>(lines: &mut Vec<Line<C>>, clause: C) {
+    let variant = discriminant(&clause);
+    match lines
+        .iter()
+        .position(|line| discriminant(line.as_inner()) == variant)
+    {
+        Some(index) => {
+            lines[index] = Line::from(clause);
+            let mut rest = lines.split_off(index + 1);
+            rest.retain(|line| discriminant(line.as_inner()) != variant);
+            lines.append(&mut rest);
+        }
+        None => lines.push(Line::from(clause)),
+    }
+}
+
+/// Remove the lines with a clause matching the given predicate.
+///
+/// Returns `true` if at least one line was removed.
+pub(crate) fn remove_clauses<C, F>(lines: &mut Vec<Line<C>>, predicate: F) -> bool
+where
+    F: Fn(&C) -> bool,
+{
+    let len = lines.len();
+    lines.retain(|line| !predicate(line.as_inner()));
+    lines.len() != len
+}
+
+macro_rules! single_clause {
+    ($clause:ident::$variant:ident($ty:ty), $tag:literal, $get:ident, $set:ident) => {
+        #[doc = concat!("Get the `", $tag, "` clause of the frame, if exactly one is declared.")]
+        pub fn $get(&self) -> Result<&$ty, CardinalityError> {
+            let mut value: Option<&$ty> = None;
+            for line in &self.clauses {
+                if let $clause::$variant(v) = line.as_inner() {
+                    match value {
+                        Some(_) => return Err(CardinalityError::duplicate($tag)),
+                        None => value = Some(v),
+                    }
+                }
+            }
+            value.ok_or_else(|| CardinalityError::missing($tag))
+        }
+
+        #[doc = concat!("Set the `", $tag, "` clause of the frame, replacing any existing one.")]
+        pub fn $set<V: Into<$ty>>(&mut self, value: V) {
+            let clause = $clause::$variant(Box::new(value.into()));
+            crate::ast::accessors::set_clause(&mut self.clauses, clause)
+        }
+    };
+    ($clause:ident::$variant:ident($ty:ty), $tag:literal, $get:ident, $set:ident, $clear:ident) => {
+        single_clause!($clause::$variant($ty), $tag, $get, $set);
+
+        #[doc = concat!("Remove the `", $tag, "` clause of the frame, returning whether one was declared.")]
+        pub fn $clear(&mut self) -> bool {
+            crate::ast::accessors::remove_clauses(&mut self.clauses, |c| {
+                matches!(c, $clause::$variant(_))
+            })
+        }
+    };
+}
+
+macro_rules! flag_clause {
+    ($clause:ident::$variant:ident, $tag:literal, $get:ident, $set:ident) => {
+        #[doc = concat!("Check whether the frame declares `", $tag, ": true`.")]
+        pub fn $get(&self) -> bool {
+            self.clauses
+                .iter()
+                .any(|line| matches!(line.as_inner(), $clause::$variant(true)))
+        }
+
+        #[doc = concat!("Set the `", $tag, "` clause of the frame, removing it when `false`.")]
+        pub fn $set(&mut self, value: bool) {
+            if value {
+                crate::ast::accessors::set_clause(&mut self.clauses, $clause::$variant(true));
+            } else {
+                crate::ast::accessors::remove_clauses(&mut self.clauses, |c| {
+                    matches!(c, $clause::$variant(_))
+                });
+            }
+        }
+    };
+}
+
+macro_rules! many_clause {
+    ($clause:ident::$variant:ident($ty:ty), $tag:literal, $get:ident, $add:ident, $remove:ident) => {
+        #[doc = concat!("Get an iterator over the values of the `", $tag, "` clauses of the frame.")]
+        pub fn $get(&self) -> impl Iterator<Item = &$ty> + '_ {
+            self.clauses.iter().filter_map(|line| match line.as_inner() {
+                $clause::$variant(v) => Some(v.as_ref()),
+                _ => None,
+            })
+        }
+
+        #[doc = concat!("Add a `", $tag, "` clause to the frame.")]
+        pub fn $add<V: Into<$ty>>(&mut self, value: V) {
+            let clause = $clause::$variant(Box::new(value.into()));
+            self.clauses.push(Line::from(clause));
+        }
+
+        #[doc = concat!("Remove the `", $tag, "` clauses with the given value, returning whether any was declared.")]
+        pub fn $remove(&mut self, value: &$ty) -> bool {
+            crate::ast::accessors::remove_clauses(&mut self.clauses, |c| {
+                matches!(c, $clause::$variant(v) if v.as_ref() == value)
+            })
+        }
+    };
+}
+
+macro_rules! pair_clause {
+    ($clause:ident::$variant:ident($a:ty, $b:ty), $tag:literal, $get:ident, $add:ident, $remove:ident) => {
+        #[doc = concat!("Get an iterator over the values of the `", $tag, "` clauses of the frame.")]
+        pub fn $get(&self) -> impl Iterator<Item = (&$a, &$b)> + '_ {
+            self.clauses.iter().filter_map(|line| match line.as_inner() {
+                $clause::$variant(a, b) => Some((a.as_ref(), b.as_ref())),
+                _ => None,
+            })
+        }
+
+        #[doc = concat!("Add a `", $tag, "` clause to the frame.")]
+        pub fn $add<A: Into<$a>, B: Into<$b>>(&mut self, a: A, b: B) {
+            let clause = $clause::$variant(Box::new(a.into()), Box::new(b.into()));
+            self.clauses.push(Line::from(clause));
+        }
+
+        #[doc = concat!("Remove the `", $tag, "` clauses with the given values, returning whether any was declared.")]
+        pub fn $remove(&mut self, a: &$a, b: &$b) -> bool {
+            crate::ast::accessors::remove_clauses(&mut self.clauses, |c| {
+                matches!(c, $clause::$variant(x, y) if x.as_ref() == a && y.as_ref() == b)
+            })
+        }
+    };
+}
+
+macro_rules! not_one_clause {
+    ($clause:ident::$variant:ident($ty:ty), $tag:literal, $get:ident, $set:ident) => {
+        #[doc = concat!("Get an iterator over the values of the `", $tag, "` clauses of the frame.")]
+        pub fn $get(&self) -> impl Iterator<Item = &$ty> + '_ {
+            self.clauses
+                .iter()
+                .filter_map(|line| match line.as_inner() {
+                    $clause::$variant(v) => Some(v.as_ref()),
+                    _ => None,
+                })
+        }
+
+        #[doc = concat!("Replace the `", $tag, "` clauses of the frame with the given values.")]
+        #[doc = ""]
+        #[doc = "# Errors"]
+        #[doc = "Returns a `CardinalityError` and leaves the frame unchanged if exactly"]
+        #[doc = concat!("one value is given, since a single `", $tag, "` clause is invalid.")]
+        pub fn $set<I>(&mut self, values: I) -> Result<(), CardinalityError>
+        where
+            I: IntoIterator,
+            I::Item: Into<$ty>,
+        {
+            let clauses: Vec<_> = values
+                .into_iter()
+                .map(|v| Line::from($clause::$variant(Box::new(v.into()))))
+                .collect();
+            if clauses.len() == 1 {
+                return Err(CardinalityError::single($tag));
+            }
+            crate::ast::accessors::remove_clauses(&mut self.clauses, |c| {
+                matches!(c, $clause::$variant(..))
+            });
+            self.clauses.extend(clauses);
+            Ok(())
+        }
+    };
+}
@@ -0,0 +1,32 @@
+//! Macros generating the methods of the frame builders.
+//!
+//! Each macro expands to a method adding a single clause to the frame being
+//! built, and is meant to be invoked in the `impl` block of a builder type
+//! with a `clause` method.
+
+macro_rules! build_clause {
+    ($clause:ident::$variant:ident($ty:ty), $tag:literal, $method:ident) => {
+        #[doc = concat!("Add a `", $tag, "` clause to the frame.")]
+        pub fn $method<V: Into<$ty>>(self, value: V) -> Self {
+            self.clause($clause::$variant(Box::new(value.into())))
+        }
+    };
+}
+
+macro_rules! build_flag {
+    ($clause:ident::$variant:ident, $tag:literal, $method:ident) => {
+        #[doc = concat!("Add a `", $tag, "` clause to the frame.")]
+        pub fn $method(self, value: bool) -> Self {
+            self.clause($clause::$variant(value))
+        }
+    };
+}
+
+macro_rules! build_pair {
+    ($clause:ident::$variant:ident($a:ty, $b:ty), $tag:literal, $method:ident) => {
+        #[doc = concat!("Add a `", $tag, "` clause to the frame.")]
+        pub fn $method<A: Into<$a>, B: Into<$b>>(self, a: A, b: B) -> Self {
+            self.clause($clause::$variant(Box::new(a.into()), Box::new(b.into())))
+        }
+    };
+}
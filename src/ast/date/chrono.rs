@@ -0,0 +1,177 @@
+//! Conversions between OBO datetimes and [`chrono`](https://docs.rs/chrono) types.
+
+use std::convert::TryFrom;
+
+use ::chrono::DateTime;
+use ::chrono::Datelike;
+use ::chrono::FixedOffset;
+use ::chrono::NaiveDate;
+use ::chrono::Offset;
+use ::chrono::TimeZone;
+use ::chrono::Timelike;
+
+use super::*;
+
+/// Build a `::chrono::NaiveDateTime`, assuming the components were validated.
+fn naive(
+    year: u16,
+    month: u8,
+    day: u8,
+    time: (u8, u8, u8),
+    nanos: u32,
+) -> Result<::chrono::NaiveDateTime, DateError> {
+    let (hour, minute, second) = time;
+    NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+        .ok_or(DateError::InvalidDate { year, month, day })?
+        .and_hms_nano_opt(hour.into(), minute.into(), second.into(), nanos)
+        .ok_or(DateError::InvalidTime {
+            hour,
+            minute,
+            second,
+        })
+}
+
+impl TryFrom<&NaiveDateTime> for ::chrono::NaiveDateTime {
+    type Error = DateError;
+    fn try_from(dt: &NaiveDateTime) -> Result<Self, Self::Error> {
+        dt.validate()?;
+        naive(dt.year, dt.month, dt.day, (dt.hour, dt.minute, 0), 0)
+    }
+}
+
+impl TryFrom<::chrono::NaiveDateTime> for NaiveDateTime {
+    type Error = DateError;
+    /// Convert a `::chrono::NaiveDateTime`, truncating the seconds.
+    fn try_from(dt: ::chrono::NaiveDateTime) -> Result<Self, Self::Error> {
+        Ok(NaiveDateTime::new(
+            dt.day() as u8,
+            dt.month() as u8,
+            obo_year(dt.year())?,
+            dt.hour() as u8,
+            dt.minute() as u8,
+        ))
+    }
+}
+
+impl TryFrom<&IsoDateTime> for ::chrono::NaiveDateTime {
+    type Error = DateError;
+    /// Convert an `IsoDateTime` to a local datetime, ignoring its timezone.
+    fn try_from(dt: &IsoDateTime) -> Result<Self, Self::Error> {
+        dt.validate()?;
+        let time = (dt.hour, dt.minute, dt.second);
+        let nanos = fraction_to_nanos(dt.fraction());
+        naive(dt.year, dt.month, dt.day, time, nanos)
+    }
+}
+
+impl TryFrom<&IsoDateTime> for DateTime<FixedOffset> {
+    type Error = DateError;
+    fn try_from(dt: &IsoDateTime) -> Result<Self, Self::Error> {
+        let offset = FixedOffset::try_from(dt.timezone().ok_or(DateError::MissingTimezone)?)?;
+        let local = ::chrono::NaiveDateTime::try_from(dt)?;
+        offset
+            .from_local_datetime(&local)
+            .single()
+            .ok_or(DateError::InvalidDate {
+                year: dt.year,
+                month: dt.month,
+                day: dt.day,
+            })
+    }
+}
+
+impl TryFrom<::chrono::NaiveDateTime> for IsoDateTime {
+    type Error = DateError;
+    /// Convert a `::chrono::NaiveDateTime` to an `IsoDateTime` without a timezone.
+    fn try_from(dt: ::chrono::NaiveDateTime) -> Result<Self, Self::Error> {
+        Ok(IsoDateTime {
+            year: obo_year(dt.year())?,
+            month: dt.month() as u8,
+            day: dt.day() as u8,
+            hour: dt.hour() as u8,
+            minute: dt.minute() as u8,
+            second: dt.second() as u8,
+            fraction: nanos_to_fraction(dt.nanosecond().min(999_999_999)),
+            timezone: None,
+        })
+    }
+}
+
+impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for IsoDateTime {
+    type Error = DateError;
+    fn try_from(dt: DateTime<Tz>) -> Result<Self, Self::Error> {
+        let timezone = IsoTimezone::from(dt.offset().fix());
+        IsoDateTime::try_from(dt.naive_local()).map(|iso| iso.with_timezone(timezone))
+    }
+}
+
+impl TryFrom<&IsoTimezone> for FixedOffset {
+    type Error = DateError;
+    fn try_from(tz: &IsoTimezone) -> Result<Self, Self::Error> {
+        tz.validate()?;
+        FixedOffset::east_opt(i32::from(tz.offset_minutes()) * 60).ok_or_else(|| {
+            DateError::InvalidTimezone {
+                timezone: tz.clone(),
+            }
+        })
+    }
+}
+
+impl From<FixedOffset> for IsoTimezone {
+    /// Convert a `::chrono::FixedOffset`, truncating the seconds of the offset.
+    fn from(offset: FixedOffset) -> Self {
+        timezone_from_seconds(offset.local_minus_utc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn naive_roundtrip() {
+        let dt = NaiveDateTime::new(8, 5, 2019, 13, 2);
+        let chrono_dt = ::chrono::NaiveDateTime::try_from(&dt).unwrap();
+        assert_eq!(chrono_dt.to_string(), "2019-05-08 13:02:00");
+        assert_eq!(NaiveDateTime::try_from(chrono_dt), Ok(dt));
+
+        let invalid = NaiveDateTime::new(31, 2, 2019, 13, 2);
+        assert_eq!(
+            ::chrono::NaiveDateTime::try_from(&invalid),
+            Err(DateError::InvalidDate {
+                year: 2019,
+                month: 2,
+                day: 31
+            })
+        );
+    }
+
+    #[test]
+    fn iso_roundtrip() {
+        let dt = IsoDateTime::from_str("2017-01-24T14:41:36.5-03:30").unwrap();
+        let chrono_dt = DateTime::<FixedOffset>::try_from(&dt).unwrap();
+        assert_eq!(chrono_dt.timestamp(), 1485281496);
+        assert_eq!(chrono_dt.timestamp_subsec_millis(), 500);
+        assert_eq!(IsoDateTime::try_from(chrono_dt), Ok(dt));
+
+        let local = IsoDateTime::from_str("2017-01-24T14:41:36").unwrap();
+        assert_eq!(
+            DateTime::<FixedOffset>::try_from(&local),
+            Err(DateError::MissingTimezone)
+        );
+    }
+
+    #[test]
+    fn utc() {
+        let dt = ::chrono::Utc
+            .with_ymd_and_hms(2021, 1, 30, 8, 0, 0)
+            .unwrap();
+        let iso = IsoDateTime::try_from(dt).unwrap();
+        assert_eq!(iso.to_string(), "2021-01-30T08:00:00Z");
+    }
+}
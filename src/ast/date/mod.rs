@@ -0,0 +1,694 @@
+use std::cmp::Ord;
+use std::cmp::Ordering;
+use std::cmp::PartialOrd;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use fastobo_derive_internal::FromStr;
+use ordered_float::OrderedFloat;
+use pest::iterators::Pair;
+
+use crate::error::DateError;
+use crate::error::SyntaxError;
+use crate::parser::FromPair;
+use crate::syntax::Rule;
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "chrono")))]
+mod chrono;
+#[cfg(feature = "time")]
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "time")))]
+mod time;
+
+/// A trait for common operations on OBO datetimes.
+pub trait DateTime {
+    /// Generate an XML Schema datetime serialization of the `DateTime`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// let dt = NaiveDateTime::new(8, 5, 2019, 13, 2);
+    /// assert_eq!(dt.to_xsd_datetime(), "2019-05-08T13:02:00");
+    /// ```
+    fn to_xsd_datetime(&self) -> String;
+}
+
+/// A naive datetime, as found in header frames.
+///
+/// For historical reasons, OBO headers do not contain ISO datetimes but
+/// *day-month-year* dates, which can be confusing for US-based users.
+#[derive(Clone, Debug, Hash, Eq, FromStr, Ord, PartialEq, PartialOrd)]
+pub struct NaiveDateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+}
+
+impl NaiveDateTime {
+    /// Create a new `NaiveDateTime` without checking the date is valid.
+    ///
+    /// Use [`NaiveDateTime::try_new`](#method.try_new) to reject impossible
+    /// dates and times.
+    pub fn new(day: u8, month: u8, year: u16, hour: u8, minute: u8) -> Self {
+        NaiveDateTime {
+            day,
+            month,
+            year,
+            hour,
+            minute,
+        }
+    }
+
+    /// Create a new `NaiveDateTime`, checking the date and time are valid.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// assert!(NaiveDateTime::try_new(29, 2, 2020, 13, 2).is_ok());
+    /// assert!(NaiveDateTime::try_new(29, 2, 2019, 13, 2).is_err());
+    /// ```
+    pub fn try_new(day: u8, month: u8, year: u16, hour: u8, minute: u8) -> Result<Self, DateError> {
+        let dt = Self::new(day, month, year, hour, minute);
+        dt.validate().map(|_| dt)
+    }
+
+    /// Check the `NaiveDateTime` is a valid date and time.
+    pub fn validate(&self) -> Result<(), DateError> {
+        check_date(self.year, self.month, self.day)?;
+        check_time(self.hour, self.minute, 0)
+    }
+
+    /// Change the date component of the `NaiveDateTime`.
+    pub fn with_date(mut self, day: u8, month: u8, year: u16) -> Self {
+        self.day = day;
+        self.month = month;
+        self.year = year;
+        self
+    }
+
+    /// Change the time component of the `NaiveDateTime`.
+    pub fn with_time(mut self, hour: u8, minute: u8) -> Self {
+        self.hour = hour;
+        self.minute = minute;
+        self
+    }
+
+    /// Get the day of the `NaiveDateTime`.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Get the month of the `NaiveDateTime`.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Get the year of the `NaiveDateTime`.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the hour of the `NaiveDateTime`.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute of the `NaiveDateTime`.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+}
+
+impl DateTime for NaiveDateTime {
+    /// Generate an XML Schema datetime serialization of the `NaiveDateTime`.
+    ///
+    /// # Note
+    /// While `NaiveDateTime` structs do not store seconds, the `xsd:dateTime`
+    /// format requires all components to be present in the serialization, so
+    /// the date is initialized with seconds set to `0`.
+    fn to_xsd_datetime(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:00",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+impl Display for NaiveDateTime {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{:02}:{:02}:{:04} {:02}:{:02}",
+            self.day, self.month, self.year, self.hour, self.minute
+        )
+    }
+}
+
+impl<'i> FromPair<'i> for NaiveDateTime {
+    const RULE: Rule = Rule::NaiveDateTime;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self, SyntaxError> {
+        let mut inner = pair.into_inner();
+        let date = inner.next().unwrap();
+        let time = inner.next().unwrap();
+
+        let datestr = date.as_str();
+        let timestr = time.as_str();
+
+        Ok(NaiveDateTime {
+            day: datestr[..2].parse::<u8>().unwrap(),
+            month: datestr[3..5].parse::<u8>().unwrap(),
+            year: datestr[6..10].parse::<u16>().unwrap(),
+            hour: timestr[..2].parse::<u8>().unwrap(),
+            minute: timestr[3..5].parse::<u8>().unwrap(),
+        })
+    }
+}
+
+/// A comprehensive ISO-8601 datetime, as found in `creation_date` clauses.
+#[derive(Clone, Debug, Hash, Eq, FromStr, PartialEq, Ord, PartialOrd)]
+pub struct IsoDateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    fraction: Option<OrderedFloat<f32>>,
+    timezone: Option<IsoTimezone>,
+}
+
+impl IsoDateTime {
+    /// Create a new `IsoDateTime` without a timezone.
+    ///
+    /// The date is not checked to be valid: use
+    /// [`IsoDateTime::try_new`](#method.try_new) to reject impossible dates
+    /// and times.
+    pub fn new(day: u8, month: u8, year: u16, hour: u8, minute: u8, second: u8) -> Self {
+        IsoDateTime {
+            day,
+            month,
+            year,
+            hour,
+            minute,
+            second,
+            fraction: None,
+            timezone: None,
+        }
+    }
+
+    /// Create a new `IsoDateTime` without a timezone, checking it is valid.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// assert!(IsoDateTime::try_new(30, 1, 2021, 8, 0, 0).is_ok());
+    /// assert!(IsoDateTime::try_new(31, 2, 2021, 8, 0, 0).is_err());
+    /// ```
+    pub fn try_new(
+        day: u8,
+        month: u8,
+        year: u16,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Self, DateError> {
+        let dt = Self::new(day, month, year, hour, minute, second);
+        dt.validate().map(|_| dt)
+    }
+
+    /// Check the `IsoDateTime` is a valid date, time and timezone.
+    pub fn validate(&self) -> Result<(), DateError> {
+        check_date(self.year, self.month, self.day)?;
+        check_time(self.hour, self.minute, self.second)?;
+        match &self.timezone {
+            Some(tz) => tz.validate(),
+            None => Ok(()),
+        }
+    }
+
+    /// Compare the instants represented by two `IsoDateTime`, if possible.
+    ///
+    /// Unlike the `Ord` implementation, which compares the components of
+    /// the datetimes, this takes the timezones into account, so that two
+    /// datetimes in different timezones are equal if they represent the same
+    /// instant. Returns `None` when only one of the datetimes has a timezone,
+    /// since they cannot be compared without knowing the local timezone.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::cmp::Ordering;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let utc = IsoDateTime::from_str("2021-01-30T08:00:00Z").unwrap();
+    /// let paris = IsoDateTime::from_str("2021-01-30T09:00:00+01:00").unwrap();
+    /// let local = IsoDateTime::from_str("2021-01-30T08:00:00").unwrap();
+    /// assert!(utc != paris);
+    /// assert_eq!(utc.cmp_instant(&paris), Some(Ordering::Equal));
+    /// assert_eq!(utc.cmp_instant(&local), None);
+    /// ```
+    pub fn cmp_instant(&self, other: &Self) -> Option<Ordering> {
+        let offset = |tz: &IsoTimezone| i64::from(tz.offset_minutes()) * 60;
+        let (lhs, rhs) = match (&self.timezone, &other.timezone) {
+            (None, None) => (self.local_seconds(), other.local_seconds()),
+            (Some(l), Some(r)) => (
+                self.local_seconds() - offset(l),
+                other.local_seconds() - offset(r),
+            ),
+            _ => return None,
+        };
+        let fraction = |dt: &Self| dt.fraction.unwrap_or_default();
+        Some(
+            lhs.cmp(&rhs)
+                .then_with(|| fraction(self).cmp(&fraction(other))),
+        )
+    }
+
+    /// Get the number of seconds since the Unix epoch, ignoring the timezone.
+    fn local_seconds(&self) -> i64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        days * 86400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+    }
+
+    /// Change the timezone component of the `IsoDateTime`.
+    pub fn with_timezone<I>(mut self, tz: I) -> Self
+    where
+        I: Into<Option<IsoTimezone>>,
+    {
+        self.timezone = tz.into();
+        self
+    }
+
+    /// Change the date component of the `IsoDateTime`.
+    pub fn with_date(mut self, day: u8, month: u8, year: u16) -> Self {
+        self.day = day;
+        self.month = month;
+        self.year = year;
+        self
+    }
+
+    /// Change the time component of the `IsoDateTime`.
+    pub fn with_time(mut self, hour: u8, minute: u8, second: u8) -> Self {
+        self.hour = hour;
+        self.minute = minute;
+        self.second = second;
+        self
+    }
+
+    /// Get the day of the `IsoDateTime`.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Get the month of the `IsoDateTime`.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Get the year of the `IsoDateTime`.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the hour of the `IsoDateTime`.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute of the `IsoDateTime`.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Get the second of the `IsoDateTime`.
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Get the fraction of the `IsoDateTime`.
+    pub fn fraction(&self) -> Option<f32> {
+        self.fraction.as_ref().map(|f| f.0)
+    }
+
+    /// Get the timezone of the `IsoDateTime`, if any.
+    pub fn timezone(&self) -> Option<&IsoTimezone> {
+        self.timezone.as_ref()
+    }
+}
+
+impl Display for IsoDateTime {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second,
+        )?;
+        if let Some(ref frac) = self.fraction() {
+            let n = format!("{:.02}", frac);
+            f.write_str(&n[1..])?;
+        }
+        match self.timezone() {
+            Some(tz) => tz.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for IsoDateTime {
+    const RULE: Rule = Rule::Iso8601DateTime;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self, SyntaxError> {
+        let mut inner = pair.into_inner();
+        let mut date = inner.next().unwrap().into_inner();
+        let mut time = inner.next().unwrap().into_inner();
+
+        let year = date.next().unwrap().as_str().parse::<u16>().unwrap();
+        let month = date.next().unwrap().as_str().parse::<u8>().unwrap();
+        let day = date.next().unwrap().as_str().parse::<u8>().unwrap();
+
+        let hour = time.next().unwrap().as_str().parse::<u8>().unwrap();
+        let minute = time.next().unwrap().as_str().parse::<u8>().unwrap();
+        let second = time.next().unwrap().as_str().parse::<u8>().unwrap();
+        let fraction = time
+            .next()
+            .map(|p| f32::from_str(p.as_str()).unwrap().into());
+
+        let timezone = match inner.next() {
+            Some(pair) => Some(IsoTimezone::from_pair_unchecked(pair)?),
+            None => None,
+        };
+
+        Ok(IsoDateTime {
+            day,
+            month,
+            year,
+            hour,
+            minute,
+            second,
+            fraction,
+            timezone,
+        })
+    }
+}
+
+/// An ISO-8601 timezone.
+#[derive(Clone, Debug, Eq, FromStr, Hash, Ord, PartialEq, PartialOrd)]
+pub enum IsoTimezone {
+    Minus(u8, Option<u8>),
+    Utc,
+    Plus(u8, Option<u8>),
+}
+
+impl IsoTimezone {
+    /// Get the offset of the timezone from UTC, in minutes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// assert_eq!(IsoTimezone::Minus(3, Some(30)).offset_minutes(), -210);
+    /// ```
+    pub fn offset_minutes(&self) -> i16 {
+        let minutes = |hh: u8, mm: Option<u8>| i16::from(hh) * 60 + i16::from(mm.unwrap_or(0));
+        match *self {
+            IsoTimezone::Utc => 0,
+            IsoTimezone::Plus(hh, mm) => minutes(hh, mm),
+            IsoTimezone::Minus(hh, mm) => -minutes(hh, mm),
+        }
+    }
+
+    /// Check the offset of the timezone is a valid time of the day.
+    pub fn validate(&self) -> Result<(), DateError> {
+        match *self {
+            IsoTimezone::Utc => Ok(()),
+            IsoTimezone::Plus(hh, mm) | IsoTimezone::Minus(hh, mm) => {
+                if hh < 24 && mm.unwrap_or(0) < 60 {
+                    Ok(())
+                } else {
+                    Err(DateError::InvalidTimezone {
+                        timezone: self.clone(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl DateTime for IsoDateTime {
+    /// Generate an XML Schema datetime serialization of the `IsoDateTime`.
+    fn to_xsd_datetime(&self) -> String {
+        let tz = match self.timezone() {
+            None => String::new(),
+            Some(dt) => dt.to_string(),
+        };
+        let fraction = match self.fraction() {
+            None => String::new(),
+            Some(frac) => format!("{:0.02}", frac)[1..].to_string(),
+        };
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}{}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second, fraction, tz,
+        )
+    }
+}
+
+impl Display for IsoTimezone {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::IsoTimezone::*;
+        match self {
+            Utc => f.write_char('Z'),
+            Plus(hh, Some(mm)) => write!(f, "+{:02}:{:02}", hh, mm),
+            Minus(hh, Some(mm)) => write!(f, "-{:02}:{:02}", hh, mm),
+            Plus(hh, None) => write!(f, "-{:02}", hh),
+            Minus(hh, None) => write!(f, "-{:02}", hh),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for IsoTimezone {
+    const RULE: Rule = Rule::Iso8601TimeZone;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self, SyntaxError> {
+        use self::IsoTimezone::*;
+
+        let tag = pair.as_str().chars().next().unwrap();
+        if tag == 'Z' {
+            return Ok(Utc);
+        }
+
+        let mut inner = pair.into_inner();
+        let hh = inner.next().unwrap().as_str().parse::<u8>().unwrap();
+        let mm = inner.next().map(|p| p.as_str().parse::<u8>().unwrap());
+
+        match tag {
+            '+' => Ok(Plus(hh, mm)),
+            '-' => Ok(Minus(hh, mm)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Check whether a year is a leap year in the Gregorian calendar.
+// `u16::is_multiple_of` would require Rust 1.87.
+#[allow(clippy::manual_is_multiple_of)]
+fn is_leap_year(year: u16) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Get the number of days in the given month, or `0` for an invalid month.
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+fn check_date(year: u16, month: u8, day: u8) -> Result<(), DateError> {
+    if day >= 1 && day <= days_in_month(year, month) {
+        Ok(())
+    } else {
+        Err(DateError::InvalidDate { year, month, day })
+    }
+}
+
+fn check_time(hour: u8, minute: u8, second: u8) -> Result<(), DateError> {
+    if hour < 24 && minute < 60 && second < 60 {
+        Ok(())
+    } else {
+        Err(DateError::InvalidTime {
+            hour,
+            minute,
+            second,
+        })
+    }
+}
+
+/// Get the number of days between the Unix epoch and a date.
+///
+/// This uses the `days_from_civil` algorithm from Howard Hinnant's
+/// [date algorithms](http://howardhinnant.github.io/date_algorithms.html).
+fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = i64::from(month);
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Convert a year from a datetime library to the range of OBO dates.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn obo_year(year: i32) -> Result<u16, DateError> {
+    if (0..=9999).contains(&year) {
+        Ok(year as u16)
+    } else {
+        Err(DateError::YearOutOfRange { year })
+    }
+}
+
+/// Convert a fraction of second to a number of nanoseconds.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn fraction_to_nanos(fraction: Option<f32>) -> u32 {
+    let nanos = (fraction.unwrap_or(0.0) * 1e9).round();
+    (nanos as u32).min(999_999_999)
+}
+
+/// Convert a number of nanoseconds to a fraction of second.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn nanos_to_fraction(nanos: u32) -> Option<OrderedFloat<f32>> {
+    match nanos {
+        0 => None,
+        n => Some(OrderedFloat(n as f32 / 1e9)),
+    }
+}
+
+/// Convert a timezone offset in seconds to an `IsoTimezone`.
+///
+/// Seconds are truncated, since ISO-8601 timezones are given in minutes.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn timezone_from_seconds(seconds: i32) -> IsoTimezone {
+    let minutes = seconds.unsigned_abs() / 60;
+    let (hh, mm) = ((minutes / 60) as u8, (minutes % 60) as u8);
+    match seconds {
+        s if s / 60 == 0 => IsoTimezone::Utc,
+        s if s > 0 => IsoTimezone::Plus(hh, Some(mm)),
+        _ => IsoTimezone::Minus(hh, Some(mm)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+
+    mod naive {
+
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let naive = NaiveDateTime::from_str("12:06:2018 17:13").unwrap();
+            self::assert_eq!(naive, NaiveDateTime::new(12, 6, 2018, 17, 13));
+        }
+
+        #[test]
+        fn try_new() {
+            assert!(NaiveDateTime::try_new(29, 2, 2000, 0, 0).is_ok());
+            self::assert_eq!(
+                NaiveDateTime::try_new(29, 2, 1900, 0, 0),
+                Err(DateError::InvalidDate {
+                    year: 1900,
+                    month: 2,
+                    day: 29
+                })
+            );
+            self::assert_eq!(
+                NaiveDateTime::try_new(1, 1, 2000, 24, 0),
+                Err(DateError::InvalidTime {
+                    hour: 24,
+                    minute: 0,
+                    second: 0
+                })
+            );
+        }
+    }
+
+    mod iso {
+
+        use super::*;
+
+        macro_rules! assert_date_to_xsd {
+            ($x:expr) => {
+                assert_date_to_xsd!($x, $x)
+            };
+            ($x:expr, $y:expr) => {
+                match IsoDateTime::from_str($x) {
+                    Ok(x) => self::assert_eq!(x.to_xsd_datetime(), $y, "{}", x),
+                    Err(e) => panic!("{}", e),
+                }
+            };
+        }
+
+        #[test]
+        fn from_str() {
+            assert_date_to_xsd!("2017-1-24T14:41:36Z", "2017-01-24T14:41:36Z");
+            assert_date_to_xsd!("2015-08-11T15:05:12Z");
+            assert_date_to_xsd!("2016-10-26T10:51:48Z");
+            assert_date_to_xsd!("2017-1-24T14:41:36Z", "2017-01-24T14:41:36Z");
+            assert_date_to_xsd!("2017-1-24T14:41:36.05Z", "2017-01-24T14:41:36.05Z");
+            assert_date_to_xsd!("2017-1-24T14:41:36+01:30", "2017-01-24T14:41:36+01:30");
+        }
+
+        #[test]
+        fn validate() {
+            assert!(IsoDateTime::from_str("2017-04-31T00:00:00Z")
+                .unwrap()
+                .validate()
+                .is_err());
+            assert!(IsoDateTime::new(24, 1, 2017, 14, 41, 36)
+                .with_timezone(IsoTimezone::Plus(25, None))
+                .validate()
+                .is_err());
+            assert!(IsoDateTime::from_str("2017-01-24T14:41:36+05:45")
+                .unwrap()
+                .validate()
+                .is_ok());
+        }
+
+        #[test]
+        fn cmp_instant() {
+            let dt = |s| IsoDateTime::from_str(s).unwrap();
+            self::assert_eq!(
+                dt("2016-12-31T23:30:00-01:00").cmp_instant(&dt("2017-01-01T00:30:00Z")),
+                Some(Ordering::Equal)
+            );
+            self::assert_eq!(
+                dt("2017-03-01T00:10:00+01:00").cmp_instant(&dt("2017-02-28T23:00:00Z")),
+                Some(Ordering::Greater)
+            );
+            self::assert_eq!(
+                dt("2017-01-01T00:00:00.5Z").cmp_instant(&dt("2017-01-01T00:00:00.25Z")),
+                Some(Ordering::Greater)
+            );
+            self::assert_eq!(
+                dt("2017-01-01T00:00:00").cmp_instant(&dt("2017-01-01T00:00:00Z")),
+                None
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Conversions between OBO datetimes and [`time`](https://docs.rs/time) types.
+
+use std::convert::TryFrom;
+
+use ::time::Date;
+use ::time::Month;
+use ::time::OffsetDateTime;
+use ::time::PrimitiveDateTime;
+use ::time::Time;
+use ::time::UtcOffset;
+
+use super::*;
+
+/// Build a `::time::PrimitiveDateTime`, assuming the components were validated.
+fn primitive(
+    year: u16,
+    month: u8,
+    day: u8,
+    time: (u8, u8, u8),
+    nanos: u32,
+) -> Result<PrimitiveDateTime, DateError> {
+    let (hour, minute, second) = time;
+    let invalid_date = DateError::InvalidDate { year, month, day };
+    let date = Month::try_from(month)
+        .and_then(|m| Date::from_calendar_date(year.into(), m, day))
+        .map_err(|_| invalid_date)?;
+    let time =
+        Time::from_hms_nano(hour, minute, second, nanos).map_err(|_| DateError::InvalidTime {
+            hour,
+            minute,
+            second,
+        })?;
+    Ok(PrimitiveDateTime::new(date, time))
+}
+
+impl TryFrom<&NaiveDateTime> for PrimitiveDateTime {
+    type Error = DateError;
+    fn try_from(dt: &NaiveDateTime) -> Result<Self, Self::Error> {
+        dt.validate()?;
+        primitive(dt.year, dt.month, dt.day, (dt.hour, dt.minute, 0), 0)
+    }
+}
+
+impl TryFrom<PrimitiveDateTime> for NaiveDateTime {
+    type Error = DateError;
+    /// Convert a `::time::PrimitiveDateTime`, truncating the seconds.
+    fn try_from(dt: PrimitiveDateTime) -> Result<Self, Self::Error> {
+        Ok(NaiveDateTime::new(
+            dt.day(),
+            dt.month().into(),
+            obo_year(dt.year())?,
+            dt.hour(),
+            dt.minute(),
+        ))
+    }
+}
+
+impl TryFrom<&IsoDateTime> for PrimitiveDateTime {
+    type Error = DateError;
+    /// Convert an `IsoDateTime` to a local datetime, ignoring its timezone.
+    fn try_from(dt: &IsoDateTime) -> Result<Self, Self::Error> {
+        dt.validate()?;
+        let time = (dt.hour, dt.minute, dt.second);
+        let nanos = fraction_to_nanos(dt.fraction());
+        primitive(dt.year, dt.month, dt.day, time, nanos)
+    }
+}
+
+impl TryFrom<&IsoDateTime> for OffsetDateTime {
+    type Error = DateError;
+    fn try_from(dt: &IsoDateTime) -> Result<Self, Self::Error> {
+        let offset = UtcOffset::try_from(dt.timezone().ok_or(DateError::MissingTimezone)?)?;
+        PrimitiveDateTime::try_from(dt).map(|local| local.assume_offset(offset))
+    }
+}
+
+impl TryFrom<PrimitiveDateTime> for IsoDateTime {
+    type Error = DateError;
+    /// Convert a `::time::PrimitiveDateTime` to an `IsoDateTime` without a timezone.
+    fn try_from(dt: PrimitiveDateTime) -> Result<Self, Self::Error> {
+        Ok(IsoDateTime {
+            year: obo_year(dt.year())?,
+            month: dt.month().into(),
+            day: dt.day(),
+            hour: dt.hour(),
+            minute: dt.minute(),
+            second: dt.second(),
+            fraction: nanos_to_fraction(dt.nanosecond()),
+            timezone: None,
+        })
+    }
+}
+
+impl TryFrom<OffsetDateTime> for IsoDateTime {
+    type Error = DateError;
+    fn try_from(dt: OffsetDateTime) -> Result<Self, Self::Error> {
+        let timezone = IsoTimezone::from(dt.offset());
+        let local = PrimitiveDateTime::new(dt.date(), dt.time());
+        IsoDateTime::try_from(local).map(|iso| iso.with_timezone(timezone))
+    }
+}
+
+impl TryFrom<&IsoTimezone> for UtcOffset {
+    type Error = DateError;
+    fn try_from(tz: &IsoTimezone) -> Result<Self, Self::Error> {
+        tz.validate()?;
+        UtcOffset::from_whole_seconds(i32::from(tz.offset_minutes()) * 60).map_err(|_| {
+            DateError::InvalidTimezone {
+                timezone: tz.clone(),
+            }
+        })
+    }
+}
+
+impl From<UtcOffset> for IsoTimezone {
+    /// Convert a `::time::UtcOffset`, truncating the seconds of the offset.
+    fn from(offset: UtcOffset) -> Self {
+        timezone_from_seconds(offset.whole_seconds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn naive_roundtrip() {
+        let dt = NaiveDateTime::new(8, 5, 2019, 13, 2);
+        let primitive = PrimitiveDateTime::try_from(&dt).unwrap();
+        assert_eq!(primitive.to_string(), "2019-05-08 13:02:00.0");
+        assert_eq!(NaiveDateTime::try_from(primitive), Ok(dt));
+
+        let invalid = NaiveDateTime::new(29, 2, 2019, 13, 2);
+        assert!(PrimitiveDateTime::try_from(&invalid).is_err());
+    }
+
+    #[test]
+    fn iso_roundtrip() {
+        let dt = IsoDateTime::from_str("2017-01-24T14:41:36.5+01:00").unwrap();
+        let offset = OffsetDateTime::try_from(&dt).unwrap();
+        assert_eq!(offset.unix_timestamp(), 1485265296);
+        assert_eq!(IsoDateTime::try_from(offset), Ok(dt));
+
+        let local = IsoDateTime::from_str("2017-01-24T14:41:36").unwrap();
+        assert_eq!(
+            OffsetDateTime::try_from(&local),
+            Err(DateError::MissingTimezone)
+        );
+    }
+}
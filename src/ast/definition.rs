@@ -52,6 +52,11 @@ impl Definition {
     pub fn xrefs_mut(&mut self) -> &mut XrefList {
         &mut self.xrefs
     }
+
+    /// Consume the definition, returning its text and cross-references.
+    pub fn into_parts(self) -> (QuotedString, XrefList) {
+        (self.text, self.xrefs)
+    }
 }
 
 impl Display for Definition {
@@ -79,8 +84,8 @@ impl<'i> FromPair<'i> for Definition {
     }
 }
 
-impl Into<QuotedString> for Definition {
-    fn into(self) -> QuotedString {
-        self.text
+impl From<Definition> for QuotedString {
+    fn from(val: Definition) -> Self {
+        val.text
     }
 }
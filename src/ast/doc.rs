@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
@@ -7,10 +8,10 @@ use fastobo_derive_internal::FromStr;
 use pest::iterators::Pair;
 
 use crate::ast::*;
-use crate::error::CardinalityError;
-
+use crate::error::Error;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
+use crate::semantics::cmp_entities;
 use crate::semantics::Identified;
 use crate::semantics::Orderable;
 use crate::syntax::Rule;
@@ -113,6 +114,11 @@ impl OboDoc {
         &mut self.entities
     }
 
+    /// Consume the document, returning its header and entity frames.
+    pub fn into_parts(self) -> (HeaderFrame, Vec<EntityFrame>) {
+        (self.header, self.entities)
+    }
+
     /// Check whether or not the document is empty.
     ///
     /// An empty document has no header clauses and no entity frames.
@@ -131,9 +137,12 @@ impl OboDoc {
     ///
     /// This function will not check the cardinality of `namespace` clauses in
     /// entity frames: it will only add a single `namespace` clause to all
-    /// frames that have none.
+    /// frames that have none. The header frame is only checked if at least
+    /// one frame needs the default namespace.
     ///
     /// # Errors
+    /// Returns an `Error::CardinalityError`, with the identifier of the first
+    /// frame without a `namespace` clause, wrapping:
     /// - `CardinalityError::MissingClause`: if the header frame does not
     ///   contain any default namespace definition.
     /// - `CardinalityError::DuplicateClauses` if the header frame does
@@ -171,28 +180,42 @@ impl OboDoc {
     /// namespace: quality
     /// ");
     ///
-    pub fn assign_namespaces(&mut self) -> Result<(), CardinalityError> {
-        macro_rules! expand {
-            ($frame:ident, $clause:ident, $ns:ident, $outer:lifetime) => ({
-                for clause in $frame.iter() {
-                    if let $clause::Namespace(_) = clause.as_ref() {
-                        continue $outer
-                    }
-                }
-                $frame.push(Line::from($clause::Namespace(Box::new($ns.clone()))));
-            });
+    pub fn assign_namespaces(&mut self) -> Result<(), Error> {
+        macro_rules! has_namespace {
+            ($frame:ident, $clause:ident) => {
+                $frame
+                    .iter()
+                    .any(|line| matches!(line.as_ref(), $clause::Namespace(_)))
+            };
         }
 
         use self::EntityFrame::*;
 
-        // Force borrowck to split borrows: we shoudl be able to borrow
+        // Force borrowck to split borrows: we should be able to borrow
         // the header AND the entities at the same time.
-        let ns = self.header.default_namespace()?;
-        'outer: for entity in &mut self.entities {
+        let default = self.header.default_namespace();
+        for entity in &mut self.entities {
+            let found = match entity {
+                Term(x) => has_namespace!(x, TermClause),
+                Typedef(x) => has_namespace!(x, TypedefClause),
+                Instance(x) => has_namespace!(x, InstanceClause),
+            };
+            if found {
+                continue;
+            }
+            let ns = match &default {
+                Ok(ns) => Box::new((*ns).clone()),
+                Err(inner) => {
+                    return Err(Error::CardinalityError {
+                        id: Some(entity.as_id().clone()),
+                        inner: inner.clone(),
+                    })
+                }
+            };
             match entity {
-                Term(x) => expand!(x, TermClause, ns, 'outer),
-                Typedef(x) => expand!(x, TypedefClause, ns, 'outer),
-                Instance(x) => expand!(x, InstanceClause, ns, 'outer),
+                Term(x) => x.push(Line::from(TermClause::Namespace(ns))),
+                Typedef(x) => x.push(Line::from(TypedefClause::Namespace(ns))),
+                Instance(x) => x.push(Line::from(InstanceClause::Namespace(ns))),
             }
         }
 
@@ -232,21 +255,19 @@ impl OboDoc {
         // Apply all `treat-xrefs` macros to the document.
         for clause in &self.header {
             match clause {
-                TreatXrefsAsEquivalent(prefix) => {
-                    crate::semantics::as_equivalent(entities, &prefix)
-                }
-                TreatXrefsAsIsA(prefix) => crate::semantics::as_is_a(entities, &prefix),
+                TreatXrefsAsEquivalent(prefix) => crate::semantics::as_equivalent(entities, prefix),
+                TreatXrefsAsIsA(prefix) => crate::semantics::as_is_a(entities, prefix),
                 TreatXrefsAsHasSubclass(prefix) => {
-                    crate::semantics::as_has_subclass(entities, &prefix)
+                    crate::semantics::as_has_subclass(entities, prefix)
                 }
                 TreatXrefsAsGenusDifferentia(prefix, rel, cls) => {
-                    crate::semantics::as_genus_differentia(entities, &prefix, &rel, &cls)
+                    crate::semantics::as_genus_differentia(entities, prefix, rel, cls)
                 }
                 TreatXrefsAsReverseGenusDifferentia(prefix, rel, cls) => {
-                    crate::semantics::as_reverse_genus_differentia(entities, &prefix, &rel, &cls)
+                    crate::semantics::as_reverse_genus_differentia(entities, prefix, rel, cls)
                 }
                 TreatXrefsAsRelationship(prefix, rel) => {
-                    crate::semantics::as_relationship(entities, &prefix, &rel)
+                    crate::semantics::as_relationship(entities, prefix, rel)
                 }
                 _ => (),
             }
@@ -320,9 +341,8 @@ where
 impl Orderable for OboDoc {
     /// Sort the document in the right serialization order.
     fn sort(&mut self) {
-        self.header.sort_unstable();
-        // FIXME(@althonos): should probably not require cloning here.
-        self.entities.sort_unstable_by_key(|e| e.as_id().clone());
+        self.header.sort();
+        self.entities.sort_by(cmp_entities);
         for entity in &mut self.entities {
             entity.sort()
         }
@@ -330,9 +350,9 @@ impl Orderable for OboDoc {
 
     /// Check if the document is sorted in the right serialization order.
     fn is_sorted(&self) -> bool {
-        // Check entities are sorted on their identifier.
+        // Check entities are sorted on their kind and identifier.
         for i in 1..self.entities.len() {
-            if self.entities[i - 1].as_id() > self.entities[i].as_id() {
+            if cmp_entities(&self.entities[i - 1], &self.entities[i]) == Ordering::Greater {
                 return false;
             }
         }
@@ -16,10 +16,10 @@ use crate::syntax::Rule;
 /// An entity frame, describing either a term, an instance, or a typedef.
 ///
 /// # Ordering
-/// [Serializer conventions](https://owlcollab.github.io/oboformat/doc/GO.format.obo-1_4.html#S.3.5.2)
-/// dictate that frames should be Serialized first with `[Typedef]` frames, then
-/// `[Term]`, and then `[Instance]`, which is reflected here in the order of the
-/// variants.
+/// Following the [Serializer conventions](https://owlcollab.github.io/oboformat/doc/GO.format.obo-1_4.html#S.3.5.2)
+/// as implemented by the reference serializer, `OboDoc::sort` writes `[Term]`
+/// frames first, then `[Typedef]` frames, and then `[Instance]` frames, each
+/// sorted by identifier.
 #[derive(Clone, Debug, Hash, FromStr, Eq, PartialEq)]
 pub enum EntityFrame {
     Typedef(Box<TypedefFrame>),
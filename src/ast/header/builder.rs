@@ -0,0 +1,209 @@
+use crate::ast::*;
+use crate::error::CardinalityError;
+use crate::semantics::OboFrame;
+
+/// A builder for `HeaderFrame`, adding clauses in a fluent style.
+///
+/// Clauses are added in the order the builder methods are called. The
+/// cardinality of each clause is only checked when the frame is built.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// let header = HeaderFrame::builder()
+///     .format_version("1.4")
+///     .subsetdef(SubsetIdent::from(UnprefixedIdent::new("slim")), "Slim subset")
+///     .ontology("tst")
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     header.to_string(),
+///     "format-version: 1.4\nsubsetdef: slim \"Slim subset\"\nontology: tst\n"
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HeaderFrameBuilder {
+    frame: HeaderFrame,
+}
+
+impl HeaderFrameBuilder {
+    /// Create a new builder for an empty header frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an arbitrary clause to the frame.
+    pub fn clause(mut self, clause: HeaderClause) -> Self {
+        self.frame.push(clause);
+        self
+    }
+
+    build_clause!(
+        HeaderClause::FormatVersion(UnquotedString),
+        "format-version",
+        format_version
+    );
+    build_clause!(
+        HeaderClause::DataVersion(UnquotedString),
+        "data-version",
+        data_version
+    );
+    build_clause!(HeaderClause::Date(NaiveDateTime), "date", date);
+    build_clause!(HeaderClause::SavedBy(UnquotedString), "saved-by", saved_by);
+    build_clause!(
+        HeaderClause::AutoGeneratedBy(UnquotedString),
+        "auto-generated-by",
+        auto_generated_by
+    );
+    build_clause!(HeaderClause::Import(Import), "import", import);
+    build_pair!(
+        HeaderClause::Subsetdef(SubsetIdent, QuotedString),
+        "subsetdef",
+        subsetdef
+    );
+
+    /// Add a `synonymtypedef` clause to the frame.
+    pub fn synonymtypedef<I, D>(self, id: I, desc: D, scope: Option<SynonymScope>) -> Self
+    where
+        I: Into<SynonymTypeIdent>,
+        D: Into<QuotedString>,
+    {
+        self.clause(HeaderClause::SynonymTypedef(
+            Box::new(id.into()),
+            Box::new(desc.into()),
+            scope.map(Box::new),
+        ))
+    }
+
+    build_clause!(
+        HeaderClause::DefaultNamespace(NamespaceIdent),
+        "default-namespace",
+        default_namespace
+    );
+    build_clause!(
+        HeaderClause::NamespaceIdRule(UnquotedString),
+        "namespace-id-rule",
+        namespace_id_rule
+    );
+
+    /// Add an `idspace` clause to the frame.
+    pub fn idspace<P, U>(self, prefix: P, url: U, desc: Option<QuotedString>) -> Self
+    where
+        P: Into<IdentPrefix>,
+        U: Into<Url>,
+    {
+        self.clause(HeaderClause::Idspace(
+            Box::new(prefix.into()),
+            Box::new(url.into()),
+            desc.map(Box::new),
+        ))
+    }
+
+    build_clause!(
+        HeaderClause::TreatXrefsAsEquivalent(IdentPrefix),
+        "treat-xrefs-as-equivalent",
+        treat_xrefs_as_equivalent
+    );
+
+    /// Add a `treat-xrefs-as-genus-differentia` clause to the frame.
+    pub fn treat_xrefs_as_genus_differentia<P, R, C>(self, prefix: P, rel: R, class: C) -> Self
+    where
+        P: Into<IdentPrefix>,
+        R: Into<RelationIdent>,
+        C: Into<ClassIdent>,
+    {
+        self.clause(HeaderClause::TreatXrefsAsGenusDifferentia(
+            Box::new(prefix.into()),
+            Box::new(rel.into()),
+            Box::new(class.into()),
+        ))
+    }
+
+    /// Add a `treat-xrefs-as-reverse-genus-differentia` clause to the frame.
+    pub fn treat_xrefs_as_reverse_genus_differentia<P, R, C>(
+        self,
+        prefix: P,
+        rel: R,
+        class: C,
+    ) -> Self
+    where
+        P: Into<IdentPrefix>,
+        R: Into<RelationIdent>,
+        C: Into<ClassIdent>,
+    {
+        self.clause(HeaderClause::TreatXrefsAsReverseGenusDifferentia(
+            Box::new(prefix.into()),
+            Box::new(rel.into()),
+            Box::new(class.into()),
+        ))
+    }
+
+    build_pair!(
+        HeaderClause::TreatXrefsAsRelationship(IdentPrefix, RelationIdent),
+        "treat-xrefs-as-relationship",
+        treat_xrefs_as_relationship
+    );
+    build_clause!(
+        HeaderClause::TreatXrefsAsIsA(IdentPrefix),
+        "treat-xrefs-as-is_a",
+        treat_xrefs_as_is_a
+    );
+    build_clause!(
+        HeaderClause::TreatXrefsAsHasSubclass(IdentPrefix),
+        "treat-xrefs-as-has-subclass",
+        treat_xrefs_as_has_subclass
+    );
+    build_clause!(
+        HeaderClause::PropertyValue(PropertyValue),
+        "property_value",
+        property_value
+    );
+    build_clause!(HeaderClause::Remark(UnquotedString), "remark", remark);
+    build_clause!(HeaderClause::Ontology(UnquotedString), "ontology", ontology);
+    build_clause!(
+        HeaderClause::OwlAxioms(UnquotedString),
+        "owl-axioms",
+        owl_axioms
+    );
+    build_pair!(
+        HeaderClause::Unreserved(UnquotedString, UnquotedString),
+        "unreserved",
+        unreserved
+    );
+
+    /// Build the frame, checking the cardinality of its clauses.
+    ///
+    /// # Errors
+    /// Returns a `CardinalityError` if a clause is declared more times than
+    /// allowed.
+    pub fn build(self) -> Result<HeaderFrame, CardinalityError> {
+        self.frame.cardinality_check()?;
+        Ok(self.frame)
+    }
+}
+
+impl HeaderFrame {
+    /// Create a new builder for a header frame.
+    pub fn builder() -> HeaderFrameBuilder {
+        HeaderFrameBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn build_cardinality() {
+        let err = HeaderFrame::builder()
+            .format_version("1.2")
+            .format_version("1.4")
+            .build()
+            .unwrap_err();
+        assert_eq!(err, CardinalityError::duplicate("format-version"));
+    }
+}
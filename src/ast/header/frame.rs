@@ -68,7 +68,7 @@ impl HeaderFrame {
             if let HeaderClause::FormatVersion(v) = clause {
                 match version {
                     Some(_) => return Err(CardinalityError::duplicate("format-version")),
-                    None => version = Some(&v),
+                    None => version = Some(v),
                 }
             }
         }
@@ -82,7 +82,7 @@ impl HeaderFrame {
             if let HeaderClause::DataVersion(v) = clause {
                 match version {
                     Some(_) => return Err(CardinalityError::duplicate("data-version")),
-                    None => version = Some(&v),
+                    None => version = Some(v),
                 }
             }
         }
@@ -186,9 +186,9 @@ impl<'i> FromPair<'i> for HeaderFrame {
     }
 }
 
-impl Into<Vec<HeaderClause>> for HeaderFrame {
-    fn into(self) -> Vec<HeaderClause> {
-        self.clauses
+impl From<HeaderFrame> for Vec<HeaderClause> {
+    fn from(val: HeaderFrame) -> Self {
+        val.clauses
     }
 }
 
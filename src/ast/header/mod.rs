@@ -1,7 +1,9 @@
+mod builder;
 mod clause;
 mod frame;
 mod import;
 
+pub use self::builder::HeaderFrameBuilder;
 pub use self::clause::HeaderClause;
 pub use self::frame::HeaderFrame;
 pub use self::import::Import;
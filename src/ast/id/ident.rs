@@ -9,6 +9,7 @@ use pest::iterators::Pair;
 
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
+use crate::semantics::PrefixMap;
 use crate::syntax::Rule;
 
 use super::PrefixedIdent;
@@ -23,9 +24,36 @@ pub enum Ident {
     Url(Box<Url>),
 }
 
+impl Ident {
+    /// Expand the identifier into an IRI using the given prefix map.
+    ///
+    /// Unprefixed identifiers can only be expanded if the prefix map knows
+    /// about the ontology they are declared in.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// # use fastobo::semantics::PrefixMap;
+    /// let mut prefixes = PrefixMap::new();
+    /// prefixes.set_ontology(UnquotedString::new("ro"));
+    ///
+    /// let id = Ident::from(UnprefixedIdent::new("part_of"));
+    /// let url = id.expand(&prefixes).unwrap();
+    /// assert_eq!(url.as_str(), "http://purl.obolibrary.org/obo/ro#part_of");
+    /// ```
+    ///
+    /// # See also
+    /// * [`PrefixMap::expand`](../semantics/struct.PrefixMap.html#method.expand)
+    ///   for the rules used to expand an identifier.
+    pub fn expand(&self, prefixes: &PrefixMap) -> Option<Url> {
+        prefixes.expand(self)
+    }
+}
+
 impl AsRef<Ident> for Ident {
     fn as_ref(&self) -> &Self {
-        &self
+        self
     }
 }
 
@@ -94,9 +122,7 @@ mod tests {
 
     #[test]
     fn from_str() {
-        let actual = Ident::from_str("http://purl.obolibrary.org/obo/po.owl")
-            .map(Ident::from)
-            .unwrap();
+        let actual = Ident::from_str("http://purl.obolibrary.org/obo/po.owl").unwrap();
         let expected = Url::from_str("http://purl.obolibrary.org/obo/po.owl")
             .map(Ident::from)
             .unwrap();
@@ -10,8 +10,11 @@ use pest::iterators::Pair;
 use crate::ast::StringType;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
+use crate::semantics::PrefixMap;
 use crate::syntax::Rule;
 
+use super::Url;
+
 use super::escape;
 use super::unescape;
 
@@ -86,6 +89,25 @@ impl PrefixedIdent {
         &self.data.as_str()[self.local_offset..]
     }
 
+    /// Expand the identifier into an IRI using the given prefix map.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// # use fastobo::semantics::PrefixMap;
+    /// let id = PrefixedIdent::new("MS", "1000031");
+    /// let url = id.expand(&PrefixMap::new());
+    /// assert_eq!(url.as_str(), "http://purl.obolibrary.org/obo/MS_1000031");
+    /// ```
+    ///
+    /// # See also
+    /// * [`PrefixMap::expand_prefixed`](../semantics/struct.PrefixMap.html#method.expand_prefixed)
+    ///   for the rules used to expand a prefixed identifier.
+    pub fn expand(&self, prefixes: &PrefixMap) -> Url {
+        prefixes.expand_prefixed(self)
+    }
+
     // /// Get a reference to the prefix of the `PrefixedIdent`.
     // pub fn prefix(&self) -> &IdentPrefix {
     //     &self.prefix
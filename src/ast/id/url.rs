@@ -8,8 +8,11 @@ use pest::iterators::Pair;
 use crate::ast::StringType;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
+use crate::semantics::PrefixMap;
 use crate::syntax::Rule;
 
+use super::Ident;
+
 /// A Uniform Resource Locator used as an identifier for an entity.
 #[derive(Clone, Debug, FromStr, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Url(StringType);
@@ -32,10 +35,37 @@ impl Url {
         std::str::FromStr::from_str(s)
     }
 
+    /// Create a new `Url` from a string without checking it is a valid IRI.
+    pub(crate) fn new_unchecked<S>(s: S) -> Self
+    where
+        S: Into<StringType>,
+    {
+        Url(s.into())
+    }
+
     /// View the URL as a string slice.
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Compact the URL into an OBO identifier using the given prefix map.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// # use fastobo::semantics::PrefixMap;
+    /// let url = Url::parse("http://purl.obolibrary.org/obo/BFO_0000055").unwrap();
+    /// let id = url.compact(&PrefixMap::new()).unwrap();
+    /// assert_eq!(id.to_string(), "BFO:0000055");
+    /// ```
+    ///
+    /// # See also
+    /// * [`PrefixMap::compact`](../semantics/struct.PrefixMap.html#method.compact)
+    ///   for the rules used to compact an URL.
+    pub fn compact(&self, prefixes: &PrefixMap) -> Option<Ident> {
+        prefixes.compact(self)
+    }
 }
 
 impl AsRef<str> for Url {
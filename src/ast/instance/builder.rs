@@ -0,0 +1,141 @@
+use crate::ast::*;
+use crate::error::Error;
+use crate::semantics::Identified;
+use crate::semantics::OboFrame;
+
+/// A builder for `InstanceFrame`, adding clauses in a fluent style.
+///
+/// Clauses are added in the order the builder methods are called. The
+/// cardinality of each clause is only checked when the frame is built.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use std::str::FromStr;
+/// # use fastobo::ast::*;
+/// let frame = InstanceFrame::builder(InstanceIdent::from_str("TST:001").unwrap())
+///     .name("example")
+///     .instance_of(ClassIdent::from_str("TST:002").unwrap())
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     frame.to_string(),
+///     "[Instance]\nid: TST:001\nname: example\ninstance_of: TST:002\n"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct InstanceFrameBuilder {
+    frame: InstanceFrame,
+}
+
+impl InstanceFrameBuilder {
+    /// Create a new builder for an instance frame with the given ID.
+    pub fn new<I>(id: I) -> Self
+    where
+        I: Into<Line<InstanceIdent>>,
+    {
+        Self {
+            frame: InstanceFrame::new(id),
+        }
+    }
+
+    /// Add an arbitrary clause to the frame.
+    pub fn clause(self, clause: InstanceClause) -> Self {
+        self.line(Line::from(clause))
+    }
+
+    /// Add an arbitrary clause line, with qualifiers or a comment, to the frame.
+    pub fn line(mut self, line: Line<InstanceClause>) -> Self {
+        self.frame.clauses_mut().push(line);
+        self
+    }
+
+    build_flag!(InstanceClause::IsAnonymous, "is_anonymous", is_anonymous);
+    build_clause!(InstanceClause::Name(UnquotedString), "name", name);
+    build_clause!(
+        InstanceClause::Namespace(NamespaceIdent),
+        "namespace",
+        namespace
+    );
+    build_clause!(InstanceClause::AltId(Ident), "alt_id", alt_id);
+
+    /// Add a `def` clause with the given text and cross-references to the frame.
+    pub fn def<T, L>(self, text: T, xrefs: L) -> Self
+    where
+        T: Into<QuotedString>,
+        L: Into<XrefList>,
+    {
+        let def = Definition::with_xrefs(text, xrefs);
+        self.clause(InstanceClause::Def(Box::new(def)))
+    }
+
+    build_clause!(InstanceClause::Comment(UnquotedString), "comment", comment);
+    build_clause!(InstanceClause::Subset(SubsetIdent), "subset", subset);
+
+    /// Add a `synonym` clause with the given description and scope to the frame.
+    pub fn synonym<D>(self, desc: D, scope: SynonymScope) -> Self
+    where
+        D: Into<QuotedString>,
+    {
+        self.clause(InstanceClause::Synonym(Box::new(Synonym::new(desc, scope))))
+    }
+
+    build_clause!(InstanceClause::Xref(Xref), "xref", xref);
+    build_clause!(
+        InstanceClause::PropertyValue(PropertyValue),
+        "property_value",
+        property_value
+    );
+    build_clause!(
+        InstanceClause::InstanceOf(ClassIdent),
+        "instance_of",
+        instance_of
+    );
+    build_pair!(
+        InstanceClause::Relationship(RelationIdent, Ident),
+        "relationship",
+        relationship
+    );
+    build_clause!(
+        InstanceClause::CreatedBy(UnquotedString),
+        "created_by",
+        created_by
+    );
+    build_clause!(
+        InstanceClause::CreationDate(IsoDateTime),
+        "creation_date",
+        creation_date
+    );
+    build_flag!(InstanceClause::IsObsolete, "is_obsolete", is_obsolete);
+    build_clause!(
+        InstanceClause::ReplacedBy(InstanceIdent),
+        "replaced_by",
+        replaced_by
+    );
+    build_clause!(InstanceClause::Consider(Ident), "consider", consider);
+
+    /// Build the frame, checking the cardinality of its clauses.
+    ///
+    /// # Errors
+    /// Returns an `Error::CardinalityError`, with the identifier of the frame,
+    /// if a clause is declared more times than allowed.
+    pub fn build(self) -> Result<InstanceFrame, Error> {
+        match self.frame.cardinality_check() {
+            Ok(()) => Ok(self.frame),
+            Err(inner) => Err(Error::CardinalityError {
+                id: Some(self.frame.as_id().clone()),
+                inner,
+            }),
+        }
+    }
+}
+
+impl InstanceFrame {
+    /// Create a new builder for an instance frame with the given ID.
+    pub fn builder<I>(id: I) -> InstanceFrameBuilder
+    where
+        I: Into<Line<InstanceIdent>>,
+    {
+        InstanceFrameBuilder::new(id)
+    }
+}
@@ -10,6 +10,8 @@ use pest::iterators::Pair;
 use crate::ast::*;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
+use crate::semantics::lines_sorted;
+use crate::semantics::sort_lines;
 use crate::semantics::Identified;
 use crate::semantics::OboFrame;
 use crate::semantics::Orderable;
@@ -62,33 +64,130 @@ impl InstanceFrame {
         &mut self.clauses
     }
 
-    /// Get the name of the instance, if exactly one is declared.
-    pub fn name(&self) -> Result<&UnquotedString, CardinalityError> {
-        let mut name: Option<&UnquotedString> = None;
-        for clause in &self.clauses {
-            if let InstanceClause::Name(n) = clause.as_inner() {
-                match name {
-                    Some(_) => return Err(CardinalityError::duplicate("name")),
-                    None => name = Some(&n),
-                }
-            }
-        }
-        name.ok_or_else(|| CardinalityError::missing("name"))
-    }
-
-    /// Get the definition of the instance, if exactly one is declared.
-    pub fn definition(&self) -> Result<&Definition, CardinalityError> {
-        let mut def: Option<&Definition> = None;
-        for clause in &self.clauses {
-            if let InstanceClause::Def(n) = clause.as_inner() {
-                match def {
-                    Some(_) => return Err(CardinalityError::duplicate("def")),
-                    None => def = Some(&n),
-                }
-            }
-        }
-        def.ok_or_else(|| CardinalityError::missing("def"))
+    /// Consume the `InstanceFrame`, returning its identifier and clauses.
+    pub fn into_parts(self) -> (Line<InstanceIdent>, Vec<Line<InstanceClause>>) {
+        (self.id, self.clauses)
     }
+
+    single_clause!(
+        InstanceClause::Name(UnquotedString),
+        "name",
+        name,
+        set_name,
+        clear_name
+    );
+    single_clause!(
+        InstanceClause::Namespace(NamespaceIdent),
+        "namespace",
+        namespace,
+        set_namespace
+    );
+    single_clause!(
+        InstanceClause::Def(Definition),
+        "def",
+        definition,
+        set_definition,
+        clear_definition
+    );
+    single_clause!(
+        InstanceClause::CreatedBy(UnquotedString),
+        "created_by",
+        created_by,
+        set_created_by,
+        clear_created_by
+    );
+    single_clause!(
+        InstanceClause::CreationDate(IsoDateTime),
+        "creation_date",
+        creation_date,
+        set_creation_date,
+        clear_creation_date
+    );
+
+    flag_clause!(
+        InstanceClause::IsAnonymous,
+        "is_anonymous",
+        is_anonymous,
+        set_anonymous
+    );
+    flag_clause!(
+        InstanceClause::IsObsolete,
+        "is_obsolete",
+        is_obsolete,
+        set_obsolete
+    );
+
+    many_clause!(
+        InstanceClause::AltId(Ident),
+        "alt_id",
+        alt_ids,
+        add_alt_id,
+        remove_alt_id
+    );
+    many_clause!(
+        InstanceClause::Comment(UnquotedString),
+        "comment",
+        comments,
+        add_comment,
+        remove_comment
+    );
+    many_clause!(
+        InstanceClause::Subset(SubsetIdent),
+        "subset",
+        subsets,
+        add_subset,
+        remove_subset
+    );
+    many_clause!(
+        InstanceClause::Synonym(Synonym),
+        "synonym",
+        synonyms,
+        add_synonym,
+        remove_synonym
+    );
+    many_clause!(
+        InstanceClause::Xref(Xref),
+        "xref",
+        xrefs,
+        add_xref,
+        remove_xref
+    );
+    many_clause!(
+        InstanceClause::PropertyValue(PropertyValue),
+        "property_value",
+        property_values,
+        add_property_value,
+        remove_property_value
+    );
+    many_clause!(
+        InstanceClause::InstanceOf(ClassIdent),
+        "instance_of",
+        instance_of,
+        add_instance_of,
+        remove_instance_of
+    );
+    many_clause!(
+        InstanceClause::ReplacedBy(InstanceIdent),
+        "replaced_by",
+        replaced_by,
+        add_replaced_by,
+        remove_replaced_by
+    );
+    many_clause!(
+        InstanceClause::Consider(Ident),
+        "consider",
+        consider,
+        add_consider,
+        remove_consider
+    );
+
+    pair_clause!(
+        InstanceClause::Relationship(RelationIdent, Ident),
+        "relationship",
+        relationships,
+        add_relationship,
+        remove_relationship
+    );
 }
 
 impl AsRef<Vec<Line<InstanceClause>>> for InstanceFrame {
@@ -169,15 +268,10 @@ impl<'a> IntoIterator for &'a InstanceFrame {
 
 impl Orderable for InstanceFrame {
     fn sort(&mut self) {
-        self.clauses.sort_unstable();
+        sort_lines(&mut self.clauses);
     }
     fn is_sorted(&self) -> bool {
-        for i in 1..self.clauses.len() {
-            if self.clauses[i - 1] > self.clauses[i] {
-                return false;
-            }
-        }
-        true
+        lines_sorted(&self.clauses)
     }
 }
 
@@ -188,3 +282,79 @@ impl OboFrame for InstanceFrame {
         self.clauses.iter().map(Line::as_inner).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+
+    #[test]
+    fn accessors() {
+        let instance = InstanceFrame::from_str(
+            "[Instance]
+            id: TST:I01
+            name: sample
+            instance_of: TST:001
+            comment: first
+            comment: second
+            is_obsolete: true\n",
+        )
+        .unwrap();
+
+        assert_eq!(instance.name().unwrap().as_str(), "sample");
+        assert!(instance.definition().is_err());
+        assert!(instance.is_obsolete());
+        assert!(!instance.is_anonymous());
+
+        let classes: Vec<_> = instance.instance_of().map(ToString::to_string).collect();
+        assert_eq!(classes, vec!["TST:001"]);
+        let comments: Vec<_> = instance.comments().map(UnquotedString::as_str).collect();
+        assert_eq!(comments, vec!["first", "second"]);
+        assert_eq!(instance.relationships().count(), 0);
+    }
+
+    #[test]
+    fn mutators() {
+        let mut instance = InstanceFrame::from_str(
+            "[Instance]
+            id: TST:I01
+            name: first {source=\"PMID:1\"} ! comment
+            name: duplicate
+            instance_of: TST:001
+            instance_of: TST:002\n",
+        )
+        .unwrap();
+        assert!(instance.name().is_err());
+
+        instance.set_name("sample");
+        instance.set_obsolete(true);
+        instance.add_consider(Ident::from_str("TST:I02").unwrap());
+        instance.add_relationship(
+            RelationIdent::from_str("part_of").unwrap(),
+            Ident::from_str("TST:I03").unwrap(),
+        );
+        assert!(instance.remove_instance_of(&ClassIdent::from_str("TST:001").unwrap()));
+        assert!(!instance.remove_instance_of(&ClassIdent::from_str("TST:003").unwrap()));
+
+        assert_eq!(
+            instance.to_string(),
+            concat!(
+                "[Instance]\n",
+                "id: TST:I01\n",
+                "name: sample\n",
+                "instance_of: TST:002\n",
+                "is_obsolete: true\n",
+                "consider: TST:I02\n",
+                "relationship: part_of TST:I03\n",
+            )
+        );
+
+        instance.set_obsolete(false);
+        assert!(instance.clear_name());
+        assert!(!instance.clear_name());
+        assert!(!instance.is_obsolete());
+        assert_eq!(instance.relationships().count(), 1);
+    }
+}
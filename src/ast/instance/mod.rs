@@ -1,5 +1,7 @@
+mod builder;
 mod clause;
 mod frame;
 
+pub use self::builder::InstanceFrameBuilder;
 pub use self::clause::InstanceClause;
 pub use self::frame::InstanceFrame;
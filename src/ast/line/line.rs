@@ -6,11 +6,13 @@ use std::fmt::Result as FmtResult;
 use std::fmt::Write;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::str::FromStr;
 
 use pest::iterators::Pair;
 
 use crate::ast::*;
 
+use crate::error::QualifierError;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
 use crate::syntax::Rule;
@@ -57,20 +59,88 @@ impl<T> Line<T> {
         }
     }
 
+    /// Replace the line comment with the given one, or remove it.
+    pub fn set_comment<C>(&mut self, comment: C)
+    where
+        C: Into<Option<Comment>>,
+    {
+        self.comment = comment.into().map(Box::new);
+    }
+
+    /// Replace the line qualifier list with the given one, or remove it.
+    pub fn set_qualifiers<Q>(&mut self, qualifiers: Q)
+    where
+        Q: Into<Option<QualifierList>>,
+    {
+        self.qualifiers = qualifiers.into().map(Box::new);
+    }
+
     pub fn qualifiers(&self) -> Option<&QualifierList> {
-        self.qualifiers.as_ref().map(Deref::deref)
+        self.qualifiers.as_deref()
     }
 
     pub fn qualifiers_mut(&mut self) -> Option<&mut QualifierList> {
-        self.qualifiers.as_mut().map(DerefMut::deref_mut)
+        self.qualifiers.as_deref_mut()
+    }
+
+    /// Get a mutable reference to the line qualifier list, adding one if needed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// let mut line = Line::from(TermClause::IsObsolete(true));
+    /// line.qualifiers_or_insert().set_is_inferred(true);
+    /// assert_eq!(line.to_string(), "is_obsolete: true {is_inferred=\"true\"}\n");
+    /// ```
+    pub fn qualifiers_or_insert(&mut self) -> &mut QualifierList {
+        self.qualifiers.get_or_insert_with(Default::default)
+    }
+
+    /// Get the value of the first line qualifier with the given key, if any.
+    pub fn qualifier(&self, key: &str) -> Option<&QuotedString> {
+        self.qualifiers().and_then(|q| q.value(key))
+    }
+
+    /// Parse the value of the line qualifier with the given key, if declared once.
+    ///
+    /// See [`QualifierList::parse_value`](./struct.QualifierList.html#method.parse_value).
+    pub fn parse_qualifier<Q: FromStr>(&self, key: &str) -> Result<Option<Q>, QualifierError> {
+        match self.qualifiers() {
+            Some(qualifiers) => qualifiers.parse_value(key),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the value of the line qualifier with the given key.
+    ///
+    /// See [`QualifierList::set_value`](./struct.QualifierList.html#method.set_value).
+    pub fn set_qualifier<K, V>(&mut self, key: K, value: V) -> Option<QuotedString>
+    where
+        K: Into<RelationIdent>,
+        V: Into<QuotedString>,
+    {
+        self.qualifiers_or_insert().set_value(key, value)
+    }
+
+    /// Remove the line qualifiers with the given key, returning the first value.
+    ///
+    /// The qualifier list is removed from the line once empty.
+    pub fn remove_qualifier(&mut self, key: &str) -> Option<QuotedString> {
+        let qualifiers = self.qualifiers.as_deref_mut()?;
+        let removed = qualifiers.remove_values(key);
+        if qualifiers.is_empty() {
+            self.qualifiers = None;
+        }
+        removed
     }
 
     pub fn comment(&self) -> Option<&Comment> {
-        self.comment.as_ref().map(Deref::deref)
+        self.comment.as_deref()
     }
 
     pub fn comment_mut(&mut self) -> Option<&mut Comment> {
-        self.comment.as_mut().map(DerefMut::deref_mut)
+        self.comment.as_deref_mut()
     }
 
     /// Get a reference to the OBO clause wrapped in the line.
@@ -87,6 +157,28 @@ impl<T> Line<T> {
     pub fn into_inner(self) -> T {
         self.inner
     }
+
+    /// Transform the OBO clause wrapped in the line, keeping qualifiers and comment.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// let line = Line::from(TermClause::IsObsolete(true))
+    ///     .and_comment(Comment::new("deprecated in v3"));
+    /// let line = line.map(|_| TypedefClause::IsObsolete(true));
+    /// assert_eq!(line.to_string(), "is_obsolete: true ! deprecated in v3\n");
+    /// ```
+    pub fn map<U, F>(self, f: F) -> Line<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Line {
+            inner: f(self.inner),
+            qualifiers: self.qualifiers,
+            comment: self.comment,
+        }
+    }
 }
 
 impl<T> AsRef<T> for Line<T> {
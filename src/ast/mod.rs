@@ -23,6 +23,10 @@
 //! [`fastobo::from_stream`]: ../fn.from_file.html
 //! [OBO format version 1.4]: http://owlcollab.github.io/oboformat/doc/GO.format.obo-1_4.html.
 
+#[macro_use]
+mod accessors;
+#[macro_use]
+mod builder;
 mod date;
 mod definition;
 mod doc;
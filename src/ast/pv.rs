@@ -4,12 +4,14 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::fmt::Write;
+use std::str::FromStr;
 
 use fastobo_derive_internal::FromStr;
 use pest::iterators::Pair;
 
 use crate::ast::*;
 
+use crate::error::LiteralError;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
 use crate::syntax::Rule;
@@ -74,7 +76,7 @@ impl<'i> FromPair<'i> for PropertyValue {
 impl PartialOrd for PropertyValue {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.property()
-            .cmp(&other.property())
+            .cmp(other.property())
             .then_with(|| self.to_string().cmp(&other.to_string()))
             .into()
     }
@@ -120,6 +122,11 @@ impl ResourcePropertyValue {
     pub fn target_mut(&mut self) -> &mut Ident {
         &mut self.target
     }
+
+    /// Consume the property value, returning its property and target.
+    pub fn into_parts(self) -> (RelationIdent, Ident) {
+        (self.property, self.target)
+    }
 }
 
 impl Display for ResourcePropertyValue {
@@ -186,6 +193,132 @@ impl LiteralPropertyValue {
     pub fn datatype_mut(&mut self) -> &mut Ident {
         &mut self.datatype
     }
+
+    /// Consume the property value, returning its property, literal and datatype.
+    pub fn into_parts(self) -> (RelationIdent, QuotedString, Ident) {
+        (self.property, self.literal, self.datatype)
+    }
+
+    /// Check the literal is in the lexical space of its declared datatype.
+    ///
+    /// The `xsd:integer`, `xsd:decimal`, `xsd:double`, `xsd:float`,
+    /// `xsd:boolean`, `xsd:dateTime` and `xsd:anyURI` datatypes are checked,
+    /// as well as the integer datatypes derived from `xsd:decimal` (such as
+    /// `xsd:positiveInteger`). Literals of any other datatype are valid.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let pv = LiteralPropertyValue::from_str("shoe_size \"-8\" xsd:positiveInteger").unwrap();
+    /// assert!(pv.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), LiteralError> {
+        let value = self.value();
+        let valid = match Datatype::of(&self.datatype) {
+            None => true,
+            Some(Datatype::Integer(min, max)) => is_integer(value, min, max),
+            Some(Datatype::Decimal) => is_decimal(value),
+            Some(Datatype::Double) => is_double(value),
+            Some(Datatype::Boolean) => is_boolean(value),
+            Some(Datatype::DateTime) => IsoDateTime::from_str(value)
+                .map(|dt| dt.validate().is_ok())
+                .unwrap_or(false),
+            Some(Datatype::AnyUri) => Url::from_str(value).is_ok(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(self.invalid())
+        }
+    }
+
+    /// Read the literal as an `i64`, if declared with an integer datatype.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let pv = LiteralPropertyValue::from_str("shoe_size \"8\" xsd:positiveInteger").unwrap();
+    /// assert_eq!(pv.as_i64(), Ok(8));
+    /// ```
+    pub fn as_i64(&self) -> Result<i64, LiteralError> {
+        match Datatype::of(&self.datatype) {
+            Some(Datatype::Integer(..)) => self.validate()?,
+            _ => return Err(self.unexpected("i64")),
+        }
+        self.value().parse().map_err(|_| self.out_of_range())
+    }
+
+    /// Read the literal as an `f64`, if declared with a numeric datatype.
+    pub fn as_f64(&self) -> Result<f64, LiteralError> {
+        match Datatype::of(&self.datatype) {
+            Some(Datatype::Integer(..)) | Some(Datatype::Decimal) | Some(Datatype::Double) => {
+                self.validate()?
+            }
+            _ => return Err(self.unexpected("f64")),
+        }
+        self.value().parse().map_err(|_| self.out_of_range())
+    }
+
+    /// Read the literal as a `bool`, if declared as `xsd:boolean`.
+    pub fn as_bool(&self) -> Result<bool, LiteralError> {
+        match Datatype::of(&self.datatype) {
+            Some(Datatype::Boolean) => match self.value() {
+                "true" | "1" => Ok(true),
+                "false" | "0" => Ok(false),
+                _ => Err(self.invalid()),
+            },
+            _ => Err(self.unexpected("bool")),
+        }
+    }
+
+    /// Read the literal as an `IsoDateTime`, if declared as `xsd:dateTime`.
+    pub fn as_iso_datetime(&self) -> Result<IsoDateTime, LiteralError> {
+        match Datatype::of(&self.datatype) {
+            Some(Datatype::DateTime) => match IsoDateTime::from_str(self.value()) {
+                Ok(dt) if dt.validate().is_ok() => Ok(dt),
+                _ => Err(self.invalid()),
+            },
+            _ => Err(self.unexpected("IsoDateTime")),
+        }
+    }
+
+    /// Read the literal as an `Url`, if declared as `xsd:anyURI`.
+    pub fn as_url(&self) -> Result<Url, LiteralError> {
+        match Datatype::of(&self.datatype) {
+            Some(Datatype::AnyUri) => Url::from_str(self.value()).map_err(|_| self.invalid()),
+            _ => Err(self.unexpected("Url")),
+        }
+    }
+
+    /// Get the literal with leading and trailing whitespaces collapsed.
+    fn value(&self) -> &str {
+        self.literal.as_str().trim()
+    }
+
+    fn unexpected(&self, expected: &'static str) -> LiteralError {
+        LiteralError::UnexpectedDatatype {
+            expected,
+            datatype: self.datatype.clone(),
+        }
+    }
+
+    fn invalid(&self) -> LiteralError {
+        LiteralError::InvalidLiteral {
+            literal: self.literal.as_str().to_string(),
+            datatype: self.datatype.clone(),
+        }
+    }
+
+    fn out_of_range(&self) -> LiteralError {
+        LiteralError::OutOfRange {
+            literal: self.literal.as_str().to_string(),
+            datatype: self.datatype.clone(),
+        }
+    }
 }
 
 impl Display for LiteralPropertyValue {
@@ -217,6 +350,106 @@ impl<'i> FromPair<'i> for LiteralPropertyValue {
     }
 }
 
+/// The namespace of the XML Schema datatypes.
+const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// An XML Schema datatype with a typed view in `LiteralPropertyValue`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Datatype {
+    /// An integer datatype, with optional inclusive bounds.
+    Integer(Option<i128>, Option<i128>),
+    Decimal,
+    Double,
+    Boolean,
+    DateTime,
+    AnyUri,
+}
+
+impl Datatype {
+    /// Get the datatype identified by `xsd:<name>` or by its full URL.
+    fn of(id: &Ident) -> Option<Self> {
+        let name = match id {
+            Ident::Prefixed(p) if p.prefix() == "xsd" => p.local(),
+            Ident::Url(u) => u.as_str().strip_prefix(XSD_NAMESPACE)?,
+            _ => return None,
+        };
+        let bounds = |min: Option<i128>, max: Option<i128>| Some(Datatype::Integer(min, max));
+        match name {
+            "integer" => bounds(None, None),
+            "long" => bounds(Some(i64::MIN.into()), Some(i64::MAX.into())),
+            "int" => bounds(Some(i32::MIN.into()), Some(i32::MAX.into())),
+            "short" => bounds(Some(i16::MIN.into()), Some(i16::MAX.into())),
+            "byte" => bounds(Some(i8::MIN.into()), Some(i8::MAX.into())),
+            "nonNegativeInteger" => bounds(Some(0), None),
+            "positiveInteger" => bounds(Some(1), None),
+            "nonPositiveInteger" => bounds(None, Some(0)),
+            "negativeInteger" => bounds(None, Some(-1)),
+            "unsignedLong" => bounds(Some(0), Some(u64::MAX.into())),
+            "unsignedInt" => bounds(Some(0), Some(u32::MAX.into())),
+            "unsignedShort" => bounds(Some(0), Some(u16::MAX.into())),
+            "unsignedByte" => bounds(Some(0), Some(u8::MAX.into())),
+            "decimal" => Some(Datatype::Decimal),
+            "double" | "float" => Some(Datatype::Double),
+            "boolean" => Some(Datatype::Boolean),
+            "dateTime" => Some(Datatype::DateTime),
+            "anyURI" => Some(Datatype::AnyUri),
+            _ => None,
+        }
+    }
+}
+
+/// Strip the optional sign of a numeric literal, returning whether it is negative.
+fn split_sign(s: &str) -> (bool, &str) {
+    match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    }
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_integer(s: &str, min: Option<i128>, max: Option<i128>) -> bool {
+    let (negative, digits) = split_sign(s);
+    if !is_digits(digits) {
+        return false;
+    }
+    match s.parse::<i128>() {
+        Ok(n) => min.iter().all(|&m| n >= m) && max.iter().all(|&m| n <= m),
+        // the value overflows an `i128`, so only unbounded sides accept it
+        Err(_) if negative => min.is_none(),
+        Err(_) => max.is_none(),
+    }
+}
+
+fn is_decimal(s: &str) -> bool {
+    let (_, unsigned) = split_sign(s);
+    match unsigned.split_once('.') {
+        None => is_digits(unsigned),
+        Some((int, frac)) => {
+            (int.is_empty() || is_digits(int))
+                && (frac.is_empty() || is_digits(frac))
+                && !(int.is_empty() && frac.is_empty())
+        }
+    }
+}
+
+fn is_double(s: &str) -> bool {
+    match s {
+        "INF" | "+INF" | "-INF" | "NaN" => true,
+        _ => match s.split_once(['e', 'E']) {
+            None => is_decimal(s),
+            Some((mantissa, exponent)) => is_decimal(mantissa) && is_digits(split_sign(exponent).1),
+        },
+    }
+}
+
+fn is_boolean(s: &str) -> bool {
+    matches!(s, "true" | "false" | "1" | "0")
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -260,4 +493,87 @@ mod tests {
         let r4 = PropertyValue::from_str("has_kid jenny").unwrap();
         assert!(l4 < r4);
     }
+
+    fn literal(s: &str) -> LiteralPropertyValue {
+        LiteralPropertyValue::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn validate() {
+        assert!(literal("p \"-12\" xsd:integer").validate().is_ok());
+        assert!(literal("p \"1.\" xsd:decimal").validate().is_ok());
+        assert!(literal("p \"-1.5E3\" xsd:double").validate().is_ok());
+        assert!(literal("p \"INF\" xsd:float").validate().is_ok());
+        assert!(literal("p \"anything\" xsd:string").validate().is_ok());
+        assert!(
+            literal("p \"99999999999999999999999999999999999999999\" xsd:nonNegativeInteger")
+                .validate()
+                .is_ok()
+        );
+
+        assert!(literal("p \"1.5\" xsd:integer").validate().is_err());
+        assert!(literal("p \"256\" xsd:unsignedByte").validate().is_err());
+        assert!(literal("p \"0\" xsd:positiveInteger").validate().is_err());
+        assert!(literal("p \"1e3\" xsd:decimal").validate().is_err());
+        assert!(literal("p \"yes\" xsd:boolean").validate().is_err());
+        assert!(literal("p \"today\" xsd:dateTime").validate().is_err());
+    }
+
+    #[test]
+    fn as_i64() {
+        assert_eq!(literal("p \" 42 \" xsd:int").as_i64(), Ok(42));
+        let url = "http://www.w3.org/2001/XMLSchema#integer";
+        assert_eq!(literal(&format!("p \"-7\" {}", url)).as_i64(), Ok(-7));
+
+        let pv = literal("p \"99999999999999999999\" xsd:integer");
+        assert_eq!(
+            pv.as_i64(),
+            Err(LiteralError::OutOfRange {
+                literal: String::from("99999999999999999999"),
+                datatype: Ident::from(PrefixedIdent::new("xsd", "integer")),
+            })
+        );
+
+        let pv = literal("p \"42\" xsd:string");
+        assert_eq!(
+            pv.as_i64(),
+            Err(LiteralError::UnexpectedDatatype {
+                expected: "i64",
+                datatype: Ident::from(PrefixedIdent::new("xsd", "string")),
+            })
+        );
+    }
+
+    #[test]
+    fn as_f64() {
+        assert_eq!(literal("p \"1.5\" xsd:decimal").as_f64(), Ok(1.5));
+        assert_eq!(literal("p \"2\" xsd:integer").as_f64(), Ok(2.0));
+        assert_eq!(
+            literal("p \"-INF\" xsd:double").as_f64(),
+            Ok(f64::NEG_INFINITY)
+        );
+        assert!(literal("p \"inf\" xsd:double").as_f64().is_err());
+    }
+
+    #[test]
+    fn as_bool() {
+        assert_eq!(literal("p \"true\" xsd:boolean").as_bool(), Ok(true));
+        assert_eq!(literal("p \"0\" xsd:boolean").as_bool(), Ok(false));
+        assert!(literal("p \"True\" xsd:boolean").as_bool().is_err());
+        assert!(literal("p \"true\" xsd:string").as_bool().is_err());
+    }
+
+    #[test]
+    fn as_iso_datetime() {
+        let pv = literal("p \"2019-04-08T12:30:00Z\" xsd:dateTime");
+        let dt = pv.as_iso_datetime().unwrap();
+        assert_eq!(dt.to_string(), "2019-04-08T12:30:00Z");
+    }
+
+    #[test]
+    fn as_url() {
+        let pv = literal("p \"https://example.com/\" xsd:anyURI");
+        assert_eq!(pv.as_url().unwrap().as_str(), "https://example.com/");
+        assert!(literal("p \"not a url\" xsd:anyURI").as_url().is_err());
+    }
 }
@@ -11,6 +11,7 @@ use pest::iterators::Pair;
 
 use crate::ast::*;
 
+use crate::error::QualifierError;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
 
@@ -50,6 +51,32 @@ impl Qualifier {
     pub fn value_mut(&mut self) -> &mut QuotedString {
         &mut self.value
     }
+
+    /// Consume the qualifier, returning its key and value.
+    pub fn into_parts(self) -> (RelationIdent, QuotedString) {
+        (self.key, self.value)
+    }
+
+    /// Check whether the key of the qualifier is the given one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let qualifier = Qualifier::from_str("source=\"PMID:1234\"").unwrap();
+    /// assert!(qualifier.has_key("source"));
+    /// ```
+    pub fn has_key(&self, key: &str) -> bool {
+        match self.key.as_ref() {
+            Ident::Unprefixed(id) => id.as_str() == key,
+            Ident::Url(url) => url.as_str() == key,
+            Ident::Prefixed(id) => match key.split_once(':') {
+                Some((prefix, local)) => id.prefix() == prefix && id.local() == local,
+                None => false,
+            },
+        }
+    }
 }
 
 impl Display for Qualifier {
@@ -94,6 +121,211 @@ impl QualifierList {
     pub fn sort(&mut self) {
         self.qualifiers.sort_unstable();
     }
+
+    /// Check whether the list contains a qualifier with the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.qualifiers.iter().any(|q| q.has_key(key))
+    }
+
+    /// Get the value of the first qualifier with the given key, if any.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let list = QualifierList::from_str("{source=\"PMID:1234\"}").unwrap();
+    /// assert_eq!(list.value("source").unwrap().as_str(), "PMID:1234");
+    /// assert!(list.value("comment").is_none());
+    /// ```
+    pub fn value(&self, key: &str) -> Option<&QuotedString> {
+        self.qualifiers
+            .iter()
+            .find(|q| q.has_key(key))
+            .map(Qualifier::value)
+    }
+
+    /// Get an iterator over the values of the qualifiers with the given key.
+    pub fn values<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a QuotedString> + 'a {
+        self.qualifiers
+            .iter()
+            .filter(move |q| q.has_key(key))
+            .map(Qualifier::value)
+    }
+
+    /// Set the value of the qualifier with the given key.
+    ///
+    /// The first qualifier with the same key is updated in place, and any
+    /// other qualifier with that key is removed; otherwise a new qualifier
+    /// is added at the end of the list. Returns the previous value, if any.
+    pub fn set_value<K, V>(&mut self, key: K, value: V) -> Option<QuotedString>
+    where
+        K: Into<RelationIdent>,
+        V: Into<QuotedString>,
+    {
+        let key = key.into();
+        let value = value.into();
+        match self.qualifiers.iter().position(|q| q.key == key) {
+            Some(index) => {
+                let old = std::mem::replace(&mut self.qualifiers[index].value, value);
+                let mut rest = self.qualifiers.split_off(index + 1);
+                rest.retain(|q| q.key != key);
+                self.qualifiers.append(&mut rest);
+                Some(old)
+            }
+            None => {
+                self.qualifiers.push(Qualifier::new(key, value));
+                None
+            }
+        }
+    }
+
+    /// Remove the qualifiers with the given key, returning the first value.
+    pub fn remove_values(&mut self, key: &str) -> Option<QuotedString> {
+        let mut removed = None;
+        self.qualifiers.retain(|q| {
+            if !q.has_key(key) {
+                return true;
+            }
+            if removed.is_none() {
+                removed = Some(q.value.clone());
+            }
+            false
+        });
+        removed
+    }
+
+    /// Parse the value of the qualifier with the given key, if declared once.
+    ///
+    /// # Errors
+    /// Returns a `QualifierError` if the qualifier is declared more than
+    /// once, or if its value cannot be parsed as a `T`.
+    pub fn parse_value<T: FromStr>(&self, key: &str) -> Result<Option<T>, QualifierError> {
+        let mut values = self.values(key);
+        let value = match values.next() {
+            None => return Ok(None),
+            Some(value) => value,
+        };
+        if values.next().is_some() {
+            return Err(QualifierError::DuplicateQualifier {
+                key: key.to_string(),
+            });
+        }
+        value
+            .as_str()
+            .parse()
+            .map(Some)
+            .map_err(|_| QualifierError::InvalidValue {
+                key: key.to_string(),
+                value: value.as_str().to_string(),
+            })
+    }
+
+    /// Get the `source` qualifier, giving the provenance of the line.
+    pub fn source(&self) -> Result<Option<Ident>, QualifierError> {
+        self.parse_value("source")
+    }
+
+    /// Set the `source` qualifier, giving the provenance of the line.
+    pub fn set_source<I: Into<Ident>>(&mut self, source: I) {
+        self.set_well_known("source", source.into().to_string());
+    }
+
+    /// Get the `is_inferred` qualifier, marking lines added by a reasoner.
+    pub fn is_inferred(&self) -> Result<Option<bool>, QualifierError> {
+        self.parse_value("is_inferred")
+    }
+
+    /// Set the `is_inferred` qualifier, marking lines added by a reasoner.
+    pub fn set_is_inferred(&mut self, inferred: bool) {
+        self.set_well_known("is_inferred", inferred.to_string());
+    }
+
+    /// Get the `cardinality` qualifier of a `relationship` line.
+    pub fn cardinality(&self) -> Result<Option<u32>, QualifierError> {
+        self.parse_value("cardinality")
+    }
+
+    /// Set the `cardinality` qualifier of a `relationship` line.
+    pub fn set_cardinality(&mut self, cardinality: u32) {
+        self.set_well_known("cardinality", cardinality.to_string());
+    }
+
+    /// Get the `minCardinality` qualifier of a `relationship` line.
+    pub fn min_cardinality(&self) -> Result<Option<u32>, QualifierError> {
+        self.parse_value("minCardinality")
+    }
+
+    /// Set the `minCardinality` qualifier of a `relationship` line.
+    pub fn set_min_cardinality(&mut self, cardinality: u32) {
+        self.set_well_known("minCardinality", cardinality.to_string());
+    }
+
+    /// Get the `maxCardinality` qualifier of a `relationship` line.
+    pub fn max_cardinality(&self) -> Result<Option<u32>, QualifierError> {
+        self.parse_value("maxCardinality")
+    }
+
+    /// Set the `maxCardinality` qualifier of a `relationship` line.
+    pub fn set_max_cardinality(&mut self, cardinality: u32) {
+        self.set_well_known("maxCardinality", cardinality.to_string());
+    }
+
+    /// Get the `gci_relation` qualifier of a `relationship` line.
+    pub fn gci_relation(&self) -> Result<Option<RelationIdent>, QualifierError> {
+        self.parse_value("gci_relation")
+    }
+
+    /// Get the `gci_filler` qualifier of a `relationship` line.
+    pub fn gci_filler(&self) -> Result<Option<ClassIdent>, QualifierError> {
+        self.parse_value("gci_filler")
+    }
+
+    /// Get the `gci_relation` and `gci_filler` qualifiers of a `relationship` line.
+    ///
+    /// # Errors
+    /// Returns a `QualifierError` if only one of the two qualifiers is
+    /// declared, or if either of them is invalid.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let list = QualifierList::from_str(
+    ///     "{gci_relation=\"part_of\", gci_filler=\"UBERON:0000955\"}"
+    /// ).unwrap();
+    /// let (relation, filler) = list.gci().unwrap().unwrap();
+    /// assert_eq!(relation.to_string(), "part_of");
+    /// assert_eq!(filler.to_string(), "UBERON:0000955");
+    /// ```
+    pub fn gci(&self) -> Result<Option<(RelationIdent, ClassIdent)>, QualifierError> {
+        match (self.gci_relation()?, self.gci_filler()?) {
+            (Some(relation), Some(filler)) => Ok(Some((relation, filler))),
+            (None, None) => Ok(None),
+            (Some(_), None) => Err(QualifierError::MissingQualifier {
+                key: String::from("gci_filler"),
+            }),
+            (None, Some(_)) => Err(QualifierError::MissingQualifier {
+                key: String::from("gci_relation"),
+            }),
+        }
+    }
+
+    /// Set the `gci_relation` and `gci_filler` qualifiers of a `relationship` line.
+    pub fn set_gci<R, C>(&mut self, relation: R, filler: C)
+    where
+        R: Into<RelationIdent>,
+        C: Into<ClassIdent>,
+    {
+        self.set_well_known("gci_relation", relation.into().to_string());
+        self.set_well_known("gci_filler", filler.into().to_string());
+    }
+
+    fn set_well_known(&mut self, key: &str, value: String) {
+        let key = RelationIdent::from(UnprefixedIdent::new(key));
+        self.set_value(key, QuotedString::new(value));
+    }
 }
 
 impl AsMut<[Qualifier]> for QualifierList {
@@ -217,6 +449,7 @@ mod tests {
     mod list {
 
         use super::*;
+        use pretty_assertions::assert_eq;
 
         #[test]
         fn from_str() {
@@ -228,5 +461,71 @@ mod tests {
                 Err(e) => panic!("{}", e),
             }
         }
+
+        #[test]
+        fn value() {
+            let list = QualifierList::from_str(
+                "{comment=\"first\", comment=\"second\", RO:0002612=\"x\"}",
+            )
+            .unwrap();
+            assert!(list.contains_key("comment"));
+            assert!(list.contains_key("RO:0002612"));
+            assert!(!list.contains_key("RO"));
+            assert_eq!(list.value("comment").unwrap().as_str(), "first");
+            assert_eq!(list.values("comment").count(), 2);
+        }
+
+        #[test]
+        fn set_value() {
+            let mut list = QualifierList::from_str(
+                "{comment=\"first\", source=\"PMID:1\", comment=\"second\"}",
+            )
+            .unwrap();
+            let key = RelationIdent::from(UnprefixedIdent::new("comment"));
+            let old = list.set_value(key, "third");
+            assert_eq!(old.unwrap().as_str(), "first");
+            assert_eq!(list.to_string(), "{comment=\"third\", source=\"PMID:1\"}");
+
+            assert_eq!(list.remove_values("source").unwrap().as_str(), "PMID:1");
+            assert!(list.remove_values("source").is_none());
+            assert_eq!(list.to_string(), "{comment=\"third\"}");
+        }
+
+        #[test]
+        fn typed() {
+            let mut list = QualifierList::default();
+            list.set_is_inferred(true);
+            list.set_min_cardinality(1);
+            list.set_source(Ident::from(PrefixedIdent::new("PMID", "1234")));
+            assert_eq!(
+                list.to_string(),
+                "{is_inferred=\"true\", minCardinality=\"1\", source=\"PMID:1234\"}"
+            );
+            assert_eq!(list.is_inferred(), Ok(Some(true)));
+            assert_eq!(list.min_cardinality(), Ok(Some(1)));
+            assert_eq!(list.max_cardinality(), Ok(None));
+            assert_eq!(
+                list.source(),
+                Ok(Some(Ident::from(PrefixedIdent::new("PMID", "1234"))))
+            );
+
+            let list = QualifierList::from_str("{cardinality=\"-1\"}").unwrap();
+            assert_eq!(
+                list.cardinality(),
+                Err(QualifierError::InvalidValue {
+                    key: String::from("cardinality"),
+                    value: String::from("-1"),
+                })
+            );
+
+            let list =
+                QualifierList::from_str("{is_inferred=\"true\", is_inferred=\"false\"}").unwrap();
+            assert_eq!(
+                list.is_inferred(),
+                Err(QualifierError::DuplicateQualifier {
+                    key: String::from("is_inferred"),
+                })
+            );
+        }
     }
 }
@@ -2,6 +2,7 @@ use std::borrow::Borrow;
 use std::borrow::Cow;
 use std::borrow::ToOwned;
 use std::fmt::Display;
+use std::fmt::Error as FmtError;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::fmt::Write;
@@ -40,7 +41,7 @@ fn unescape<W: Write>(f: &mut W, s: &str) -> FmtResult {
                 Some('f') => f.write_char('\u{000c}')?,
                 Some('t') => f.write_char('\t')?,
                 Some(other) => f.write_char(other)?,
-                None => panic!("invalid escape"), // FIXME(@althonos)
+                None => return Err(FmtError),
             }
         } else {
             f.write_char(char)?;
@@ -145,7 +146,7 @@ impl<'i> FromPair<'i> for QuotedString {
         let escaped = s.quickcount(b'\\');
         let mut local = String::with_capacity(s.len() + escaped);
         unescape(&mut local, s.get_unchecked(1..s.len() - 1))
-            .expect("String as fmt::Write cannot fail");
+            .map_err(|_| SyntaxError::invalid_escape(pair.as_span()))?;
         Ok(QuotedString::new(local))
     }
 }
@@ -194,7 +195,7 @@ impl Deref for QuotedStr {
     }
 }
 
-impl<'a> Display for QuotedStr {
+impl Display for QuotedStr {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         f.write_char('"')
             .and(escape(f, &self.0))
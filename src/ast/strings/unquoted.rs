@@ -2,6 +2,7 @@ use std::borrow::Borrow;
 use std::borrow::Cow;
 use std::borrow::ToOwned;
 use std::fmt::Display;
+use std::fmt::Error as FmtError;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::fmt::Write;
@@ -44,7 +45,7 @@ fn unescape<W: Write>(f: &mut W, s: &str) -> FmtResult {
                 Some('f') => f.write_char('\u{000c}')?,
                 Some('t') => f.write_char('\t')?,
                 Some(other) => f.write_char(other)?,
-                None => panic!("invalid escape"), // FIXME(@althonos)
+                None => return Err(FmtError),
             }
         } else {
             f.write_char(char)?;
@@ -166,7 +167,7 @@ impl<'i> FromPair<'i> for UnquotedString {
         let s = pair.as_str();
         let escaped = s.quickcount(b'\\'); // number of escaped characters
         let mut local = String::with_capacity(s.len() + escaped);
-        unescape(&mut local, s).expect("String as fmt::Write cannot fail");
+        unescape(&mut local, s).map_err(|_| SyntaxError::invalid_escape(pair.as_span()))?;
         Ok(UnquotedString::new(local))
     }
 }
@@ -196,7 +197,7 @@ impl Deref for UnquotedStr {
     }
 }
 
-impl<'a> Display for UnquotedStr {
+impl Display for UnquotedStr {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         escape(f, &self.0)
     }
@@ -0,0 +1,255 @@
+use crate::ast::*;
+use crate::error::Error;
+use crate::semantics::Identified;
+use crate::semantics::OboFrame;
+
+/// A builder for `TermFrame`, adding clauses in a fluent style.
+///
+/// Clauses are added in the order the builder methods are called. The
+/// cardinality of each clause is only checked when the frame is built.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use std::str::FromStr;
+/// # use fastobo::ast::*;
+/// let id = ClassIdent::from_str("MS:1000031").unwrap();
+/// let parent = ClassIdent::from_str("MS:1000030").unwrap();
+/// let frame = TermFrame::builder(id)
+///     .name("instrument model")
+///     .def("An instrument model.", vec![Xref::new(Ident::from_str("PSI:MS").unwrap())])
+///     .is_a(parent)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     frame.to_string(),
+///     concat!(
+///         "[Term]\n",
+///         "id: MS:1000031\n",
+///         "name: instrument model\n",
+///         "def: \"An instrument model.\" [PSI:MS]\n",
+///         "is_a: MS:1000030\n",
+///     )
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct TermFrameBuilder {
+    frame: TermFrame,
+}
+
+impl TermFrameBuilder {
+    /// Create a new builder for a term frame with the given ID.
+    pub fn new<I>(id: I) -> Self
+    where
+        I: Into<Line<ClassIdent>>,
+    {
+        Self {
+            frame: TermFrame::new(id),
+        }
+    }
+
+    /// Add an arbitrary clause to the frame.
+    pub fn clause(self, clause: TermClause) -> Self {
+        self.line(Line::from(clause))
+    }
+
+    /// Add an arbitrary clause line, with qualifiers or a comment, to the frame.
+    pub fn line(mut self, line: Line<TermClause>) -> Self {
+        self.frame.clauses_mut().push(line);
+        self
+    }
+
+    build_flag!(TermClause::IsAnonymous, "is_anonymous", is_anonymous);
+    build_clause!(TermClause::Name(UnquotedString), "name", name);
+    build_clause!(
+        TermClause::Namespace(NamespaceIdent),
+        "namespace",
+        namespace
+    );
+    build_clause!(TermClause::AltId(Ident), "alt_id", alt_id);
+
+    /// Add a `def` clause with the given text and cross-references to the frame.
+    pub fn def<T, L>(self, text: T, xrefs: L) -> Self
+    where
+        T: Into<QuotedString>,
+        L: Into<XrefList>,
+    {
+        self.clause(TermClause::Def(Box::new(Definition::with_xrefs(
+            text, xrefs,
+        ))))
+    }
+
+    build_clause!(TermClause::Comment(UnquotedString), "comment", comment);
+    build_clause!(TermClause::Subset(SubsetIdent), "subset", subset);
+
+    /// Add a `synonym` clause with the given description and scope to the frame.
+    ///
+    /// Use `TermFrameBuilder::clause` to add a synonym with a type or
+    /// cross-references.
+    pub fn synonym<D>(self, desc: D, scope: SynonymScope) -> Self
+    where
+        D: Into<QuotedString>,
+    {
+        self.clause(TermClause::Synonym(Box::new(Synonym::new(desc, scope))))
+    }
+
+    build_clause!(TermClause::Xref(Xref), "xref", xref);
+    build_flag!(TermClause::Builtin, "builtin", builtin);
+    build_clause!(
+        TermClause::PropertyValue(PropertyValue),
+        "property_value",
+        property_value
+    );
+    build_clause!(TermClause::IsA(ClassIdent), "is_a", is_a);
+
+    /// Add the *genus* of a `intersection_of` definition to the frame.
+    pub fn genus<C: Into<ClassIdent>>(self, class: C) -> Self {
+        self.clause(TermClause::IntersectionOf(None, Box::new(class.into())))
+    }
+
+    /// Add a *differentia* of a `intersection_of` definition to the frame.
+    pub fn differentia<R, C>(self, relation: R, class: C) -> Self
+    where
+        R: Into<RelationIdent>,
+        C: Into<ClassIdent>,
+    {
+        let relation = Some(Box::new(relation.into()));
+        self.clause(TermClause::IntersectionOf(relation, Box::new(class.into())))
+    }
+
+    build_clause!(TermClause::UnionOf(ClassIdent), "union_of", union_of);
+    build_clause!(
+        TermClause::EquivalentTo(ClassIdent),
+        "equivalent_to",
+        equivalent_to
+    );
+    build_clause!(
+        TermClause::DisjointFrom(ClassIdent),
+        "disjoint_from",
+        disjoint_from
+    );
+    build_pair!(
+        TermClause::Relationship(RelationIdent, ClassIdent),
+        "relationship",
+        relationship
+    );
+    build_clause!(
+        TermClause::CreatedBy(UnquotedString),
+        "created_by",
+        created_by
+    );
+    build_clause!(
+        TermClause::CreationDate(IsoDateTime),
+        "creation_date",
+        creation_date
+    );
+    build_flag!(TermClause::IsObsolete, "is_obsolete", is_obsolete);
+    build_clause!(
+        TermClause::ReplacedBy(ClassIdent),
+        "replaced_by",
+        replaced_by
+    );
+    build_clause!(TermClause::Consider(ClassIdent), "consider", consider);
+
+    /// Build the frame, checking the cardinality of its clauses.
+    ///
+    /// # Errors
+    /// Returns an `Error::CardinalityError`, with the identifier of the frame,
+    /// if a clause is declared more times than allowed, or if a clause such
+    /// as `union_of` is declared exactly once.
+    pub fn build(self) -> Result<TermFrame, Error> {
+        match self.frame.cardinality_check() {
+            Ok(()) => Ok(self.frame),
+            Err(inner) => Err(Error::CardinalityError {
+                id: Some(self.frame.as_id().clone()),
+                inner,
+            }),
+        }
+    }
+}
+
+impl TermFrame {
+    /// Create a new builder for a term frame with the given ID.
+    pub fn builder<I>(id: I) -> TermFrameBuilder
+    where
+        I: Into<Line<ClassIdent>>,
+    {
+        TermFrameBuilder::new(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+    use textwrap_macros::dedent;
+
+    use super::*;
+    use crate::error::CardinalityError;
+
+    fn class(id: &str) -> ClassIdent {
+        ClassIdent::from_str(id).unwrap()
+    }
+
+    #[test]
+    fn build() {
+        let frame = TermFrame::builder(class("TST:001"))
+            .name("test")
+            .namespace(NamespaceIdent::from(UnprefixedIdent::new("test")))
+            .synonym("exam", SynonymScope::Related)
+            .genus(class("TST:002"))
+            .differentia(
+                RelationIdent::from(UnprefixedIdent::new("part_of")),
+                class("TST:003"),
+            )
+            .is_obsolete(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            frame.to_string(),
+            dedent!(
+                r#"
+                [Term]
+                id: TST:001
+                name: test
+                namespace: test
+                synonym: "exam" RELATED []
+                intersection_of: TST:002
+                intersection_of: part_of TST:003
+                is_obsolete: true
+                "#
+            )
+            .trim_start()
+        );
+    }
+
+    #[test]
+    fn build_cardinality() {
+        let err = TermFrame::builder(class("TST:001"))
+            .name("test")
+            .name("other")
+            .build()
+            .unwrap_err();
+        match err {
+            Error::CardinalityError { id, inner } => {
+                assert_eq!(id, Some(Ident::from_str("TST:001").unwrap()));
+                assert_eq!(inner, CardinalityError::duplicate("name"));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        let err = TermFrame::builder(class("TST:002"))
+            .union_of(class("TST:003"))
+            .build()
+            .unwrap_err();
+        match err {
+            Error::CardinalityError { id, inner } => {
+                assert_eq!(id, Some(Ident::from_str("TST:002").unwrap()));
+                assert_eq!(inner, CardinalityError::single("union_of"));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+}
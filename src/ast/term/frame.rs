@@ -12,6 +12,8 @@ use crate::ast::*;
 use crate::error::CardinalityError;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
+use crate::semantics::lines_sorted;
+use crate::semantics::sort_lines;
 use crate::semantics::Identified;
 use crate::semantics::OboFrame;
 use crate::semantics::Orderable;
@@ -74,6 +76,11 @@ impl TermFrame {
         &mut self.clauses
     }
 
+    /// Consume the `TermFrame`, returning its identifier and clauses.
+    pub fn into_parts(self) -> (Line<ClassIdent>, Vec<Line<TermClause>>) {
+        (self.id, self.clauses)
+    }
+
     /// Check if the class has a *genus-differentia* definition.
     ///
     /// *Genus-differentia* definition is a method of intensional definition
@@ -102,32 +109,175 @@ impl TermFrame {
         genus_count == 1 && has_differentia
     }
 
-    /// Get the name (label) of the term, if exactly one is declared.
-    pub fn name(&self) -> Result<&UnquotedString, CardinalityError> {
-        let mut name: Option<&UnquotedString> = None;
-        for clause in &self.clauses {
-            if let TermClause::Name(n) = clause.as_inner() {
-                match name {
-                    Some(_) => return Err(CardinalityError::duplicate("name")),
-                    None => name = Some(&n),
-                }
-            }
-        }
-        name.ok_or_else(|| CardinalityError::missing("name"))
+    single_clause!(
+        TermClause::Name(UnquotedString),
+        "name",
+        name,
+        set_name,
+        clear_name
+    );
+    single_clause!(
+        TermClause::Namespace(NamespaceIdent),
+        "namespace",
+        namespace,
+        set_namespace
+    );
+    single_clause!(
+        TermClause::Def(Definition),
+        "def",
+        definition,
+        set_definition,
+        clear_definition
+    );
+    single_clause!(
+        TermClause::Comment(UnquotedString),
+        "comment",
+        comment,
+        set_comment,
+        clear_comment
+    );
+    single_clause!(
+        TermClause::CreatedBy(UnquotedString),
+        "created_by",
+        created_by,
+        set_created_by,
+        clear_created_by
+    );
+    single_clause!(
+        TermClause::CreationDate(IsoDateTime),
+        "creation_date",
+        creation_date,
+        set_creation_date,
+        clear_creation_date
+    );
+
+    flag_clause!(
+        TermClause::IsAnonymous,
+        "is_anonymous",
+        is_anonymous,
+        set_anonymous
+    );
+    flag_clause!(TermClause::Builtin, "builtin", is_builtin, set_builtin);
+    flag_clause!(
+        TermClause::IsObsolete,
+        "is_obsolete",
+        is_obsolete,
+        set_obsolete
+    );
+
+    many_clause!(
+        TermClause::AltId(Ident),
+        "alt_id",
+        alt_ids,
+        add_alt_id,
+        remove_alt_id
+    );
+    many_clause!(
+        TermClause::Subset(SubsetIdent),
+        "subset",
+        subsets,
+        add_subset,
+        remove_subset
+    );
+    many_clause!(
+        TermClause::Synonym(Synonym),
+        "synonym",
+        synonyms,
+        add_synonym,
+        remove_synonym
+    );
+    many_clause!(TermClause::Xref(Xref), "xref", xrefs, add_xref, remove_xref);
+    many_clause!(
+        TermClause::PropertyValue(PropertyValue),
+        "property_value",
+        property_values,
+        add_property_value,
+        remove_property_value
+    );
+    many_clause!(
+        TermClause::IsA(ClassIdent),
+        "is_a",
+        is_a,
+        add_is_a,
+        remove_is_a
+    );
+    many_clause!(
+        TermClause::EquivalentTo(ClassIdent),
+        "equivalent_to",
+        equivalent_to,
+        add_equivalent_to,
+        remove_equivalent_to
+    );
+    many_clause!(
+        TermClause::DisjointFrom(ClassIdent),
+        "disjoint_from",
+        disjoint_from,
+        add_disjoint_from,
+        remove_disjoint_from
+    );
+    many_clause!(
+        TermClause::ReplacedBy(ClassIdent),
+        "replaced_by",
+        replaced_by,
+        add_replaced_by,
+        remove_replaced_by
+    );
+    many_clause!(
+        TermClause::Consider(ClassIdent),
+        "consider",
+        consider,
+        add_consider,
+        remove_consider
+    );
+
+    pair_clause!(
+        TermClause::Relationship(RelationIdent, ClassIdent),
+        "relationship",
+        relationships,
+        add_relationship,
+        remove_relationship
+    );
+
+    not_one_clause!(
+        TermClause::UnionOf(ClassIdent),
+        "union_of",
+        union_of,
+        set_union_of
+    );
+
+    /// Get an iterator over the values of the `intersection_of` clauses of the frame.
+    ///
+    /// The genus of the intersection is yielded without a relation.
+    pub fn intersection_of(&self) -> impl Iterator<Item = (Option<&RelationIdent>, &ClassIdent)> {
+        self.clauses
+            .iter()
+            .filter_map(|line| match line.as_inner() {
+                TermClause::IntersectionOf(r, c) => Some((r.as_deref(), c.as_ref())),
+                _ => None,
+            })
     }
 
-    /// Get the definition of the term, if exactly one is declared.
-    pub fn definition(&self) -> Result<&Definition, CardinalityError> {
-        let mut def: Option<&Definition> = None;
-        for clause in &self.clauses {
-            if let TermClause::Def(n) = clause.as_inner() {
-                match def {
-                    Some(_) => return Err(CardinalityError::duplicate("def")),
-                    None => def = Some(&n),
-                }
-            }
+    /// Replace the `intersection_of` clauses of the frame with the given values.
+    ///
+    /// # Errors
+    /// Returns a `CardinalityError` and leaves the frame unchanged if exactly
+    /// one value is given, since a single `intersection_of` clause is invalid.
+    pub fn set_intersection_of<I>(&mut self, values: I) -> Result<(), CardinalityError>
+    where
+        I: IntoIterator<Item = (Option<RelationIdent>, ClassIdent)>,
+    {
+        let clauses: Vec<_> = values
+            .into_iter()
+            .map(|(r, c)| Line::from(TermClause::IntersectionOf(r.map(Box::new), Box::new(c))))
+            .collect();
+        if clauses.len() == 1 {
+            return Err(CardinalityError::single("intersection_of"));
         }
-        def.ok_or_else(|| CardinalityError::missing("def"))
+        crate::ast::accessors::remove_clauses(&mut self.clauses, |c| {
+            matches!(c, TermClause::IntersectionOf(..))
+        });
+        self.clauses.extend(clauses);
+        Ok(())
     }
 }
 
@@ -227,15 +377,10 @@ impl<'a> IntoIterator for &'a TermFrame {
 
 impl Orderable for TermFrame {
     fn sort(&mut self) {
-        self.clauses.sort_unstable();
+        sort_lines(&mut self.clauses);
     }
     fn is_sorted(&self) -> bool {
-        for i in 1..self.clauses.len() {
-            if self.clauses[i - 1] > self.clauses[i] {
-                return false;
-            }
-        }
-        true
+        lines_sorted(&self.clauses)
     }
 }
 
@@ -327,4 +472,97 @@ mod tests {
         )
         .is_ok());
     }
+
+    #[test]
+    fn accessors() {
+        let term = TermFrame::from_str(
+            "[Term]
+            id: TST:001
+            name: test
+            synonym: \"trial\" EXACT []
+            is_a: TST:002
+            is_a: TST:003
+            relationship: part_of TST:004
+            intersection_of: TST:002
+            intersection_of: part_of TST:004
+            is_obsolete: true\n",
+        )
+        .unwrap();
+
+        assert_eq!(term.name().unwrap().as_str(), "test");
+        assert!(term.comment().is_err());
+        assert!(term.is_obsolete());
+        assert!(!term.is_anonymous());
+
+        let is_a: Vec<_> = term.is_a().map(ToString::to_string).collect();
+        assert_eq!(is_a, vec!["TST:002", "TST:003"]);
+        let relationships: Vec<_> = term
+            .relationships()
+            .map(|(r, c)| (r.to_string(), c.to_string()))
+            .collect();
+        assert_eq!(
+            relationships,
+            vec![("part_of".to_string(), "TST:004".to_string())]
+        );
+        let intersection: Vec<_> = term
+            .intersection_of()
+            .map(|(r, c)| (r.map(ToString::to_string), c.to_string()))
+            .collect();
+        assert_eq!(
+            intersection,
+            vec![
+                (None, "TST:002".to_string()),
+                (Some("part_of".to_string()), "TST:004".to_string())
+            ]
+        );
+        assert_eq!(term.synonyms().count(), 1);
+    }
+
+    #[test]
+    fn mutators() {
+        let mut term = TermFrame::from_str(
+            "[Term]
+            id: TST:001
+            name: first {source=\"PMID:1\"} ! comment
+            name: duplicate
+            xref: PMID:1
+            xref: PMID:2\n",
+        )
+        .unwrap();
+        assert!(term.name().is_err());
+
+        term.set_name("test");
+        term.set_obsolete(true);
+        term.add_is_a(ClassIdent::from_str("TST:002").unwrap());
+        assert!(term.remove_xref(&Xref::from_str("PMID:1").unwrap()));
+        assert!(!term.remove_xref(&Xref::from_str("PMID:3").unwrap()));
+
+        let c1 = ClassIdent::from_str("TST:003").unwrap();
+        let c2 = ClassIdent::from_str("TST:004").unwrap();
+        assert_eq!(
+            term.set_union_of(vec![c1.clone()]),
+            Err(CardinalityError::single("union_of"))
+        );
+        term.set_union_of(vec![c1, c2]).unwrap();
+
+        assert_eq!(
+            term.to_string(),
+            concat!(
+                "[Term]\n",
+                "id: TST:001\n",
+                "name: test\n",
+                "xref: PMID:2\n",
+                "is_obsolete: true\n",
+                "is_a: TST:002\n",
+                "union_of: TST:003\n",
+                "union_of: TST:004\n",
+            )
+        );
+
+        term.set_obsolete(false);
+        assert!(term.clear_name());
+        assert!(!term.clear_name());
+        assert!(term.name().is_err());
+        assert!(!term.is_obsolete());
+    }
 }
@@ -1,5 +1,7 @@
+mod builder;
 mod clause;
 mod frame;
 
+pub use self::builder::TermFrameBuilder;
 pub use self::clause::TermClause;
 pub use self::frame::TermFrame;
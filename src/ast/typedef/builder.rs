@@ -0,0 +1,217 @@
+use crate::ast::*;
+use crate::error::Error;
+use crate::semantics::Identified;
+use crate::semantics::OboFrame;
+
+/// A builder for `TypedefFrame`, adding clauses in a fluent style.
+///
+/// Clauses are added in the order the builder methods are called. The
+/// cardinality of each clause is only checked when the frame is built.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// let frame = TypedefFrame::builder(RelationIdent::from(UnprefixedIdent::new("part_of")))
+///     .name("part of")
+///     .is_transitive(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     frame.to_string(),
+///     "[Typedef]\nid: part_of\nname: part of\nis_transitive: true\n"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct TypedefFrameBuilder {
+    frame: TypedefFrame,
+}
+
+impl TypedefFrameBuilder {
+    /// Create a new builder for a typedef frame with the given ID.
+    pub fn new<I>(id: I) -> Self
+    where
+        I: Into<Line<RelationIdent>>,
+    {
+        Self {
+            frame: TypedefFrame::new(id),
+        }
+    }
+
+    /// Add an arbitrary clause to the frame.
+    pub fn clause(self, clause: TypedefClause) -> Self {
+        self.line(Line::from(clause))
+    }
+
+    /// Add an arbitrary clause line, with qualifiers or a comment, to the frame.
+    pub fn line(mut self, line: Line<TypedefClause>) -> Self {
+        self.frame.clauses_mut().push(line);
+        self
+    }
+
+    build_flag!(TypedefClause::IsAnonymous, "is_anonymous", is_anonymous);
+    build_clause!(TypedefClause::Name(UnquotedString), "name", name);
+    build_clause!(
+        TypedefClause::Namespace(NamespaceIdent),
+        "namespace",
+        namespace
+    );
+    build_clause!(TypedefClause::AltId(Ident), "alt_id", alt_id);
+
+    /// Add a `def` clause with the given text and cross-references to the frame.
+    pub fn def<T, L>(self, text: T, xrefs: L) -> Self
+    where
+        T: Into<QuotedString>,
+        L: Into<XrefList>,
+    {
+        let def = Definition::with_xrefs(text, xrefs);
+        self.clause(TypedefClause::Def(Box::new(def)))
+    }
+
+    build_clause!(TypedefClause::Comment(UnquotedString), "comment", comment);
+    build_clause!(TypedefClause::Subset(SubsetIdent), "subset", subset);
+
+    /// Add a `synonym` clause with the given description and scope to the frame.
+    pub fn synonym<D>(self, desc: D, scope: SynonymScope) -> Self
+    where
+        D: Into<QuotedString>,
+    {
+        self.clause(TypedefClause::Synonym(Box::new(Synonym::new(desc, scope))))
+    }
+
+    build_clause!(TypedefClause::Xref(Xref), "xref", xref);
+    build_clause!(
+        TypedefClause::PropertyValue(PropertyValue),
+        "property_value",
+        property_value
+    );
+    build_clause!(TypedefClause::Domain(ClassIdent), "domain", domain);
+    build_clause!(TypedefClause::Range(ClassIdent), "range", range);
+    build_flag!(TypedefClause::Builtin, "builtin", builtin);
+    build_pair!(
+        TypedefClause::HoldsOverChain(RelationIdent, RelationIdent),
+        "holds_over_chain",
+        holds_over_chain
+    );
+    build_flag!(
+        TypedefClause::IsAntiSymmetric,
+        "is_anti_symmetric",
+        is_anti_symmetric
+    );
+    build_flag!(TypedefClause::IsCyclic, "is_cyclic", is_cyclic);
+    build_flag!(TypedefClause::IsReflexive, "is_reflexive", is_reflexive);
+    build_flag!(TypedefClause::IsSymmetric, "is_symmetric", is_symmetric);
+    build_flag!(TypedefClause::IsAsymmetric, "is_asymmetric", is_asymmetric);
+    build_flag!(TypedefClause::IsTransitive, "is_transitive", is_transitive);
+    build_flag!(TypedefClause::IsFunctional, "is_functional", is_functional);
+    build_flag!(
+        TypedefClause::IsInverseFunctional,
+        "is_inverse_functional",
+        is_inverse_functional
+    );
+    build_clause!(TypedefClause::IsA(RelationIdent), "is_a", is_a);
+    build_clause!(
+        TypedefClause::IntersectionOf(RelationIdent),
+        "intersection_of",
+        intersection_of
+    );
+    build_clause!(TypedefClause::UnionOf(RelationIdent), "union_of", union_of);
+    build_clause!(
+        TypedefClause::EquivalentTo(RelationIdent),
+        "equivalent_to",
+        equivalent_to
+    );
+    build_clause!(
+        TypedefClause::DisjointFrom(RelationIdent),
+        "disjoint_from",
+        disjoint_from
+    );
+    build_clause!(
+        TypedefClause::InverseOf(RelationIdent),
+        "inverse_of",
+        inverse_of
+    );
+    build_clause!(
+        TypedefClause::TransitiveOver(RelationIdent),
+        "transitive_over",
+        transitive_over
+    );
+    build_pair!(
+        TypedefClause::EquivalentToChain(RelationIdent, RelationIdent),
+        "equivalent_to_chain",
+        equivalent_to_chain
+    );
+    build_clause!(
+        TypedefClause::DisjointOver(RelationIdent),
+        "disjoint_over",
+        disjoint_over
+    );
+    build_pair!(
+        TypedefClause::Relationship(RelationIdent, RelationIdent),
+        "relationship",
+        relationship
+    );
+    build_flag!(TypedefClause::IsObsolete, "is_obsolete", is_obsolete);
+    build_clause!(
+        TypedefClause::ReplacedBy(RelationIdent),
+        "replaced_by",
+        replaced_by
+    );
+    build_clause!(TypedefClause::Consider(Ident), "consider", consider);
+    build_clause!(
+        TypedefClause::CreatedBy(UnquotedString),
+        "created_by",
+        created_by
+    );
+    build_clause!(
+        TypedefClause::CreationDate(IsoDateTime),
+        "creation_date",
+        creation_date
+    );
+    build_pair!(
+        TypedefClause::ExpandAssertionTo(QuotedString, XrefList),
+        "expand_assertion_to",
+        expand_assertion_to
+    );
+    build_pair!(
+        TypedefClause::ExpandExpressionTo(QuotedString, XrefList),
+        "expand_expression_to",
+        expand_expression_to
+    );
+    build_flag!(
+        TypedefClause::IsMetadataTag,
+        "is_metadata_tag",
+        is_metadata_tag
+    );
+    build_flag!(
+        TypedefClause::IsClassLevel,
+        "is_class_level",
+        is_class_level
+    );
+
+    /// Build the frame, checking the cardinality of its clauses.
+    ///
+    /// # Errors
+    /// Returns an `Error::CardinalityError`, with the identifier of the frame,
+    /// if a clause is declared more times than allowed, or if a clause such
+    /// as `union_of` is declared exactly once.
+    pub fn build(self) -> Result<TypedefFrame, Error> {
+        match self.frame.cardinality_check() {
+            Ok(()) => Ok(self.frame),
+            Err(inner) => Err(Error::CardinalityError {
+                id: Some(self.frame.as_id().clone()),
+                inner,
+            }),
+        }
+    }
+}
+
+impl TypedefFrame {
+    /// Create a new builder for a typedef frame with the given ID.
+    pub fn builder<I>(id: I) -> TypedefFrameBuilder
+    where
+        I: Into<Line<RelationIdent>>,
+    {
+        TypedefFrameBuilder::new(id)
+    }
+}
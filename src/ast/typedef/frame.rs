@@ -10,6 +10,8 @@ use pest::iterators::Pair;
 use crate::ast::*;
 use crate::error::SyntaxError;
 use crate::parser::FromPair;
+use crate::semantics::lines_sorted;
+use crate::semantics::sort_lines;
 use crate::semantics::Identified;
 use crate::semantics::OboFrame;
 use crate::semantics::Orderable;
@@ -62,33 +64,276 @@ impl TypedefFrame {
         &mut self.clauses
     }
 
-    /// Get the name of the typedef, if exactly one is declared.
-    pub fn name(&self) -> Result<&UnquotedString, CardinalityError> {
-        let mut name: Option<&UnquotedString> = None;
-        for clause in &self.clauses {
-            if let TypedefClause::Name(n) = clause.as_inner() {
-                match name {
-                    Some(_) => return Err(CardinalityError::duplicate("name")),
-                    None => name = Some(&n),
-                }
-            }
-        }
-        name.ok_or_else(|| CardinalityError::missing("name"))
-    }
-
-    /// Get the definition of the typedef, if exactly one is declared.
-    pub fn definition(&self) -> Result<&Definition, CardinalityError> {
-        let mut def: Option<&Definition> = None;
-        for clause in &self.clauses {
-            if let TypedefClause::Def(n) = clause.as_inner() {
-                match def {
-                    Some(_) => return Err(CardinalityError::duplicate("def")),
-                    None => def = Some(&n),
-                }
-            }
-        }
-        def.ok_or_else(|| CardinalityError::missing("def"))
+    /// Consume the `TypedefFrame`, returning its identifier and clauses.
+    pub fn into_parts(self) -> (Line<RelationIdent>, Vec<Line<TypedefClause>>) {
+        (self.id, self.clauses)
     }
+
+    single_clause!(
+        TypedefClause::Name(UnquotedString),
+        "name",
+        name,
+        set_name,
+        clear_name
+    );
+    single_clause!(
+        TypedefClause::Namespace(NamespaceIdent),
+        "namespace",
+        namespace,
+        set_namespace
+    );
+    single_clause!(
+        TypedefClause::Def(Definition),
+        "def",
+        definition,
+        set_definition,
+        clear_definition
+    );
+    single_clause!(
+        TypedefClause::Comment(UnquotedString),
+        "comment",
+        comment,
+        set_comment,
+        clear_comment
+    );
+    single_clause!(
+        TypedefClause::Domain(ClassIdent),
+        "domain",
+        domain,
+        set_domain,
+        clear_domain
+    );
+    single_clause!(
+        TypedefClause::Range(ClassIdent),
+        "range",
+        range,
+        set_range,
+        clear_range
+    );
+    single_clause!(
+        TypedefClause::InverseOf(RelationIdent),
+        "inverse_of",
+        inverse_of,
+        set_inverse_of,
+        clear_inverse_of
+    );
+    single_clause!(
+        TypedefClause::CreatedBy(UnquotedString),
+        "created_by",
+        created_by,
+        set_created_by,
+        clear_created_by
+    );
+    single_clause!(
+        TypedefClause::CreationDate(IsoDateTime),
+        "creation_date",
+        creation_date,
+        set_creation_date,
+        clear_creation_date
+    );
+
+    flag_clause!(
+        TypedefClause::IsAnonymous,
+        "is_anonymous",
+        is_anonymous,
+        set_anonymous
+    );
+    flag_clause!(TypedefClause::Builtin, "builtin", is_builtin, set_builtin);
+    flag_clause!(
+        TypedefClause::IsAntiSymmetric,
+        "is_anti_symmetric",
+        is_anti_symmetric,
+        set_anti_symmetric
+    );
+    flag_clause!(TypedefClause::IsCyclic, "is_cyclic", is_cyclic, set_cyclic);
+    flag_clause!(
+        TypedefClause::IsReflexive,
+        "is_reflexive",
+        is_reflexive,
+        set_reflexive
+    );
+    flag_clause!(
+        TypedefClause::IsSymmetric,
+        "is_symmetric",
+        is_symmetric,
+        set_symmetric
+    );
+    flag_clause!(
+        TypedefClause::IsAsymmetric,
+        "is_asymmetric",
+        is_asymmetric,
+        set_asymmetric
+    );
+    flag_clause!(
+        TypedefClause::IsTransitive,
+        "is_transitive",
+        is_transitive,
+        set_transitive
+    );
+    flag_clause!(
+        TypedefClause::IsFunctional,
+        "is_functional",
+        is_functional,
+        set_functional
+    );
+    flag_clause!(
+        TypedefClause::IsInverseFunctional,
+        "is_inverse_functional",
+        is_inverse_functional,
+        set_inverse_functional
+    );
+    flag_clause!(
+        TypedefClause::IsObsolete,
+        "is_obsolete",
+        is_obsolete,
+        set_obsolete
+    );
+    flag_clause!(
+        TypedefClause::IsMetadataTag,
+        "is_metadata_tag",
+        is_metadata_tag,
+        set_metadata_tag
+    );
+    flag_clause!(
+        TypedefClause::IsClassLevel,
+        "is_class_level",
+        is_class_level,
+        set_class_level
+    );
+
+    many_clause!(
+        TypedefClause::AltId(Ident),
+        "alt_id",
+        alt_ids,
+        add_alt_id,
+        remove_alt_id
+    );
+    many_clause!(
+        TypedefClause::Subset(SubsetIdent),
+        "subset",
+        subsets,
+        add_subset,
+        remove_subset
+    );
+    many_clause!(
+        TypedefClause::Synonym(Synonym),
+        "synonym",
+        synonyms,
+        add_synonym,
+        remove_synonym
+    );
+    many_clause!(
+        TypedefClause::Xref(Xref),
+        "xref",
+        xrefs,
+        add_xref,
+        remove_xref
+    );
+    many_clause!(
+        TypedefClause::PropertyValue(PropertyValue),
+        "property_value",
+        property_values,
+        add_property_value,
+        remove_property_value
+    );
+    many_clause!(
+        TypedefClause::IsA(RelationIdent),
+        "is_a",
+        is_a,
+        add_is_a,
+        remove_is_a
+    );
+    many_clause!(
+        TypedefClause::EquivalentTo(RelationIdent),
+        "equivalent_to",
+        equivalent_to,
+        add_equivalent_to,
+        remove_equivalent_to
+    );
+    many_clause!(
+        TypedefClause::DisjointFrom(RelationIdent),
+        "disjoint_from",
+        disjoint_from,
+        add_disjoint_from,
+        remove_disjoint_from
+    );
+    many_clause!(
+        TypedefClause::TransitiveOver(RelationIdent),
+        "transitive_over",
+        transitive_over,
+        add_transitive_over,
+        remove_transitive_over
+    );
+    many_clause!(
+        TypedefClause::DisjointOver(RelationIdent),
+        "disjoint_over",
+        disjoint_over,
+        add_disjoint_over,
+        remove_disjoint_over
+    );
+    many_clause!(
+        TypedefClause::ReplacedBy(RelationIdent),
+        "replaced_by",
+        replaced_by,
+        add_replaced_by,
+        remove_replaced_by
+    );
+    many_clause!(
+        TypedefClause::Consider(Ident),
+        "consider",
+        consider,
+        add_consider,
+        remove_consider
+    );
+
+    pair_clause!(
+        TypedefClause::Relationship(RelationIdent, RelationIdent),
+        "relationship",
+        relationships,
+        add_relationship,
+        remove_relationship
+    );
+    pair_clause!(
+        TypedefClause::HoldsOverChain(RelationIdent, RelationIdent),
+        "holds_over_chain",
+        holds_over_chain,
+        add_holds_over_chain,
+        remove_holds_over_chain
+    );
+    pair_clause!(
+        TypedefClause::EquivalentToChain(RelationIdent, RelationIdent),
+        "equivalent_to_chain",
+        equivalent_to_chain,
+        add_equivalent_to_chain,
+        remove_equivalent_to_chain
+    );
+    pair_clause!(
+        TypedefClause::ExpandAssertionTo(QuotedString, XrefList),
+        "expand_assertion_to",
+        expand_assertion_to,
+        add_expand_assertion_to,
+        remove_expand_assertion_to
+    );
+    pair_clause!(
+        TypedefClause::ExpandExpressionTo(QuotedString, XrefList),
+        "expand_expression_to",
+        expand_expression_to,
+        add_expand_expression_to,
+        remove_expand_expression_to
+    );
+
+    not_one_clause!(
+        TypedefClause::IntersectionOf(RelationIdent),
+        "intersection_of",
+        intersection_of,
+        set_intersection_of
+    );
+    not_one_clause!(
+        TypedefClause::UnionOf(RelationIdent),
+        "union_of",
+        union_of,
+        set_union_of
+    );
 }
 
 impl AsRef<Vec<Line<TypedefClause>>> for TypedefFrame {
@@ -169,15 +414,10 @@ impl<'a> IntoIterator for &'a TypedefFrame {
 
 impl Orderable for TypedefFrame {
     fn sort(&mut self) {
-        self.clauses.sort_unstable();
+        sort_lines(&mut self.clauses);
     }
     fn is_sorted(&self) -> bool {
-        for i in 1..self.clauses.len() {
-            if self.clauses[i - 1] > self.clauses[i] {
-                return false;
-            }
-        }
-        true
+        lines_sorted(&self.clauses)
     }
 }
 
@@ -188,3 +428,79 @@ impl OboFrame for TypedefFrame {
         self.clauses.iter().map(Line::as_inner).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+
+    #[test]
+    fn accessors() {
+        let typedef = TypedefFrame::from_str(
+            "[Typedef]
+            id: part_of
+            name: part of
+            domain: TST:001
+            is_transitive: true
+            is_a: overlaps
+            holds_over_chain: part_of part_of
+            xref: BFO:0000050\n",
+        )
+        .unwrap();
+
+        assert_eq!(typedef.name().unwrap().as_str(), "part of");
+        assert_eq!(typedef.domain().unwrap().to_string(), "TST:001");
+        assert!(typedef.range().is_err());
+        assert!(typedef.is_transitive());
+        assert!(!typedef.is_symmetric());
+
+        let is_a: Vec<_> = typedef.is_a().map(ToString::to_string).collect();
+        assert_eq!(is_a, vec!["overlaps"]);
+        let chains: Vec<_> = typedef
+            .holds_over_chain()
+            .map(|(r1, r2)| (r1.to_string(), r2.to_string()))
+            .collect();
+        assert_eq!(chains, vec![("part_of".to_string(), "part_of".to_string())]);
+        assert_eq!(typedef.xrefs().count(), 1);
+    }
+
+    #[test]
+    fn mutators() {
+        let mut typedef = TypedefFrame::from_str(
+            "[Typedef]
+            id: part_of
+            name: part ! comment
+            range: TST:001 {source=\"PMID:1\"}
+            range: TST:002
+            is_a: overlaps\n",
+        )
+        .unwrap();
+        assert!(typedef.range().is_err());
+
+        typedef.set_name("part of");
+        typedef.set_range(ClassIdent::from_str("TST:003").unwrap());
+        typedef.set_transitive(true);
+        typedef.set_inverse_of(RelationIdent::from_str("has_part").unwrap());
+        assert!(typedef.remove_is_a(&RelationIdent::from_str("overlaps").unwrap()));
+        assert!(!typedef.remove_is_a(&RelationIdent::from_str("overlaps").unwrap()));
+
+        assert_eq!(
+            typedef.to_string(),
+            concat!(
+                "[Typedef]\n",
+                "id: part_of\n",
+                "name: part of\n",
+                "range: TST:003\n",
+                "is_transitive: true\n",
+                "inverse_of: has_part\n",
+            )
+        );
+
+        typedef.set_transitive(false);
+        assert!(typedef.clear_range());
+        assert!(!typedef.clear_range());
+        assert!(!typedef.is_transitive());
+    }
+}
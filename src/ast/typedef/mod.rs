@@ -1,5 +1,7 @@
+mod builder;
 mod clause;
 mod frame;
 
+pub use self::builder::TypedefFrameBuilder;
 pub use self::clause::TypedefClause;
 pub use self::frame::TypedefFrame;
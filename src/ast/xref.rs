@@ -70,12 +70,17 @@ impl Xref {
 
     /// Get a reference to the description of the xref, if any.
     pub fn description(&self) -> Option<&QuotedString> {
-        self.desc.as_ref().map(Deref::deref)
+        self.desc.as_deref()
     }
 
     /// Get a mutable reference to the description of the xref, if any.
     pub fn description_mut(&mut self) -> Option<&mut QuotedString> {
-        self.desc.as_mut().map(DerefMut::deref_mut)
+        self.desc.as_deref_mut()
+    }
+
+    /// Consume the xref, returning its identifier and description.
+    pub fn into_parts(self) -> (Ident, Option<QuotedString>) {
+        (self.id, self.desc.map(|desc| *desc))
     }
 }
 
@@ -211,9 +216,9 @@ impl<'i> FromPair<'i> for XrefList {
     }
 }
 
-impl Into<Vec<Xref>> for XrefList {
-    fn into(self) -> Vec<Xref> {
-        self.xrefs
+impl From<XrefList> for Vec<Xref> {
+    fn from(val: XrefList) -> Self {
+        val.xrefs
     }
 }
 
@@ -0,0 +1,969 @@
+//! Diagnostics with stable error codes, for reporting errors to end-users.
+//!
+//! A [`Diagnostic`] can be obtained from any error of the [`error`] module,
+//! and carries a stable code from the [`code`] module, a message, and
+//! labelled spans locating the problem in the source text. Diagnostics can
+//! be rendered as annotated source snippets for a terminal (with the
+//! `render` feature), or serialized as JSON for tools such as continuous
+//! integration bots.
+//!
+//! # Example
+//! ```rust
+//! # extern crate fastobo;
+//! # use fastobo::diagnostic::*;
+//! let text = "format-version: 1.4\n[Term]\nid: TST:001\nis_a TST:002\n";
+//! let error = fastobo::from_str(text).unwrap_err();
+//!
+//! let diagnostic = Diagnostic::from(&error);
+//! assert_eq!(diagnostic.code(), code::PARSER_ERROR);
+//! assert_eq!(diagnostic.to_string(), "error[E0101]: invalid syntax");
+//! ```
+//!
+//! [`error`]: ../error/index.html
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+use std::ops::Range;
+
+#[cfg(feature = "render")]
+use annotate_snippets::Level;
+#[cfg(feature = "render")]
+use annotate_snippets::Renderer;
+#[cfg(feature = "render")]
+use annotate_snippets::Snippet;
+use pest::error::InputLocation;
+
+use crate::ast::Ident;
+use crate::error::CardinalityError;
+use crate::error::DateError;
+use crate::error::Error;
+use crate::error::LiteralError;
+use crate::error::OwlError;
+use crate::error::QualifierError;
+use crate::error::RedirectError;
+use crate::error::SyntaxError;
+#[cfg(feature = "threading")]
+use crate::error::ThreadingError;
+use crate::error::WriterError;
+
+/// Stable codes identifying the kind of problem reported by a diagnostic.
+///
+/// Codes are never reused nor reassigned once published, so they can be
+/// safely matched on by downstream tools.
+pub mod code {
+    /// A clause required by a frame is missing (e.g. a missing `namespace`).
+    pub const MISSING_CLAUSE: &str = "E0001";
+    /// A clause expected at most once in a frame is declared several times.
+    pub const DUPLICATE_CLAUSES: &str = "E0002";
+    /// A clause which cannot appear exactly once is declared only once.
+    pub const SINGLE_CLAUSE: &str = "E0003";
+
+    /// The source text does not follow the OBO grammar.
+    pub const PARSER_ERROR: &str = "E0101";
+    /// A syntax tree node was built from the wrong grammar rule.
+    pub const UNEXPECTED_RULE: &str = "E0102";
+    /// A quoted string is never closed.
+    pub const UNTERMINATED_STRING: &str = "E0103";
+    /// A backslash is not followed by an escaped character.
+    pub const INVALID_ESCAPE: &str = "E0104";
+    /// A clause of an entity frame has an unknown tag.
+    pub const UNKNOWN_TAG: &str = "E0105";
+    /// An entity frame does not start with an `id` clause.
+    pub const MISSING_ID: &str = "E0106";
+    /// A date or a datetime does not follow the expected format.
+    pub const MALFORMED_DATE: &str = "E0107";
+    /// A boolean value is neither `true` nor `false`.
+    pub const INVALID_BOOLEAN: &str = "E0108";
+
+    /// The source could not be read, or the output could not be written.
+    pub const IO_ERROR: &str = "E0201";
+
+    /// A literal does not match the lexical space of its datatype.
+    pub const INVALID_LITERAL: &str = "E0301";
+    /// A literal is outside the value space of its datatype.
+    pub const LITERAL_OUT_OF_RANGE: &str = "E0302";
+    /// A literal was read with a datatype other than the declared one.
+    pub const UNEXPECTED_DATATYPE: &str = "E0303";
+
+    /// A required line qualifier is missing.
+    pub const MISSING_QUALIFIER: &str = "E0401";
+    /// A line qualifier expected at most once is declared several times.
+    pub const DUPLICATE_QUALIFIER: &str = "E0402";
+    /// A line qualifier value cannot be read as the expected type.
+    pub const INVALID_QUALIFIER: &str = "E0403";
+
+    /// A date does not exist in the calendar.
+    pub const INVALID_DATE: &str = "E0501";
+    /// A time of day is out of range.
+    pub const INVALID_TIME: &str = "E0502";
+    /// A timezone offset is out of range.
+    pub const INVALID_TIMEZONE: &str = "E0503";
+    /// A year cannot be represented.
+    pub const YEAR_OUT_OF_RANGE: &str = "E0504";
+    /// A datetime without timezone was used where one is required.
+    pub const MISSING_TIMEZONE: &str = "E0505";
+
+    /// An identifier is not declared in the document.
+    pub const UNKNOWN_IDENT: &str = "E0601";
+    /// An identifier is replaced by an undeclared identifier.
+    pub const MISSING_REPLACEMENT: &str = "E0602";
+    /// Identifier replacements form a loop.
+    pub const REPLACEMENT_LOOP: &str = "E0603";
+
+    /// An OWL2 document ended unexpectedly.
+    pub const OWL_UNEXPECTED_EOF: &str = "E0701";
+    /// An OWL2 document contains an unexpected token.
+    pub const OWL_UNEXPECTED_TOKEN: &str = "E0702";
+    /// An OWL2 document uses an undeclared prefix.
+    pub const OWL_UNDECLARED_PREFIX: &str = "E0703";
+
+    /// Frames were written out of order.
+    pub const FRAME_ORDER: &str = "E0801";
+
+    /// A worker thread disconnected unexpectedly.
+    pub const THREADING_ERROR: &str = "E0901";
+
+    /// A clause references an entity that is not declared in the document.
+    pub const DANGLING_REFERENCE: &str = "E1001";
+    /// A frame is part of a subset not declared in the header frame.
+    pub const UNDECLARED_SUBSET: &str = "E1002";
+    /// An identifier is not written in the canonical form of its IDspace.
+    pub const NON_CANONICAL_IDENT: &str = "E1003";
+    /// Clauses or frames are not in serialization order.
+    pub const UNSORTED: &str = "E1004";
+}
+
+/// The severity of a diagnostic.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+#[cfg(feature = "render")]
+impl Severity {
+    fn level(self) -> Level {
+        match self {
+            Severity::Note => Level::Note,
+            Severity::Warning => Level::Warning,
+            Severity::Error => Level::Error,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Severity::Note => f.write_str("note"),
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A message attached to a span of the source text.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Label {
+    range: Range<usize>,
+    message: String,
+}
+
+impl Label {
+    /// Create a new label for the given byte range of the source text.
+    pub fn new<S: Into<String>>(range: Range<usize>, message: S) -> Self {
+        Self {
+            range,
+            message: message.into(),
+        }
+    }
+
+    /// Get the byte range of the source text covered by the label.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Get the message of the label.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// The location of an entity frame in the source text.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FrameLocation {
+    range: Range<usize>,
+    header: Range<usize>,
+    id: Range<usize>,
+}
+
+impl FrameLocation {
+    /// Get the byte range of the frame, from its header to its last non-blank line.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Get the byte range of the frame header, such as `[Term]`.
+    pub fn header(&self) -> Range<usize> {
+        self.header.clone()
+    }
+
+    /// Get the byte range of the identifier in the `id` clause of the frame.
+    pub fn id(&self) -> Range<usize> {
+        self.id.clone()
+    }
+}
+
+/// A problem found in an OBO document, with a stable code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    path: Option<String>,
+    entity: Option<Ident>,
+    clause: Option<String>,
+    labels: Vec<Label>,
+    notes: Vec<String>,
+    help: Option<String>,
+}
+
+impl Diagnostic {
+    /// Create a new diagnostic with the given severity, code and message.
+    pub fn new<S: Into<String>>(severity: Severity, code: &'static str, message: S) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            path: None,
+            entity: None,
+            clause: None,
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    /// Create a new error diagnostic with the given code and message.
+    pub fn error<S: Into<String>>(code: &'static str, message: S) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    /// Create a new warning diagnostic with the given code and message.
+    pub fn warning<S: Into<String>>(code: &'static str, message: S) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    /// Attach the path of the source file to the diagnostic.
+    pub fn with_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Attach the identifier of the frame where the problem was found.
+    ///
+    /// When the diagnostic has no label, the `id` clause of the frame is
+    /// located in the source text and labelled instead.
+    pub fn with_entity<I: Into<Ident>>(mut self, id: I) -> Self {
+        self.entity = Some(id.into());
+        self
+    }
+
+    /// Attach the tag of the clause where the problem was found.
+    ///
+    /// When the diagnostic has no label, the lines with this tag in the
+    /// frame (or else in the header frame) are located in the source text
+    /// and labelled instead.
+    pub fn with_clause<S: Into<String>>(mut self, tag: S) -> Self {
+        self.clause = Some(tag.into());
+        self
+    }
+
+    /// Attach a label to the given byte range of the source text.
+    pub fn with_label<S: Into<String>>(mut self, range: Range<usize>, message: S) -> Self {
+        self.labels.push(Label::new(range, message));
+        self
+    }
+
+    /// Attach a note, written after the source excerpt.
+    pub fn with_note<S: Into<String>>(mut self, note: S) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Attach a suggestion to fix the problem.
+    pub fn with_help<S: Into<String>>(mut self, help: S) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Get the severity of the diagnostic.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Get the stable code identifying the kind of problem.
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Get the message of the diagnostic.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the path of the source file, if known.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Get the identifier of the frame where the problem was found, if known.
+    pub fn entity(&self) -> Option<&Ident> {
+        self.entity.as_ref()
+    }
+
+    /// Get the tag of the clause where the problem was found, if known.
+    pub fn clause(&self) -> Option<&str> {
+        self.clause.as_deref()
+    }
+
+    /// Get the labels attached to the source text.
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Get the notes of the diagnostic.
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Get the suggestion to fix the problem, if any.
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Get the labels to display for the given source text.
+    fn resolve_labels(&self, source: &str) -> Vec<Label> {
+        let mut labels: Vec<Label> = self
+            .labels
+            .iter()
+            .map(|label| {
+                let start = floor_char_boundary(source, label.range.start);
+                let end = floor_char_boundary(source, label.range.end).max(start);
+                Label::new(start..end, label.message.as_str())
+            })
+            .collect();
+        if !labels.is_empty() {
+            return labels;
+        }
+
+        let frame = self.entity.as_ref().and_then(|id| locate_frame(source, id));
+        if let Some(tag) = &self.clause {
+            let mut ranges = match &frame {
+                Some(range) => locate_clauses(source, range.start, tag),
+                None => Vec::new(),
+            };
+            if ranges.is_empty() {
+                ranges = locate_clauses(source, 0, tag);
+            }
+            labels.extend(ranges.into_iter().map(|r| Label::new(r, "declared here")));
+        }
+        if labels.is_empty() {
+            if let Some(range) = frame {
+                labels.push(Label::new(range, "in this frame"));
+            }
+        }
+        labels
+    }
+
+    /// Render the diagnostic with annotated excerpts of the source text.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::PrefixedIdent;
+    /// # use fastobo::diagnostic::*;
+    /// let text = "[Term]\nid: TST:001\nname: test\n";
+    /// let diagnostic = Diagnostic::error(code::MISSING_CLAUSE, "missing namespace clause")
+    ///     .with_path("test.obo")
+    ///     .with_entity(PrefixedIdent::new("TST", "001"));
+    /// assert_eq!(
+    ///     diagnostic.render(text),
+    ///     concat!(
+    ///         "error[E0001]: missing namespace clause\n",
+    ///         " --> test.obo:2:5\n",
+    ///         "  |\n",
+    ///         "2 | id: TST:001\n",
+    ///         "  |     ^^^^^^^ in this frame\n",
+    ///         "  |",
+    ///     )
+    /// );
+    /// ```
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "render")))]
+    pub fn render(&self, source: &str) -> String {
+        self.render_with(source, Renderer::plain())
+    }
+
+    /// Render the diagnostic like `render`, using ANSI colors.
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "render")))]
+    pub fn render_ansi(&self, source: &str) -> String {
+        self.render_with(source, Renderer::styled())
+    }
+
+    #[cfg(feature = "render")]
+    fn render_with(&self, source: &str, renderer: Renderer) -> String {
+        let level = self.severity.level();
+        let labels = self.resolve_labels(source);
+
+        let mut message = level.title(&self.message).id(self.code);
+        if !labels.is_empty() {
+            let mut snippet = Snippet::source(source).line_start(1).fold(true);
+            if let Some(path) = &self.path {
+                snippet = snippet.origin(path);
+            }
+            for label in &labels {
+                snippet = snippet.annotation(level.span(label.range()).label(label.message()));
+            }
+            message = message.snippet(snippet);
+        }
+        for note in &self.notes {
+            message = message.footer(Level::Note.title(note));
+        }
+        if let Some(help) = &self.help {
+            message = message.footer(Level::Help.title(help));
+        }
+
+        let rendered = renderer.render(message).to_string();
+        rendered
+    }
+
+    /// Serialize the diagnostic as a JSON object.
+    ///
+    /// The source text is used to convert the byte ranges of the labels to
+    /// 1-based line and column numbers, which are given along with the byte
+    /// offsets for each label.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::diagnostic::*;
+    /// let text = "[Term]\nid: TST:001\nis_a: TST:002\n";
+    /// let diagnostic = Diagnostic::warning("W0001", "undeclared class")
+    ///     .with_path("test.obo")
+    ///     .with_label(25..32, "not declared in this document");
+    /// assert_eq!(
+    ///     diagnostic.to_json(text),
+    ///     concat!(
+    ///         r#"{"severity":"warning","code":"W0001","message":"undeclared class","#,
+    ///         r#""path":"test.obo","entity":null,"labels":[{"start":25,"end":32,"#,
+    ///         r#""line":3,"column":7,"end_line":3,"end_column":14,"#,
+    ///         r#""message":"not declared in this document"}],"notes":[],"help":null}"#,
+    ///     )
+    /// );
+    /// ```
+    pub fn to_json(&self, source: &str) -> String {
+        let mut out = String::new();
+        // NB: writing to a `String` never fails.
+        let _ = self.write_json(&mut out, source);
+        out
+    }
+
+    fn write_json(&self, out: &mut String, source: &str) -> FmtResult {
+        let entity = self.entity.as_ref().map(ToString::to_string);
+        write!(out, "{{\"severity\":")?;
+        write_json_str(out, Some(&self.severity.to_string()))?;
+        write!(out, ",\"code\":")?;
+        write_json_str(out, Some(self.code))?;
+        write!(out, ",\"message\":")?;
+        write_json_str(out, Some(&self.message))?;
+        write!(out, ",\"path\":")?;
+        write_json_str(out, self.path.as_deref())?;
+        write!(out, ",\"entity\":")?;
+        write_json_str(out, entity.as_deref())?;
+        write!(out, ",\"labels\":[")?;
+        for (i, label) in self.resolve_labels(source).iter().enumerate() {
+            let (line, column) = line_col(source, label.range.start);
+            let (end_line, end_column) = line_col(source, label.range.end);
+            if i > 0 {
+                out.push(',');
+            }
+            write!(
+                out,
+                "{{\"start\":{},\"end\":{},\"line\":{},\"column\":{},\"end_line\":{},\"end_column\":{},\"message\":",
+                label.range.start, label.range.end, line, column, end_line, end_column,
+            )?;
+            write_json_str(out, Some(&label.message))?;
+            out.push('}');
+        }
+        write!(out, "],\"notes\":[")?;
+        for (i, note) in self.notes.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            write_json_str(out, Some(note))?;
+        }
+        write!(out, "],\"help\":")?;
+        write_json_str(out, self.help.as_deref())?;
+        out.push('}');
+        Ok(())
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl From<&CardinalityError> for Diagnostic {
+    fn from(error: &CardinalityError) -> Self {
+        let code = match error {
+            CardinalityError::MissingClause { .. } => code::MISSING_CLAUSE,
+            CardinalityError::DuplicateClauses { .. } => code::DUPLICATE_CLAUSES,
+            CardinalityError::SingleClause { .. } => code::SINGLE_CLAUSE,
+        };
+        Diagnostic::error(code, error.to_string()).with_clause(error.name())
+    }
+}
+
+impl From<&DateError> for Diagnostic {
+    fn from(error: &DateError) -> Self {
+        let code = match error {
+            DateError::InvalidDate { .. } => code::INVALID_DATE,
+            DateError::InvalidTime { .. } => code::INVALID_TIME,
+            DateError::InvalidTimezone { .. } => code::INVALID_TIMEZONE,
+            DateError::YearOutOfRange { .. } => code::YEAR_OUT_OF_RANGE,
+            DateError::MissingTimezone => code::MISSING_TIMEZONE,
+        };
+        Diagnostic::error(code, error.to_string())
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::SyntaxError { error } => Diagnostic::from(error),
+            Error::IOError { error } => Diagnostic::error(code::IO_ERROR, error.to_string()),
+            Error::CardinalityError { id, inner } => {
+                let diagnostic = Diagnostic::from(inner);
+                match id {
+                    Some(id) => diagnostic.with_entity(id.clone()),
+                    None => diagnostic,
+                }
+            }
+            Error::OwlError { error } => Diagnostic::from(error),
+            Error::WriterError { error } => Diagnostic::from(error),
+            Error::RedirectError { error } => Diagnostic::from(error),
+            Error::LiteralError { error } => Diagnostic::from(error),
+            Error::QualifierError { error } => Diagnostic::from(error),
+            Error::DateError { error } => Diagnostic::from(error),
+            #[cfg(feature = "threading")]
+            Error::ThreadingError { error } => Diagnostic::from(error),
+        }
+    }
+}
+
+impl From<&LiteralError> for Diagnostic {
+    fn from(error: &LiteralError) -> Self {
+        let code = match error {
+            LiteralError::UnexpectedDatatype { .. } => code::UNEXPECTED_DATATYPE,
+            LiteralError::InvalidLiteral { .. } => code::INVALID_LITERAL,
+            LiteralError::OutOfRange { .. } => code::LITERAL_OUT_OF_RANGE,
+        };
+        Diagnostic::error(code, error.to_string())
+    }
+}
+
+impl From<&OwlError> for Diagnostic {
+    fn from(error: &OwlError) -> Self {
+        let code = match error {
+            OwlError::UnexpectedEof => code::OWL_UNEXPECTED_EOF,
+            OwlError::UnexpectedToken { .. } => code::OWL_UNEXPECTED_TOKEN,
+            OwlError::UndeclaredPrefix { .. } => code::OWL_UNDECLARED_PREFIX,
+        };
+        Diagnostic::error(code, error.to_string())
+    }
+}
+
+impl From<&QualifierError> for Diagnostic {
+    fn from(error: &QualifierError) -> Self {
+        let code = match error {
+            QualifierError::MissingQualifier { .. } => code::MISSING_QUALIFIER,
+            QualifierError::DuplicateQualifier { .. } => code::DUPLICATE_QUALIFIER,
+            QualifierError::InvalidValue { .. } => code::INVALID_QUALIFIER,
+        };
+        Diagnostic::error(code, error.to_string())
+    }
+}
+
+impl From<&RedirectError> for Diagnostic {
+    fn from(error: &RedirectError) -> Self {
+        let (code, id) = match error {
+            RedirectError::UnknownIdent { id } => (code::UNKNOWN_IDENT, id),
+            RedirectError::MissingTarget { id, .. } => (code::MISSING_REPLACEMENT, id),
+            RedirectError::Loop { id } => (code::REPLACEMENT_LOOP, id),
+        };
+        Diagnostic::error(code, error.to_string()).with_entity(id.clone())
+    }
+}
+
+impl From<&SyntaxError> for Diagnostic {
+    fn from(error: &SyntaxError) -> Self {
+        let pest_error = match error.parser_error() {
+            Some(pest_error) => pest_error,
+            None => return Diagnostic::error(code::UNEXPECTED_RULE, error.to_string()),
+        };
+        let (code, label) = match error {
+            SyntaxError::UnexpectedRule { .. } | SyntaxError::ParserError { .. } => {
+                (code::PARSER_ERROR, None)
+            }
+            SyntaxError::UnterminatedString { .. } => {
+                (code::UNTERMINATED_STRING, Some("string opened here"))
+            }
+            SyntaxError::InvalidEscape { .. } => {
+                (code::INVALID_ESCAPE, Some("expected an escaped character"))
+            }
+            SyntaxError::UnknownTag { .. } => (code::UNKNOWN_TAG, Some("unknown tag")),
+            SyntaxError::MissingId { .. } => (code::MISSING_ID, Some("expected an `id` clause")),
+            SyntaxError::InvalidDate { .. } => (code::MALFORMED_DATE, Some("expected a date")),
+            SyntaxError::InvalidBoolean { .. } => {
+                (code::INVALID_BOOLEAN, Some("expected `true` or `false`"))
+            }
+        };
+        let range = match pest_error.location {
+            InputLocation::Pos(pos) => pos..pos,
+            InputLocation::Span((start, end)) => start..end,
+        };
+        let diagnostic = match label {
+            None => Diagnostic::error(code, "invalid syntax")
+                .with_label(range, pest_error.variant.message()),
+            Some(label) => {
+                Diagnostic::error(code, pest_error.variant.message()).with_label(range, label)
+            }
+        };
+        match pest_error.path() {
+            Some(path) => diagnostic.with_path(path),
+            None => diagnostic,
+        }
+    }
+}
+
+#[cfg(feature = "threading")]
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "threading")))]
+impl From<&ThreadingError> for Diagnostic {
+    fn from(error: &ThreadingError) -> Self {
+        Diagnostic::error(code::THREADING_ERROR, error.to_string())
+    }
+}
+
+impl From<&WriterError> for Diagnostic {
+    fn from(error: &WriterError) -> Self {
+        Diagnostic::error(code::FRAME_ORDER, error.to_string())
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Get the largest character boundary of `source` not after `index`.
+fn floor_char_boundary(source: &str, index: usize) -> usize {
+    let mut index = index.min(source.len());
+    while !source.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Get the 1-based line and column of a byte offset in `source`.
+fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..floor_char_boundary(source, offset)];
+    let line = before.matches('\n').count() + 1;
+    let start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    (line, before[start..].chars().count() + 1)
+}
+
+/// Locate the entity frames of a source text.
+///
+/// The syntax tree does not record source positions, so frames are located
+/// by scanning the source text, which does not need to be syntactically
+/// valid. Frames without an `id` clause are ignored.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::diagnostic::*;
+/// let text = "format-version: 1.4\n\n[Term]\nid: TST:001\nname: test\n\n";
+/// let frames = locate_frames(text);
+/// assert_eq!(frames.len(), 1);
+/// assert_eq!(&text[frames[0].header()], "[Term]");
+/// assert_eq!(&text[frames[0].id()], "TST:001");
+/// assert_eq!(&text[frames[0].range()], "[Term]\nid: TST:001\nname: test");
+/// ```
+pub fn locate_frames(source: &str) -> Vec<FrameLocation> {
+    let mut frames = Vec::new();
+    let mut current: Option<(Range<usize>, Option<Range<usize>>, usize)> = None;
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let start = offset + (line.len() - trimmed.len());
+        let end = offset + line.trim_end().len();
+        if trimmed.starts_with('[') {
+            if let Some((header, Some(id), last)) = current.take() {
+                frames.push(FrameLocation {
+                    range: header.start..last,
+                    header,
+                    id,
+                });
+            }
+            current = Some((start..end, None, end));
+        } else if let Some((_, id, last)) = current.as_mut() {
+            if end > start {
+                *last = end;
+            }
+            if let (None, Some(value)) = (&id, trimmed.strip_prefix("id:")) {
+                let value_start = start + 3 + (value.len() - value.trim_start().len());
+                *id = value
+                    .split_whitespace()
+                    .next()
+                    .map(|v| value_start..value_start + v.len());
+            }
+        }
+        offset += line.len();
+    }
+    if let Some((header, Some(id), last)) = current {
+        frames.push(FrameLocation {
+            range: header.start..last,
+            header,
+            id,
+        });
+    }
+    frames
+}
+
+/// Locate the identifier in the `id` clause of the frame with the given identifier.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::PrefixedIdent;
+/// # use fastobo::diagnostic::*;
+/// let text = "[Term]\nid: TST:001\nname: test\n";
+/// let id = PrefixedIdent::new("TST", "001").into();
+/// assert_eq!(locate_frame(text, &id), Some(11..18));
+/// ```
+pub fn locate_frame(source: &str, id: &Ident) -> Option<Range<usize>> {
+    let id = id.to_string();
+    locate_frames(source)
+        .into_iter()
+        .map(|frame| frame.id)
+        .find(|range| source[range.clone()] == id)
+}
+
+/// Locate the tags of the clauses with the given tag in a frame.
+///
+/// The frame starts at the line containing `start`, and ends before the
+/// next frame header; the header frame is located with a `start` of 0.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::PrefixedIdent;
+/// # use fastobo::diagnostic::*;
+/// let text = "[Term]\nid: TST:001\nis_a: TST:002\nis_a: TST:003\n";
+/// let frame = locate_frame(text, &PrefixedIdent::new("TST", "001").into()).unwrap();
+/// assert_eq!(locate_clauses(text, frame.start, "is_a"), vec![19..23, 33..37]);
+/// ```
+pub fn locate_clauses(source: &str, start: usize, tag: &str) -> Vec<Range<usize>> {
+    let line_start = source[..floor_char_boundary(source, start)]
+        .rfind('\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let mut ranges = Vec::new();
+    let mut offset = line_start;
+    for line in source[line_start..].split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('[') {
+            break;
+        }
+        if let Some(rest) = trimmed.strip_prefix(tag) {
+            if rest.starts_with(':') {
+                let start = offset + (line.len() - trimmed.len());
+                ranges.push(start..start + tag.len());
+            }
+        }
+        offset += line.len();
+    }
+    ranges
+}
+
+/// Write an optional string as a JSON string literal or `null`.
+fn write_json_str(out: &mut String, s: Option<&str>) -> FmtResult {
+    let s = match s {
+        Some(s) => s,
+        None => return out.write_str("null"),
+    };
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn syntax_error() {
+        let text = "[Term]\nid: TST:001\nis_a TST:002\n";
+        let error = crate::from_str(text).unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), code::PARSER_ERROR);
+        assert_eq!(diagnostic.labels().len(), 1);
+        assert_eq!(line_col(text, diagnostic.labels()[0].range().start), (3, 1));
+        #[cfg(feature = "render")]
+        assert!(diagnostic.render(text).contains("3 | is_a TST:002"));
+    }
+
+    #[test]
+    fn typed_syntax_error() {
+        let text = "[Term]\nid: TST:001\nfoo_bar: baz\n";
+        let error = crate::from_str(text).unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), code::UNKNOWN_TAG);
+        assert_eq!(diagnostic.message(), "unknown tag `foo_bar`");
+        assert_eq!(line_col(text, diagnostic.labels()[0].range().start), (3, 1));
+    }
+
+    #[test]
+    fn cardinality_error() {
+        let text = "[Term]\nid: TST:001\nnamespace: test\n\n[Term]\nid: TST:002\n";
+        let mut doc = crate::from_str(text).unwrap();
+        let error = doc.assign_namespaces().unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), code::MISSING_CLAUSE);
+        assert_eq!(
+            diagnostic.to_string(),
+            "error[E0001]: missing default-namespace clause"
+        );
+        assert_eq!(
+            diagnostic.entity().map(ToString::to_string).as_deref(),
+            Some("TST:002")
+        );
+        assert_eq!(
+            diagnostic.resolve_labels(text),
+            vec![Label::new(47..54, "in this frame")]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn render_cardinality_error() {
+        let text = "[Term]\nid: TST:001\nnamespace: test\n\n[Term]\nid: TST:002\n";
+        let mut doc = crate::from_str(text).unwrap();
+        let error = doc.assign_namespaces().unwrap_err();
+        let diagnostic = Diagnostic::from(&error).with_path("test.obo");
+        assert_eq!(
+            diagnostic.render(text),
+            concat!(
+                "error[E0001]: missing default-namespace clause\n",
+                " --> test.obo:6:5\n",
+                "  |\n",
+                "6 | id: TST:002\n",
+                "  |     ^^^^^^^ in this frame\n",
+                "  |",
+            )
+        );
+    }
+
+    #[test]
+    fn duplicate_clauses() {
+        let text = "[Term]\nid: TST:001\nname: a\nname: b\n\n[Term]\nid: TST:002\nname: c\n";
+        let error = crate::ast::TermFrame::builder(crate::ast::ClassIdent::from(
+            crate::ast::PrefixedIdent::new("TST", "001"),
+        ))
+        .name("a")
+        .name("b")
+        .build()
+        .unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), code::DUPLICATE_CLAUSES);
+        assert_eq!(diagnostic.clause(), Some("name"));
+        assert_eq!(
+            diagnostic.resolve_labels(text),
+            vec![
+                Label::new(19..23, "declared here"),
+                Label::new(27..31, "declared here")
+            ]
+        );
+    }
+
+    #[test]
+    fn header_clauses() {
+        let text = "format-version: 1.2\nformat-version: 1.4\n\n[Term]\nid: TST:001\n";
+        let error = crate::ast::HeaderFrame::builder()
+            .format_version("1.2")
+            .format_version("1.4")
+            .build()
+            .unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(
+            diagnostic.resolve_labels(text),
+            vec![
+                Label::new(0..14, "declared here"),
+                Label::new(20..34, "declared here")
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn render_without_labels() {
+        let diagnostic =
+            Diagnostic::from(&WriterError::MissingHeader).with_help("write the header frame first");
+        assert_eq!(
+            diagnostic.render(""),
+            concat!(
+                "error[E0801]: entity frame written before the header frame\n",
+                " = help: write the header frame first",
+            )
+        );
+    }
+
+    #[test]
+    fn json_escape() {
+        let diagnostic = Diagnostic::error("E0000", "quote \" and\nnewline").with_note("tab\t");
+        assert_eq!(
+            diagnostic.to_json(""),
+            concat!(
+                r#"{"severity":"error","code":"E0000","message":"quote \" and\nnewline","#,
+                r#""path":null,"entity":null,"labels":[],"notes":["tab\t"],"help":null}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn line_col_unicode() {
+        let text = "name: é\nis_a: X\n";
+        assert_eq!(line_col(text, 0), (1, 1));
+        assert_eq!(line_col(text, 6), (1, 7));
+        assert_eq!(line_col(text, 8), (1, 8));
+        assert_eq!(line_col(text, 9), (2, 1));
+    }
+}
@@ -3,6 +3,7 @@
 use std::io::Error as IOError;
 
 use pest::error::Error as PestError;
+use pest::error::ErrorVariant;
 use thiserror::Error;
 
 use pest::Span;
@@ -15,7 +16,7 @@ use crate::syntax::Rule;
 /// This error is highly dependent on the function that returns it: the `name`
 /// field can provide more information about the specific clause that errored
 /// to the end-user.
-#[derive(Debug, Eq, Error, PartialEq)]
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
 pub enum CardinalityError {
     #[error("missing {name} clause")]
     MissingClause { name: String },
@@ -37,10 +38,19 @@ impl CardinalityError {
     pub fn single<S: Into<String>>(name: S) -> Self {
         CardinalityError::SingleClause { name: name.into() }
     }
+
+    /// Get the name of the clause with an invalid cardinality.
+    pub fn name(&self) -> &str {
+        use self::CardinalityError::*;
+        match self {
+            MissingClause { name } | DuplicateClauses { name } | SingleClause { name } => name,
+        }
+    }
 }
 
 /// A syntax error.
 #[derive(Debug, Eq, Error, PartialEq)]
+#[non_exhaustive]
 pub enum SyntaxError {
     /// An unexpected rule was used in `FromPair::from_pair`.
     ///
@@ -79,62 +89,206 @@ pub enum SyntaxError {
     /// # };
     /// ```
     #[error("parser error: {error}")]
-    ParserError {
-        #[from]
+    ParserError { error: Box<PestError<Rule>> },
+
+    /// A quoted string was opened but never closed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// OboDoc::from_str("[Term]\nid: TST:001\ndef: \"a term []\n").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::SyntaxError::UnterminatedString { .. } => (),
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    #[error("parser error: {error}")]
+    UnterminatedString { error: Box<PestError<Rule>> },
+
+    /// An escape sequence was not followed by the escaped character.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// OboDoc::from_str("[Term]\nid: TST:001\nname: a term\\").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::SyntaxError::InvalidEscape { .. } => (),
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    #[error("parser error: {error}")]
+    InvalidEscape { error: Box<PestError<Rule>> },
+
+    /// An entity frame contains a clause with an unknown tag.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// OboDoc::from_str("[Term]\nid: TST:001\nsynonyms: \"term\" EXACT []\n").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::SyntaxError::UnknownTag { tag, .. } => {
+    /// #       assert_eq!(tag, "synonyms");
+    /// #   }
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    #[error("parser error: {error}")]
+    UnknownTag {
+        tag: String,
+        error: Box<PestError<Rule>>,
+    },
+
+    /// An entity frame does not start with an `id` clause.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// OboDoc::from_str("[Term]\nname: a term\n").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::SyntaxError::MissingId { .. } => (),
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    #[error("parser error: {error}")]
+    MissingId { error: Box<PestError<Rule>> },
+
+    /// A date or a datetime could not be parsed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// OboDoc::from_str("[Term]\nid: TST:001\ncreation_date: 2020-13-45\n").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::SyntaxError::InvalidDate { .. } => (),
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    #[error("parser error: {error}")]
+    InvalidDate { error: Box<PestError<Rule>> },
+
+    /// A boolean clause has a value other than `true` or `false`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// OboDoc::from_str("[Term]\nid: TST:001\nis_obsolete: yes\n").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::SyntaxError::InvalidBoolean { value, .. } => {
+    /// #       assert_eq!(value, "yes");
+    /// #   }
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    #[error("parser error: {error}")]
+    InvalidBoolean {
+        value: String,
         error: Box<PestError<Rule>>,
     },
 }
 
 impl SyntaxError {
-    /// Update the line of the error, if needed.
-    pub(crate) fn with_offsets(self, line_offset: usize, offset: usize) -> Self {
+    /// Create an error for an invalid escape in the given span.
+    pub(crate) fn invalid_escape(span: Span) -> Self {
+        let variant = ErrorVariant::CustomError {
+            message: String::from("incomplete escape sequence"),
+        };
+        SyntaxError::InvalidEscape {
+            error: Box::new(PestError::new_from_span(variant, span)),
+        }
+    }
+
+    /// Get the underlying parser error, if any.
+    ///
+    /// Every variant but `UnexpectedRule` wraps a `pest` error, which records
+    /// the position of the failure in the source text.
+    pub fn parser_error(&self) -> Option<&PestError<Rule>> {
         use self::SyntaxError::*;
-        use pest::error::InputLocation;
-        use pest::error::LineColLocation;
         match self {
-            e @ UnexpectedRule { .. } => e,
-            ParserError { mut error } => {
-                error.location = match error.location {
-                    InputLocation::Pos(s) => InputLocation::Pos(s + offset),
-                    InputLocation::Span((s, e)) => InputLocation::Span((s + offset, e + offset)),
-                };
-                error.line_col = match error.line_col {
-                    LineColLocation::Pos((l, c)) => LineColLocation::Pos((l + line_offset, c)),
-                    LineColLocation::Span((ls, cs), (le, ce)) => {
-                        LineColLocation::Span((ls + line_offset, cs), (le + line_offset, ce))
-                    }
-                };
-                ParserError { error }
-            }
+            UnexpectedRule { .. } => None,
+            ParserError { error }
+            | UnterminatedString { error }
+            | InvalidEscape { error }
+            | UnknownTag { error, .. }
+            | MissingId { error }
+            | InvalidDate { error }
+            | InvalidBoolean { error, .. } => Some(error),
         }
     }
 
-    /// Update the path of the error, if needed.
-    pub(crate) fn with_path(self, path: &str) -> Self {
+    /// Apply a function to the underlying parser error, if any.
+    fn map_parser_error<F>(self, f: F) -> Self
+    where
+        F: FnOnce(PestError<Rule>) -> PestError<Rule>,
+    {
         use self::SyntaxError::*;
+        let f = |error: Box<PestError<Rule>>| Box::new(f(*error));
         match self {
             e @ UnexpectedRule { .. } => e,
-            ParserError { error } => ParserError {
-                error: Box::new(error.with_path(path)),
+            ParserError { error } => ParserError { error: f(error) },
+            UnterminatedString { error } => UnterminatedString { error: f(error) },
+            InvalidEscape { error } => InvalidEscape { error: f(error) },
+            UnknownTag { tag, error } => UnknownTag {
+                tag,
+                error: f(error),
+            },
+            MissingId { error } => MissingId { error: f(error) },
+            InvalidDate { error } => InvalidDate { error: f(error) },
+            InvalidBoolean { value, error } => InvalidBoolean {
+                value,
+                error: f(error),
             },
         }
     }
 
+    /// Update the line of the error, if needed.
+    pub(crate) fn with_offsets(self, line_offset: usize, offset: usize) -> Self {
+        use pest::error::InputLocation;
+        use pest::error::LineColLocation;
+        self.map_parser_error(|mut error| {
+            error.location = match error.location {
+                InputLocation::Pos(s) => InputLocation::Pos(s + offset),
+                InputLocation::Span((s, e)) => InputLocation::Span((s + offset, e + offset)),
+            };
+            error.line_col = match error.line_col {
+                LineColLocation::Pos((l, c)) => LineColLocation::Pos((l + line_offset, c)),
+                LineColLocation::Span((ls, cs), (le, ce)) => {
+                    LineColLocation::Span((ls + line_offset, cs), (le + line_offset, ce))
+                }
+            };
+            error
+        })
+    }
+
+    /// Update the path of the error, if needed.
+    pub(crate) fn with_path(self, path: &str) -> Self {
+        self.map_parser_error(|error| error.with_path(path))
+    }
+
     /// Update the span of the error, if needed.
     pub(crate) fn with_span(self, span: Span) -> Self {
-        use self::SyntaxError::*;
-        match self {
-            e @ UnexpectedRule { .. } => e,
-            ParserError { error } => {
-                // FIXME(@althonos): the new error should be spanned only if
-                //                   the original error is spanned, but there
-                //                   is no clean way to create an error at
-                //                   the right position with `pest::error`.
-                ParserError {
-                    error: Box::new(PestError::new_from_span(error.variant, span)),
-                }
-            }
-        }
+        // FIXME(@althonos): the new error should be spanned only if
+        //                   the original error is spanned, but there
+        //                   is no clean way to create an error at
+        //                   the right position with `pest::error`.
+        self.map_parser_error(|error| PestError::new_from_span(error.variant, span))
     }
 }
 
@@ -144,6 +298,112 @@ impl From<PestError<Rule>> for SyntaxError {
     }
 }
 
+impl From<Box<PestError<Rule>>> for SyntaxError {
+    /// Classify a parser error into the most specific variant.
+    fn from(error: Box<PestError<Rule>>) -> Self {
+        use self::SyntaxError::*;
+        use pest::error::LineColLocation;
+
+        let positives = match &error.variant {
+            ErrorVariant::ParsingError { positives, .. } => positives.clone(),
+            ErrorVariant::CustomError { .. } => return ParserError { error },
+        };
+        let column = match error.line_col {
+            LineColLocation::Pos((_, c)) => c,
+            LineColLocation::Span((_, c), _) => c,
+        };
+        let line = error.line().to_string();
+        let rest: String = line.chars().skip(column.saturating_sub(1)).collect();
+        let expects = |rule: Rule| positives.contains(&rule);
+        let relabel = |mut error: Box<PestError<Rule>>, message: String| {
+            error.variant = ErrorVariant::CustomError { message };
+            error
+        };
+
+        if expects(Rule::QuotedString) && rest.starts_with('"') {
+            let message = String::from("unterminated quoted string");
+            UnterminatedString {
+                error: relabel(error, message),
+            }
+        } else if positives == [Rule::EOL] && (line.is_empty() || rest == "\\") {
+            let message = String::from("incomplete escape sequence");
+            InvalidEscape {
+                error: relabel(error, message),
+            }
+        } else if positives.iter().any(|rule| DATE_RULES.contains(rule)) {
+            let message = String::from("invalid date");
+            InvalidDate {
+                error: relabel(error, message),
+            }
+        } else if expects(Rule::Boolean) {
+            let value = rest
+                .split(|c: char| c.is_whitespace() || c == '!' || c == '{')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let message = format!("invalid boolean `{}`, expected `true` or `false`", value);
+            InvalidBoolean {
+                value,
+                error: relabel(error, message),
+            }
+        } else if positives == [Rule::Comment] {
+            let message = String::from("missing `id` clause");
+            MissingId {
+                error: relabel(error, message),
+            }
+        } else if let Some(tag) = CLAUSE_RULES
+            .iter()
+            .any(|&rule| expects(rule))
+            .then(|| clause_tag(&rest))
+            .flatten()
+        {
+            let tag = tag.to_string();
+            let message = format!("unknown tag `{}`", tag);
+            UnknownTag {
+                tag,
+                error: relabel(error, message),
+            }
+        } else {
+            ParserError { error }
+        }
+    }
+}
+
+/// The rules expected when a clause of an entity frame failed to parse.
+const CLAUSE_RULES: &[Rule] = &[Rule::TermClause, Rule::TypedefClause, Rule::InstanceClause];
+
+/// The rules expected when a date or a datetime failed to parse.
+const DATE_RULES: &[Rule] = &[
+    Rule::Iso8601DateTime,
+    Rule::Iso8601Date,
+    Rule::Iso8601Year,
+    Rule::Iso8601Month,
+    Rule::Iso8601Day,
+    Rule::Iso8601Time,
+    Rule::Iso8601Hour,
+    Rule::Iso8601Minute,
+    Rule::Iso8601Second,
+    Rule::Iso8601TimeZone,
+    Rule::NaiveDateTime,
+    Rule::NaiveDate,
+    Rule::NaiveTime,
+    Rule::NaiveDay,
+    Rule::NaiveMonth,
+    Rule::NaiveYear,
+    Rule::NaiveHour,
+    Rule::NaiveMinute,
+];
+
+/// Extract the tag of a clause line, if it has one.
+fn clause_tag(line: &str) -> Option<&str> {
+    let (tag, _) = line.trim_start().split_once(':')?;
+    if tag.is_empty() || tag.contains(char::is_whitespace) {
+        None
+    } else {
+        Some(tag)
+    }
+}
+
 /// A threading error.
 #[cfg(feature = "threading")]
 #[cfg_attr(feature = "_doc", doc(cfg(feature = "threading")))]
@@ -154,11 +414,113 @@ pub enum ThreadingError {
     DisconnectedChannel,
 }
 
+/// An error in an OWL2 document written in functional syntax.
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum OwlError {
+    /// The document ended unexpectedly.
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+
+    /// An unexpected token was found in the document.
+    #[error("unexpected token {token:?} at line {line}, column {column}")]
+    UnexpectedToken {
+        token: String,
+        line: usize,
+        column: usize,
+    },
+
+    /// An abbreviated IRI used a prefix that was not declared.
+    #[error("undeclared prefix {prefix:?} at line {line}, column {column}")]
+    UndeclaredPrefix {
+        prefix: String,
+        line: usize,
+        column: usize,
+    },
+}
+
+/// An error raised when frames are written out of order.
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum WriterError {
+    /// An entity frame was written before the header frame.
+    #[error("entity frame written before the header frame")]
+    MissingHeader,
+
+    /// A header frame was written after another header or entity frame.
+    #[error("header frame written after the first frame")]
+    UnexpectedHeader,
+}
+
+/// An error raised when an identifier cannot be resolved to a primary identifier.
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum RedirectError {
+    /// The identifier is neither declared nor an alternative identifier.
+    #[error("unknown identifier: {id}")]
+    UnknownIdent { id: Ident },
+    /// An obsolete entity is replaced by an entity that is not declared.
+    #[error("{id} is replaced by undeclared {target}")]
+    MissingTarget { id: Ident, target: Ident },
+    /// A chain of replacements loops back to an entity already visited.
+    #[error("replacement loop through {id}")]
+    Loop { id: Ident },
+}
+
+/// An error raised when a literal does not match its declared datatype.
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum LiteralError {
+    /// The literal was read as a Rust type incompatible with its datatype.
+    #[error("cannot read {datatype} literal as {expected}")]
+    UnexpectedDatatype {
+        expected: &'static str,
+        datatype: Ident,
+    },
+    /// The literal is not in the lexical space of its datatype.
+    #[error("invalid {datatype} literal: {literal:?}")]
+    InvalidLiteral { literal: String, datatype: Ident },
+    /// The literal is valid, but its value cannot be represented as a Rust type.
+    #[error("{datatype} literal out of range: {literal:?}")]
+    OutOfRange { literal: String, datatype: Ident },
+}
+
+/// An error raised when a qualifier cannot be read as a typed value.
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum QualifierError {
+    /// A qualifier required by another one is missing.
+    #[error("missing {key} qualifier")]
+    MissingQualifier { key: String },
+    /// A qualifier expected at most once is declared several times.
+    #[error("duplicate {key} qualifiers")]
+    DuplicateQualifier { key: String },
+    /// The value of a qualifier is not valid for its key.
+    #[error("invalid value for {key} qualifier: {value:?}")]
+    InvalidValue { key: String, value: String },
+}
+
+/// An error raised when a date, a time or a timezone is not valid.
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum DateError {
+    /// The day does not exist in the given month and year.
+    #[error("invalid date: {year:04}-{month:02}-{day:02}")]
+    InvalidDate { year: u16, month: u8, day: u8 },
+    /// The time is not a valid time of the day.
+    #[error("invalid time: {hour:02}:{minute:02}:{second:02}")]
+    InvalidTime { hour: u8, minute: u8, second: u8 },
+    /// The timezone offset is out of range.
+    #[error("invalid timezone: {timezone}")]
+    InvalidTimezone { timezone: IsoTimezone },
+    /// The year cannot be represented in an OBO date.
+    #[error("year out of range: {year}")]
+    YearOutOfRange { year: i32 },
+    /// A timezone is required but the datetime does not declare one.
+    #[error("missing timezone")]
+    MissingTimezone,
+}
+
 /// The result type for this crate.
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// The error type for this crate.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// A syntax error occurred.
     #[error("syntax error: {error}")]
@@ -193,6 +555,48 @@ pub enum Error {
         inner: CardinalityError,
     },
 
+    /// An OWL-related error occurred.
+    #[error("OWL error: {error}")]
+    OwlError {
+        #[from]
+        error: OwlError,
+    },
+
+    /// A frame was written out of order by a streaming writer.
+    #[error("writer error: {error}")]
+    WriterError {
+        #[from]
+        error: WriterError,
+    },
+
+    /// An identifier could not be resolved to a primary identifier.
+    #[error("redirect error: {error}")]
+    RedirectError {
+        #[from]
+        error: RedirectError,
+    },
+
+    /// A literal did not match its declared datatype.
+    #[error("literal error: {error}")]
+    LiteralError {
+        #[from]
+        error: LiteralError,
+    },
+
+    /// A qualifier could not be read as a typed value.
+    #[error("qualifier error: {error}")]
+    QualifierError {
+        #[from]
+        error: QualifierError,
+    },
+
+    /// A date, a time or a timezone was not valid.
+    #[error("date error: {error}")]
+    DateError {
+        #[from]
+        error: DateError,
+    },
+
     /// A threading-related error occurred.
     #[cfg(feature = "threading")]
     #[cfg_attr(feature = "_doc", doc(cfg(feature = "threading")))]
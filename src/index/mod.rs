@@ -0,0 +1,23 @@
+//! In-memory indices over the entities of an OBO document.
+//!
+//! The [`OntologyIndex`] borrows from an [`OboDoc`] and provides constant
+//! time lookups of entity frames by identifier, label, synonym, xref,
+//! alternative identifier or subset. The [`SearchIndex`] provides ranked
+//! fuzzy full-text search over the names, synonyms and definitions of terms.
+//! Alternative identifiers and obsolete entities can be redirected to their
+//! current primary identifier with [`OntologyIndex::resolve`].
+//!
+//! [`OntologyIndex`]: ./struct.OntologyIndex.html
+//! [`SearchIndex`]: ./struct.SearchIndex.html
+//! [`OntologyIndex::resolve`]: ./struct.OntologyIndex.html#method.resolve
+//! [`OboDoc`]: ../ast/struct.OboDoc.html
+
+mod ontology;
+mod redirect;
+mod search;
+
+pub use self::ontology::OntologyIndex;
+pub use self::redirect::Resolution;
+pub use self::search::Field;
+pub use self::search::SearchHit;
+pub use self::search::SearchIndex;
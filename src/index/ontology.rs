@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::semantics::Identified;
+
+/// An index of the entities of an OBO document.
+///
+/// The index borrows the identifiers, labels and frames from the document
+/// it was built from, so building it does not clone any part of the syntax
+/// tree. Labels and synonyms are compared case-insensitively.
+///
+/// When several frames share the same identifier, the first one is
+/// indexed by [`get`](#method.get), while the clauses of all the frames
+/// are indexed by the other lookups.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// # use fastobo::index::OntologyIndex;
+/// let doc = fastobo::from_str("[Term]\nid: TST:001\nname: Test\nalt_id: TST:002\n").unwrap();
+/// let index = OntologyIndex::new(&doc);
+///
+/// let id = Ident::from(PrefixedIdent::new("TST", "001"));
+/// assert_eq!(index.by_label("test"), &[&id]);
+///
+/// let alt = Ident::from(PrefixedIdent::new("TST", "002"));
+/// assert_eq!(index.primary_id(&alt), Some(&id));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OntologyIndex<'a> {
+    entities: HashMap<&'a Ident, &'a EntityFrame>,
+    labels: HashMap<String, Vec<&'a Ident>>,
+    synonyms: HashMap<String, Vec<(&'a Ident, &'a SynonymScope)>>,
+    xrefs: HashMap<&'a Ident, Vec<&'a Ident>>,
+    pub(super) alt_ids: HashMap<&'a Ident, &'a Ident>,
+    subsets: HashMap<&'a SubsetIdent, Vec<&'a Ident>>,
+}
+
+macro_rules! index_clauses {
+    ($index:ident, $id:ident, $frame:ident, $clause:ident) => {
+        for line in $frame.clauses() {
+            match line.as_inner() {
+                $clause::Name(name) => $index.add_label(name.as_str(), $id),
+                $clause::Synonym(syn) => $index.add_synonym(syn, $id),
+                $clause::Xref(xref) => $index.add_xref(xref.id(), $id),
+                $clause::AltId(alt) => $index.add_alt_id(alt, $id),
+                $clause::Subset(subset) => $index.add_subset(subset, $id),
+                _ => (),
+            }
+        }
+    };
+}
+
+impl<'a> OntologyIndex<'a> {
+    /// Build an index of the entities of the given document.
+    pub fn new(doc: &'a OboDoc) -> Self {
+        let mut index = Self::default();
+        for entity in doc.entities() {
+            index.add_entity(entity);
+        }
+        index
+    }
+
+    /// Add an entity frame to the index.
+    pub fn add_entity(&mut self, entity: &'a EntityFrame) {
+        let id = entity.as_id();
+        self.entities.entry(id).or_insert(entity);
+        match entity {
+            EntityFrame::Term(frame) => index_clauses!(self, id, frame, TermClause),
+            EntityFrame::Typedef(frame) => index_clauses!(self, id, frame, TypedefClause),
+            EntityFrame::Instance(frame) => index_clauses!(self, id, frame, InstanceClause),
+        }
+    }
+
+    fn add_label(&mut self, label: &str, id: &'a Ident) {
+        self.labels
+            .entry(label.to_lowercase())
+            .or_default()
+            .push(id);
+    }
+
+    fn add_synonym(&mut self, synonym: &'a Synonym, id: &'a Ident) {
+        let text = synonym.description().as_str().to_lowercase();
+        self.synonyms
+            .entry(text)
+            .or_default()
+            .push((id, synonym.scope()));
+    }
+
+    fn add_xref(&mut self, xref: &'a Ident, id: &'a Ident) {
+        self.xrefs.entry(xref).or_default().push(id);
+    }
+
+    fn add_alt_id(&mut self, alt: &'a Ident, id: &'a Ident) {
+        self.alt_ids.entry(alt).or_insert(id);
+    }
+
+    fn add_subset(&mut self, subset: &'a SubsetIdent, id: &'a Ident) {
+        self.subsets.entry(subset).or_default().push(id);
+    }
+
+    /// Get the number of entities in the index.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Check whether the index contains no entity.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Get an iterator over the identifiers and frames of the indexed entities.
+    pub fn entities(&self) -> impl Iterator<Item = (&'a Ident, &'a EntityFrame)> + '_ {
+        self.entities.iter().map(|(id, frame)| (*id, *frame))
+    }
+
+    /// Get the entity frame with the given identifier.
+    pub fn get(&self, id: &Ident) -> Option<&'a EntityFrame> {
+        self.entities.get(id).cloned()
+    }
+
+    /// Check whether an entity with the given identifier is indexed.
+    pub fn contains(&self, id: &Ident) -> bool {
+        self.entities.contains_key(id)
+    }
+
+    /// Get the identifiers of the entities with the given label.
+    pub fn by_label(&self, label: &str) -> &[&'a Ident] {
+        self.labels
+            .get(&label.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the identifiers of the entities with the given synonym, with its scope.
+    pub fn by_synonym(&self, text: &str) -> &[(&'a Ident, &'a SynonymScope)] {
+        self.synonyms
+            .get(&text.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the identifiers of the entities with the given cross-reference.
+    pub fn by_xref(&self, xref: &Ident) -> &[&'a Ident] {
+        self.xrefs.get(xref).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Get the primary identifier of the entity with the given alternative identifier.
+    pub fn primary_id(&self, alt_id: &Ident) -> Option<&'a Ident> {
+        self.alt_ids.get(alt_id).cloned()
+    }
+
+    /// Get the identifiers of the entities declared in the given subset.
+    pub fn subset_members(&self, subset: &SubsetIdent) -> &[&'a Ident] {
+        self.subsets
+            .get(subset)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+    use textwrap_macros::dedent;
+
+    use super::*;
+
+    fn id(s: &str) -> Ident {
+        Ident::from_str(s).unwrap()
+    }
+
+    fn doc() -> OboDoc {
+        OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            name: Test
+            synonym: "trial" EXACT []
+            xref: Wikipedia:Test
+            alt_id: TST:100
+            subset: slim
+
+            [Term]
+            id: TST:002
+            name: test
+            synonym: "Trial" BROAD []
+            xref: Wikipedia:Test
+
+            [Typedef]
+            id: part_of
+            name: part of
+            subset: slim
+            "#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn lookups() {
+        let doc = doc();
+        let index = OntologyIndex::new(&doc);
+        let (t1, t2, part_of) = (id("TST:001"), id("TST:002"), id("part_of"));
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get(&t1), Some(&doc.entities()[0]));
+        assert_eq!(index.get(&id("TST:003")), None);
+
+        assert_eq!(index.by_label("TEST"), &[&t1, &t2]);
+        assert_eq!(index.by_label("part of"), &[&part_of]);
+        assert!(index.by_label("missing").is_empty());
+
+        assert_eq!(
+            index.by_synonym("trial"),
+            &[(&t1, &SynonymScope::Exact), (&t2, &SynonymScope::Broad)]
+        );
+        assert_eq!(index.by_xref(&id("Wikipedia:Test")), &[&t1, &t2]);
+        assert_eq!(index.primary_id(&id("TST:100")), Some(&t1));
+        assert_eq!(index.primary_id(&t1), None);
+
+        let slim = SubsetIdent::from_str("slim").unwrap();
+        assert_eq!(index.subset_members(&slim), &[&t1, &part_of]);
+    }
+
+    #[test]
+    fn instances() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Instance]
+            id: TST:I01
+            name: Sample
+            instance_of: TST:001
+            synonym: "specimen" RELATED []
+            xref: Biosample:1
+            alt_id: TST:I99
+            subset: slim
+            "#
+        ))
+        .unwrap();
+        let index = OntologyIndex::new(&doc);
+        let i1 = id("TST:I01");
+
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get(&i1), Some(&doc.entities()[0]));
+        assert_eq!(index.by_label("sample"), &[&i1]);
+        assert_eq!(
+            index.by_synonym("SPECIMEN"),
+            &[(&i1, &SynonymScope::Related)]
+        );
+        assert_eq!(index.by_xref(&id("Biosample:1")), &[&i1]);
+        assert_eq!(index.primary_id(&id("TST:I99")), Some(&i1));
+
+        let slim = SubsetIdent::from_str("slim").unwrap();
+        assert_eq!(index.subset_members(&slim), &[&i1]);
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            name: first
+
+            [Term]
+            id: TST:001
+            name: second
+            alt_id: TST:100
+            "#
+        ))
+        .unwrap();
+        let index = OntologyIndex::new(&doc);
+        let t1 = id("TST:001");
+
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get(&t1), Some(&doc.entities()[0]));
+        assert_eq!(index.by_label("first"), &[&t1]);
+        assert_eq!(index.by_label("second"), &[&t1]);
+        assert_eq!(index.primary_id(&id("TST:100")), Some(&t1));
+    }
+
+    #[test]
+    fn non_ascii_labels() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            name: Ängström Éclair
+            synonym: "ΣΊΣΥΦΟΣ" EXACT []
+            "#
+        ))
+        .unwrap();
+        let index = OntologyIndex::new(&doc);
+        let t1 = id("TST:001");
+
+        assert_eq!(index.by_label("ängström éclair"), &[&t1]);
+        assert_eq!(index.by_label("ÄNGSTRÖM ÉCLAIR"), &[&t1]);
+        assert_eq!(index.by_synonym("σίσυφος"), &[(&t1, &SynonymScope::Exact)]);
+        assert!(index.by_label("angstrom eclair").is_empty());
+    }
+}
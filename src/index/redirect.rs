@@ -0,0 +1,364 @@
+use std::collections::HashSet;
+
+use crate::ast::*;
+use crate::error::RedirectError;
+use crate::semantics::Identified;
+
+use super::OntologyIndex;
+
+/// The result of resolving an identifier to a current entity.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Resolution<'a> {
+    /// The identifier resolves to the primary identifier of a current entity.
+    Primary(&'a Ident),
+    /// The identifier resolves to an obsolete entity with several candidate
+    /// replacements, listed in its `consider` clauses.
+    Ambiguous {
+        id: &'a Ident,
+        candidates: Vec<&'a Ident>,
+    },
+    /// The identifier resolves to an obsolete entity without replacement.
+    Obsolete(&'a Ident),
+}
+
+/// The clauses of an entity frame used to redirect references to it.
+#[derive(Default)]
+struct Redirects<'a> {
+    obsolete: bool,
+    replaced_by: Option<&'a Ident>,
+    consider: Vec<&'a Ident>,
+}
+
+macro_rules! collect_redirects {
+    ($redirects:ident, $frame:ident, $clause:ident) => {
+        for line in $frame.clauses() {
+            match line.as_inner() {
+                $clause::IsObsolete(b) => $redirects.obsolete |= *b,
+                $clause::ReplacedBy(id) if $redirects.replaced_by.is_none() => {
+                    $redirects.replaced_by = Some(id.as_ref().as_ref())
+                }
+                $clause::Consider(id) => $redirects.consider.push(id.as_ref().as_ref()),
+                _ => (),
+            }
+        }
+    };
+}
+
+impl<'a> Redirects<'a> {
+    fn new(entity: &'a EntityFrame) -> Self {
+        let mut redirects = Self::default();
+        match entity {
+            EntityFrame::Term(frame) => collect_redirects!(redirects, frame, TermClause),
+            EntityFrame::Typedef(frame) => collect_redirects!(redirects, frame, TypedefClause),
+            EntityFrame::Instance(frame) => collect_redirects!(redirects, frame, InstanceClause),
+        }
+        redirects
+    }
+}
+
+impl<'a> OntologyIndex<'a> {
+    /// Resolve an identifier to the primary identifier of a current entity.
+    ///
+    /// Alternative identifiers are redirected to the entity declaring them
+    /// with an `alt_id` clause, and obsolete entities to the entity in their
+    /// `replaced_by` clause, until a current entity is reached. Obsolete
+    /// entities without replacement but with `consider` clauses resolve to
+    /// their candidates, which are left for the caller to choose from.
+    ///
+    /// Each `consider` candidate is itself redirected through its `alt_id`
+    /// and `replaced_by` clauses, so the candidates are always primary
+    /// identifiers of current entities. Candidates that are obsolete without
+    /// replacement are discarded, and an obsolete entity with no remaining
+    /// candidate resolves to [`Resolution::Obsolete`].
+    ///
+    /// # Errors
+    /// Returns a [`RedirectError`] if the identifier or one of its `consider`
+    /// candidates is not declared in the document, if a `replaced_by` clause
+    /// points to an undeclared entity, or if a chain of replacements loops.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # use fastobo::index::*;
+    /// let doc = fastobo::from_str(concat!(
+    ///     "[Term]\nid: TST:001\nis_obsolete: true\nreplaced_by: TST:002\n\n",
+    ///     "[Term]\nid: TST:002\nalt_id: TST:003\n",
+    /// )).unwrap();
+    /// let index = OntologyIndex::new(&doc);
+    ///
+    /// let primary = Ident::from_str("TST:002").unwrap();
+    /// for id in &["TST:001", "TST:002", "TST:003"] {
+    ///     let id = Ident::from_str(id).unwrap();
+    ///     assert_eq!(index.resolve(&id), Ok(Resolution::Primary(&primary)));
+    /// }
+    /// ```
+    ///
+    /// [`RedirectError`]: ../error/enum.RedirectError.html
+    /// [`Resolution::Obsolete`]: ./enum.Resolution.html#variant.Obsolete
+    pub fn resolve(&self, id: &Ident) -> Result<Resolution<'a>, RedirectError> {
+        let (current, redirects) = self.redirect(id)?;
+        if !redirects.obsolete {
+            return Ok(Resolution::Primary(current));
+        }
+
+        let mut candidates = Vec::new();
+        for candidate in redirects.consider {
+            let (primary, r) = self.redirect(candidate)?;
+            if !r.obsolete && !candidates.contains(&primary) {
+                candidates.push(primary);
+            }
+        }
+        match candidates.is_empty() {
+            true => Ok(Resolution::Obsolete(current)),
+            false => Ok(Resolution::Ambiguous {
+                id: current,
+                candidates,
+            }),
+        }
+    }
+
+    /// Follow the `alt_id` and `replaced_by` redirections of an identifier.
+    ///
+    /// Returns the identifier of the first entity that is either current or
+    /// obsolete without a `replaced_by` clause, with its redirections.
+    fn redirect(&self, id: &Ident) -> Result<(&'a Ident, Redirects<'a>), RedirectError> {
+        let (mut current, mut entity) = match self.get(id) {
+            Some(entity) => (entity.as_id(), entity),
+            None => {
+                let primary = self
+                    .primary_id(id)
+                    .ok_or_else(|| RedirectError::UnknownIdent { id: id.clone() })?;
+                let entity = self
+                    .get(primary)
+                    .ok_or_else(|| RedirectError::MissingTarget {
+                        id: id.clone(),
+                        target: primary.clone(),
+                    })?;
+                (primary, entity)
+            }
+        };
+
+        let mut visited = HashSet::new();
+        loop {
+            if !visited.insert(current) {
+                return Err(RedirectError::Loop {
+                    id: current.clone(),
+                });
+            }
+
+            let redirects = Redirects::new(entity);
+            let target = match redirects.replaced_by {
+                Some(target) if redirects.obsolete => target,
+                _ => return Ok((current, redirects)),
+            };
+
+            let primary = match self.get(target) {
+                Some(_) => Some(target),
+                None => self.primary_id(target),
+            };
+            match primary.and_then(|p| self.get(p).map(|e| (p, e))) {
+                Some((p, e)) => {
+                    current = p;
+                    entity = e;
+                }
+                None => {
+                    return Err(RedirectError::MissingTarget {
+                        id: current.clone(),
+                        target: target.clone(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+    use textwrap_macros::dedent;
+
+    use super::*;
+
+    fn id(s: &str) -> Ident {
+        Ident::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn alt_id() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            alt_id: TST:101
+
+            [Typedef]
+            id: part_of
+            alt_id: TST:102
+            "#
+        ))
+        .unwrap();
+        let mut index = OntologyIndex::new(&doc);
+        let t1 = id("TST:001");
+
+        assert_eq!(index.resolve(&t1), Ok(Resolution::Primary(&t1)));
+        assert_eq!(index.resolve(&id("TST:101")), Ok(Resolution::Primary(&t1)));
+
+        // an alternative identifier pointing to an undeclared primary
+        let missing = id("TST:002");
+        let alt = id("TST:103");
+        index.alt_ids.insert(&alt, &missing);
+        assert_eq!(
+            index.resolve(&alt),
+            Err(RedirectError::MissingTarget {
+                id: alt.clone(),
+                target: missing.clone(),
+            })
+        );
+    }
+
+    #[test]
+    fn replaced_by_chain() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            alt_id: TST:101
+
+            [Term]
+            id: TST:002
+            is_obsolete: true
+            replaced_by: TST:101
+
+            [Term]
+            id: TST:003
+            is_obsolete: true
+            replaced_by: TST:002
+            "#
+        ))
+        .unwrap();
+        let index = OntologyIndex::new(&doc);
+        let t1 = id("TST:001");
+
+        assert_eq!(index.resolve(&id("TST:002")), Ok(Resolution::Primary(&t1)));
+        assert_eq!(index.resolve(&id("TST:003")), Ok(Resolution::Primary(&t1)));
+    }
+
+    #[test]
+    fn consider() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            alt_id: TST:101
+
+            [Term]
+            id: TST:002
+            is_obsolete: true
+            replaced_by: TST:101
+
+            [Term]
+            id: TST:003
+            is_obsolete: true
+            consider: TST:101
+            consider: TST:002
+            consider: TST:004
+            consider: TST:005
+
+            [Term]
+            id: TST:004
+
+            [Term]
+            id: TST:005
+            is_obsolete: true
+
+            [Term]
+            id: TST:006
+            is_obsolete: true
+            consider: TST:005
+
+            [Term]
+            id: TST:007
+            is_obsolete: true
+            consider: TST:004
+            consider: TST:999
+            "#
+        ))
+        .unwrap();
+        let index = OntologyIndex::new(&doc);
+
+        assert_eq!(
+            index.resolve(&id("TST:003")),
+            Ok(Resolution::Ambiguous {
+                id: &id("TST:003"),
+                candidates: vec![&id("TST:001"), &id("TST:004")],
+            })
+        );
+        assert_eq!(
+            index.resolve(&id("TST:005")),
+            Ok(Resolution::Obsolete(&id("TST:005")))
+        );
+        assert_eq!(
+            index.resolve(&id("TST:006")),
+            Ok(Resolution::Obsolete(&id("TST:006")))
+        );
+        assert_eq!(
+            index.resolve(&id("TST:007")),
+            Err(RedirectError::UnknownIdent { id: id("TST:999") })
+        );
+    }
+
+    #[test]
+    fn replacement_loop() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            is_obsolete: true
+            replaced_by: TST:002
+
+            [Term]
+            id: TST:002
+            is_obsolete: true
+            replaced_by: TST:001
+            "#
+        ))
+        .unwrap();
+        let index = OntologyIndex::new(&doc);
+
+        assert_eq!(
+            index.resolve(&id("TST:001")),
+            Err(RedirectError::Loop { id: id("TST:001") })
+        );
+    }
+
+    #[test]
+    fn missing_target() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            is_obsolete: true
+            replaced_by: TST:999
+            "#
+        ))
+        .unwrap();
+        let index = OntologyIndex::new(&doc);
+
+        assert_eq!(
+            index.resolve(&id("TST:001")),
+            Err(RedirectError::MissingTarget {
+                id: id("TST:001"),
+                target: id("TST:999"),
+            })
+        );
+        assert_eq!(
+            index.resolve(&id("TST:000")),
+            Err(RedirectError::UnknownIdent { id: id("TST:000") })
+        );
+    }
+}
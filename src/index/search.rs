@@ -0,0 +1,291 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::ast::*;
+
+/// The clause a token was found in, used to weight search matches.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Field {
+    /// The `name` clause of a term.
+    Name,
+    /// The description of an `EXACT` synonym.
+    ExactSynonym,
+    /// The description of a `RELATED`, `BROAD` or `NARROW` synonym.
+    RelatedSynonym,
+    /// The text of the `def` clause of a term.
+    Definition,
+}
+
+impl Field {
+    /// Get the weight of a match in this field.
+    pub fn weight(self) -> f64 {
+        match self {
+            Field::Name => 4.0,
+            Field::ExactSynonym => 3.0,
+            Field::RelatedSynonym => 2.0,
+            Field::Definition => 1.0,
+        }
+    }
+}
+
+impl From<&SynonymScope> for Field {
+    fn from(scope: &SynonymScope) -> Self {
+        match scope {
+            SynonymScope::Exact => Field::ExactSynonym,
+            _ => Field::RelatedSynonym,
+        }
+    }
+}
+
+/// A term matching a search query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit<'a> {
+    id: &'a ClassIdent,
+    score: f64,
+}
+
+impl<'a> SearchHit<'a> {
+    /// Get the identifier of the matching term.
+    pub fn id(&self) -> &'a ClassIdent {
+        self.id
+    }
+
+    /// Get the score of the match, higher meaning more relevant.
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// An occurrence of a token in a term.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Posting {
+    term: usize,
+    field: Field,
+}
+
+/// A full-text index of the names, synonyms and definitions of terms.
+///
+/// Texts are split into lowercase alphanumeric tokens stored in an inverted
+/// index. Query tokens match index tokens exactly or within a small edit
+/// distance, and each match is weighted by the clause it was found in:
+/// names rank above exact synonyms, which rank above other synonyms, which
+/// rank above definitions.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::index::SearchIndex;
+/// let doc = fastobo::from_str(concat!(
+///     "[Term]\nid: TST:001\nname: heart muscle\n\n",
+///     "[Term]\nid: TST:002\nname: muscle\ndef: \"A tissue, e.g. of the heart.\" []\n",
+/// )).unwrap();
+///
+/// let index = SearchIndex::new(&doc);
+/// let hits = index.search("hart muscle");
+/// assert_eq!(hits[0].id().to_string(), "TST:001");
+/// assert_eq!(hits[1].id().to_string(), "TST:002");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SearchIndex<'a> {
+    terms: Vec<&'a ClassIdent>,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl<'a> SearchIndex<'a> {
+    /// Build a search index of the terms of the given document.
+    pub fn new(doc: &'a OboDoc) -> Self {
+        let mut index = Self::default();
+        for frame in doc.entities().iter().filter_map(EntityFrame::as_term_frame) {
+            index.add_term(frame);
+        }
+        index
+    }
+
+    /// Add the names, synonyms and definitions of a term to the index.
+    pub fn add_term(&mut self, frame: &'a TermFrame) {
+        let term = self.terms.len();
+        self.terms.push(frame.id().as_inner());
+        for line in frame.clauses() {
+            match line.as_inner() {
+                TermClause::Name(name) => self.add_text(term, Field::Name, name.as_str()),
+                TermClause::Synonym(syn) => {
+                    let text = syn.description().as_str();
+                    self.add_text(term, Field::from(syn.scope()), text)
+                }
+                TermClause::Def(def) => self.add_text(term, Field::Definition, def.text().as_str()),
+                _ => (),
+            }
+        }
+    }
+
+    fn add_text(&mut self, term: usize, field: Field, text: &str) {
+        for token in tokenize(text) {
+            let postings = self.postings.entry(token).or_default();
+            let posting = Posting { term, field };
+            if !postings.contains(&posting) {
+                postings.push(posting);
+            }
+        }
+    }
+
+    /// Search the index, returning the matching terms by decreasing score.
+    ///
+    /// Each query token contributes the weight of its best match in a term,
+    /// scaled down by the edit distance for fuzzy matches. Hits with the
+    /// same score are sorted by identifier.
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'a>> {
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for query_token in tokenize(query) {
+            let mut best: HashMap<usize, f64> = HashMap::new();
+            for (token, postings) in &self.postings {
+                let similarity = match similarity(&query_token, token) {
+                    Some(similarity) => similarity,
+                    None => continue,
+                };
+                for posting in postings {
+                    let score = posting.field.weight() * similarity;
+                    let entry = best.entry(posting.term).or_insert(0.0);
+                    if score > *entry {
+                        *entry = score;
+                    }
+                }
+            }
+            for (term, score) in best {
+                *scores.entry(term).or_insert(0.0) += score;
+            }
+        }
+
+        let mut hits: Vec<SearchHit<'a>> = scores
+            .into_iter()
+            .map(|(term, score)| SearchHit {
+                id: self.terms[term],
+                score,
+            })
+            .collect();
+        hits.sort_by(|x, y| {
+            y.score
+                .partial_cmp(&x.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| x.id.cmp(y.id))
+        });
+        hits
+    }
+}
+
+/// Split a text into lowercase alphanumeric tokens.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Get the similarity of two tokens, or `None` if they are too different.
+///
+/// Short tokens must match exactly, longer tokens may differ by one edit,
+/// and tokens of more than eight characters by two edits.
+fn similarity(query: &str, token: &str) -> Option<f64> {
+    if query == token {
+        return Some(1.0);
+    }
+    let (q, t): (Vec<char>, Vec<char>) = (query.chars().collect(), token.chars().collect());
+    let max_distance = match q.len() {
+        0..=3 => return None,
+        4..=8 => 1,
+        _ => 2,
+    };
+    if (q.len() as isize - t.len() as isize).abs() > max_distance as isize {
+        return None;
+    }
+    let distance = levenshtein(&q, &t);
+    if distance > max_distance {
+        return None;
+    }
+    Some(1.0 - distance as f64 / q.len().max(t.len()) as f64)
+}
+
+/// Compute the edit distance between two sequences of characters.
+fn levenshtein(x: &[char], y: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=y.len()).collect();
+    for (i, cx) in x.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cy) in y.iter().enumerate() {
+            let substitution = diagonal + (cx != cy) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[y.len()]
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+    use textwrap_macros::dedent;
+
+    use super::*;
+
+    #[test]
+    fn levenshtein() {
+        let distance = |x: &str, y: &str| {
+            let x: Vec<char> = x.chars().collect();
+            let y: Vec<char> = y.chars().collect();
+            super::levenshtein(&x, &y)
+        };
+        assert_eq!(distance("heart", "heart"), 0);
+        assert_eq!(distance("heart", "hart"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn similarity() {
+        assert_eq!(super::similarity("cell", "cell"), Some(1.0));
+        assert_eq!(super::similarity("cel", "cell"), None);
+        assert_eq!(super::similarity("muscel", "muscle"), None);
+        assert_eq!(super::similarity("musle", "muscle"), Some(1.0 - 1.0 / 6.0));
+    }
+
+    #[test]
+    fn search_weights() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            def: "A heart tissue." []
+
+            [Term]
+            id: TST:002
+            synonym: "heart" RELATED []
+
+            [Term]
+            id: TST:003
+            synonym: "heart" EXACT []
+
+            [Term]
+            id: TST:004
+            name: heart
+            "#
+        ))
+        .unwrap();
+        let index = SearchIndex::new(&doc);
+        let hits: Vec<_> = index
+            .search("Heart")
+            .into_iter()
+            .map(|hit| (hit.id().to_string(), hit.score()))
+            .collect();
+        assert_eq!(
+            hits,
+            vec![
+                ("TST:004".to_string(), 4.0),
+                ("TST:003".to_string(), 3.0),
+                ("TST:002".to_string(), 2.0),
+                ("TST:001".to_string(), 1.0),
+            ]
+        );
+        assert!(index.search("lung").is_empty());
+    }
+}
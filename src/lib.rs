@@ -1,6 +1,11 @@
 #![cfg_attr(feature = "_doc", feature(doc_cfg, external_doc))]
 #![cfg_attr(feature = "_doc", doc(include = "../README.md"))]
 #![warn(clippy::all)]
+#![allow(clippy::module_inception)]
+#![allow(
+    clippy::derive_ord_xor_partial_ord,
+    clippy::non_canonical_partial_ord_impl
+)]
 
 extern crate blanket;
 extern crate fastobo_derive_internal;
@@ -22,15 +27,28 @@ extern crate num_cpus;
 #[cfg(feature = "smartstring")]
 extern crate smartstring;
 
+#[cfg(feature = "chrono")]
+extern crate chrono;
+
+#[cfg(feature = "time")]
+extern crate time;
+
+#[cfg(feature = "render")]
+extern crate annotate_snippets;
+
 #[cfg(test)]
 extern crate textwrap_macros;
 
 pub mod ast;
+pub mod diagnostic;
 pub mod error;
+pub mod index;
+pub mod owl;
 pub mod parser;
 pub mod semantics;
 pub mod syntax;
 pub mod visit;
+pub mod writer;
 
 use std::convert::TryFrom;
 use std::fs::File;
@@ -97,5 +115,5 @@ pub fn to_writer<W: Write>(mut writer: W, doc: &OboDoc) -> Result<()> {
 pub fn to_file<P: AsRef<Path>>(path: P, doc: &OboDoc) -> Result<()> {
     File::create(path)
         .map_err(From::from)
-        .and_then(|r| to_writer(r, doc).map_err(From::from))
+        .and_then(|r| to_writer(r, doc))
 }
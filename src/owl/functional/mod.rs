@@ -0,0 +1,756 @@
+//! Conversion of OBO documents to and from the OWL2 functional syntax.
+//!
+//! # Example
+//! ```rust
+//! # extern crate fastobo;
+//! let doc = fastobo::from_str("ontology: test\n\n[Term]\nid: TEST:001\nname: test term\n").unwrap();
+//! let mut out = Vec::new();
+//! fastobo::owl::functional::to_writer(&mut out, &doc).unwrap();
+//!
+//! let text = String::from_utf8(out).unwrap();
+//! assert!(text.contains("Ontology(<http://purl.obolibrary.org/obo/test.owl>"));
+//! assert!(text.contains("Declaration(Class(obo:TEST_001))"));
+//! assert!(text.contains(r#"AnnotationAssertion(rdfs:label obo:TEST_001 "test term")"#));
+//! ```
+
+use std::io::Read;
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::ast::*;
+use crate::error::Error;
+use crate::error::Result;
+
+use super::model::*;
+use super::prefix;
+use super::reverse::ReverseTranslator;
+use super::translate::Translator;
+use super::vocab;
+
+pub(crate) mod parser;
+
+/// A writer serializing OBO frames to the OWL2 functional syntax.
+///
+/// The writer serializes the ontology header as soon as it is created, so
+/// that entity frames can then be written one at a time. The closing
+/// parenthesis of the ontology is written by [`finish`](#method.finish),
+/// which must be called once every frame has been written.
+///
+/// The contents of `owl-axioms` header clauses are written verbatim at the
+/// end of the ontology: if they contain a complete ontology document, only
+/// the axioms are kept, and their prefix declarations are merged with the
+/// ones of the output document.
+#[derive(Debug)]
+pub struct FunctionalWriter<W: Write> {
+    writer: W,
+    translator: Translator,
+    prefixes: Vec<(String, String)>,
+    owl_axioms: Vec<String>,
+}
+
+impl<W: Write> FunctionalWriter<W> {
+    /// Create a new writer, and write the ontology header.
+    pub fn new(mut writer: W, header: &HeaderFrame) -> Result<Self> {
+        let translator = Translator::new(header);
+
+        // collect the prefixes and the axioms from `owl-axioms` clauses
+        let mut prefixes: Vec<(String, String)> = vocab::PREFIXES
+            .iter()
+            .map(|(p, u)| (p.to_string(), u.to_string()))
+            .collect();
+        let mut owl_axioms = Vec::new();
+        for clause in header.iter() {
+            if let HeaderClause::OwlAxioms(axioms) = clause {
+                let (declared, body) = split_document(axioms.as_str());
+                for (prefix, url) in declared {
+                    // the declaration of the document wins over the default
+                    // one, so that the axioms expand to the same IRIs
+                    match prefixes.iter_mut().find(|(p, _)| p == &prefix) {
+                        Some(entry) => entry.1 = url,
+                        None => prefixes.push((prefix, url)),
+                    }
+                }
+                owl_axioms.push(body);
+            }
+        }
+
+        // write the prefix declarations
+        for (prefix, url) in prefixes.iter() {
+            writeln!(writer, "Prefix({}:=<{}>)", prefix, url)?;
+        }
+        writeln!(writer)?;
+
+        let mut this = Self {
+            writer,
+            translator,
+            prefixes,
+            owl_axioms,
+        };
+
+        // write the ontology IRI, imports and annotations
+        this.writer.write_all(b"Ontology(")?;
+        if let Some(iri) = this.translator.ontology_iri() {
+            write!(this.writer, "<{}>", iri.as_str())?;
+            if let Some(version) = this.translator.version_iri(header) {
+                write!(this.writer, " <{}>", version.as_str())?;
+            }
+        }
+        writeln!(this.writer)?;
+        for import in this.translator.imports(header) {
+            writeln!(this.writer, "Import(<{}>)", import.as_str())?;
+        }
+        for annotation in this.translator.header_annotations(header) {
+            let mut line = String::new();
+            this.annotation(&mut line, &annotation);
+            writeln!(this.writer, "{}", line)?;
+        }
+        writeln!(this.writer)?;
+        for axiom in this.translator.header_axioms(header) {
+            this.write_axiom(&axiom)?;
+        }
+
+        Ok(this)
+    }
+
+    /// Get a reference to the translator used by the writer.
+    pub fn translator(&self) -> &Translator {
+        &self.translator
+    }
+
+    /// Write the axioms corresponding to an entity frame.
+    pub fn write_frame(&mut self, frame: &EntityFrame) -> Result<()> {
+        writeln!(self.writer)?;
+        for axiom in self.translator.entity_axioms(frame) {
+            self.write_axiom(&axiom)?;
+        }
+        Ok(())
+    }
+
+    /// Write a single axiom.
+    pub fn write_axiom(&mut self, axiom: &Axiom) -> Result<()> {
+        let mut line = String::new();
+        self.axiom(&mut line, axiom);
+        writeln!(self.writer, "{}", line).map_err(From::from)
+    }
+
+    /// Write the `owl-axioms` of the header and close the ontology.
+    pub fn finish(mut self) -> Result<W> {
+        for axioms in self.owl_axioms.iter() {
+            writeln!(self.writer)?;
+            writeln!(self.writer, "{}", axioms)?;
+        }
+        writeln!(self.writer, ")")?;
+        Ok(self.writer)
+    }
+}
+
+/// Helper methods to render OWL2 constructs.
+impl<W: Write> FunctionalWriter<W> {
+    fn iri(&self, out: &mut String, iri: &Url) {
+        if let Some((prefix, local)) = prefix::compact(&self.prefixes, iri) {
+            out.push_str(prefix);
+            out.push(':');
+            out.push_str(local);
+            return;
+        }
+        out.push('<');
+        out.push_str(iri.as_str());
+        out.push('>');
+    }
+
+    fn literal(&self, out: &mut String, literal: &Literal) {
+        out.push('"');
+        for c in literal.value().chars() {
+            if c == '"' || c == '\\' {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out.push('"');
+        if let Some(datatype) = literal.datatype() {
+            out.push_str("^^");
+            self.iri(out, datatype);
+        }
+    }
+
+    fn annotation_value(&self, out: &mut String, value: &AnnotationValue) {
+        match value {
+            AnnotationValue::Iri(iri) => self.iri(out, iri),
+            AnnotationValue::Literal(literal) => self.literal(out, literal),
+        }
+    }
+
+    fn annotation(&self, out: &mut String, annotation: &Annotation) {
+        out.push_str("Annotation(");
+        self.iri(out, &annotation.property);
+        out.push(' ');
+        self.annotation_value(out, &annotation.value);
+        out.push(')');
+    }
+
+    fn annotations(&self, out: &mut String, annotations: &[Annotation]) {
+        for annotation in annotations {
+            self.annotation(out, annotation);
+            out.push(' ');
+        }
+    }
+
+    fn class_expression(&self, out: &mut String, ce: &ClassExpression) {
+        match ce {
+            ClassExpression::Class(iri) => self.iri(out, iri),
+            ClassExpression::ObjectSomeValuesFrom(r, filler) => {
+                out.push_str("ObjectSomeValuesFrom(");
+                self.iri(out, r);
+                out.push(' ');
+                self.class_expression(out, filler);
+                out.push(')');
+            }
+            ClassExpression::ObjectIntersectionOf(operands) => {
+                out.push_str("ObjectIntersectionOf(");
+                self.class_expressions(out, operands);
+                out.push(')');
+            }
+            ClassExpression::ObjectUnionOf(operands) => {
+                out.push_str("ObjectUnionOf(");
+                self.class_expressions(out, operands);
+                out.push(')');
+            }
+            ClassExpression::ObjectMinCardinality(n, r, filler) => {
+                self.cardinality(out, "ObjectMinCardinality", *n, r, filler)
+            }
+            ClassExpression::ObjectMaxCardinality(n, r, filler) => {
+                self.cardinality(out, "ObjectMaxCardinality", *n, r, filler)
+            }
+            ClassExpression::ObjectExactCardinality(n, r, filler) => {
+                self.cardinality(out, "ObjectExactCardinality", *n, r, filler)
+            }
+        }
+    }
+
+    fn cardinality(&self, out: &mut String, name: &str, n: u32, r: &Url, filler: &ClassExpression) {
+        out.push_str(name);
+        out.push('(');
+        out.push_str(&n.to_string());
+        out.push(' ');
+        self.iri(out, r);
+        out.push(' ');
+        self.class_expression(out, filler);
+        out.push(')');
+    }
+
+    fn class_expressions(&self, out: &mut String, ces: &[ClassExpression]) {
+        for (i, ce) in ces.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            self.class_expression(out, ce);
+        }
+    }
+
+    fn iris(&self, out: &mut String, iris: &[Url]) {
+        for (i, iri) in iris.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            self.iri(out, iri);
+        }
+    }
+
+    fn axiom(&self, out: &mut String, axiom: &Axiom) {
+        use self::Axiom::*;
+        match axiom {
+            Declaration(kind, iri) => {
+                let kind = match kind {
+                    EntityKind::Class => "Class",
+                    EntityKind::ObjectProperty => "ObjectProperty",
+                    EntityKind::AnnotationProperty => "AnnotationProperty",
+                    EntityKind::NamedIndividual => "NamedIndividual",
+                };
+                out.push_str("Declaration(");
+                out.push_str(kind);
+                out.push('(');
+                self.iri(out, iri);
+                out.push_str("))");
+            }
+            AnnotationAssertion(anns, property, subject, value) => {
+                out.push_str("AnnotationAssertion(");
+                self.annotations(out, anns);
+                self.iri(out, property);
+                out.push(' ');
+                self.iri(out, subject);
+                out.push(' ');
+                self.annotation_value(out, value);
+                out.push(')');
+            }
+            SubAnnotationPropertyOf(anns, sub, sup) => {
+                out.push_str("SubAnnotationPropertyOf(");
+                self.annotations(out, anns);
+                self.iris(out, &[sub.clone(), sup.clone()]);
+                out.push(')');
+            }
+            SubClassOf(anns, sub, sup) => {
+                out.push_str("SubClassOf(");
+                self.annotations(out, anns);
+                self.class_expression(out, sub);
+                out.push(' ');
+                self.class_expression(out, sup);
+                out.push(')');
+            }
+            EquivalentClasses(anns, ces) => {
+                out.push_str("EquivalentClasses(");
+                self.annotations(out, anns);
+                self.class_expressions(out, ces);
+                out.push(')');
+            }
+            DisjointClasses(anns, ces) => {
+                out.push_str("DisjointClasses(");
+                self.annotations(out, anns);
+                self.class_expressions(out, ces);
+                out.push(')');
+            }
+            SubObjectPropertyOf(anns, chain, sup) => {
+                out.push_str("SubObjectPropertyOf(");
+                self.annotations(out, anns);
+                if chain.len() == 1 {
+                    self.iri(out, &chain[0]);
+                } else {
+                    out.push_str("ObjectPropertyChain(");
+                    self.iris(out, chain);
+                    out.push(')');
+                }
+                out.push(' ');
+                self.iri(out, sup);
+                out.push(')');
+            }
+            EquivalentObjectProperties(anns, iris) => {
+                out.push_str("EquivalentObjectProperties(");
+                self.annotations(out, anns);
+                self.iris(out, iris);
+                out.push(')');
+            }
+            DisjointObjectProperties(anns, iris) => {
+                out.push_str("DisjointObjectProperties(");
+                self.annotations(out, anns);
+                self.iris(out, iris);
+                out.push(')');
+            }
+            InverseObjectProperties(anns, r1, r2) => {
+                out.push_str("InverseObjectProperties(");
+                self.annotations(out, anns);
+                self.iris(out, &[r1.clone(), r2.clone()]);
+                out.push(')');
+            }
+            ObjectPropertyDomain(anns, r, ce) => {
+                out.push_str("ObjectPropertyDomain(");
+                self.annotations(out, anns);
+                self.iri(out, r);
+                out.push(' ');
+                self.class_expression(out, ce);
+                out.push(')');
+            }
+            ObjectPropertyRange(anns, r, ce) => {
+                out.push_str("ObjectPropertyRange(");
+                self.annotations(out, anns);
+                self.iri(out, r);
+                out.push(' ');
+                self.class_expression(out, ce);
+                out.push(')');
+            }
+            ObjectPropertyCharacteristic(anns, c, r) => {
+                out.push_str(match c {
+                    Characteristic::Functional => "FunctionalObjectProperty(",
+                    Characteristic::InverseFunctional => "InverseFunctionalObjectProperty(",
+                    Characteristic::Reflexive => "ReflexiveObjectProperty(",
+                    Characteristic::Symmetric => "SymmetricObjectProperty(",
+                    Characteristic::Asymmetric => "AsymmetricObjectProperty(",
+                    Characteristic::Transitive => "TransitiveObjectProperty(",
+                });
+                self.annotations(out, anns);
+                self.iri(out, r);
+                out.push(')');
+            }
+            ClassAssertion(anns, ce, individual) => {
+                out.push_str("ClassAssertion(");
+                self.annotations(out, anns);
+                self.class_expression(out, ce);
+                out.push(' ');
+                self.iri(out, individual);
+                out.push(')');
+            }
+            ObjectPropertyAssertion(anns, r, subject, object) => {
+                out.push_str("ObjectPropertyAssertion(");
+                self.annotations(out, anns);
+                self.iris(out, &[r.clone(), subject.clone(), object.clone()]);
+                out.push(')');
+            }
+        }
+    }
+}
+
+/// Write an OBO document to a `Write` implementor in OWL2 functional syntax.
+pub fn to_writer<W: Write>(writer: W, doc: &OboDoc) -> Result<()> {
+    let mut writer = FunctionalWriter::new(writer, doc.header())?;
+    for entity in doc.entities() {
+        writer.write_frame(entity)?;
+    }
+    writer.finish().map(|_| ())
+}
+
+/// Parse an OWL2 functional syntax document into an OBO document.
+///
+/// Only the axioms that have an OBO equivalent in the OBO to OWL mapping
+/// are translated into OBO clauses: the remaining axioms, including those
+/// with language-tagged literals, are preserved verbatim in an `owl-axioms`
+/// header clause.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// let doc = fastobo::owl::functional::from_str(
+///     "Prefix(obo:=<http://purl.obolibrary.org/obo/>)
+///      Ontology(<http://purl.obolibrary.org/obo/test.owl>
+///      Declaration(Class(obo:TEST_001))
+///      SubClassOf(obo:TEST_001 obo:TEST_002)
+///      )"
+/// ).unwrap();
+///
+/// let term = TermFrame::with_clauses(
+///     ClassIdent::from(PrefixedIdent::new("TEST", "001")),
+///     vec![Line::from(TermClause::IsA(Box::new(PrefixedIdent::new("TEST", "002").into())))],
+/// );
+/// assert_eq!(doc.entities(), &[EntityFrame::from(term)]);
+/// ```
+pub fn from_str<S: AsRef<str>>(src: S) -> Result<OboDoc> {
+    parser::Document::from_str(src.as_ref())
+        .map(ReverseTranslator::translate)
+        .map_err(Error::from)
+}
+
+/// Parse an OWL2 functional syntax document from a `Read` implementor.
+pub fn from_reader<R: Read>(mut reader: R) -> Result<OboDoc> {
+    let mut src = String::new();
+    reader.read_to_string(&mut src)?;
+    from_str(src)
+}
+
+// ---------------------------------------------------------------------------
+
+/// Split an `owl-axioms` value into its prefix declarations and its axioms.
+///
+/// If the value is not a complete ontology document, it is returned
+/// unchanged with no prefix declarations.
+fn split_document(text: &str) -> (Vec<(String, String)>, String) {
+    let start = match text.find("Ontology(") {
+        Some(i) => i,
+        None => return (Vec::new(), text.trim().to_string()),
+    };
+
+    // extract the prefix declarations before the ontology
+    let mut prefixes = Vec::new();
+    let mut rest = &text[..start];
+    while let Some(i) = rest.find("Prefix(") {
+        rest = &rest[i + 7..];
+        if let (Some(eq), Some(lt), Some(gt)) = (rest.find(":="), rest.find('<'), rest.find('>')) {
+            if eq < lt && lt < gt {
+                let prefix = rest[..eq].trim().to_string();
+                let url = rest[lt + 1..gt].to_string();
+                prefixes.push((prefix, url));
+                rest = &rest[gt..];
+            }
+        }
+    }
+
+    // skip the ontology and version IRIs, then remove the final parenthesis
+    let mut body = text[start + 9..].trim_start();
+    while body.starts_with('<') {
+        match body.find('>') {
+            Some(i) => body = body[i + 1..].trim_start(),
+            None => break,
+        }
+    }
+    let body = body.trim_end();
+    let body = body.strip_suffix(')').unwrap_or(body).trim_end();
+
+    (prefixes, body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::semantics::Orderable;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+    use textwrap_macros::dedent;
+
+    fn to_string(doc: &str) -> String {
+        let doc = OboDoc::from_str(doc).unwrap();
+        let mut out = Vec::new();
+        to_writer(&mut out, &doc).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn body(text: &str) -> Vec<&str> {
+        text.lines()
+            .skip_while(|l| !l.starts_with("Ontology("))
+            .filter(|l| !l.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn header() {
+        let text = to_string(dedent!(
+            r#"
+            format-version: 1.4
+            data-version: releases/2019-01-01
+            subsetdef: goslim "GO slim"
+            import: http://purl.obolibrary.org/obo/bfo.owl
+            ontology: test
+            "#
+        ));
+        self::assert_eq!(
+            body(&text),
+            vec![
+                "Ontology(<http://purl.obolibrary.org/obo/test.owl> <http://purl.obolibrary.org/obo/test/releases/2019-01-01/test.owl>",
+                "Import(<http://purl.obolibrary.org/obo/bfo.owl>)",
+                "Annotation(oboInOwl:hasOBOFormatVersion \"1.4\")",
+                "Declaration(AnnotationProperty(<http://purl.obolibrary.org/obo/test#goslim>))",
+                "SubAnnotationPropertyOf(<http://purl.obolibrary.org/obo/test#goslim> oboInOwl:SubsetProperty)",
+                "AnnotationAssertion(rdfs:comment <http://purl.obolibrary.org/obo/test#goslim> \"GO slim\")",
+                ")",
+            ]
+        );
+    }
+
+    #[test]
+    fn term() {
+        let text = to_string(dedent!(
+            r#"
+            ontology: test
+
+            [Term]
+            id: TST:001
+            name: test "term"
+            def: "A term." [PMID:1234]
+            is_a: TST:002 {source="PMID:5678"}
+            intersection_of: TST:002
+            intersection_of: part_of TST:003
+            relationship: part_of TST:003
+            is_obsolete: false
+            "#
+        ));
+        self::assert_eq!(
+            body(&text)[1..],
+            [
+                "Declaration(Class(obo:TST_001))",
+                "AnnotationAssertion(oboInOwl:id obo:TST_001 \"TST:001\")",
+                "AnnotationAssertion(rdfs:label obo:TST_001 \"test \\\"term\\\"\")",
+                "AnnotationAssertion(Annotation(oboInOwl:hasDbXref \"PMID:1234\") obo:IAO_0000115 obo:TST_001 \"A term.\")",
+                "SubClassOf(Annotation(oboInOwl:source \"PMID:5678\") obo:TST_001 obo:TST_002)",
+                "SubClassOf(obo:TST_001 ObjectSomeValuesFrom(<http://purl.obolibrary.org/obo/test#part_of> obo:TST_003))",
+                "EquivalentClasses(obo:TST_001 ObjectIntersectionOf(obo:TST_002 ObjectSomeValuesFrom(<http://purl.obolibrary.org/obo/test#part_of> obo:TST_003)))",
+                ")",
+            ]
+        );
+    }
+
+    #[test]
+    fn relationship_qualifiers() {
+        let text = to_string(dedent!(
+            r#"
+            ontology: test
+
+            [Term]
+            id: TST:001
+            intersection_of: TST:002 {source="PMID:1"}
+            intersection_of: part_of TST:003 {source="PMID:1"}
+            relationship: part_of TST:003 {gci_relation="part_of", gci_filler="TST:004", source="PMID:2"}
+            relationship: has_part TST:005 {cardinality="2"}
+            relationship: has_part TST:006 {minCardinality="1", maxCardinality="3"}
+            relationship: has_part TST:007 {maxCardinality="many"}
+            "#
+        ));
+        self::assert_eq!(
+            body(&text)[3..],
+            [
+                "SubClassOf(Annotation(oboInOwl:source \"PMID:2\") ObjectIntersectionOf(obo:TST_001 ObjectSomeValuesFrom(<http://purl.obolibrary.org/obo/test#part_of> obo:TST_004)) ObjectSomeValuesFrom(<http://purl.obolibrary.org/obo/test#part_of> obo:TST_003))",
+                "SubClassOf(obo:TST_001 ObjectExactCardinality(2 <http://purl.obolibrary.org/obo/test#has_part> obo:TST_005))",
+                "SubClassOf(obo:TST_001 ObjectIntersectionOf(ObjectMinCardinality(1 <http://purl.obolibrary.org/obo/test#has_part> obo:TST_006) ObjectMaxCardinality(3 <http://purl.obolibrary.org/obo/test#has_part> obo:TST_006)))",
+                "SubClassOf(Annotation(oboInOwl:maxCardinality \"many\") obo:TST_001 ObjectSomeValuesFrom(<http://purl.obolibrary.org/obo/test#has_part> obo:TST_007))",
+                "EquivalentClasses(Annotation(oboInOwl:source \"PMID:1\") obo:TST_001 ObjectIntersectionOf(obo:TST_002 ObjectSomeValuesFrom(<http://purl.obolibrary.org/obo/test#part_of> obo:TST_003)))",
+                ")",
+            ]
+        );
+    }
+
+    #[test]
+    fn typedef() {
+        let text = to_string(dedent!(
+            r#"
+            [Typedef]
+            id: BFO:0000050
+            is_transitive: true
+            inverse_of: BFO:0000051
+            transitive_over: BFO:0000050
+            holds_over_chain: BFO:0000050 BFO:0000051
+            "#
+        ));
+        self::assert_eq!(
+            body(&text)[1..],
+            [
+                "Declaration(ObjectProperty(obo:BFO_0000050))",
+                "AnnotationAssertion(oboInOwl:id obo:BFO_0000050 \"BFO:0000050\")",
+                "TransitiveObjectProperty(obo:BFO_0000050)",
+                "InverseObjectProperties(obo:BFO_0000050 obo:BFO_0000051)",
+                "SubObjectPropertyOf(ObjectPropertyChain(obo:BFO_0000050 obo:BFO_0000050) obo:BFO_0000050)",
+                "SubObjectPropertyOf(ObjectPropertyChain(obo:BFO_0000050 obo:BFO_0000051) obo:BFO_0000050)",
+                ")",
+            ]
+        );
+    }
+
+    #[test]
+    fn owl_axioms() {
+        let text = to_string(dedent!(
+            r#"
+            owl-axioms: Prefix(ex:=<http://example.com/>)\nOntology(<http://example.com/x.owl>\nSubClassOf(ex:A ex:B)\n)
+            "#
+        ));
+        assert!(text.contains("Prefix(ex:=<http://example.com/>)"));
+        self::assert_eq!(body(&text), ["Ontology(", "SubClassOf(ex:A ex:B)", ")"]);
+    }
+
+    #[test]
+    fn owl_axioms_prefix_override() {
+        let text = to_string(dedent!(
+            r#"
+            owl-axioms: Prefix(obo:=<http://example.com/obo/>)\nOntology(<http://example.com/x.owl>\nSubClassOf(obo:A obo:B)\n)
+
+            [Term]
+            id: TST:001
+            "#
+        ));
+        assert!(text.contains("Prefix(obo:=<http://example.com/obo/>)"));
+        assert!(!text.contains("Prefix(obo:=<http://purl.obolibrary.org/obo/>)"));
+        assert!(text.contains("Declaration(Class(<http://purl.obolibrary.org/obo/TST_001>))"));
+        assert!(text.contains("SubClassOf(obo:A obo:B)"));
+    }
+
+    #[test]
+    fn longest_prefix() {
+        let text = to_string(dedent!(
+            r#"
+            owl-axioms: Prefix(tst:=<http://purl.obolibrary.org/obo/TST_>)\nOntology(<http://example.com/x.owl>\n)
+
+            [Term]
+            id: TST:001
+            is_a: GO:0000001
+            "#
+        ));
+        assert!(text.contains("Declaration(Class(tst:001))"));
+        assert!(text.contains("SubClassOf(tst:001 obo:GO_0000001)"));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let obo = dedent!(
+            r#"
+            format-version: 1.4
+            data-version: releases/2021-01-01
+            subsetdef: goslim "GO slim"
+            synonymtypedef: systematic "Systematic synonym" EXACT
+            ontology: tst
+
+            [Term]
+            id: TST:001
+            name: test term
+            namespace: test
+            def: "A test term." [PMID:1234, ISBN:5678]
+            comment: for testing only
+            subset: goslim
+            synonym: "term for testing" EXACT systematic [PMID:1234]
+            xref: Wikipedia:Test "test page"
+            is_a: TST:002 {source="PMID:5678"}
+            intersection_of: TST:002
+            intersection_of: part_of TST:003
+            relationship: part_of TST:003
+            creation_date: 2021-01-01T00:00:00Z
+
+            [Term]
+            id: TST:002
+            union_of: TST:003
+            union_of: TST:004
+            is_obsolete: true
+            replaced_by: TST:003
+
+            [Typedef]
+            id: part_of
+            name: part of
+            domain: TST:002
+            is_transitive: true
+            inverse_of: has_part
+            transitive_over: has_part
+            holds_over_chain: has_part has_part
+            equivalent_to_chain: has_part has_part
+            expand_expression_to: "BFO_0000051 some ?Y" []
+
+            [Instance]
+            id: TST:005
+            instance_of: TST:001
+            "#
+        );
+
+        let mut doc = OboDoc::from_str(obo).unwrap();
+        let mut out = Vec::new();
+        to_writer(&mut out, &doc).unwrap();
+
+        let mut actual = from_reader(out.as_slice()).unwrap();
+        actual.sort();
+        doc.sort();
+        self::assert_eq!(actual.to_string(), doc.to_string());
+    }
+
+    #[test]
+    fn from_str_owl_axioms() {
+        let doc = super::from_str(dedent!(
+            r#"
+            Prefix(obo:=<http://purl.obolibrary.org/obo/>)
+            Ontology(<http://purl.obolibrary.org/obo/tst.owl>
+            Declaration(Class(obo:TST_001))
+            SubClassOf(obo:TST_001 ObjectAllValuesFrom(obo:BFO_0000050 obo:TST_002))
+            )
+            "#
+        ))
+        .unwrap();
+        self::assert_eq!(
+            doc.header()[1],
+            HeaderClause::OwlAxioms(Box::new(UnquotedString::new(
+                "Prefix(obo:=<http://purl.obolibrary.org/obo/>)\nOntology(\nSubClassOf(obo:TST_001 ObjectAllValuesFrom(obo:BFO_0000050 obo:TST_002))\n)"
+            )))
+        );
+        self::assert_eq!(doc.entities().len(), 1);
+    }
+    #[test]
+    fn from_str_language_tag() {
+        let doc = super::from_str(dedent!(
+            r#"
+            Prefix(obo:=<http://purl.obolibrary.org/obo/>)
+            Prefix(rdfs:=<http://www.w3.org/2000/01/rdf-schema#>)
+            Ontology(<http://purl.obolibrary.org/obo/tst.owl>
+            Annotation(rdfs:comment "un test"@fr)
+            Declaration(Class(obo:TST_001))
+            AnnotationAssertion(rdfs:label obo:TST_001 "test"@en)
+            )
+            "#
+        ))
+        .unwrap();
+        self::assert_eq!(
+            doc.header()[1],
+            HeaderClause::OwlAxioms(Box::new(UnquotedString::new(
+                "Prefix(obo:=<http://purl.obolibrary.org/obo/>)\nPrefix(rdfs:=<http://www.w3.org/2000/01/rdf-schema#>)\nOntology(\nAnnotation(rdfs:comment \"un test\"@fr)\nAnnotationAssertion(rdfs:label obo:TST_001 \"test\"@en)\n)"
+            )))
+        );
+        self::assert_eq!(doc.entities().len(), 1);
+    }
+}
@@ -0,0 +1,535 @@
+//! A parser for the OWL2 functional syntax.
+//!
+//! The parser reads any document in functional syntax into a generic tree
+//! of expressions, and then converts the axioms supported by the
+//! [`owl`](../../index.html) model. Axioms that cannot be converted are
+//! kept as raw text so that they can be preserved verbatim.
+
+use std::str::FromStr;
+
+use crate::ast::Url;
+use crate::error::OwlError;
+
+use super::super::model::*;
+
+/// A document in OWL2 functional syntax.
+#[derive(Clone, Debug, Default)]
+pub struct Document {
+    /// The prefix declarations of the document, in declaration order.
+    pub prefixes: Vec<(String, String)>,
+    pub iri: Option<Url>,
+    pub version_iri: Option<Url>,
+    pub imports: Vec<Url>,
+    pub annotations: Vec<Annotation>,
+    /// The axioms of the document, with their source text.
+    pub axioms: Vec<(Option<Axiom>, String)>,
+}
+
+/// A generic expression of the functional syntax.
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Call(String, Vec<Expr>),
+    Iri(Url),
+    Literal(Literal),
+    /// A literal with a language tag, which the OWL model cannot represent,
+    /// so that axioms using it are kept verbatim.
+    LangLiteral,
+    Blank(String),
+}
+
+/// A lexical token of the functional syntax.
+#[derive(Clone, Debug, PartialEq)]
+enum Token<'a> {
+    Open,
+    Close,
+    Iri(&'a str),
+    Name(&'a str),
+    Literal(String),
+    DatatypeMarker,
+    Language(&'a str),
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    prefixes: Vec<(String, String)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            prefixes: Vec::new(),
+        }
+    }
+
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let before = &self.src[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+        (line, column)
+    }
+
+    fn unexpected(&self, offset: usize, token: &str) -> OwlError {
+        let (line, column) = self.position(offset);
+        OwlError::UnexpectedToken {
+            token: token.to_string(),
+            line,
+            column,
+        }
+    }
+
+    /// Skip whitespaces and comments.
+    fn skip(&mut self) {
+        loop {
+            let rest = &self.src[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with('#') {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Read the next token, and return it with its starting offset.
+    fn next(&mut self) -> Result<(usize, Token<'a>), OwlError> {
+        self.skip();
+        let start = self.pos;
+        let rest = &self.src[start..];
+        let c = rest.chars().next().ok_or(OwlError::UnexpectedEof)?;
+        let token = match c {
+            '(' => {
+                self.pos += 1;
+                Token::Open
+            }
+            ')' => {
+                self.pos += 1;
+                Token::Close
+            }
+            '<' => {
+                let end = rest.find('>').ok_or(OwlError::UnexpectedEof)?;
+                self.pos += end + 1;
+                Token::Iri(&rest[1..end])
+            }
+            '"' => {
+                let mut value = String::new();
+                let mut chars = rest.char_indices().skip(1);
+                loop {
+                    match chars.next() {
+                        Some((i, '"')) => {
+                            self.pos += i + 1;
+                            break;
+                        }
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) => value.push(c),
+                            None => return Err(OwlError::UnexpectedEof),
+                        },
+                        Some((_, c)) => value.push(c),
+                        None => return Err(OwlError::UnexpectedEof),
+                    }
+                }
+                Token::Literal(value)
+            }
+            '^' if rest.starts_with("^^") => {
+                self.pos += 2;
+                Token::DatatypeMarker
+            }
+            '@' => {
+                let end = rest[1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                    .map(|i| i + 1)
+                    .unwrap_or(rest.len());
+                self.pos += end;
+                Token::Language(&rest[1..end])
+            }
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || "()<>\"^@".contains(c))
+                    .unwrap_or(rest.len());
+                self.pos += end;
+                Token::Name(&rest[..end])
+            }
+        };
+        Ok((start, token))
+    }
+
+    /// Read the next token without consuming it.
+    fn peek(&mut self) -> Result<Token<'a>, OwlError> {
+        let pos = self.pos;
+        let token = self.next();
+        self.pos = pos;
+        token.map(|(_, t)| t)
+    }
+
+    fn expect_open(&mut self) -> Result<(), OwlError> {
+        match self.next()? {
+            (_, Token::Open) => Ok(()),
+            (i, t) => Err(self.unexpected(i, &format!("{:?}", t))),
+        }
+    }
+
+    fn expect_close(&mut self) -> Result<(), OwlError> {
+        match self.next()? {
+            (_, Token::Close) => Ok(()),
+            (i, t) => Err(self.unexpected(i, &format!("{:?}", t))),
+        }
+    }
+
+    /// Expand an abbreviated IRI using the declared prefixes.
+    fn expand(&self, offset: usize, name: &str) -> Result<Url, OwlError> {
+        let i = name
+            .find(':')
+            .ok_or_else(|| self.unexpected(offset, name))?;
+        let (prefix, local) = (&name[..i], &name[i + 1..]);
+        match self.prefixes.iter().find(|(p, _)| p == prefix) {
+            Some((_, url)) => Ok(Url::new_unchecked(format!("{}{}", url, local))),
+            None => {
+                let (line, column) = self.position(offset);
+                Err(OwlError::UndeclaredPrefix {
+                    prefix: prefix.to_string(),
+                    line,
+                    column,
+                })
+            }
+        }
+    }
+
+    fn iri(&self, offset: usize, token: Token) -> Result<Url, OwlError> {
+        match token {
+            Token::Iri(iri) => Ok(Url::new_unchecked(iri)),
+            Token::Name(name) => self.expand(offset, name),
+            other => Err(self.unexpected(offset, &format!("{:?}", other))),
+        }
+    }
+
+    /// Parse a single expression.
+    fn expr(&mut self) -> Result<Expr, OwlError> {
+        let (offset, token) = self.next()?;
+        match token {
+            Token::Name(name) if self.peek()? == Token::Open => {
+                self.expect_open()?;
+                let mut args = Vec::new();
+                while self.peek()? != Token::Close {
+                    args.push(self.expr()?);
+                }
+                self.expect_close()?;
+                Ok(Expr::Call(name.to_string(), args))
+            }
+            Token::Name(name) if name.starts_with("_:") => Ok(Expr::Blank(name.to_string())),
+            Token::Literal(value) => match self.peek() {
+                Ok(Token::DatatypeMarker) => {
+                    self.next()?;
+                    let (offset, token) = self.next()?;
+                    let datatype = self.iri(offset, token)?;
+                    Ok(Expr::Literal(Literal::with_datatype(value, datatype)))
+                }
+                Ok(Token::Language(_)) => {
+                    self.next()?;
+                    Ok(Expr::LangLiteral)
+                }
+                _ => Ok(Expr::Literal(Literal::new(value))),
+            },
+            token => self.iri(offset, token).map(Expr::Iri),
+        }
+    }
+
+    /// Parse a complete document.
+    fn document(mut self) -> Result<Document, OwlError> {
+        let mut doc = Document::default();
+
+        // read the prefix declarations
+        loop {
+            match self.next()? {
+                (_, Token::Name("Prefix")) => {
+                    self.expect_open()?;
+                    let (offset, token) = self.next()?;
+                    let prefix = match token {
+                        Token::Name(name) if name.ends_with(":=") => &name[..name.len() - 2],
+                        other => return Err(self.unexpected(offset, &format!("{:?}", other))),
+                    };
+                    let url = match self.next()? {
+                        (_, Token::Iri(url)) => url,
+                        (i, other) => return Err(self.unexpected(i, &format!("{:?}", other))),
+                    };
+                    self.expect_close()?;
+                    self.prefixes.push((prefix.to_string(), url.to_string()));
+                }
+                (_, Token::Name("Ontology")) => break,
+                (i, other) => return Err(self.unexpected(i, &format!("{:?}", other))),
+            }
+        }
+
+        // read the ontology IRIs
+        self.expect_open()?;
+        if let Token::Iri(_) | Token::Name(_) = self.peek()? {
+            let pos = self.pos;
+            let (offset, token) = self.next()?;
+            if self.peek()? == Token::Open {
+                self.pos = pos;
+            } else {
+                doc.iri = Some(self.iri(offset, token)?);
+                if let Token::Iri(_) | Token::Name(_) = self.peek()? {
+                    let pos = self.pos;
+                    let (offset, token) = self.next()?;
+                    if self.peek()? == Token::Open {
+                        self.pos = pos;
+                    } else {
+                        doc.version_iri = Some(self.iri(offset, token)?);
+                    }
+                }
+            }
+        }
+
+        // read the imports, annotations and axioms
+        loop {
+            self.skip();
+            let start = self.pos;
+            if self.peek()? == Token::Close {
+                self.next()?;
+                break;
+            }
+            let expr = self.expr()?;
+            let text = self.src[start..self.pos].to_string();
+            match expr {
+                Expr::Call(ref name, ref args) if name == "Import" && args.len() == 1 => {
+                    if let Expr::Iri(ref iri) = args[0] {
+                        doc.imports.push(iri.clone());
+                    }
+                }
+                Expr::Call(ref name, _) if name == "Annotation" => match to_annotation(&expr) {
+                    Some(annotation) => doc.annotations.push(annotation),
+                    None => doc.axioms.push((None, text)),
+                },
+                expr => doc.axioms.push((to_axiom(&expr), text)),
+            }
+        }
+
+        doc.prefixes = self.prefixes;
+        Ok(doc)
+    }
+}
+
+impl FromStr for Document {
+    type Err = OwlError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(s).document()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Split the arguments of an axiom into its annotations and its operands.
+fn split_annotations(args: &[Expr]) -> Option<(Vec<Annotation>, &[Expr])> {
+    let n = args
+        .iter()
+        .take_while(|e| matches!(e, Expr::Call(name, _) if name == "Annotation"))
+        .count();
+    let annotations = args[..n]
+        .iter()
+        .map(to_annotation)
+        .collect::<Option<Vec<_>>>()?;
+    Some((annotations, &args[n..]))
+}
+
+fn to_iri(expr: &Expr) -> Option<Url> {
+    match expr {
+        Expr::Iri(iri) => Some(iri.clone()),
+        _ => None,
+    }
+}
+
+fn to_iris(exprs: &[Expr]) -> Option<Vec<Url>> {
+    exprs.iter().map(to_iri).collect()
+}
+
+/// Convert an annotation, rejecting annotations with nested annotations.
+fn to_annotation(expr: &Expr) -> Option<Annotation> {
+    match expr {
+        Expr::Call(name, args) if name == "Annotation" && args.len() == 2 => {
+            let value = match &args[1] {
+                Expr::Iri(iri) => AnnotationValue::Iri(iri.clone()),
+                Expr::Literal(literal) => AnnotationValue::Literal(literal.clone()),
+                _ => return None,
+            };
+            Some(Annotation::new(to_iri(&args[0])?, value))
+        }
+        _ => None,
+    }
+}
+
+fn to_class_expression(expr: &Expr) -> Option<ClassExpression> {
+    match expr {
+        Expr::Iri(iri) => Some(ClassExpression::Class(iri.clone())),
+        Expr::Call(name, args) => match name.as_str() {
+            "ObjectSomeValuesFrom" if args.len() == 2 => {
+                let filler = to_class_expression(&args[1])?;
+                Some(ClassExpression::ObjectSomeValuesFrom(
+                    to_iri(&args[0])?,
+                    Box::new(filler),
+                ))
+            }
+            "ObjectIntersectionOf" => {
+                to_class_expressions(args).map(ClassExpression::ObjectIntersectionOf)
+            }
+            "ObjectUnionOf" => to_class_expressions(args).map(ClassExpression::ObjectUnionOf),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn to_class_expressions(exprs: &[Expr]) -> Option<Vec<ClassExpression>> {
+    exprs.iter().map(to_class_expression).collect()
+}
+
+/// Convert an axiom, if it is supported by the OWL model.
+fn to_axiom(expr: &Expr) -> Option<Axiom> {
+    let (name, args) = match expr {
+        Expr::Call(name, args) => (name.as_str(), args),
+        _ => return None,
+    };
+
+    if name == "Declaration" {
+        return match args.as_slice() {
+            [Expr::Call(kind, entity)] if entity.len() == 1 => {
+                let kind = match kind.as_str() {
+                    "Class" => EntityKind::Class,
+                    "ObjectProperty" => EntityKind::ObjectProperty,
+                    "AnnotationProperty" => EntityKind::AnnotationProperty,
+                    "NamedIndividual" => EntityKind::NamedIndividual,
+                    _ => return None,
+                };
+                Some(Axiom::Declaration(kind, to_iri(&entity[0])?))
+            }
+            _ => None,
+        };
+    }
+
+    let (anns, args) = split_annotations(args)?;
+    let axiom = match (name, args) {
+        ("AnnotationAssertion", [p, s, v]) => {
+            let value = match v {
+                Expr::Iri(iri) => AnnotationValue::Iri(iri.clone()),
+                Expr::Literal(literal) => AnnotationValue::Literal(literal.clone()),
+                _ => return None,
+            };
+            Axiom::AnnotationAssertion(anns, to_iri(p)?, to_iri(s)?, value)
+        }
+        ("SubAnnotationPropertyOf", [sub, sup]) => {
+            Axiom::SubAnnotationPropertyOf(anns, to_iri(sub)?, to_iri(sup)?)
+        }
+        ("SubClassOf", [sub, sup]) => {
+            Axiom::SubClassOf(anns, to_class_expression(sub)?, to_class_expression(sup)?)
+        }
+        ("EquivalentClasses", ces) => Axiom::EquivalentClasses(anns, to_class_expressions(ces)?),
+        ("DisjointClasses", ces) => Axiom::DisjointClasses(anns, to_class_expressions(ces)?),
+        ("SubObjectPropertyOf", [Expr::Call(chain, props), sup])
+            if chain == "ObjectPropertyChain" =>
+        {
+            Axiom::SubObjectPropertyOf(anns, to_iris(props)?, to_iri(sup)?)
+        }
+        ("SubObjectPropertyOf", [sub, sup]) => {
+            Axiom::SubObjectPropertyOf(anns, vec![to_iri(sub)?], to_iri(sup)?)
+        }
+        ("EquivalentObjectProperties", props) => {
+            Axiom::EquivalentObjectProperties(anns, to_iris(props)?)
+        }
+        ("DisjointObjectProperties", props) => {
+            Axiom::DisjointObjectProperties(anns, to_iris(props)?)
+        }
+        ("InverseObjectProperties", [r1, r2]) => {
+            Axiom::InverseObjectProperties(anns, to_iri(r1)?, to_iri(r2)?)
+        }
+        ("ObjectPropertyDomain", [r, ce]) => {
+            Axiom::ObjectPropertyDomain(anns, to_iri(r)?, to_class_expression(ce)?)
+        }
+        ("ObjectPropertyRange", [r, ce]) => {
+            Axiom::ObjectPropertyRange(anns, to_iri(r)?, to_class_expression(ce)?)
+        }
+        ("ClassAssertion", [ce, i]) => {
+            Axiom::ClassAssertion(anns, to_class_expression(ce)?, to_iri(i)?)
+        }
+        ("ObjectPropertyAssertion", [r, s, o]) => {
+            Axiom::ObjectPropertyAssertion(anns, to_iri(r)?, to_iri(s)?, to_iri(o)?)
+        }
+        (_, [r]) => {
+            let characteristic = match name {
+                "FunctionalObjectProperty" => Characteristic::Functional,
+                "InverseFunctionalObjectProperty" => Characteristic::InverseFunctional,
+                "ReflexiveObjectProperty" => Characteristic::Reflexive,
+                "SymmetricObjectProperty" => Characteristic::Symmetric,
+                "AsymmetricObjectProperty" => Characteristic::Asymmetric,
+                "TransitiveObjectProperty" => Characteristic::Transitive,
+                _ => return None,
+            };
+            Axiom::ObjectPropertyCharacteristic(anns, characteristic, to_iri(r)?)
+        }
+        _ => return None,
+    };
+
+    Some(axiom)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn document() {
+        let doc = Document::from_str(
+            r#"
+            Prefix(obo:=<http://purl.obolibrary.org/obo/>)
+            Prefix(rdfs:=<http://www.w3.org/2000/01/rdf-schema#>)
+            # a comment
+            Ontology(<http://purl.obolibrary.org/obo/test.owl>
+            Import(<http://purl.obolibrary.org/obo/bfo.owl>)
+            Annotation(rdfs:comment "a \"test\" ontology")
+            Declaration(Class(obo:TST_001))
+            SubClassOf(obo:TST_001 ObjectSomeValuesFrom(obo:BFO_0000050 obo:TST_002))
+            SubClassOf(obo:TST_001 ObjectAllValuesFrom(obo:BFO_0000050 obo:TST_002))
+            )
+            "#,
+        )
+        .unwrap();
+
+        self::assert_eq!(doc.prefixes.len(), 2);
+        self::assert_eq!(
+            doc.iri,
+            Some(Url::new_unchecked(
+                "http://purl.obolibrary.org/obo/test.owl"
+            ))
+        );
+        self::assert_eq!(doc.imports.len(), 1);
+        self::assert_eq!(
+            doc.annotations[0].value,
+            AnnotationValue::Literal(Literal::new("a \"test\" ontology"))
+        );
+        self::assert_eq!(doc.axioms.len(), 3);
+        assert!(doc.axioms[1].0.is_some());
+        self::assert_eq!(doc.axioms[2].0, None);
+        self::assert_eq!(
+            doc.axioms[2].1,
+            "SubClassOf(obo:TST_001 ObjectAllValuesFrom(obo:BFO_0000050 obo:TST_002))"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = Document::from_str("Ontology(\nDeclaration(Class(ex:A)))").unwrap_err();
+        self::assert_eq!(
+            err,
+            OwlError::UndeclaredPrefix {
+                prefix: String::from("ex"),
+                line: 2,
+                column: 19,
+            }
+        );
+        let err = Document::from_str("Ontology(").unwrap_err();
+        self::assert_eq!(err, OwlError::UnexpectedEof);
+    }
+}
@@ -0,0 +1,21 @@
+//! Translation of OBO documents to OWL2, following the OBO 1.4 semantics.
+//!
+//! The [`Translator`](struct.Translator.html) converts OBO frames into
+//! a minimal OWL2 axiom model, which can then be serialized with one of
+//! the writers of this module.
+//!
+//! # See also
+//! - The [Mapping to OWL](http://owlcollab.github.io/oboformat/doc/obo-syntax.html#5)
+//!   section of the OBO 1.4 syntax and semantics specification.
+
+mod model;
+mod prefix;
+mod reverse;
+mod translate;
+mod vocab;
+
+pub mod functional;
+pub mod rdf;
+
+pub use self::model::*;
+pub use self::translate::Translator;
@@ -0,0 +1,146 @@
+use crate::ast::Url;
+
+/// A literal value, with an optional datatype.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Literal {
+    value: String,
+    datatype: Option<Url>,
+}
+
+impl Literal {
+    /// Create a new plain literal (an `xsd:string` literal).
+    pub fn new<S>(value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            value: value.into(),
+            datatype: None,
+        }
+    }
+
+    /// Create a new literal with the given datatype.
+    pub fn with_datatype<S>(value: S, datatype: Url) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            value: value.into(),
+            datatype: Some(datatype),
+        }
+    }
+
+    /// Get the lexical value of the literal.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Get the datatype of the literal, if it is not a plain literal.
+    pub fn datatype(&self) -> Option<&Url> {
+        self.datatype.as_ref()
+    }
+}
+
+/// The value of an annotation, either an IRI or a literal.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AnnotationValue {
+    Iri(Url),
+    Literal(Literal),
+}
+
+impl From<Url> for AnnotationValue {
+    fn from(iri: Url) -> Self {
+        AnnotationValue::Iri(iri)
+    }
+}
+
+impl From<Literal> for AnnotationValue {
+    fn from(literal: Literal) -> Self {
+        AnnotationValue::Literal(literal)
+    }
+}
+
+/// An annotation, used on an ontology or on an axiom.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Annotation {
+    pub property: Url,
+    pub value: AnnotationValue,
+}
+
+impl Annotation {
+    /// Create a new annotation with the given property and value.
+    pub fn new<V>(property: Url, value: V) -> Self
+    where
+        V: Into<AnnotationValue>,
+    {
+        Self {
+            property,
+            value: value.into(),
+        }
+    }
+}
+
+/// The kind of entity being declared.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EntityKind {
+    Class,
+    ObjectProperty,
+    AnnotationProperty,
+    NamedIndividual,
+}
+
+/// A class expression.
+///
+/// Only the class expressions used in the OBO to OWL mapping are supported.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ClassExpression {
+    Class(Url),
+    ObjectSomeValuesFrom(Url, Box<ClassExpression>),
+    ObjectIntersectionOf(Vec<ClassExpression>),
+    ObjectUnionOf(Vec<ClassExpression>),
+    ObjectMinCardinality(u32, Url, Box<ClassExpression>),
+    ObjectMaxCardinality(u32, Url, Box<ClassExpression>),
+    ObjectExactCardinality(u32, Url, Box<ClassExpression>),
+}
+
+impl From<Url> for ClassExpression {
+    fn from(iri: Url) -> Self {
+        ClassExpression::Class(iri)
+    }
+}
+
+/// A characteristic of an object property.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Characteristic {
+    Functional,
+    InverseFunctional,
+    Reflexive,
+    Symmetric,
+    Asymmetric,
+    Transitive,
+}
+
+/// An OWL2 axiom.
+///
+/// Only the axioms used in the OBO to OWL mapping are supported. Every
+/// axiom variant stores the annotations of the axiom as its first field.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Axiom {
+    Declaration(EntityKind, Url),
+    AnnotationAssertion(Vec<Annotation>, Url, Url, AnnotationValue),
+    SubAnnotationPropertyOf(Vec<Annotation>, Url, Url),
+    SubClassOf(Vec<Annotation>, ClassExpression, ClassExpression),
+    EquivalentClasses(Vec<Annotation>, Vec<ClassExpression>),
+    DisjointClasses(Vec<Annotation>, Vec<ClassExpression>),
+    /// A sub-property axiom, using a property chain if the first field
+    /// contains more than one property.
+    SubObjectPropertyOf(Vec<Annotation>, Vec<Url>, Url),
+    EquivalentObjectProperties(Vec<Annotation>, Vec<Url>),
+    DisjointObjectProperties(Vec<Annotation>, Vec<Url>),
+    InverseObjectProperties(Vec<Annotation>, Url, Url),
+    ObjectPropertyDomain(Vec<Annotation>, Url, ClassExpression),
+    ObjectPropertyRange(Vec<Annotation>, Url, ClassExpression),
+    ObjectPropertyCharacteristic(Vec<Annotation>, Characteristic, Url),
+    ClassAssertion(Vec<Annotation>, ClassExpression, Url),
+    ObjectPropertyAssertion(Vec<Annotation>, Url, Url, Url),
+}
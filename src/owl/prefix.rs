@@ -0,0 +1,112 @@
+//! Abbreviation of IRIs with namespace prefixes, shared by the OWL writers.
+
+use crate::ast::Url;
+
+/// Abbreviate an IRI with the longest matching namespace of `prefixes`.
+///
+/// Returns the prefix and the local name of the abbreviated IRI, or `None`
+/// if no namespace allows a valid prefixed name.
+pub fn compact<'a>(prefixes: &'a [(String, String)], iri: &'a Url) -> Option<(&'a str, &'a str)> {
+    let s = iri.as_str();
+    let mut best: Option<(&str, &str)> = None;
+    for (prefix, url) in prefixes.iter() {
+        if let Some(local) = s.strip_prefix(url.as_str()) {
+            let longer = match best {
+                Some((_, l)) => local.len() < l.len(),
+                None => true,
+            };
+            if longer && is_simple_local(local) {
+                best = Some((prefix, local));
+            }
+        }
+    }
+    best
+}
+
+/// Check if a local name can be written as part of an abbreviated IRI.
+pub fn is_simple_local(local: &str) -> bool {
+    let mut chars = local.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphanumeric() || c == '_' => (),
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Check if a prefix is a valid Turtle `PN_PREFIX`.
+///
+/// # See also
+/// - The [Turtle grammar](https://www.w3.org/TR/turtle/#grammar-production-PN_PREFIX).
+pub fn is_prefix_name(prefix: &str) -> bool {
+    let mut chars = prefix.chars();
+    match chars.next() {
+        Some(c) if is_pn_chars_base(c) => (),
+        _ => return false,
+    }
+    !prefix.ends_with('.') && chars.all(|c| c == '.' || is_pn_chars(c))
+}
+
+/// Check if a character matches the Turtle `PN_CHARS_BASE` production.
+fn is_pn_chars_base(c: char) -> bool {
+    matches!(c,
+        'A'..='Z'
+        | 'a'..='z'
+        | '\u{00C0}'..='\u{00D6}'
+        | '\u{00D8}'..='\u{00F6}'
+        | '\u{00F8}'..='\u{02FF}'
+        | '\u{0370}'..='\u{037D}'
+        | '\u{037F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// Check if a character matches the Turtle `PN_CHARS` production.
+fn is_pn_chars(c: char) -> bool {
+    is_pn_chars_base(c)
+        || matches!(c,
+            '_'
+            | '-'
+            | '0'..='9'
+            | '\u{00B7}'
+            | '\u{0300}'..='\u{036F}'
+            | '\u{203F}'..='\u{2040}'
+        )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn prefix_name() {
+        assert!(is_prefix_name("GO"));
+        assert!(is_prefix_name("go.plus-1_x"));
+        assert!(is_prefix_name("é"));
+        assert!(!is_prefix_name(""));
+        assert!(!is_prefix_name("1GO"));
+        assert!(!is_prefix_name("_GO"));
+        assert!(!is_prefix_name("-GO"));
+        assert!(!is_prefix_name("GO."));
+        assert!(!is_prefix_name("G O"));
+    }
+
+    #[test]
+    fn compact_longest() {
+        let prefixes = vec![
+            (String::from("ex"), String::from("http://example.com/")),
+            (String::from("exa"), String::from("http://example.com/a/")),
+        ];
+        let iri = Url::from_str("http://example.com/a/b").unwrap();
+        assert_eq!(compact(&prefixes, &iri), Some(("exa", "b")));
+        let iri = Url::from_str("http://example.com/a/b/c").unwrap();
+        assert_eq!(compact(&prefixes, &iri), None);
+    }
+}
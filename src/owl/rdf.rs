@@ -0,0 +1,692 @@
+//! Serialization of OBO documents to RDF, as Turtle or N-Triples.
+//!
+//! The RDF graph is obtained with the OWL2 to RDF mapping of the axioms
+//! produced by the [`Translator`](../struct.Translator.html). Since every
+//! frame is translated independently, documents can be serialized straight
+//! from a [`Parser`](../../parser/trait.Parser.html) without loading them
+//! in memory first.
+//!
+//! # Example
+//! ```rust
+//! # extern crate fastobo;
+//! # use fastobo::parser::*;
+//! # use fastobo::owl::rdf::*;
+//! let txt = "ontology: test\n\n[Term]\nid: TEST:001\nname: test term\n";
+//! let parser = DefaultParser::new(std::io::Cursor::new(txt));
+//! let out = write_frames(Vec::new(), parser, RdfFormat::NTriples).unwrap();
+//!
+//! let text = String::from_utf8(out).unwrap();
+//! assert!(text.contains(
+//!     "<http://purl.obolibrary.org/obo/TEST_001> <http://www.w3.org/2000/01/rdf-schema#label> \"test term\" .\n"
+//! ));
+//! ```
+
+use std::io::Write;
+
+use crate::ast::*;
+use crate::error::Error;
+use crate::error::Result;
+
+use super::model::*;
+use super::prefix;
+use super::translate::Translator;
+use super::vocab;
+use super::vocab::{owl, rdf, rdfs, xsd};
+
+/// The RDF serialization format to use.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RdfFormat {
+    /// The [Turtle](https://www.w3.org/TR/turtle/) format, using prefixed
+    /// names and grouping triples by subject.
+    Turtle,
+    /// The [N-Triples](https://www.w3.org/TR/n-triples/) format, with one
+    /// complete triple per line.
+    NTriples,
+}
+
+/// A node of the RDF graph.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Node {
+    Iri(Url),
+    Blank(usize),
+    Literal(Literal),
+}
+
+impl From<Url> for Node {
+    fn from(iri: Url) -> Self {
+        Node::Iri(iri)
+    }
+}
+
+impl From<AnnotationValue> for Node {
+    fn from(value: AnnotationValue) -> Self {
+        match value {
+            AnnotationValue::Iri(iri) => Node::Iri(iri),
+            AnnotationValue::Literal(literal) => Node::Literal(literal),
+        }
+    }
+}
+
+/// A writer serializing OBO frames to RDF.
+///
+/// The writer serializes the ontology header as soon as it is created, so
+/// that entity frames can then be written one at a time. Blank node labels
+/// are unique across the whole output.
+///
+/// # Note
+/// The `owl-axioms` header clauses are not serialized, since they are
+/// written in OWL2 functional syntax.
+#[derive(Debug)]
+pub struct RdfWriter<W: Write> {
+    writer: W,
+    format: RdfFormat,
+    translator: Translator,
+    prefixes: Vec<(String, String)>,
+    triples: Vec<(Node, Url, Node)>,
+    blank: usize,
+}
+
+impl<W: Write> RdfWriter<W> {
+    /// Create a new writer, and write the ontology header.
+    pub fn new(writer: W, header: &HeaderFrame, format: RdfFormat) -> Result<Self> {
+        let translator = Translator::new(header);
+
+        // declare the IDspaces of the header after the default prefixes
+        let mut prefixes: Vec<(String, String)> = vocab::PREFIXES
+            .iter()
+            .map(|(p, u)| (p.to_string(), u.to_string()))
+            .collect();
+        for clause in header.iter() {
+            if let HeaderClause::Idspace(prefix, url, _) = clause {
+                if prefix::is_prefix_name(prefix.as_str())
+                    && prefixes.iter().all(|(p, _)| p != prefix.as_str())
+                {
+                    prefixes.push((prefix.as_str().to_string(), url.as_str().to_string()));
+                }
+            }
+        }
+
+        let mut this = Self {
+            writer,
+            format,
+            translator,
+            prefixes,
+            triples: Vec::new(),
+            blank: 0,
+        };
+
+        if format == RdfFormat::Turtle {
+            for (prefix, url) in this.prefixes.iter() {
+                writeln!(this.writer, "@prefix {}: <{}> .", prefix, url)?;
+            }
+            writeln!(this.writer)?;
+        }
+
+        // describe the ontology
+        let ontology = match this.translator.ontology_iri() {
+            Some(iri) => Node::Iri(iri),
+            None => this.blank(),
+        };
+        this.triple(ontology.clone(), rdf("type"), owl("Ontology").into());
+        if let Some(version) = this.translator.version_iri(header) {
+            this.triple(ontology.clone(), owl("versionIRI"), version.into());
+        }
+        for import in this.translator.imports(header) {
+            this.triple(ontology.clone(), owl("imports"), import.into());
+        }
+        for annotation in this.translator.header_annotations(header) {
+            this.triple(
+                ontology.clone(),
+                annotation.property,
+                annotation.value.into(),
+            );
+        }
+        for axiom in this.translator.header_axioms(header) {
+            this.axiom(axiom);
+        }
+
+        this.flush()?;
+        Ok(this)
+    }
+
+    /// Get a reference to the translator used by the writer.
+    pub fn translator(&self) -> &Translator {
+        &self.translator
+    }
+
+    /// Write the triples corresponding to an entity frame.
+    pub fn write_frame(&mut self, frame: &EntityFrame) -> Result<()> {
+        for axiom in self.translator.entity_axioms(frame) {
+            self.axiom(axiom);
+        }
+        self.flush()
+    }
+
+    /// Write the triples corresponding to a single axiom.
+    pub fn write_axiom(&mut self, axiom: &Axiom) -> Result<()> {
+        self.axiom(axiom.clone());
+        self.flush()
+    }
+
+    /// Flush the underlying writer and return it.
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Helper methods to translate OWL2 constructs into triples.
+impl<W: Write> RdfWriter<W> {
+    fn blank(&mut self) -> Node {
+        self.blank += 1;
+        Node::Blank(self.blank)
+    }
+
+    fn triple(&mut self, subject: Node, predicate: Url, object: Node) {
+        self.triples.push((subject, predicate, object));
+    }
+
+    /// Add a triple, and reify it if the axiom has annotations.
+    fn annotated(&mut self, anns: Vec<Annotation>, subject: Node, predicate: Url, object: Node) {
+        if !anns.is_empty() {
+            let axiom = self.blank();
+            self.triple(axiom.clone(), rdf("type"), owl("Axiom").into());
+            self.triple(axiom.clone(), owl("annotatedSource"), subject.clone());
+            self.triple(
+                axiom.clone(),
+                owl("annotatedProperty"),
+                predicate.clone().into(),
+            );
+            self.triple(axiom.clone(), owl("annotatedTarget"), object.clone());
+            self.annotations(axiom, anns);
+        }
+        self.triple(subject, predicate, object);
+    }
+
+    fn annotations(&mut self, subject: Node, anns: Vec<Annotation>) {
+        for annotation in anns {
+            self.triple(
+                subject.clone(),
+                annotation.property,
+                annotation.value.into(),
+            );
+        }
+    }
+
+    fn list(&mut self, items: Vec<Node>) -> Node {
+        let mut head = Node::Iri(rdf("nil"));
+        for item in items.into_iter().rev() {
+            let node = self.blank();
+            self.triple(node.clone(), rdf("first"), item);
+            self.triple(node.clone(), rdf("rest"), head);
+            head = node;
+        }
+        head
+    }
+
+    fn class_expression(&mut self, ce: ClassExpression) -> Node {
+        match ce {
+            ClassExpression::Class(iri) => Node::Iri(iri),
+            ClassExpression::ObjectSomeValuesFrom(property, filler) => {
+                let node = self.blank();
+                let filler = self.class_expression(*filler);
+                self.triple(node.clone(), rdf("type"), owl("Restriction").into());
+                self.triple(node.clone(), owl("onProperty"), property.into());
+                self.triple(node.clone(), owl("someValuesFrom"), filler);
+                node
+            }
+            ClassExpression::ObjectIntersectionOf(operands) => {
+                self.boolean_class(owl("intersectionOf"), operands)
+            }
+            ClassExpression::ObjectUnionOf(operands) => {
+                self.boolean_class(owl("unionOf"), operands)
+            }
+            ClassExpression::ObjectMinCardinality(n, property, filler) => {
+                self.cardinality(owl("minQualifiedCardinality"), n, property, *filler)
+            }
+            ClassExpression::ObjectMaxCardinality(n, property, filler) => {
+                self.cardinality(owl("maxQualifiedCardinality"), n, property, *filler)
+            }
+            ClassExpression::ObjectExactCardinality(n, property, filler) => {
+                self.cardinality(owl("qualifiedCardinality"), n, property, *filler)
+            }
+        }
+    }
+
+    fn cardinality(
+        &mut self,
+        predicate: Url,
+        n: u32,
+        property: Url,
+        filler: ClassExpression,
+    ) -> Node {
+        let node = self.blank();
+        let filler = self.class_expression(filler);
+        let value = Literal::with_datatype(n.to_string(), xsd("nonNegativeInteger"));
+        self.triple(node.clone(), rdf("type"), owl("Restriction").into());
+        self.triple(node.clone(), owl("onProperty"), property.into());
+        self.triple(node.clone(), predicate, Node::Literal(value));
+        self.triple(node.clone(), owl("onClass"), filler);
+        node
+    }
+
+    fn boolean_class(&mut self, predicate: Url, operands: Vec<ClassExpression>) -> Node {
+        let node = self.blank();
+        let items = operands
+            .into_iter()
+            .map(|ce| self.class_expression(ce))
+            .collect();
+        let list = self.list(items);
+        self.triple(node.clone(), rdf("type"), owl("Class").into());
+        self.triple(node.clone(), predicate, list);
+        node
+    }
+
+    /// Add the triples for an n-ary axiom, pairwise or with a list.
+    fn nary(&mut self, anns: Vec<Annotation>, nodes: Vec<Node>, pairwise: Url, all: Url) {
+        if nodes.len() == 2 || all == pairwise {
+            for pair in nodes.windows(2) {
+                self.annotated(
+                    anns.clone(),
+                    pair[0].clone(),
+                    pairwise.clone(),
+                    pair[1].clone(),
+                );
+            }
+        } else {
+            let node = self.blank();
+            let list = self.list(nodes);
+            self.triple(node.clone(), rdf("type"), all.into());
+            self.triple(node.clone(), owl("members"), list);
+            self.annotations(node, anns);
+        }
+    }
+
+    fn axiom(&mut self, axiom: Axiom) {
+        use self::Axiom::*;
+        match axiom {
+            Declaration(kind, iri) => {
+                let ty = match kind {
+                    EntityKind::Class => owl("Class"),
+                    EntityKind::ObjectProperty => owl("ObjectProperty"),
+                    EntityKind::AnnotationProperty => owl("AnnotationProperty"),
+                    EntityKind::NamedIndividual => owl("NamedIndividual"),
+                };
+                self.triple(iri.into(), rdf("type"), ty.into());
+            }
+            AnnotationAssertion(anns, property, subject, value) => {
+                self.annotated(anns, subject.into(), property, value.into())
+            }
+            SubAnnotationPropertyOf(anns, sub, sup) => {
+                self.annotated(anns, sub.into(), rdfs("subPropertyOf"), sup.into())
+            }
+            SubClassOf(anns, sub, sup) => {
+                let sub = self.class_expression(sub);
+                let sup = self.class_expression(sup);
+                self.annotated(anns, sub, rdfs("subClassOf"), sup);
+            }
+            EquivalentClasses(anns, ces) => {
+                let nodes = ces
+                    .into_iter()
+                    .map(|ce| self.class_expression(ce))
+                    .collect();
+                let p = owl("equivalentClass");
+                self.nary(anns, nodes, p.clone(), p);
+            }
+            DisjointClasses(anns, ces) => {
+                let nodes = ces
+                    .into_iter()
+                    .map(|ce| self.class_expression(ce))
+                    .collect();
+                self.nary(anns, nodes, owl("disjointWith"), owl("AllDisjointClasses"));
+            }
+            SubObjectPropertyOf(anns, mut chain, sup) => {
+                if chain.len() == 1 {
+                    let sub = chain.pop().unwrap();
+                    self.annotated(anns, sub.into(), rdfs("subPropertyOf"), sup.into());
+                } else {
+                    let list = self.list(chain.into_iter().map(Node::from).collect());
+                    self.annotated(anns, sup.into(), owl("propertyChainAxiom"), list);
+                }
+            }
+            EquivalentObjectProperties(anns, iris) => {
+                let nodes = iris.into_iter().map(Node::from).collect();
+                let p = owl("equivalentProperty");
+                self.nary(anns, nodes, p.clone(), p);
+            }
+            DisjointObjectProperties(anns, iris) => {
+                let nodes = iris.into_iter().map(Node::from).collect();
+                let all = owl("AllDisjointProperties");
+                self.nary(anns, nodes, owl("propertyDisjointWith"), all);
+            }
+            InverseObjectProperties(anns, r1, r2) => {
+                self.annotated(anns, r1.into(), owl("inverseOf"), r2.into())
+            }
+            ObjectPropertyDomain(anns, r, ce) => {
+                let ce = self.class_expression(ce);
+                self.annotated(anns, r.into(), rdfs("domain"), ce);
+            }
+            ObjectPropertyRange(anns, r, ce) => {
+                let ce = self.class_expression(ce);
+                self.annotated(anns, r.into(), rdfs("range"), ce);
+            }
+            ObjectPropertyCharacteristic(anns, c, r) => {
+                let ty = match c {
+                    Characteristic::Functional => owl("FunctionalProperty"),
+                    Characteristic::InverseFunctional => owl("InverseFunctionalProperty"),
+                    Characteristic::Reflexive => owl("ReflexiveProperty"),
+                    Characteristic::Symmetric => owl("SymmetricProperty"),
+                    Characteristic::Asymmetric => owl("AsymmetricProperty"),
+                    Characteristic::Transitive => owl("TransitiveProperty"),
+                };
+                self.annotated(anns, r.into(), rdf("type"), ty.into());
+            }
+            ClassAssertion(anns, ce, individual) => {
+                let ce = self.class_expression(ce);
+                self.annotated(anns, individual.into(), rdf("type"), ce);
+            }
+            ObjectPropertyAssertion(anns, r, subject, object) => {
+                self.annotated(anns, subject.into(), r, object.into())
+            }
+        }
+    }
+}
+
+/// Helper methods to serialize the triples.
+impl<W: Write> RdfWriter<W> {
+    /// Write the pending triples to the underlying writer.
+    fn flush(&mut self) -> Result<()> {
+        let triples = std::mem::take(&mut self.triples);
+        let mut out = String::new();
+        let mut previous: Option<&Node> = None;
+        for (subject, predicate, object) in triples.iter() {
+            match self.format {
+                RdfFormat::NTriples => {
+                    self.node(&mut out, subject);
+                    out.push(' ');
+                    self.iri(&mut out, predicate);
+                    out.push(' ');
+                    self.node(&mut out, object);
+                    out.push_str(" .\n");
+                }
+                RdfFormat::Turtle => {
+                    if previous == Some(subject) {
+                        out.push_str(" ;\n    ");
+                    } else {
+                        if previous.is_some() {
+                            out.push_str(" .\n");
+                        }
+                        self.node(&mut out, subject);
+                        out.push(' ');
+                    }
+                    if predicate.as_str() == format!("{}type", vocab::RDF) {
+                        out.push('a');
+                    } else {
+                        self.iri(&mut out, predicate);
+                    }
+                    out.push(' ');
+                    self.node(&mut out, object);
+                    previous = Some(subject);
+                }
+            }
+        }
+        if previous.is_some() {
+            out.push_str(" .\n\n");
+        }
+        self.writer.write_all(out.as_bytes()).map_err(Error::from)
+    }
+
+    fn iri(&self, out: &mut String, iri: &Url) {
+        let s = iri.as_str();
+        if self.format == RdfFormat::Turtle {
+            if let Some((prefix, local)) = prefix::compact(&self.prefixes, iri) {
+                out.push_str(prefix);
+                out.push(':');
+                out.push_str(local);
+                return;
+            }
+        }
+        out.push('<');
+        out.push_str(s);
+        out.push('>');
+    }
+
+    fn node(&self, out: &mut String, node: &Node) {
+        match node {
+            Node::Iri(iri) => self.iri(out, iri),
+            Node::Blank(n) => {
+                out.push_str("_:b");
+                out.push_str(&n.to_string());
+            }
+            Node::Literal(literal) => {
+                out.push('"');
+                for c in literal.value().chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        _ => out.push(c),
+                    }
+                }
+                out.push('"');
+                if let Some(datatype) = literal.datatype() {
+                    out.push_str("^^");
+                    self.iri(out, datatype);
+                }
+            }
+        }
+    }
+}
+
+/// Write an OBO document to a `Write` implementor in the given RDF format.
+pub fn to_writer<W: Write>(writer: W, doc: &OboDoc, format: RdfFormat) -> Result<()> {
+    let mut writer = RdfWriter::new(writer, doc.header(), format)?;
+    for entity in doc.entities() {
+        writer.write_frame(entity)?;
+    }
+    writer.finish().map(|_| ())
+}
+
+/// Write a stream of OBO frames to a `Write` implementor in the given RDF format.
+///
+/// The stream is expected to start with the header frame, as produced by
+/// any [`Parser`](../../parser/trait.Parser.html): if it does not, the
+/// document is assumed to have an empty header.
+pub fn write_frames<W, I>(writer: W, frames: I, format: RdfFormat) -> Result<W>
+where
+    W: Write,
+    I: IntoIterator<Item = Result<Frame>>,
+{
+    let mut frames = frames.into_iter().peekable();
+    let header = match frames.peek() {
+        Some(Ok(Frame::Header(_))) => frames.next().unwrap()?.into_header_frame(),
+        _ => None,
+    };
+
+    let mut writer = RdfWriter::new(writer, &header.unwrap_or_default(), format)?;
+    for frame in frames {
+        if let Some(entity) = frame?.into_entity_frame() {
+            writer.write_frame(&entity)?;
+        }
+    }
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+    use textwrap_macros::dedent;
+
+    fn to_string(doc: &str, format: RdfFormat) -> String {
+        let doc = OboDoc::from_str(doc).unwrap();
+        let mut out = Vec::new();
+        to_writer(&mut out, &doc, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn ntriples() {
+        let text = to_string(
+            dedent!(
+                r#"
+                [Term]
+                id: TST:001
+                is_a: TST:002 {source="PMID:5678"}
+                relationship: BFO:0000050 TST:003
+                "#
+            ),
+            RdfFormat::NTriples,
+        );
+        self::assert_eq!(
+            text.lines().skip(1).collect::<Vec<_>>(),
+            vec![
+                "<http://purl.obolibrary.org/obo/TST_001> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Class> .",
+                "<http://purl.obolibrary.org/obo/TST_001> <http://www.geneontology.org/formats/oboInOwl#id> \"TST:001\" .",
+                "_:b2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Axiom> .",
+                "_:b2 <http://www.w3.org/2002/07/owl#annotatedSource> <http://purl.obolibrary.org/obo/TST_001> .",
+                "_:b2 <http://www.w3.org/2002/07/owl#annotatedProperty> <http://www.w3.org/2000/01/rdf-schema#subClassOf> .",
+                "_:b2 <http://www.w3.org/2002/07/owl#annotatedTarget> <http://purl.obolibrary.org/obo/TST_002> .",
+                "_:b2 <http://www.geneontology.org/formats/oboInOwl#source> \"PMID:5678\" .",
+                "<http://purl.obolibrary.org/obo/TST_001> <http://www.w3.org/2000/01/rdf-schema#subClassOf> <http://purl.obolibrary.org/obo/TST_002> .",
+                "_:b3 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Restriction> .",
+                "_:b3 <http://www.w3.org/2002/07/owl#onProperty> <http://purl.obolibrary.org/obo/BFO_0000050> .",
+                "_:b3 <http://www.w3.org/2002/07/owl#someValuesFrom> <http://purl.obolibrary.org/obo/TST_003> .",
+                "<http://purl.obolibrary.org/obo/TST_001> <http://www.w3.org/2000/01/rdf-schema#subClassOf> _:b3 .",
+            ]
+        );
+    }
+
+    #[test]
+    fn cardinality() {
+        let text = to_string(
+            dedent!(
+                r#"
+                [Term]
+                id: TST:001
+                relationship: BFO:0000051 TST:002 {cardinality="2"}
+                "#
+            ),
+            RdfFormat::NTriples,
+        );
+        self::assert_eq!(
+            text.lines().skip(3).collect::<Vec<_>>(),
+            vec![
+                "_:b2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Restriction> .",
+                "_:b2 <http://www.w3.org/2002/07/owl#onProperty> <http://purl.obolibrary.org/obo/BFO_0000051> .",
+                "_:b2 <http://www.w3.org/2002/07/owl#qualifiedCardinality> \"2\"^^<http://www.w3.org/2001/XMLSchema#nonNegativeInteger> .",
+                "_:b2 <http://www.w3.org/2002/07/owl#onClass> <http://purl.obolibrary.org/obo/TST_002> .",
+                "<http://purl.obolibrary.org/obo/TST_001> <http://www.w3.org/2000/01/rdf-schema#subClassOf> _:b2 .",
+            ]
+        );
+    }
+
+    #[test]
+    fn turtle() {
+        let text = to_string(
+            dedent!(
+                r#"
+                idspace: ex http://example.com/
+                ontology: test
+
+                [Term]
+                id: ex:A
+                name: A "quoted" name
+                intersection_of: ex:B
+                intersection_of: BFO:0000050 ex:C
+                "#
+            ),
+            RdfFormat::Turtle,
+        );
+        let expected = dedent!(
+            r#"
+            <http://purl.obolibrary.org/obo/test.owl> a owl:Ontology .
+
+            ex:A a owl:Class ;
+                oboInOwl:id "ex:A" ;
+                rdfs:label "A \"quoted\" name" .
+            _:b2 a owl:Restriction ;
+                owl:onProperty obo:BFO_0000050 ;
+                owl:someValuesFrom ex:C .
+            _:b3 rdf:first _:b2 ;
+                rdf:rest rdf:nil .
+            _:b4 rdf:first ex:B ;
+                rdf:rest _:b3 .
+            _:b1 a owl:Class ;
+                owl:intersectionOf _:b4 .
+            ex:A owl:equivalentClass _:b1 .
+
+            "#
+        );
+        assert!(text.starts_with("@prefix obo: <http://purl.obolibrary.org/obo/> ."));
+        assert!(text.contains("@prefix ex: <http://example.com/> ."));
+        self::assert_eq!(
+            text.split("\n\n").skip(1).collect::<Vec<_>>().join("\n\n"),
+            &expected[1..]
+        );
+    }
+
+    #[test]
+    fn longest_prefix() {
+        let text = to_string(
+            dedent!(
+                r#"
+                idspace: tst http://purl.obolibrary.org/obo/TST_
+
+                [Term]
+                id: TST:001
+                is_a: GO:0000001
+                "#
+            ),
+            RdfFormat::Turtle,
+        );
+        let expected = dedent!(
+            r#"
+            tst:001 a owl:Class ;
+                oboInOwl:id "TST:001" ;
+                rdfs:subClassOf obo:GO_0000001 .
+            "#
+        );
+        assert!(text.contains(&expected[1..]));
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        let text = to_string(
+            dedent!(
+                r#"
+                idspace: 1TST http://example.com/one/
+                idspace: _TST http://example.com/underscore/
+
+                [Term]
+                id: 1TST:001
+                is_a: _TST:001
+                "#
+            ),
+            RdfFormat::Turtle,
+        );
+        assert!(!text.contains("@prefix 1TST:"));
+        assert!(!text.contains("@prefix _TST:"));
+        let expected = dedent!(
+            r#"
+            <http://example.com/one/001> a owl:Class ;
+                oboInOwl:id "1TST:001" ;
+                rdfs:subClassOf <http://example.com/underscore/001> .
+            "#
+        );
+        assert!(text.contains(&expected[1..]), "{}", text);
+    }
+
+    #[test]
+    fn write_frames() {
+        let txt = "[Term]\nid: TST:001\n";
+        let parser = crate::parser::DefaultParser::from(std::io::Cursor::new(txt));
+        let out = super::write_frames(Vec::new(), parser, RdfFormat::NTriples).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        self::assert_eq!(text.lines().count(), 3);
+        assert!(text.starts_with("_:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Ontology> .\n"));
+    }
+}
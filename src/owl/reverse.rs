@@ -0,0 +1,829 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::ast::*;
+use crate::semantics::OboClause;
+use crate::semantics::PrefixMap;
+
+use super::functional::parser::Document;
+use super::model::*;
+use super::vocab;
+use super::vocab::iao;
+use super::vocab::obo_in_owl;
+use super::vocab::{obo, oio, owl, rdfs};
+
+/// A clause shared by all entity frames.
+enum Common {
+    Name(UnquotedString),
+    Namespace(NamespaceIdent),
+    AltId(Ident),
+    Def(Definition),
+    Comment(UnquotedString),
+    Subset(SubsetIdent),
+    Synonym(Synonym),
+    Xref(Xref),
+    PropertyValue(PropertyValue),
+    CreatedBy(UnquotedString),
+    CreationDate(IsoDateTime),
+    IsObsolete(bool),
+    ReplacedBy(Ident),
+    Consider(Ident),
+}
+
+macro_rules! impl_from_common {
+    ($($clause:ident),*) => {
+        $(impl From<Common> for $clause {
+            fn from(common: Common) -> Self {
+                match common {
+                    Common::Name(n) => $clause::Name(Box::new(n)),
+                    Common::Namespace(ns) => $clause::Namespace(Box::new(ns)),
+                    Common::AltId(id) => $clause::AltId(Box::new(id)),
+                    Common::Def(def) => $clause::Def(Box::new(def)),
+                    Common::Comment(c) => $clause::Comment(Box::new(c)),
+                    Common::Subset(s) => $clause::Subset(Box::new(s)),
+                    Common::Synonym(s) => $clause::Synonym(Box::new(s)),
+                    Common::Xref(x) => $clause::Xref(Box::new(x)),
+                    Common::PropertyValue(pv) => $clause::PropertyValue(Box::new(pv)),
+                    Common::CreatedBy(s) => $clause::CreatedBy(Box::new(s)),
+                    Common::CreationDate(d) => $clause::CreationDate(Box::new(d)),
+                    Common::IsObsolete(b) => $clause::IsObsolete(b),
+                    Common::ReplacedBy(id) => $clause::ReplacedBy(Box::new(id.into())),
+                    Common::Consider(id) => $clause::Consider(Box::new(id.into())),
+                }
+            }
+        })*
+    };
+}
+
+impl_from_common!(TermClause, TypedefClause, InstanceClause);
+
+/// A translator from OWL2 axioms back to OBO frames.
+///
+/// This implements the reverse of the OBO to OWL mapping used by the
+/// [`Translator`](struct.Translator.html): classes become term frames,
+/// object properties and annotation properties become typedef frames,
+/// and named individuals become instance frames. Axioms which cannot be
+/// represented with OBO clauses are collected, as an OWL2 functional
+/// syntax document, into an `owl-axioms` header clause.
+pub(crate) struct ReverseTranslator {
+    prefixes: PrefixMap,
+    ids: HashMap<String, Ident>,
+    subsets: Vec<(Url, Option<String>)>,
+    synonym_types: Vec<(Url, Option<String>, Option<SynonymScope>)>,
+    frames: Vec<EntityFrame>,
+    index: HashMap<String, usize>,
+    unmapped: Vec<String>,
+}
+
+impl ReverseTranslator {
+    /// Translate a functional syntax document into an OBO document.
+    pub fn translate(doc: Document) -> OboDoc {
+        let ontology = doc.iri.as_ref().map(ontology_name);
+
+        // build the prefix map used to compact IRIs into identifiers
+        let mut prefixes = PrefixMap::new();
+        if let Some(ont) = ontology.as_ref().filter(|ont| !ont.contains("://")) {
+            prefixes.set_ontology(UnquotedString::new(ont.clone()));
+        }
+        for (prefix, url) in vocab::PREFIXES {
+            if *prefix != "obo" {
+                prefixes.insert(*prefix, Url::new_unchecked(*url));
+            }
+        }
+
+        let mut translator = ReverseTranslator {
+            prefixes,
+            ids: HashMap::new(),
+            subsets: Vec::new(),
+            synonym_types: Vec::new(),
+            frames: Vec::new(),
+            index: HashMap::new(),
+            unmapped: Vec::new(),
+        };
+
+        // collect OBO identifiers, subsets and synonym types first
+        for (axiom, _) in doc.axioms.iter() {
+            match axiom {
+                Some(Axiom::AnnotationAssertion(_, p, s, AnnotationValue::Literal(l)))
+                    if *p == oio(obo_in_owl::ID) =>
+                {
+                    translator
+                        .ids
+                        .insert(s.as_str().to_string(), parse_ident(l.value()));
+                }
+                Some(Axiom::SubAnnotationPropertyOf(_, sub, sup)) => {
+                    if *sup == oio(obo_in_owl::SUBSET_PROPERTY) {
+                        translator.subsets.push((sub.clone(), None));
+                    } else if *sup == oio(obo_in_owl::SYNONYM_TYPE_PROPERTY) {
+                        translator.synonym_types.push((sub.clone(), None, None));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // translate every axiom, keeping the text of unsupported ones
+        for (axiom, text) in doc.axioms.iter() {
+            let translated = match axiom {
+                Some(axiom) => translator.axiom(axiom.clone()),
+                None => false,
+            };
+            if !translated {
+                translator.unmapped.push(text.clone());
+            }
+        }
+
+        let header = translator.header(&doc, ontology);
+        OboDoc::with_header(header).and_entities(translator.frames)
+    }
+
+    /// Build the header frame of the translated document.
+    fn header(&self, doc: &Document, ontology: Option<String>) -> HeaderFrame {
+        let mut clauses = Vec::new();
+        for annotation in doc.annotations.iter() {
+            clauses.push(self.header_clause(annotation));
+        }
+        if let Some(version) = doc.version_iri.as_ref() {
+            clauses.push(HeaderClause::DataVersion(Box::new(UnquotedString::new(
+                version_name(version, ontology.as_deref()),
+            ))));
+        }
+        for import in doc.imports.iter() {
+            clauses.push(HeaderClause::Import(Box::new(Import::from(import.clone()))));
+        }
+        for (iri, desc) in self.subsets.iter() {
+            let desc = QuotedString::new(desc.clone().unwrap_or_default());
+            clauses.push(HeaderClause::Subsetdef(
+                Box::new(self.ident(iri).into()),
+                Box::new(desc),
+            ));
+        }
+        for (iri, desc, scope) in self.synonym_types.iter() {
+            let desc = QuotedString::new(desc.clone().unwrap_or_default());
+            clauses.push(HeaderClause::SynonymTypedef(
+                Box::new(self.ident(iri).into()),
+                Box::new(desc),
+                scope.clone().map(Box::new),
+            ));
+        }
+        if let Some(ont) = ontology {
+            clauses.push(HeaderClause::Ontology(Box::new(UnquotedString::new(ont))));
+        }
+        if !self.unmapped.is_empty() {
+            let mut text = String::new();
+            for (prefix, url) in doc.prefixes.iter() {
+                text.push_str(&format!("Prefix({}:=<{}>)\n", prefix, url));
+            }
+            text.push_str("Ontology(\n");
+            for axiom in self.unmapped.iter() {
+                text.push_str(axiom);
+                text.push('\n');
+            }
+            text.push(')');
+            clauses.push(HeaderClause::OwlAxioms(Box::new(UnquotedString::new(text))));
+        }
+        HeaderFrame::with_clauses(clauses)
+    }
+
+    /// Translate an ontology annotation into a header clause.
+    fn header_clause(&self, annotation: &Annotation) -> HeaderClause {
+        let property = annotation.property.as_str();
+        if let AnnotationValue::Literal(literal) = &annotation.value {
+            let value = literal.value();
+            let text = || Box::new(UnquotedString::new(value));
+            if let Some(tag) = property.strip_prefix(vocab::OBO_IN_OWL) {
+                match tag {
+                    obo_in_owl::HAS_OBO_FORMAT_VERSION => {
+                        return HeaderClause::FormatVersion(text())
+                    }
+                    obo_in_owl::SAVED_BY => return HeaderClause::SavedBy(text()),
+                    obo_in_owl::AUTO_GENERATED_BY => return HeaderClause::AutoGeneratedBy(text()),
+                    obo_in_owl::NAMESPACE_ID_RULE => return HeaderClause::NamespaceIdRule(text()),
+                    obo_in_owl::DEFAULT_NAMESPACE => {
+                        let ns = parse_ident(value);
+                        return HeaderClause::DefaultNamespace(Box::new(ns.into()));
+                    }
+                    obo_in_owl::DATE => {
+                        if let Ok(date) = NaiveDateTime::from_str(value) {
+                            return HeaderClause::Date(Box::new(date));
+                        }
+                    }
+                    _ if tag.starts_with("treat-xrefs-") => {
+                        let clause = format!("{}: {}", tag, value);
+                        if let Ok(clause) = HeaderClause::from_str(&clause) {
+                            return clause;
+                        }
+                    }
+                    _ => (),
+                }
+            } else if annotation.property == rdfs("comment") {
+                return HeaderClause::Remark(text());
+            } else if annotation.property == owl("versionInfo") {
+                return HeaderClause::DataVersion(text());
+            }
+        }
+        HeaderClause::PropertyValue(Box::new(self.property_value(annotation)))
+    }
+
+    /// Get the OBO identifier of an IRI.
+    fn ident(&self, iri: &Url) -> Ident {
+        if let Some(id) = self.ids.get(iri.as_str()) {
+            return id.clone();
+        }
+        self.prefixes
+            .compact(iri)
+            .unwrap_or_else(|| Ident::from(iri.clone()))
+    }
+
+    /// Check whether an IRI is part of the vocabulary used by the mapping.
+    fn is_builtin(&self, iri: &Url) -> bool {
+        let s = iri.as_str();
+        [
+            vocab::OBO_IN_OWL,
+            vocab::OWL,
+            vocab::RDF,
+            vocab::RDFS,
+            vocab::XSD,
+        ]
+        .iter()
+        .any(|ns| s.starts_with(ns))
+            || [
+                iao::DEFINITION,
+                iao::REPLACED_BY,
+                iao::ANTISYMMETRIC,
+                iao::EXPAND_EXPRESSION_TO,
+                iao::EXPAND_ASSERTION_TO,
+            ]
+            .iter()
+            .any(|local| *iri == obo(local))
+    }
+
+    /// Declare an entity, creating the corresponding frame if needed.
+    fn declare(&mut self, kind: EntityKind, iri: &Url) {
+        if self.is_builtin(iri)
+            || self.index.contains_key(iri.as_str())
+            || self.subsets.iter().any(|(s, _)| s == iri)
+            || self.synonym_types.iter().any(|(s, _, _)| s == iri)
+        {
+            return;
+        }
+        let id = self.ident(iri);
+        let frame = match kind {
+            EntityKind::Class => EntityFrame::from(TermFrame::new(ClassIdent::from(id))),
+            EntityKind::ObjectProperty => {
+                EntityFrame::from(TypedefFrame::new(RelationIdent::from(id)))
+            }
+            EntityKind::AnnotationProperty => {
+                let mut frame = TypedefFrame::new(RelationIdent::from(id));
+                frame.push(Line::from(TypedefClause::IsMetadataTag(true)));
+                EntityFrame::from(frame)
+            }
+            EntityKind::NamedIndividual => {
+                EntityFrame::from(InstanceFrame::new(InstanceIdent::from(id)))
+            }
+        };
+        self.index
+            .insert(iri.as_str().to_string(), self.frames.len());
+        self.frames.push(frame);
+    }
+
+    fn frame(&mut self, iri: &Url) -> Option<&mut EntityFrame> {
+        let index = *self.index.get(iri.as_str())?;
+        Some(&mut self.frames[index])
+    }
+
+    fn term(&mut self, iri: &Url) -> Option<&mut TermFrame> {
+        match self.frame(iri)? {
+            EntityFrame::Term(frame) => Some(frame.as_mut()),
+            _ => None,
+        }
+    }
+
+    fn typedef(&mut self, iri: &Url) -> Option<&mut TypedefFrame> {
+        match self.frame(iri)? {
+            EntityFrame::Typedef(frame) => Some(frame.as_mut()),
+            _ => None,
+        }
+    }
+
+    fn instance(&mut self, iri: &Url) -> Option<&mut InstanceFrame> {
+        match self.frame(iri)? {
+            EntityFrame::Instance(frame) => Some(frame.as_mut()),
+            _ => None,
+        }
+    }
+
+    /// Translate annotations into line qualifiers.
+    fn qualifiers(&self, anns: Vec<Annotation>) -> Option<QualifierList> {
+        if anns.is_empty() {
+            return None;
+        }
+        let qualifiers = anns.into_iter().map(|ann| {
+            let key = match ann.property.as_str().strip_prefix(vocab::OBO_IN_OWL) {
+                Some(local) => Ident::from(UnprefixedIdent::new(local)),
+                None => self.ident(&ann.property),
+            };
+            let value = match ann.value {
+                AnnotationValue::Iri(iri) => self.ident(&iri).to_string(),
+                AnnotationValue::Literal(literal) => literal.value().to_string(),
+            };
+            Qualifier::new(RelationIdent::from(key), QuotedString::new(value))
+        });
+        Some(qualifiers.collect())
+    }
+
+    /// Extract the xrefs from a list of axiom annotations.
+    fn take_xrefs(&self, anns: &mut Vec<Annotation>) -> XrefList {
+        let property = oio(obo_in_owl::HAS_DBXREF);
+        let mut xrefs = Vec::new();
+        anns.retain(|ann| match &ann.value {
+            AnnotationValue::Literal(l) if ann.property == property => {
+                xrefs.push(Xref::new(parse_ident(l.value())));
+                false
+            }
+            _ => true,
+        });
+        XrefList::new(xrefs)
+    }
+
+    fn property_value(&self, annotation: &Annotation) -> PropertyValue {
+        let property = RelationIdent::from(self.ident(&annotation.property));
+        match &annotation.value {
+            AnnotationValue::Iri(iri) => PropertyValue::Resource(Box::new(
+                ResourcePropertyValue::new(property, self.ident(iri)),
+            )),
+            AnnotationValue::Literal(literal) => {
+                let datatype = literal
+                    .datatype()
+                    .map(|dt| self.ident(dt))
+                    .unwrap_or_else(|| PrefixedIdent::new("xsd", "string").into());
+                PropertyValue::Literal(Box::new(LiteralPropertyValue::new(
+                    property,
+                    QuotedString::new(literal.value()),
+                    datatype,
+                )))
+            }
+        }
+    }
+
+    /// Translate an annotation into a clause shared by all entity frames.
+    fn common(&self, anns: &mut Vec<Annotation>, annotation: Annotation) -> Common {
+        let property = annotation.property.as_str();
+        match &annotation.value {
+            AnnotationValue::Literal(literal) => {
+                let value = literal.value();
+                if annotation.property == rdfs("label") {
+                    return Common::Name(UnquotedString::new(value));
+                } else if annotation.property == rdfs("comment") {
+                    return Common::Comment(UnquotedString::new(value));
+                } else if annotation.property == obo(iao::DEFINITION) {
+                    let xrefs = self.take_xrefs(anns);
+                    return Common::Def(Definition::with_xrefs(QuotedString::new(value), xrefs));
+                } else if annotation.property == owl("deprecated") && value == "true" {
+                    return Common::IsObsolete(true);
+                }
+                match property.strip_prefix(vocab::OBO_IN_OWL) {
+                    Some(obo_in_owl::HAS_OBO_NAMESPACE) => {
+                        return Common::Namespace(parse_ident(value).into())
+                    }
+                    Some(obo_in_owl::HAS_ALTERNATIVE_ID) => {
+                        return Common::AltId(parse_ident(value))
+                    }
+                    Some(obo_in_owl::HAS_DBXREF) => {
+                        let label = rdfs("label");
+                        let desc = anns.iter().position(|ann| ann.property == label);
+                        let id = parse_ident(value);
+                        return match desc.map(|i| anns.remove(i).value) {
+                            Some(AnnotationValue::Literal(desc)) => {
+                                Common::Xref(Xref::with_desc(id, QuotedString::new(desc.value())))
+                            }
+                            _ => Common::Xref(Xref::new(id)),
+                        };
+                    }
+                    Some(obo_in_owl::CONSIDER) => return Common::Consider(parse_ident(value)),
+                    Some(obo_in_owl::CREATED_BY) => {
+                        return Common::CreatedBy(UnquotedString::new(value))
+                    }
+                    Some(obo_in_owl::CREATION_DATE) => {
+                        if let Ok(date) = IsoDateTime::from_str(value) {
+                            return Common::CreationDate(date);
+                        }
+                    }
+                    Some(local) => {
+                        let scope = match local {
+                            obo_in_owl::HAS_BROAD_SYNONYM => Some(SynonymScope::Broad),
+                            obo_in_owl::HAS_EXACT_SYNONYM => Some(SynonymScope::Exact),
+                            obo_in_owl::HAS_NARROW_SYNONYM => Some(SynonymScope::Narrow),
+                            obo_in_owl::HAS_RELATED_SYNONYM => Some(SynonymScope::Related),
+                            _ => None,
+                        };
+                        if let Some(scope) = scope {
+                            let ty_property = oio(obo_in_owl::HAS_SYNONYM_TYPE);
+                            let ty = anns.iter().position(|ann| ann.property == ty_property);
+                            let ty = match ty.map(|i| anns.remove(i).value) {
+                                Some(AnnotationValue::Iri(iri)) => Some(self.ident(&iri)),
+                                _ => None,
+                            };
+                            let xrefs = self.take_xrefs(anns);
+                            let desc = QuotedString::new(value);
+                            return Common::Synonym(match ty {
+                                Some(ty) => Synonym::with_type_and_xrefs(
+                                    desc,
+                                    scope,
+                                    SynonymTypeIdent::from(ty),
+                                    xrefs,
+                                ),
+                                None => Synonym::with_xrefs(desc, scope, xrefs),
+                            });
+                        }
+                    }
+                    None => (),
+                }
+            }
+            AnnotationValue::Iri(iri) => {
+                if annotation.property == oio(obo_in_owl::IN_SUBSET) {
+                    return Common::Subset(self.ident(iri).into());
+                } else if annotation.property == obo(iao::REPLACED_BY) {
+                    return Common::ReplacedBy(self.ident(iri));
+                } else if annotation.property == oio(obo_in_owl::CONSIDER) {
+                    return Common::Consider(self.ident(iri));
+                }
+            }
+        }
+        Common::PropertyValue(self.property_value(&annotation))
+    }
+
+    /// Translate an annotation assertion, returning `false` if impossible.
+    fn annotation_assertion(
+        &mut self,
+        mut anns: Vec<Annotation>,
+        property: Url,
+        subject: Url,
+        value: AnnotationValue,
+    ) -> bool {
+        let literal = match &value {
+            AnnotationValue::Literal(l) => Some(l.value().to_string()),
+            AnnotationValue::Iri(_) => None,
+        };
+
+        // annotations on subsets and synonym types
+        if let Some(subset) = self.subsets.iter_mut().find(|(s, _)| *s == subject) {
+            return match literal {
+                Some(text) if property == rdfs("comment") && anns.is_empty() => {
+                    subset.1 = Some(text);
+                    true
+                }
+                _ => false,
+            };
+        }
+        if let Some(ty) = self
+            .synonym_types
+            .iter_mut()
+            .find(|(s, _, _)| *s == subject)
+        {
+            return match literal {
+                Some(text) if property == rdfs("label") && anns.is_empty() => {
+                    ty.1 = Some(text);
+                    true
+                }
+                Some(text) if property == oio(obo_in_owl::HAS_SCOPE) && anns.is_empty() => {
+                    ty.2 = SynonymScope::from_str(&text).ok();
+                    ty.2.is_some()
+                }
+                _ => false,
+            };
+        }
+
+        if !self.index.contains_key(subject.as_str()) {
+            return false;
+        } else if property == oio(obo_in_owl::ID) && anns.is_empty() {
+            return true;
+        }
+
+        // clauses specific to typedef frames
+        if let Some(frame) = self.typedef(&subject) {
+            let flag = literal.as_deref() == Some("true");
+            let clause = match property.as_str().strip_prefix(vocab::OBO_IN_OWL) {
+                Some(obo_in_owl::IS_CYCLIC) if flag => Some(TypedefClause::IsCyclic(true)),
+                Some(obo_in_owl::IS_CLASS_LEVEL) if flag => Some(TypedefClause::IsClassLevel(true)),
+                Some(obo_in_owl::IS_METADATA_TAG) if flag => {
+                    let metadata = TypedefClause::IsMetadataTag(true);
+                    if frame.iter().any(|line| line.as_inner() == &metadata) {
+                        return anns.is_empty();
+                    }
+                    Some(metadata)
+                }
+                _ => None,
+            };
+            let clause = clause.or_else(|| {
+                if property == obo(iao::ANTISYMMETRIC) && flag {
+                    Some(TypedefClause::IsAntiSymmetric(true))
+                } else {
+                    None
+                }
+            });
+            if let Some(clause) = clause {
+                let line = Line::from(clause).and_qualifiers(self.qualifiers(anns));
+                self.typedef(&subject).unwrap().push(line);
+                return true;
+            }
+            if let Some(text) = literal.as_ref() {
+                type Expansion = fn(Box<QuotedString>, Box<XrefList>) -> TypedefClause;
+                let expansion: Option<Expansion> = if property == obo(iao::EXPAND_ASSERTION_TO) {
+                    Some(TypedefClause::ExpandAssertionTo)
+                } else if property == obo(iao::EXPAND_EXPRESSION_TO) {
+                    Some(TypedefClause::ExpandExpressionTo)
+                } else {
+                    None
+                };
+                if let Some(f) = expansion {
+                    let xrefs = self.take_xrefs(&mut anns);
+                    let clause = f(Box::new(QuotedString::new(text.clone())), Box::new(xrefs));
+                    let line = Line::from(clause).and_qualifiers(self.qualifiers(anns));
+                    self.typedef(&subject).unwrap().push(line);
+                    return true;
+                }
+            }
+        }
+
+        // clauses shared by all entity frames
+        let common = self.common(&mut anns, Annotation::new(property, value));
+        let qualifiers = self.qualifiers(anns);
+        match self.frame(&subject).unwrap() {
+            EntityFrame::Term(frame) => {
+                frame.push(Line::from(TermClause::from(common)).and_qualifiers(qualifiers))
+            }
+            EntityFrame::Typedef(frame) => {
+                frame.push(Line::from(TypedefClause::from(common)).and_qualifiers(qualifiers))
+            }
+            EntityFrame::Instance(frame) => {
+                frame.push(Line::from(InstanceClause::from(common)).and_qualifiers(qualifiers))
+            }
+        }
+        true
+    }
+
+    /// Translate an axiom, returning `false` if it cannot be represented.
+    fn axiom(&mut self, axiom: Axiom) -> bool {
+        use self::ClassExpression::*;
+
+        match axiom {
+            Axiom::Declaration(kind, iri) => {
+                self.declare(kind, &iri);
+                true
+            }
+            Axiom::AnnotationAssertion(anns, property, subject, value) => {
+                self.annotation_assertion(anns, property, subject, value)
+            }
+            Axiom::SubAnnotationPropertyOf(anns, sub, sup) => {
+                if self.subsets.iter().any(|(s, _)| *s == sub)
+                    || self.synonym_types.iter().any(|(s, _, _)| *s == sub)
+                {
+                    return anns.is_empty();
+                }
+                let clause = TypedefClause::IsA(Box::new(self.ident(&sup).into()));
+                self.push_typedef(&sub, clause, anns)
+            }
+            Axiom::SubClassOf(anns, Class(c), Class(d)) => {
+                let clause = TermClause::IsA(Box::new(self.ident(&d).into()));
+                self.push_term(&c, clause, anns)
+            }
+            Axiom::SubClassOf(anns, Class(c), ObjectSomeValuesFrom(r, filler)) => match *filler {
+                Class(d) => {
+                    let clause = TermClause::Relationship(
+                        Box::new(self.ident(&r).into()),
+                        Box::new(self.ident(&d).into()),
+                    );
+                    self.push_term(&c, clause, anns)
+                }
+                _ => false,
+            },
+            Axiom::EquivalentClasses(anns, mut ces) if ces.len() == 2 => {
+                // make sure the named class comes first
+                let named = |ce: &ClassExpression| matches!(ce, Class(_));
+                if !named(&ces[0]) || (named(&ces[1]) && self.term(&iri_of(&ces[0])).is_none()) {
+                    ces.swap(0, 1);
+                }
+                let other = ces.pop().unwrap();
+                let c = match ces.pop().unwrap() {
+                    Class(c) => c,
+                    _ => return false,
+                };
+                match other {
+                    Class(d) => {
+                        let clause = TermClause::EquivalentTo(Box::new(self.ident(&d).into()));
+                        self.push_term(&c, clause, anns)
+                    }
+                    ObjectIntersectionOf(operands) if anns.is_empty() => {
+                        let clauses = operands
+                            .into_iter()
+                            .map(|op| match op {
+                                Class(d) => Some(TermClause::IntersectionOf(
+                                    None,
+                                    Box::new(self.ident(&d).into()),
+                                )),
+                                ObjectSomeValuesFrom(r, filler) => match *filler {
+                                    Class(d) => Some(TermClause::IntersectionOf(
+                                        Some(Box::new(self.ident(&r).into())),
+                                        Box::new(self.ident(&d).into()),
+                                    )),
+                                    _ => None,
+                                },
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<_>>>();
+                        self.push_term_set(&c, clauses)
+                    }
+                    ObjectUnionOf(operands) if anns.is_empty() => {
+                        let clauses = operands
+                            .into_iter()
+                            .map(|op| match op {
+                                Class(d) => {
+                                    Some(TermClause::UnionOf(Box::new(self.ident(&d).into())))
+                                }
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<_>>>();
+                        self.push_term_set(&c, clauses)
+                    }
+                    _ => false,
+                }
+            }
+            Axiom::DisjointClasses(anns, ces) => match ces.as_slice() {
+                [Class(c), Class(d)] => {
+                    let clause = TermClause::DisjointFrom(Box::new(self.ident(d).into()));
+                    self.push_term(c, clause, anns)
+                }
+                _ => false,
+            },
+            Axiom::SubObjectPropertyOf(mut anns, chain, sup) => match chain.as_slice() {
+                [sub] => {
+                    let clause = TypedefClause::IsA(Box::new(self.ident(&sup).into()));
+                    self.push_typedef(sub, clause, anns)
+                }
+                [r1, r2] => {
+                    let marker = Annotation::new(
+                        oio(obo_in_owl::IS_EQUIVALENT_TO_CHAIN),
+                        vocab::boolean(true),
+                    );
+                    let clause = if let Some(i) = anns.iter().position(|a| *a == marker) {
+                        anns.remove(i);
+                        TypedefClause::EquivalentToChain(
+                            Box::new(self.ident(r1).into()),
+                            Box::new(self.ident(r2).into()),
+                        )
+                    } else if *r1 == sup {
+                        TypedefClause::TransitiveOver(Box::new(self.ident(r2).into()))
+                    } else {
+                        TypedefClause::HoldsOverChain(
+                            Box::new(self.ident(r1).into()),
+                            Box::new(self.ident(r2).into()),
+                        )
+                    };
+                    self.push_typedef(&sup, clause, anns)
+                }
+                _ => false,
+            },
+            Axiom::EquivalentObjectProperties(anns, props) => match props.as_slice() {
+                [r1, r2] => {
+                    let clause = TypedefClause::EquivalentTo(Box::new(self.ident(r2).into()));
+                    self.push_typedef(r1, clause, anns)
+                }
+                _ => false,
+            },
+            Axiom::DisjointObjectProperties(anns, props) => match props.as_slice() {
+                [r1, r2] => {
+                    let clause = TypedefClause::DisjointFrom(Box::new(self.ident(r2).into()));
+                    self.push_typedef(r1, clause, anns)
+                }
+                _ => false,
+            },
+            Axiom::InverseObjectProperties(anns, r1, r2) => {
+                let clause = TypedefClause::InverseOf(Box::new(self.ident(&r2).into()));
+                self.push_typedef(&r1, clause, anns)
+            }
+            Axiom::ObjectPropertyDomain(anns, r, Class(c)) => {
+                let clause = TypedefClause::Domain(Box::new(self.ident(&c).into()));
+                self.push_typedef(&r, clause, anns)
+            }
+            Axiom::ObjectPropertyRange(anns, r, Class(c)) => {
+                let clause = TypedefClause::Range(Box::new(self.ident(&c).into()));
+                self.push_typedef(&r, clause, anns)
+            }
+            Axiom::ObjectPropertyCharacteristic(anns, c, r) => {
+                let clause = match c {
+                    Characteristic::Functional => TypedefClause::IsFunctional(true),
+                    Characteristic::InverseFunctional => TypedefClause::IsInverseFunctional(true),
+                    Characteristic::Reflexive => TypedefClause::IsReflexive(true),
+                    Characteristic::Symmetric => TypedefClause::IsSymmetric(true),
+                    Characteristic::Asymmetric => TypedefClause::IsAsymmetric(true),
+                    Characteristic::Transitive => TypedefClause::IsTransitive(true),
+                };
+                self.push_typedef(&r, clause, anns)
+            }
+            Axiom::ClassAssertion(anns, Class(c), i) => {
+                let clause = InstanceClause::InstanceOf(Box::new(self.ident(&c).into()));
+                self.push_instance(&i, clause, anns)
+            }
+            Axiom::ObjectPropertyAssertion(anns, r, s, o) => {
+                let clause = InstanceClause::Relationship(
+                    Box::new(self.ident(&r).into()),
+                    Box::new(self.ident(&o)),
+                );
+                self.push_instance(&s, clause, anns)
+            }
+            _ => false,
+        }
+    }
+
+    fn push_term(&mut self, iri: &Url, clause: TermClause, anns: Vec<Annotation>) -> bool {
+        let line = Line::from(clause).and_qualifiers(self.qualifiers(anns));
+        match self.term(iri) {
+            Some(frame) => {
+                frame.push(line);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add `intersection_of` or `union_of` clauses, if the term has none yet.
+    fn push_term_set(&mut self, iri: &Url, clauses: Option<Vec<TermClause>>) -> bool {
+        let clauses = match clauses {
+            Some(clauses) if clauses.len() > 1 => clauses,
+            _ => return false,
+        };
+        let frame = match self.term(iri) {
+            Some(frame) => frame,
+            None => return false,
+        };
+        let tag = clauses[0].tag().to_string();
+        if frame.iter().any(|line| line.as_inner().tag() == tag) {
+            return false;
+        }
+        frame.extend(clauses.into_iter().map(Line::from));
+        true
+    }
+
+    fn push_typedef(&mut self, iri: &Url, clause: TypedefClause, anns: Vec<Annotation>) -> bool {
+        let line = Line::from(clause).and_qualifiers(self.qualifiers(anns));
+        match self.typedef(iri) {
+            Some(frame) => {
+                frame.push(line);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn push_instance(&mut self, iri: &Url, clause: InstanceClause, anns: Vec<Annotation>) -> bool {
+        let line = Line::from(clause).and_qualifiers(self.qualifiers(anns));
+        match self.instance(iri) {
+            Some(frame) => {
+                frame.push(line);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Parse an identifier, falling back to an unprefixed identifier.
+fn parse_ident(s: &str) -> Ident {
+    Ident::from_str(s).unwrap_or_else(|_| Ident::from(UnprefixedIdent::new(s)))
+}
+
+fn iri_of(ce: &ClassExpression) -> Url {
+    match ce {
+        ClassExpression::Class(iri) => iri.clone(),
+        _ => Url::new_unchecked(""),
+    }
+}
+
+/// Get the name of an ontology from its IRI.
+fn ontology_name(iri: &Url) -> String {
+    iri.as_str()
+        .strip_prefix(vocab::OBO)
+        .and_then(|s| s.strip_suffix(".owl"))
+        .filter(|s| !s.contains('/'))
+        .unwrap_or_else(|| iri.as_str())
+        .to_string()
+}
+
+/// Get the data version of an ontology from its version IRI.
+fn version_name(iri: &Url, ontology: Option<&str>) -> String {
+    if let Some(ont) = ontology {
+        let prefix = format!("{}{}/", vocab::OBO, ont);
+        let suffix = format!("/{}.owl", ont);
+        if let Some(version) = iri
+            .as_str()
+            .strip_prefix(&prefix)
+            .and_then(|s| s.strip_suffix(&suffix))
+        {
+            return version.to_string();
+        }
+    }
+    iri.as_str().to_string()
+}
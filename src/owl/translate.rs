@@ -0,0 +1,753 @@
+use crate::ast::*;
+use crate::semantics::OboClause;
+use crate::semantics::PrefixMap;
+
+use super::model::*;
+use super::vocab;
+use super::vocab::iao;
+use super::vocab::obo_in_owl;
+use super::vocab::{boolean, obo, oio, owl, rdfs};
+
+/// A translator from OBO syntax trees to OWL2 axioms.
+///
+/// The translator is created from the header frame of an OBO document,
+/// which it uses to resolve identifiers into IRIs. It can then be used to
+/// translate every entity frame of the document independently, making it
+/// possible to translate a document without loading it entirely in memory.
+///
+/// # Translation
+/// The translation follows the [OBO to OWL mapping] of the OBO 1.4
+/// semantics specification:
+/// * terms are translated to classes, typedefs to object properties (or
+///   annotation properties if they are declared as metadata tags), and
+///   instances to named individuals.
+/// * `is_a` clauses are translated to `SubClassOf` axioms in term frames, and
+///   to `SubObjectPropertyOf` axioms in typedef frames.
+/// * `intersection_of` and `union_of` clauses of a term frame are gathered
+///   into a single `EquivalentClasses` axiom using `ObjectIntersectionOf`
+///   and `ObjectUnionOf` class expressions respectively.
+/// * `relationship` clauses of a term frame are translated to existential
+///   restrictions, or to cardinality restrictions if they have a
+///   `cardinality`, `minCardinality` or `maxCardinality` qualifier. A
+///   `relationship` with `gci_relation` and `gci_filler` qualifiers is a
+///   general class inclusion, and is translated to a `SubClassOf` axiom
+///   whose subclass is `ObjectIntersectionOf(A, gci_relation some gci_filler)`.
+/// * metadata clauses (`name`, `def`, `synonym`, `xref`, etc.) are
+///   translated to annotation assertions, using the `oboInOwl` vocabulary
+///   when no standard property exists.
+/// * other line qualifiers are translated to axiom annotations; the ones of
+///   `intersection_of` and `union_of` clauses are merged into the
+///   annotations of the `EquivalentClasses` axiom they are gathered in.
+///   Malformed cardinality or GCI qualifiers are also kept as annotations.
+///
+/// Some clauses cannot be represented in OWL2 and are ignored: `builtin`,
+/// `is_anonymous`, and the `intersection_of`, `union_of`, `disjoint_over`
+/// and `relationship` clauses of a typedef frame.
+///
+/// Header macros are not expanded: use
+/// [`OboDoc::treat_xrefs`](../ast/struct.OboDoc.html#method.treat_xrefs)
+/// before translating a document to take them into account.
+///
+/// [OBO to OWL mapping]: http://owlcollab.github.io/oboformat/doc/obo-syntax.html#5
+#[derive(Clone, Debug)]
+pub struct Translator {
+    prefixes: PrefixMap,
+}
+
+impl Translator {
+    /// Create a new translator for a document with the given header.
+    pub fn new(header: &HeaderFrame) -> Self {
+        let mut prefixes = PrefixMap::from(header);
+        for (prefix, url) in vocab::PREFIXES {
+            if *prefix != "obo" && prefixes.get(prefix).is_none() {
+                prefixes.insert(*prefix, Url::new_unchecked(*url));
+            }
+        }
+        Self { prefixes }
+    }
+
+    /// Get the prefix map used to resolve identifiers into IRIs.
+    pub fn prefixes(&self) -> &PrefixMap {
+        &self.prefixes
+    }
+
+    /// Get the IRI of the given OBO identifier.
+    ///
+    /// Unprefixed identifiers are resolved relative to the ontology if it
+    /// is declared in the header, or relative to the OBO Library otherwise.
+    pub fn iri(&self, id: &Ident) -> Url {
+        self.prefixes.expand(id).unwrap_or_else(|| match id {
+            Ident::Unprefixed(u) => Url::new_unchecked(format!("{}{}", vocab::OBO, u.as_str())),
+            _ => unreachable!("only unprefixed identifiers can fail to expand"),
+        })
+    }
+
+    /// Get the IRI of the ontology, if the header declares one.
+    pub fn ontology_iri(&self) -> Option<Url> {
+        self.prefixes.ontology().map(|ont| {
+            if ont.as_str().contains("://") {
+                Url::new_unchecked(ont.as_str())
+            } else {
+                Url::new_unchecked(format!("{}{}.owl", vocab::OBO, ont.as_str()))
+            }
+        })
+    }
+
+    /// Get the IRI of the ontology version, if the header declares one.
+    pub fn version_iri(&self, header: &HeaderFrame) -> Option<Url> {
+        let ont = self.prefixes.ontology()?;
+        let version = header.data_version().ok()?;
+        Some(Url::new_unchecked(format!(
+            "{0}{1}/{2}/{1}.owl",
+            vocab::OBO,
+            ont.as_str(),
+            version.as_str()
+        )))
+    }
+
+    /// Get the IRIs of the ontologies imported in the header.
+    pub fn imports(&self, header: &HeaderFrame) -> Vec<Url> {
+        header
+            .iter()
+            .filter_map(|clause| match clause {
+                HeaderClause::Import(import) => Some(import.as_ref().clone().into_url()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the ontology annotations for the given header.
+    pub fn header_annotations(&self, header: &HeaderFrame) -> Vec<Annotation> {
+        use self::HeaderClause::*;
+
+        let mut annotations = Vec::new();
+        for clause in header.iter() {
+            match clause {
+                FormatVersion(v) => annotations.push(Annotation::new(
+                    oio(obo_in_owl::HAS_OBO_FORMAT_VERSION),
+                    Literal::new(v.as_str()),
+                )),
+                DataVersion(v) if self.prefixes.ontology().is_none() => annotations.push(
+                    Annotation::new(owl("versionInfo"), Literal::new(v.as_str())),
+                ),
+                Date(d) => annotations.push(Annotation::new(
+                    oio(obo_in_owl::DATE),
+                    Literal::new(d.to_string()),
+                )),
+                SavedBy(s) => annotations.push(Annotation::new(
+                    oio(obo_in_owl::SAVED_BY),
+                    Literal::new(s.as_str()),
+                )),
+                AutoGeneratedBy(s) => annotations.push(Annotation::new(
+                    oio(obo_in_owl::AUTO_GENERATED_BY),
+                    Literal::new(s.as_str()),
+                )),
+                DefaultNamespace(ns) => annotations.push(Annotation::new(
+                    oio(obo_in_owl::DEFAULT_NAMESPACE),
+                    Literal::new(ns.to_string()),
+                )),
+                NamespaceIdRule(r) => annotations.push(Annotation::new(
+                    oio(obo_in_owl::NAMESPACE_ID_RULE),
+                    Literal::new(r.as_str()),
+                )),
+                TreatXrefsAsEquivalent(_)
+                | TreatXrefsAsGenusDifferentia(_, _, _)
+                | TreatXrefsAsReverseGenusDifferentia(_, _, _)
+                | TreatXrefsAsRelationship(_, _)
+                | TreatXrefsAsIsA(_)
+                | TreatXrefsAsHasSubclass(_) => {
+                    let text = clause.to_string();
+                    let value = &text[clause.tag().len() + 2..];
+                    annotations.push(Annotation::new(oio(clause.tag()), Literal::new(value)));
+                }
+                PropertyValue(pv) => annotations.push(self.property_value(pv)),
+                Remark(s) => {
+                    annotations.push(Annotation::new(rdfs("comment"), Literal::new(s.as_str())))
+                }
+                Unreserved(tag, value) => annotations.push(Annotation::new(
+                    oio(tag.as_str()),
+                    Literal::new(value.as_str()),
+                )),
+                _ => (),
+            }
+        }
+
+        annotations
+    }
+
+    /// Get the axioms declared in the given header.
+    ///
+    /// Subsets and synonym types are declared as annotation properties.
+    pub fn header_axioms(&self, header: &HeaderFrame) -> Vec<Axiom> {
+        let mut axioms = Vec::new();
+        for clause in header.iter() {
+            match clause {
+                HeaderClause::Subsetdef(id, desc) => {
+                    let iri = self.iri(id.as_ref().as_ref());
+                    axioms.push(Axiom::Declaration(
+                        EntityKind::AnnotationProperty,
+                        iri.clone(),
+                    ));
+                    axioms.push(Axiom::SubAnnotationPropertyOf(
+                        Vec::new(),
+                        iri.clone(),
+                        oio(obo_in_owl::SUBSET_PROPERTY),
+                    ));
+                    axioms.push(Axiom::AnnotationAssertion(
+                        Vec::new(),
+                        rdfs("comment"),
+                        iri,
+                        Literal::new(desc.as_str()).into(),
+                    ));
+                }
+                HeaderClause::SynonymTypedef(id, desc, scope) => {
+                    let iri = self.iri(id.as_ref().as_ref());
+                    axioms.push(Axiom::Declaration(
+                        EntityKind::AnnotationProperty,
+                        iri.clone(),
+                    ));
+                    axioms.push(Axiom::SubAnnotationPropertyOf(
+                        Vec::new(),
+                        iri.clone(),
+                        oio(obo_in_owl::SYNONYM_TYPE_PROPERTY),
+                    ));
+                    axioms.push(Axiom::AnnotationAssertion(
+                        Vec::new(),
+                        rdfs("label"),
+                        iri.clone(),
+                        Literal::new(desc.as_str()).into(),
+                    ));
+                    if let Some(s) = scope {
+                        axioms.push(Axiom::AnnotationAssertion(
+                            Vec::new(),
+                            oio(obo_in_owl::HAS_SCOPE),
+                            iri,
+                            Literal::new(s.to_string()).into(),
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+        axioms
+    }
+
+    /// Get the axioms corresponding to the given entity frame.
+    pub fn entity_axioms(&self, frame: &EntityFrame) -> Vec<Axiom> {
+        match frame {
+            EntityFrame::Term(t) => self.term_axioms(t),
+            EntityFrame::Typedef(t) => self.typedef_axioms(t),
+            EntityFrame::Instance(i) => self.instance_axioms(i),
+        }
+    }
+
+    /// Get the axioms corresponding to the given term frame.
+    pub fn term_axioms(&self, frame: &TermFrame) -> Vec<Axiom> {
+        use self::TermClause::*;
+
+        let id = frame.id().as_inner().as_ref();
+        let iri = self.iri(id);
+        let mut axioms = self.entity_header(EntityKind::Class, id);
+
+        let mut intersection = Vec::new();
+        let mut intersection_anns = Vec::new();
+        let mut union = Vec::new();
+        let mut union_anns = Vec::new();
+
+        for line in frame.iter() {
+            let anns = self.qualifiers(line);
+            match line.as_inner() {
+                Name(n) => axioms.push(self.label(anns, &iri, n)),
+                Namespace(ns) => axioms.push(self.namespace(anns, &iri, ns)),
+                AltId(alt) => axioms.push(self.alt_id(anns, &iri, alt)),
+                Def(def) => axioms.push(self.definition(anns, &iri, def)),
+                Comment(c) => axioms.push(self.comment(anns, &iri, c)),
+                Subset(s) => axioms.push(self.subset(anns, &iri, s)),
+                Synonym(s) => axioms.push(self.synonym(anns, &iri, s)),
+                Xref(x) => axioms.push(self.xref(anns, &iri, x)),
+                PropertyValue(pv) => axioms.push(self.annotation(anns, &iri, pv)),
+                CreatedBy(s) => axioms.push(self.created_by(anns, &iri, s)),
+                CreationDate(d) => axioms.push(self.creation_date(anns, &iri, d)),
+                IsObsolete(true) => axioms.push(self.deprecated(anns, &iri)),
+                ReplacedBy(r) => axioms.push(self.replaced_by(anns, &iri, r.as_ref().as_ref())),
+                Consider(c) => axioms.push(self.consider(anns, &iri, c.as_ref().as_ref())),
+                IsA(c) => axioms.push(Axiom::SubClassOf(
+                    anns,
+                    iri.clone().into(),
+                    self.iri(c.as_ref().as_ref()).into(),
+                )),
+                IntersectionOf(None, c) => {
+                    merge(&mut intersection_anns, anns);
+                    intersection.push(ClassExpression::from(self.iri(c.as_ref().as_ref())))
+                }
+                IntersectionOf(Some(r), c) => {
+                    merge(&mut intersection_anns, anns);
+                    intersection.push(self.some(r, c))
+                }
+                UnionOf(c) => {
+                    merge(&mut union_anns, anns);
+                    union.push(ClassExpression::from(self.iri(c.as_ref().as_ref())))
+                }
+                EquivalentTo(c) => axioms.push(Axiom::EquivalentClasses(
+                    anns,
+                    vec![iri.clone().into(), self.iri(c.as_ref().as_ref()).into()],
+                )),
+                DisjointFrom(c) => axioms.push(Axiom::DisjointClasses(
+                    anns,
+                    vec![iri.clone().into(), self.iri(c.as_ref().as_ref()).into()],
+                )),
+                Relationship(r, c) => axioms.push(self.relationship(line, &iri, r, c)),
+                IsAnonymous(_) | Builtin(_) | IsObsolete(false) => (),
+            }
+        }
+
+        if !intersection.is_empty() {
+            axioms.push(Axiom::EquivalentClasses(
+                intersection_anns,
+                vec![
+                    iri.clone().into(),
+                    ClassExpression::ObjectIntersectionOf(intersection),
+                ],
+            ));
+        }
+        if !union.is_empty() {
+            axioms.push(Axiom::EquivalentClasses(
+                union_anns,
+                vec![iri.into(), ClassExpression::ObjectUnionOf(union)],
+            ));
+        }
+
+        axioms
+    }
+
+    /// Get the axioms corresponding to the given typedef frame.
+    pub fn typedef_axioms(&self, frame: &TypedefFrame) -> Vec<Axiom> {
+        use self::TypedefClause::*;
+
+        let id = frame.id().as_inner().as_ref();
+        let iri = self.iri(id);
+        let metadata = frame
+            .iter()
+            .any(|line| matches!(line.as_inner(), IsMetadataTag(true)));
+        let kind = if metadata {
+            EntityKind::AnnotationProperty
+        } else {
+            EntityKind::ObjectProperty
+        };
+
+        let mut axioms = self.entity_header(kind, id);
+        for line in frame.iter() {
+            let anns = self.qualifiers(line);
+            match line.as_inner() {
+                Name(n) => axioms.push(self.label(anns, &iri, n)),
+                Namespace(ns) => axioms.push(self.namespace(anns, &iri, ns)),
+                AltId(alt) => axioms.push(self.alt_id(anns, &iri, alt)),
+                Def(def) => axioms.push(self.definition(anns, &iri, def)),
+                Comment(c) => axioms.push(self.comment(anns, &iri, c)),
+                Subset(s) => axioms.push(self.subset(anns, &iri, s)),
+                Synonym(s) => axioms.push(self.synonym(anns, &iri, s)),
+                Xref(x) => axioms.push(self.xref(anns, &iri, x)),
+                PropertyValue(pv) => axioms.push(self.annotation(anns, &iri, pv)),
+                CreatedBy(s) => axioms.push(self.created_by(anns, &iri, s)),
+                CreationDate(d) => axioms.push(self.creation_date(anns, &iri, d)),
+                IsObsolete(true) => axioms.push(self.deprecated(anns, &iri)),
+                ReplacedBy(r) => axioms.push(self.replaced_by(anns, &iri, r.as_ref().as_ref())),
+                Consider(c) => axioms.push(self.consider(anns, &iri, c)),
+                IsAntiSymmetric(true) => {
+                    axioms.push(self.flag(anns, &iri, obo(iao::ANTISYMMETRIC)))
+                }
+                IsCyclic(true) => axioms.push(self.flag(anns, &iri, oio(obo_in_owl::IS_CYCLIC))),
+                IsMetadataTag(true) => {
+                    axioms.push(self.flag(anns, &iri, oio(obo_in_owl::IS_METADATA_TAG)))
+                }
+                IsClassLevel(true) => {
+                    axioms.push(self.flag(anns, &iri, oio(obo_in_owl::IS_CLASS_LEVEL)))
+                }
+                ExpandAssertionTo(s, xrefs) => {
+                    axioms.push(self.expansion(anns, &iri, obo(iao::EXPAND_ASSERTION_TO), s, xrefs))
+                }
+                ExpandExpressionTo(s, xrefs) => axioms.push(self.expansion(
+                    anns,
+                    &iri,
+                    obo(iao::EXPAND_EXPRESSION_TO),
+                    s,
+                    xrefs,
+                )),
+                IsA(r) if metadata => axioms.push(Axiom::SubAnnotationPropertyOf(
+                    anns,
+                    iri.clone(),
+                    self.iri(r.as_ref().as_ref()),
+                )),
+                _ if metadata => (),
+                Domain(c) => axioms.push(Axiom::ObjectPropertyDomain(
+                    anns,
+                    iri.clone(),
+                    self.iri(c.as_ref().as_ref()).into(),
+                )),
+                Range(c) => axioms.push(Axiom::ObjectPropertyRange(
+                    anns,
+                    iri.clone(),
+                    self.iri(c.as_ref().as_ref()).into(),
+                )),
+                IsReflexive(true) => {
+                    axioms.push(self.characteristic(anns, &iri, Characteristic::Reflexive))
+                }
+                IsSymmetric(true) => {
+                    axioms.push(self.characteristic(anns, &iri, Characteristic::Symmetric))
+                }
+                IsAsymmetric(true) => {
+                    axioms.push(self.characteristic(anns, &iri, Characteristic::Asymmetric))
+                }
+                IsTransitive(true) => {
+                    axioms.push(self.characteristic(anns, &iri, Characteristic::Transitive))
+                }
+                IsFunctional(true) => {
+                    axioms.push(self.characteristic(anns, &iri, Characteristic::Functional))
+                }
+                IsInverseFunctional(true) => {
+                    axioms.push(self.characteristic(anns, &iri, Characteristic::InverseFunctional))
+                }
+                IsA(r) => axioms.push(Axiom::SubObjectPropertyOf(
+                    anns,
+                    vec![iri.clone()],
+                    self.iri(r.as_ref().as_ref()),
+                )),
+                EquivalentTo(r) => axioms.push(Axiom::EquivalentObjectProperties(
+                    anns,
+                    vec![iri.clone(), self.iri(r.as_ref().as_ref())],
+                )),
+                DisjointFrom(r) => axioms.push(Axiom::DisjointObjectProperties(
+                    anns,
+                    vec![iri.clone(), self.iri(r.as_ref().as_ref())],
+                )),
+                InverseOf(r) => axioms.push(Axiom::InverseObjectProperties(
+                    anns,
+                    iri.clone(),
+                    self.iri(r.as_ref().as_ref()),
+                )),
+                TransitiveOver(r) => axioms.push(Axiom::SubObjectPropertyOf(
+                    anns,
+                    vec![iri.clone(), self.iri(r.as_ref().as_ref())],
+                    iri.clone(),
+                )),
+                HoldsOverChain(r1, r2) => axioms.push(Axiom::SubObjectPropertyOf(
+                    anns,
+                    vec![
+                        self.iri(r1.as_ref().as_ref()),
+                        self.iri(r2.as_ref().as_ref()),
+                    ],
+                    iri.clone(),
+                )),
+                EquivalentToChain(r1, r2) => {
+                    let mut anns = anns;
+                    anns.push(Annotation::new(
+                        oio(obo_in_owl::IS_EQUIVALENT_TO_CHAIN),
+                        boolean(true),
+                    ));
+                    axioms.push(Axiom::SubObjectPropertyOf(
+                        anns,
+                        vec![
+                            self.iri(r1.as_ref().as_ref()),
+                            self.iri(r2.as_ref().as_ref()),
+                        ],
+                        iri.clone(),
+                    ))
+                }
+                _ => (),
+            }
+        }
+
+        axioms
+    }
+
+    /// Get the axioms corresponding to the given instance frame.
+    pub fn instance_axioms(&self, frame: &InstanceFrame) -> Vec<Axiom> {
+        use self::InstanceClause::*;
+
+        let id = frame.id().as_inner().as_ref();
+        let iri = self.iri(id);
+        let mut axioms = self.entity_header(EntityKind::NamedIndividual, id);
+
+        for line in frame.iter() {
+            let anns = self.qualifiers(line);
+            match line.as_inner() {
+                Name(n) => axioms.push(self.label(anns, &iri, n)),
+                Namespace(ns) => axioms.push(self.namespace(anns, &iri, ns)),
+                AltId(alt) => axioms.push(self.alt_id(anns, &iri, alt)),
+                Def(def) => axioms.push(self.definition(anns, &iri, def)),
+                Comment(c) => axioms.push(self.comment(anns, &iri, c)),
+                Subset(s) => axioms.push(self.subset(anns, &iri, s)),
+                Synonym(s) => axioms.push(self.synonym(anns, &iri, s)),
+                Xref(x) => axioms.push(self.xref(anns, &iri, x)),
+                PropertyValue(pv) => axioms.push(self.annotation(anns, &iri, pv)),
+                CreatedBy(s) => axioms.push(self.created_by(anns, &iri, s)),
+                CreationDate(d) => axioms.push(self.creation_date(anns, &iri, d)),
+                IsObsolete(true) => axioms.push(self.deprecated(anns, &iri)),
+                ReplacedBy(r) => axioms.push(self.replaced_by(anns, &iri, r.as_ref().as_ref())),
+                Consider(c) => axioms.push(self.consider(anns, &iri, c)),
+                InstanceOf(c) => axioms.push(Axiom::ClassAssertion(
+                    anns,
+                    self.iri(c.as_ref().as_ref()).into(),
+                    iri.clone(),
+                )),
+                Relationship(r, target) => axioms.push(Axiom::ObjectPropertyAssertion(
+                    anns,
+                    self.iri(r.as_ref().as_ref()),
+                    iri.clone(),
+                    self.iri(target),
+                )),
+                IsAnonymous(_) | IsObsolete(false) => (),
+            }
+        }
+
+        axioms
+    }
+}
+
+/// Helper methods to build individual axioms.
+impl Translator {
+    /// Declare an entity and annotate it with its OBO identifier.
+    fn entity_header(&self, kind: EntityKind, id: &Ident) -> Vec<Axiom> {
+        let iri = self.iri(id);
+        vec![
+            Axiom::Declaration(kind, iri.clone()),
+            Axiom::AnnotationAssertion(
+                Vec::new(),
+                oio(obo_in_owl::ID),
+                iri,
+                Literal::new(id.to_string()).into(),
+            ),
+        ]
+    }
+
+    /// Translate line qualifiers into axiom annotations.
+    fn qualifiers<T>(&self, line: &Line<T>) -> Vec<Annotation> {
+        self.qualifiers_except(line, &[])
+    }
+
+    /// Translate line qualifiers into axiom annotations, except the given keys.
+    fn qualifiers_except<T>(&self, line: &Line<T>, keys: &[&str]) -> Vec<Annotation> {
+        line.qualifiers()
+            .map(|qualifiers| {
+                qualifiers
+                    .iter()
+                    .filter(|q| !keys.iter().any(|key| q.has_key(key)))
+                    .map(|q| {
+                        let property = match q.key().as_ref() {
+                            Ident::Unprefixed(u) => oio(u.as_str()),
+                            other => self.iri(other),
+                        };
+                        Annotation::new(property, Literal::new(q.value().as_str()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Translate xrefs into axiom annotations.
+    fn xrefs(&self, xrefs: &XrefList) -> Vec<Annotation> {
+        xrefs
+            .iter()
+            .map(|x| {
+                Annotation::new(
+                    oio(obo_in_owl::HAS_DBXREF),
+                    Literal::new(x.id().to_string()),
+                )
+            })
+            .collect()
+    }
+
+    fn some(&self, r: &RelationIdent, c: &ClassIdent) -> ClassExpression {
+        ClassExpression::ObjectSomeValuesFrom(
+            self.iri(r.as_ref()),
+            Box::new(self.iri(c.as_ref()).into()),
+        )
+    }
+
+    /// Translate a `relationship` line of a term frame.
+    fn relationship(
+        &self,
+        line: &Line<TermClause>,
+        iri: &Url,
+        r: &RelationIdent,
+        c: &ClassIdent,
+    ) -> Axiom {
+        let mut used = Vec::new();
+        let mut sub = ClassExpression::from(iri.clone());
+        let mut cardinality = |key: &'static str, value: Result<Option<u32>, _>| match value {
+            Ok(Some(n)) => {
+                used.push(key);
+                Some(n)
+            }
+            _ => None,
+        };
+
+        let (exact, min, max) = match line.qualifiers() {
+            Some(q) => match cardinality("cardinality", q.cardinality()) {
+                Some(n) => (Some(n), None, None),
+                None => (
+                    None,
+                    cardinality("minCardinality", q.min_cardinality()),
+                    cardinality("maxCardinality", q.max_cardinality()),
+                ),
+            },
+            None => (None, None, None),
+        };
+        if let Some(Ok(Some((gci_relation, gci_filler)))) = line.qualifiers().map(|q| q.gci()) {
+            used.extend(&["gci_relation", "gci_filler"]);
+            let restriction = self.some(&gci_relation, &gci_filler);
+            sub = ClassExpression::ObjectIntersectionOf(vec![sub, restriction]);
+        }
+
+        let property = self.iri(r.as_ref());
+        let filler = || Box::new(ClassExpression::from(self.iri(c.as_ref())));
+        let sup = match (exact, min, max) {
+            (Some(n), _, _) => ClassExpression::ObjectExactCardinality(n, property, filler()),
+            (None, Some(m), Some(n)) => ClassExpression::ObjectIntersectionOf(vec![
+                ClassExpression::ObjectMinCardinality(m, property.clone(), filler()),
+                ClassExpression::ObjectMaxCardinality(n, property, filler()),
+            ]),
+            (None, Some(m), None) => ClassExpression::ObjectMinCardinality(m, property, filler()),
+            (None, None, Some(n)) => ClassExpression::ObjectMaxCardinality(n, property, filler()),
+            (None, None, None) => self.some(r, c),
+        };
+
+        Axiom::SubClassOf(self.qualifiers_except(line, &used), sub, sup)
+    }
+
+    fn assertion<V>(&self, anns: Vec<Annotation>, property: Url, iri: &Url, value: V) -> Axiom
+    where
+        V: Into<AnnotationValue>,
+    {
+        Axiom::AnnotationAssertion(anns, property, iri.clone(), value.into())
+    }
+
+    fn label(&self, anns: Vec<Annotation>, iri: &Url, name: &UnquotedString) -> Axiom {
+        self.assertion(anns, rdfs("label"), iri, Literal::new(name.as_str()))
+    }
+
+    fn namespace(&self, anns: Vec<Annotation>, iri: &Url, ns: &NamespaceIdent) -> Axiom {
+        let value = Literal::new(ns.to_string());
+        self.assertion(anns, oio(obo_in_owl::HAS_OBO_NAMESPACE), iri, value)
+    }
+
+    fn alt_id(&self, anns: Vec<Annotation>, iri: &Url, alt: &Ident) -> Axiom {
+        let value = Literal::new(alt.to_string());
+        self.assertion(anns, oio(obo_in_owl::HAS_ALTERNATIVE_ID), iri, value)
+    }
+
+    fn definition(&self, mut anns: Vec<Annotation>, iri: &Url, def: &Definition) -> Axiom {
+        anns.extend(self.xrefs(def.xrefs()));
+        let value = Literal::new(def.text().as_str());
+        self.assertion(anns, obo(iao::DEFINITION), iri, value)
+    }
+
+    fn comment(&self, anns: Vec<Annotation>, iri: &Url, comment: &UnquotedString) -> Axiom {
+        self.assertion(anns, rdfs("comment"), iri, Literal::new(comment.as_str()))
+    }
+
+    fn subset(&self, anns: Vec<Annotation>, iri: &Url, subset: &SubsetIdent) -> Axiom {
+        let value = self.iri(subset.as_ref());
+        self.assertion(anns, oio(obo_in_owl::IN_SUBSET), iri, value)
+    }
+
+    fn synonym(&self, mut anns: Vec<Annotation>, iri: &Url, syn: &Synonym) -> Axiom {
+        let property = match syn.scope() {
+            SynonymScope::Broad => obo_in_owl::HAS_BROAD_SYNONYM,
+            SynonymScope::Exact => obo_in_owl::HAS_EXACT_SYNONYM,
+            SynonymScope::Narrow => obo_in_owl::HAS_NARROW_SYNONYM,
+            SynonymScope::Related => obo_in_owl::HAS_RELATED_SYNONYM,
+        };
+        if let Some(ty) = syn.ty() {
+            anns.push(Annotation::new(
+                oio(obo_in_owl::HAS_SYNONYM_TYPE),
+                self.iri(ty.as_ref()),
+            ));
+        }
+        anns.extend(self.xrefs(syn.xrefs()));
+        let value = Literal::new(syn.description().as_str());
+        self.assertion(anns, oio(property), iri, value)
+    }
+
+    fn xref(&self, mut anns: Vec<Annotation>, iri: &Url, xref: &Xref) -> Axiom {
+        if let Some(desc) = xref.description() {
+            anns.push(Annotation::new(rdfs("label"), Literal::new(desc.as_str())));
+        }
+        let value = Literal::new(xref.id().to_string());
+        self.assertion(anns, oio(obo_in_owl::HAS_DBXREF), iri, value)
+    }
+
+    fn property_value(&self, pv: &PropertyValue) -> Annotation {
+        match pv {
+            PropertyValue::Resource(pv) => {
+                Annotation::new(self.iri(pv.property().as_ref()), self.iri(pv.target()))
+            }
+            PropertyValue::Literal(pv) => {
+                let datatype = self.iri(pv.datatype());
+                let literal = if datatype.as_str() == format!("{}string", vocab::XSD) {
+                    Literal::new(pv.literal().as_str())
+                } else {
+                    Literal::with_datatype(pv.literal().as_str(), datatype)
+                };
+                Annotation::new(self.iri(pv.property().as_ref()), literal)
+            }
+        }
+    }
+
+    fn annotation(&self, anns: Vec<Annotation>, iri: &Url, pv: &PropertyValue) -> Axiom {
+        let annotation = self.property_value(pv);
+        self.assertion(anns, annotation.property, iri, annotation.value)
+    }
+
+    fn created_by(&self, anns: Vec<Annotation>, iri: &Url, s: &UnquotedString) -> Axiom {
+        let value = Literal::new(s.as_str());
+        self.assertion(anns, oio(obo_in_owl::CREATED_BY), iri, value)
+    }
+
+    fn creation_date(&self, anns: Vec<Annotation>, iri: &Url, d: &IsoDateTime) -> Axiom {
+        let value = Literal::new(d.to_string());
+        self.assertion(anns, oio(obo_in_owl::CREATION_DATE), iri, value)
+    }
+
+    fn deprecated(&self, anns: Vec<Annotation>, iri: &Url) -> Axiom {
+        self.flag(anns, iri, owl("deprecated"))
+    }
+
+    fn replaced_by(&self, anns: Vec<Annotation>, iri: &Url, id: &Ident) -> Axiom {
+        self.assertion(anns, obo(iao::REPLACED_BY), iri, self.iri(id))
+    }
+
+    fn consider(&self, anns: Vec<Annotation>, iri: &Url, id: &Ident) -> Axiom {
+        let value = Literal::new(id.to_string());
+        self.assertion(anns, oio(obo_in_owl::CONSIDER), iri, value)
+    }
+
+    fn flag(&self, anns: Vec<Annotation>, iri: &Url, property: Url) -> Axiom {
+        self.assertion(anns, property, iri, boolean(true))
+    }
+
+    fn expansion(
+        &self,
+        mut anns: Vec<Annotation>,
+        iri: &Url,
+        property: Url,
+        template: &QuotedString,
+        xrefs: &XrefList,
+    ) -> Axiom {
+        anns.extend(self.xrefs(xrefs));
+        self.assertion(anns, property, iri, Literal::new(template.as_str()))
+    }
+
+    fn characteristic(&self, anns: Vec<Annotation>, iri: &Url, c: Characteristic) -> Axiom {
+        Axiom::ObjectPropertyCharacteristic(anns, c, iri.clone())
+    }
+}
+
+/// Merge annotations into a list, skipping the ones already in the list.
+fn merge(into: &mut Vec<Annotation>, annotations: Vec<Annotation>) {
+    for annotation in annotations {
+        if !into.contains(&annotation) {
+            into.push(annotation);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! IRIs of the vocabulary used in the OBO to OWL mapping.
+
+use crate::ast::Url;
+
+use super::model::Literal;
+
+pub const OBO: &str = "http://purl.obolibrary.org/obo/";
+pub const OBO_IN_OWL: &str = "http://www.geneontology.org/formats/oboInOwl#";
+pub const OWL: &str = "http://www.w3.org/2002/07/owl#";
+pub const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+pub const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+pub const XML: &str = "http://www.w3.org/XML/1998/namespace";
+pub const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// The prefixes declared by the OWL writers, in declaration order.
+pub const PREFIXES: &[(&str, &str)] = &[
+    ("obo", OBO),
+    ("oboInOwl", OBO_IN_OWL),
+    ("owl", OWL),
+    ("rdf", RDF),
+    ("rdfs", RDFS),
+    ("xml", XML),
+    ("xsd", XSD),
+];
+
+/// Terms of the Information Artifact Ontology used by the mapping.
+pub mod iao {
+    pub const DEFINITION: &str = "IAO_0000115";
+    pub const REPLACED_BY: &str = "IAO_0100001";
+    pub const ANTISYMMETRIC: &str = "IAO_0000427";
+    pub const EXPAND_EXPRESSION_TO: &str = "IAO_0000424";
+    pub const EXPAND_ASSERTION_TO: &str = "IAO_0000425";
+}
+
+/// Terms of the `oboInOwl` vocabulary used by the mapping.
+pub mod obo_in_owl {
+    pub const AUTO_GENERATED_BY: &str = "auto-generated-by";
+    pub const CONSIDER: &str = "consider";
+    pub const CREATED_BY: &str = "created_by";
+    pub const CREATION_DATE: &str = "creation_date";
+    pub const DATE: &str = "date";
+    pub const DEFAULT_NAMESPACE: &str = "default-namespace";
+    pub const HAS_ALTERNATIVE_ID: &str = "hasAlternativeId";
+    pub const HAS_BROAD_SYNONYM: &str = "hasBroadSynonym";
+    pub const HAS_DBXREF: &str = "hasDbXref";
+    pub const HAS_EXACT_SYNONYM: &str = "hasExactSynonym";
+    pub const HAS_NARROW_SYNONYM: &str = "hasNarrowSynonym";
+    pub const HAS_OBO_FORMAT_VERSION: &str = "hasOBOFormatVersion";
+    pub const HAS_OBO_NAMESPACE: &str = "hasOBONamespace";
+    pub const HAS_RELATED_SYNONYM: &str = "hasRelatedSynonym";
+    pub const HAS_SCOPE: &str = "hasScope";
+    pub const HAS_SYNONYM_TYPE: &str = "hasSynonymType";
+    pub const ID: &str = "id";
+    pub const IN_SUBSET: &str = "inSubset";
+    pub const IS_CLASS_LEVEL: &str = "is_class_level";
+    pub const IS_CYCLIC: &str = "is_cyclic";
+    pub const IS_EQUIVALENT_TO_CHAIN: &str = "is_equivalent_to_chain";
+    pub const IS_METADATA_TAG: &str = "is_metadata_tag";
+    pub const NAMESPACE_ID_RULE: &str = "NamespaceIdRule";
+    pub const SAVED_BY: &str = "saved-by";
+    pub const SUBSET_PROPERTY: &str = "SubsetProperty";
+    pub const SYNONYM_TYPE_PROPERTY: &str = "SynonymTypeProperty";
+}
+
+// ---------------------------------------------------------------------------
+
+// Helpers to build the IRI of a term of the vocabulary from its local name.
+
+pub fn obo(local: &str) -> Url {
+    Url::new_unchecked(format!("{}{}", OBO, local))
+}
+
+pub fn oio(local: &str) -> Url {
+    Url::new_unchecked(format!("{}{}", OBO_IN_OWL, local))
+}
+
+pub fn owl(local: &str) -> Url {
+    Url::new_unchecked(format!("{}{}", OWL, local))
+}
+
+pub fn rdf(local: &str) -> Url {
+    Url::new_unchecked(format!("{}{}", RDF, local))
+}
+
+pub fn rdfs(local: &str) -> Url {
+    Url::new_unchecked(format!("{}{}", RDFS, local))
+}
+
+pub fn xsd(local: &str) -> Url {
+    Url::new_unchecked(format!("{}{}", XSD, local))
+}
+
+/// Create a new `xsd:boolean` literal.
+pub fn boolean(b: bool) -> Literal {
+    Literal::with_datatype(b.to_string(), xsd("boolean"))
+}
@@ -55,11 +55,11 @@ mod tests {
         fn from_pair() {
             let pairs = Lexer::tokenize(Rule::Boolean, "true");
             let pair = pairs.unwrap().next().unwrap();
-            assert_eq!(bool::from_pair(pair).unwrap(), true);
+            assert!(bool::from_pair(pair).unwrap());
 
             let pairs = Lexer::tokenize(Rule::Boolean, "false");
             let pair = pairs.unwrap().next().unwrap();
-            assert_eq!(bool::from_pair(pair).unwrap(), false);
+            assert!(!bool::from_pair(pair).unwrap());
         }
     }
 
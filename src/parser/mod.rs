@@ -181,8 +181,8 @@ mod tests {
                     };
 
                     let pe = match se {
-                        SyntaxError::ParserError { error: pe } => pe,
-                        _ => panic!("syntax error should be a ParserError"),
+                        SyntaxError::InvalidDate { error: pe } => pe,
+                        _ => panic!("syntax error should be an InvalidDate"),
                     };
 
                     match pe.line_col {
@@ -212,8 +212,8 @@ mod tests {
                     };
 
                     let pe = match se {
-                        SyntaxError::ParserError { error: pe } => pe,
-                        _ => panic!("syntax error should be a ParserError"),
+                        SyntaxError::InvalidDate { error: pe } => pe,
+                        _ => panic!("syntax error should be an InvalidDate"),
                     };
 
                     match pe.line_col {
@@ -355,17 +355,108 @@ mod tests {
                     }
                 }
             }
+
+            mod errkind {
+                use super::*;
+
+                use pest::error::LineColLocation;
+
+                fn syntax_error(txt: &str) -> SyntaxError {
+                    let res = OboDoc::try_from($constructor(Cursor::new(txt)));
+                    match res.expect_err("document should fail to parse") {
+                        Error::SyntaxError { error } => error,
+                        e => panic!("error should be a SyntaxError: {:?}", e),
+                    }
+                }
+
+                fn line_col(error: &SyntaxError) -> (usize, usize) {
+                    match error.parser_error().map(|pe| &pe.line_col) {
+                        Some(LineColLocation::Pos(pos)) => *pos,
+                        other => panic!("unexpected location: {:?}", other),
+                    }
+                }
+
+                #[test]
+                fn unterminated_string() {
+                    let txt = "[Term]\nid: TST:001\n\n[Term]\nid: TST:002\ndef: \"open []\n";
+                    let err = syntax_error(txt);
+                    assert!(matches!(err, SyntaxError::UnterminatedString { .. }));
+                    assert_eq!(line_col(&err), (6, 6));
+                }
+
+                #[test]
+                fn invalid_escape() {
+                    let txt = "[Term]\nid: TST:001\nname: trailing\\";
+                    let err = syntax_error(txt);
+                    assert!(matches!(err, SyntaxError::InvalidEscape { .. }));
+                    assert_eq!(line_col(&err), (3, 15));
+                }
+
+                #[test]
+                fn unknown_tag() {
+                    let txt = "[Term]\nid: TST:001\n\n[Term]\nid: TST:002\nfoo_bar: baz\n";
+                    let err = syntax_error(txt);
+                    match &err {
+                        SyntaxError::UnknownTag { tag, .. } => assert_eq!(tag, "foo_bar"),
+                        e => panic!("syntax error should be an UnknownTag: {:?}", e),
+                    }
+                    assert_eq!(line_col(&err), (6, 1));
+                }
+
+                #[test]
+                fn missing_id() {
+                    let txt = "[Term]\nid: TST:001\n\n[Typedef]\nname: part of\n";
+                    let err = syntax_error(txt);
+                    assert!(matches!(err, SyntaxError::MissingId { .. }));
+                    assert_eq!(line_col(&err), (5, 1));
+                }
+
+                #[test]
+                fn invalid_date() {
+                    let txt = "[Term]\nid: TST:001\ncreation_date: 2020-13-45\n";
+                    let err = syntax_error(txt);
+                    assert!(matches!(err, SyntaxError::InvalidDate { .. }));
+                    assert_eq!(line_col(&err), (3, 16));
+                }
+
+                #[test]
+                fn invalid_boolean() {
+                    let txt = "[Term]\nid: TST:001\n\n[Typedef]\nid: rel\nis_transitive: maybe\n";
+                    let err = syntax_error(txt);
+                    match &err {
+                        SyntaxError::InvalidBoolean { value, .. } => assert_eq!(value, "maybe"),
+                        e => panic!("syntax error should be an InvalidBoolean: {:?}", e),
+                    }
+                    assert_eq!(line_col(&err), (6, 16));
+                }
+
+                #[test]
+                fn parser_error() {
+                    let txt = "[Term]\nid: TST:001\nis_a TST:002\n";
+                    let err = syntax_error(txt);
+                    assert!(matches!(err, SyntaxError::ParserError { .. }));
+                    assert_eq!(line_col(&err), (3, 1));
+                }
+
+                #[test]
+                fn malformed_known_tag() {
+                    let txt = "[Term]\nid: TST:001\nrelationship: part_of\n";
+                    let err = syntax_error(txt);
+                    assert!(matches!(err, SyntaxError::ParserError { .. }));
+                    assert_eq!(line_col(&err), (3, 22));
+                }
+            }
         };
     }
 
     mod sequential {
         use super::*;
-        tests!(|x| SequentialParser::new(x));
+        tests!(SequentialParser::new);
     }
 
     #[cfg(feature = "threading")]
     mod threaded {
         use super::*;
-        tests!(|x| ThreadedParser::new(x));
+        tests!(ThreadedParser::new);
     }
 }
@@ -40,7 +40,7 @@ impl<T: AsRef<str>> QuickFind for T {
 }
 
 #[cfg(not(feature = "memchr"))]
-impl<'a> QuickFind for [u8] {
+impl QuickFind for [u8] {
     fn quickfind(&self, needle: u8) -> Option<usize> {
         self.iter().position(|&c| c == needle)
     }
@@ -55,7 +55,7 @@ impl<'a> QuickFind for [u8] {
 }
 
 #[cfg(feature = "memchr")]
-impl<'a> QuickFind for [u8] {
+impl QuickFind for [u8] {
     fn quickfind(&self, needle: u8) -> Option<usize> {
         ::memchr::memchr(needle, self)
     }
@@ -70,7 +70,7 @@ pub struct ThreadedParser<B: BufRead> {
     line_offset: usize,
     offset: usize,
 
-    ///
+    /// Whether to yield frames in the order they appear in the source
     ordered: bool,
     read_index: usize,
     sent_index: usize,
@@ -293,8 +293,7 @@ impl<B: BufRead> Parser<B> for ThreadedParser<B> {
                 let clause = Lexer::tokenize(Rule::HeaderClause, &line)
                     .map_err(SyntaxError::from)
                     .map(|mut p| p.next().unwrap())
-                    .and_then(HeaderClause::from_pair)
-                    .map_err(SyntaxError::from);
+                    .and_then(HeaderClause::from_pair);
                 // check if the clause was parsed properly or not
                 match clause {
                     Ok(c) => frame_clauses.push(c),
@@ -0,0 +1,189 @@
+//! Canonical ordering of frames and clauses, following the reference serializer.
+
+use std::cmp::Ordering;
+
+use crate::ast::*;
+use crate::semantics::Identified;
+use crate::semantics::OboClause;
+use crate::semantics::Orderable;
+
+/// A clause of an entity frame that can be sorted in canonical order.
+pub(crate) trait CanonicalClause: OboClause + Ord + ToString {
+    /// Check whether the clause is the genus of an `intersection_of` set.
+    fn is_genus(&self) -> bool {
+        false
+    }
+
+    /// Sort the lists nested in the clause value, such as xref lists.
+    fn sort_values(&mut self);
+
+    /// Check whether the lists nested in the clause value are sorted.
+    fn values_sorted(&self) -> bool;
+}
+
+macro_rules! impl_canonical_clause {
+    ($clause:ident) => {
+        fn sort_values(&mut self) {
+            match self {
+                $clause::Def(def) => def.xrefs_mut().sort(),
+                $clause::Synonym(syn) => syn.xrefs_mut().sort(),
+                _ => (),
+            }
+        }
+
+        fn values_sorted(&self) -> bool {
+            match self {
+                $clause::Def(def) => def.xrefs().is_sorted(),
+                $clause::Synonym(syn) => syn.xrefs().is_sorted(),
+                _ => true,
+            }
+        }
+    };
+}
+
+impl CanonicalClause for TermClause {
+    fn is_genus(&self) -> bool {
+        matches!(self, TermClause::IntersectionOf(None, _))
+    }
+    impl_canonical_clause!(TermClause);
+}
+
+impl CanonicalClause for TypedefClause {
+    impl_canonical_clause!(TypedefClause);
+}
+
+impl CanonicalClause for InstanceClause {
+    impl_canonical_clause!(InstanceClause);
+}
+
+/// The sort key of a line, with the serialized clause precomputed.
+struct Key<'a, C> {
+    line: &'a Line<C>,
+    value: String,
+    folded: String,
+}
+
+impl<'a, C: CanonicalClause> Key<'a, C> {
+    fn new(line: &'a Line<C>) -> Self {
+        let value = line.as_inner().to_string();
+        let folded = value.to_lowercase();
+        Self {
+            line,
+            value,
+            folded,
+        }
+    }
+
+    /// Compare two lines the way the reference serializer does.
+    ///
+    /// Clauses are ranked by tag first; clauses with the same tag are then
+    /// ranked with the genus of `intersection_of` first, then on their value
+    /// compared case-insensitively, then case-sensitively, and finally on
+    /// their qualifiers.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (x, y) = (self.line.as_inner(), other.line.as_inner());
+        if x.tag() != y.tag() {
+            return x.cmp(y);
+        }
+        y.is_genus()
+            .cmp(&x.is_genus())
+            .then_with(|| self.folded.cmp(&other.folded))
+            .then_with(|| self.value.cmp(&other.value))
+            .then_with(|| self.line.qualifiers().cmp(&other.line.qualifiers()))
+    }
+}
+
+/// Sort the lines of an entity frame in canonical order.
+pub(crate) fn sort_lines<C: CanonicalClause>(lines: &mut Vec<Line<C>>) {
+    for line in lines.iter_mut() {
+        line.as_inner_mut().sort_values();
+        if let Some(qualifiers) = line.qualifiers_mut() {
+            qualifiers.sort();
+        }
+    }
+
+    let order: Vec<usize> = {
+        let keys: Vec<Key<C>> = lines.iter().map(Key::new).collect();
+        let mut order: Vec<usize> = (0..lines.len()).collect();
+        order.sort_by(|&i, &j| keys[i].cmp(&keys[j]));
+        order
+    };
+
+    let mut taken: Vec<Option<Line<C>>> = lines.drain(..).map(Some).collect();
+    lines.extend(order.into_iter().filter_map(|i| taken[i].take()));
+}
+
+/// Check whether the lines of an entity frame are in canonical order.
+pub(crate) fn lines_sorted<C: CanonicalClause>(lines: &[Line<C>]) -> bool {
+    let nested = lines.iter().all(|line| {
+        line.as_inner().values_sorted() && line.qualifiers().iter().all(|q| q.is_sorted())
+    });
+    let keys: Vec<Key<C>> = lines.iter().map(Key::new).collect();
+    nested
+        && keys
+            .windows(2)
+            .all(|w| w[0].cmp(&w[1]) != Ordering::Greater)
+}
+
+/// Compare two entity frames in canonical order.
+///
+/// `[Term]` frames come first, followed by `[Typedef]` and `[Instance]`
+/// frames, each ranked by identifier. This is the order used to sort the
+/// entities of an `OboDoc` with [`Orderable::sort`](trait.Orderable.html#tymethod.sort).
+pub fn cmp_entities(x: &EntityFrame, y: &EntityFrame) -> Ordering {
+    fn rank(entity: &EntityFrame) -> u8 {
+        match entity {
+            EntityFrame::Term(_) => 0,
+            EntityFrame::Typedef(_) => 1,
+            EntityFrame::Instance(_) => 2,
+        }
+    }
+    rank(x).cmp(&rank(y)).then_with(|| x.as_id().cmp(y.as_id()))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn lines(src: &str) -> Vec<Line<TermClause>> {
+        let frame = TermFrame::from_str(&format!("[Term]\nid: TST:001\n{}", src)).unwrap();
+        frame.clauses().clone()
+    }
+
+    #[test]
+    fn sort_lines_tie_breaking() {
+        let mut actual = lines(concat!(
+            "intersection_of: part_of TST:003\n",
+            "is_a: tst:002\n",
+            "intersection_of: TST:004\n",
+            "is_a: TST:003\n",
+            "is_a: TST:002 {source=\"b\", comment=\"a\"}\n",
+            "is_a: TST:002\n",
+        ));
+        sort_lines(&mut actual);
+
+        let expected = lines(concat!(
+            "is_a: TST:002\n",
+            "is_a: TST:002 {comment=\"a\", source=\"b\"}\n",
+            "is_a: tst:002\n",
+            "is_a: TST:003\n",
+            "intersection_of: TST:004\n",
+            "intersection_of: part_of TST:003\n",
+        ));
+        assert_eq!(actual, expected);
+        assert!(lines_sorted(&actual));
+    }
+
+    #[test]
+    fn sort_lines_xrefs() {
+        let mut actual = lines("synonym: \"test\" EXACT [TST:2, TST:1]\n");
+        assert!(!lines_sorted(&actual));
+        sort_lines(&mut actual);
+        assert_eq!(actual, lines("synonym: \"test\" EXACT [TST:1, TST:2]\n"));
+        assert!(lines_sorted(&actual));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::ast::*;
+use crate::error::QualifierError;
+
+/// A general class inclusion axiom, declared with `gci_*` qualifiers.
+///
+/// A `relationship: R C {gci_relation="S", gci_filler="D"}` line in the
+/// frame of a class `A` does not state that every `A` is related to some
+/// `C` by `R`, but only the instances of `A` that are also related to some
+/// `D` by `S`: in OWL, `A and (S some D)` is a subclass of `R some C`.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// let doc = fastobo::from_str(
+/// r#"[Term]
+/// id: CL:0000540
+/// relationship: part_of UBERON:0001016 {gci_relation="part_of", gci_filler="NCBITaxon:9606"}
+/// "#).unwrap();
+///
+/// let frame = doc.entities()[0].as_term_frame().unwrap();
+/// let gcis = frame.general_class_inclusions()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(gcis.len(), 1);
+/// assert_eq!(gcis[0].gci_filler().to_string(), "NCBITaxon:9606");
+/// assert_eq!(gcis[0].target().to_string(), "UBERON:0001016");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GeneralClassInclusion {
+    class: ClassIdent,
+    gci_relation: RelationIdent,
+    gci_filler: ClassIdent,
+    relation: RelationIdent,
+    target: ClassIdent,
+}
+
+impl GeneralClassInclusion {
+    /// Create a new axiom stating `class and (gci_relation some gci_filler)`
+    /// is a subclass of `relation some target`.
+    pub fn new(
+        class: ClassIdent,
+        gci_relation: RelationIdent,
+        gci_filler: ClassIdent,
+        relation: RelationIdent,
+        target: ClassIdent,
+    ) -> Self {
+        Self {
+            class,
+            gci_relation,
+            gci_filler,
+            relation,
+            target,
+        }
+    }
+
+    /// Extract the axiom declared by a `relationship` line of a term frame.
+    ///
+    /// Returns `None` if the line is not a `relationship` clause, or if it
+    /// has no `gci_relation` and `gci_filler` qualifiers.
+    ///
+    /// # Errors
+    /// Returns a `QualifierError` if only one of the `gci_*` qualifiers is
+    /// declared, or if either of them is invalid.
+    pub fn from_line(
+        class: &ClassIdent,
+        line: &Line<TermClause>,
+    ) -> Result<Option<Self>, QualifierError> {
+        let (relation, target) = match line.as_inner() {
+            TermClause::Relationship(relation, target) => (relation, target),
+            _ => return Ok(None),
+        };
+        let gci = match line.qualifiers() {
+            Some(qualifiers) => qualifiers.gci()?,
+            None => None,
+        };
+        Ok(gci.map(|(gci_relation, gci_filler)| {
+            Self::new(
+                class.clone(),
+                gci_relation,
+                gci_filler,
+                relation.as_ref().clone(),
+                target.as_ref().clone(),
+            )
+        }))
+    }
+
+    /// Get the class the axiom was declared for.
+    pub fn class(&self) -> &ClassIdent {
+        &self.class
+    }
+
+    /// Get the relation restricting the class, from the `gci_relation` qualifier.
+    pub fn gci_relation(&self) -> &RelationIdent {
+        &self.gci_relation
+    }
+
+    /// Get the filler restricting the class, from the `gci_filler` qualifier.
+    pub fn gci_filler(&self) -> &ClassIdent {
+        &self.gci_filler
+    }
+
+    /// Get the relation of the `relationship` clause.
+    pub fn relation(&self) -> &RelationIdent {
+        &self.relation
+    }
+
+    /// Get the target class of the `relationship` clause.
+    pub fn target(&self) -> &ClassIdent {
+        &self.target
+    }
+}
+
+impl TermFrame {
+    /// Get an iterator over the general class inclusion axioms of the frame.
+    ///
+    /// See [`GeneralClassInclusion`](../semantics/struct.GeneralClassInclusion.html).
+    pub fn general_class_inclusions(
+        &self,
+    ) -> impl Iterator<Item = Result<GeneralClassInclusion, QualifierError>> + '_ {
+        let class = self.id().as_inner();
+        self.clauses()
+            .iter()
+            .filter_map(move |line| GeneralClassInclusion::from_line(class, line).transpose())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn general_class_inclusions() {
+        let frame = TermFrame::from_str(concat!(
+            "[Term]\n",
+            "id: TST:001\n",
+            "relationship: part_of TST:002\n",
+            "relationship: part_of TST:003 {gci_relation=\"occurs_in\", gci_filler=\"TST:004\"}\n",
+        ))
+        .unwrap();
+        let gcis: Vec<_> = frame.general_class_inclusions().collect();
+        assert_eq!(
+            gcis,
+            vec![Ok(GeneralClassInclusion::new(
+                ClassIdent::from_str("TST:001").unwrap(),
+                RelationIdent::from_str("occurs_in").unwrap(),
+                ClassIdent::from_str("TST:004").unwrap(),
+                RelationIdent::from_str("part_of").unwrap(),
+                ClassIdent::from_str("TST:003").unwrap(),
+            ))]
+        );
+    }
+
+    #[test]
+    fn missing_filler() {
+        let frame = TermFrame::from_str(concat!(
+            "[Term]\n",
+            "id: TST:001\n",
+            "relationship: part_of TST:003 {gci_relation=\"occurs_in\"}\n",
+        ))
+        .unwrap();
+        let gcis: Vec<_> = frame.general_class_inclusions().collect();
+        assert_eq!(
+            gcis,
+            vec![Err(QualifierError::MissingQualifier {
+                key: String::from("gci_filler")
+            })]
+        );
+    }
+}
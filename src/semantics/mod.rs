@@ -3,7 +3,15 @@
 use crate::ast::*;
 use crate::error::CardinalityError;
 
+mod canonical;
+mod gci;
+mod prefix_map;
 mod treat_xrefs;
+pub use self::canonical::cmp_entities;
+pub(crate) use self::canonical::*;
+pub use self::gci::GeneralClassInclusion;
+pub use self::prefix_map::PrefixMap;
+pub use self::prefix_map::OBO_PURL;
 pub(crate) use self::treat_xrefs::*;
 
 /// The cardinality constraint for a given clause type.
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use crate::ast::*;
+
+/// The base IRI used for identifiers without a declared IDspace.
+pub const OBO_PURL: &str = "http://purl.obolibrary.org/obo/";
+
+/// A mapping of identifier prefixes to their expanded IRI.
+///
+/// A `PrefixMap` can be used to translate OBO identifiers to IRIs and
+/// back, following the [identifier translation rules] of the OBO 1.4
+/// semantics:
+/// * a prefixed identifier `{p}:{id}` is expanded to `{u}{id}` if the
+///   document declares an IDspace prefix `p` that maps to the URL `u`,
+///   or to `http://purl.obolibrary.org/obo/{p}_{id}` otherwise.
+/// * an unprefixed identifier `{id}` is expanded relative to the ontology
+///   as `http://purl.obolibrary.org/obo/{ontology}#{id}`, if the document
+///   declares an `ontology` header clause.
+/// * an URL identifier is left as-is.
+///
+/// Compaction follows the same rules in the opposite direction.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use std::str::FromStr;
+/// # use fastobo::ast::*;
+/// # use fastobo::semantics::PrefixMap;
+/// let header = HeaderFrame::from_str(
+///     "idspace: Wikipedia http://en.wikipedia.org/wiki/"
+/// ).unwrap();
+/// let prefixes = PrefixMap::from(&header);
+///
+/// let id = PrefixedIdent::new("Wikipedia", "Pupil");
+/// let url = id.expand(&prefixes);
+/// assert_eq!(url.as_str(), "http://en.wikipedia.org/wiki/Pupil");
+/// assert_eq!(url.compact(&prefixes), Some(Ident::from(id)));
+///
+/// let id = PrefixedIdent::new("MS", "1000031");
+/// let url = id.expand(&prefixes);
+/// assert_eq!(url.as_str(), "http://purl.obolibrary.org/obo/MS_1000031");
+/// assert_eq!(url.compact(&prefixes), Some(Ident::from(id)));
+/// ```
+///
+/// [identifier translation rules]: http://owlcollab.github.io/oboformat/doc/obo-syntax.html#5.9
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PrefixMap {
+    idspaces: HashMap<IdentPrefix, Url>,
+    ontology: Option<UnquotedString>,
+}
+
+impl PrefixMap {
+    /// Create a new empty prefix map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a new IDspace, returning the previous URL if any.
+    pub fn insert<P>(&mut self, prefix: P, url: Url) -> Option<Url>
+    where
+        P: Into<IdentPrefix>,
+    {
+        self.idspaces.insert(prefix.into(), url)
+    }
+
+    /// Get the URL declared for the given prefix, if any.
+    pub fn get(&self, prefix: &str) -> Option<&Url> {
+        self.idspaces.get(prefix)
+    }
+
+    /// Get the ontology used to expand unprefixed identifiers, if any.
+    pub fn ontology(&self) -> Option<&UnquotedString> {
+        self.ontology.as_ref()
+    }
+
+    /// Set the ontology used to expand unprefixed identifiers.
+    pub fn set_ontology<O>(&mut self, ontology: O)
+    where
+        O: Into<Option<UnquotedString>>,
+    {
+        self.ontology = ontology.into();
+    }
+
+    /// Expand a prefixed identifier into an IRI.
+    ///
+    /// The resulting URL is built by concatenation, and is not validated.
+    pub fn expand_prefixed(&self, id: &PrefixedIdent) -> Url {
+        let iri = match self.idspaces.get(id.prefix()) {
+            Some(url) => format!("{}{}", url, id.local()),
+            None => format!("{}{}_{}", OBO_PURL, id.prefix(), id.local()),
+        };
+        Url::new_unchecked(iri)
+    }
+
+    /// Expand an unprefixed identifier into an IRI, if an ontology is known.
+    ///
+    /// The resulting URL is built by concatenation, and is not validated.
+    pub fn expand_unprefixed(&self, id: &UnprefixedIdent) -> Option<Url> {
+        self.ontology
+            .as_ref()
+            .map(|ont| Url::new_unchecked(format!("{}{}#{}", OBO_PURL, ont.as_str(), id.as_str())))
+    }
+
+    /// Expand any identifier into an IRI, if possible.
+    pub fn expand(&self, id: &Ident) -> Option<Url> {
+        match id {
+            Ident::Prefixed(p) => Some(self.expand_prefixed(p)),
+            Ident::Unprefixed(u) => self.expand_unprefixed(u),
+            Ident::Url(u) => Some(u.as_ref().clone()),
+        }
+    }
+
+    /// Compact an IRI into a prefixed identifier, if possible.
+    ///
+    /// Declared IDspaces take precedence over the OBO Library PURL scheme,
+    /// and the longest matching IDspace URL is always used. An IRI in the
+    /// OBO Library namespace is not compacted using a prefix that is also
+    /// declared as an IDspace, since the result would not expand back to
+    /// the same IRI.
+    pub fn compact_prefixed(&self, url: &Url) -> Option<PrefixedIdent> {
+        let iri = url.as_str();
+
+        // find the longest IDspace URL matching the IRI
+        let declared = self
+            .idspaces
+            .iter()
+            .filter(|(_, base)| iri.starts_with(base.as_str()))
+            .max_by_key(|(_, base)| base.as_str().len());
+        if let Some((prefix, base)) = declared {
+            return Some(PrefixedIdent::new(
+                prefix.as_str(),
+                &iri[base.as_str().len()..],
+            ));
+        }
+
+        // attempt to use the OBO Library factorisation
+        if let Some(raw_id) = iri.strip_prefix(OBO_PURL) {
+            if let Some(i) = raw_id.find('_') {
+                let prefix = &raw_id[..i];
+                if !prefix.is_empty()
+                    && !self.idspaces.contains_key(prefix)
+                    && !prefix.contains(['/', '#'])
+                {
+                    return Some(PrefixedIdent::new(prefix, &raw_id[i + 1..]));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Compact an IRI into an unprefixed identifier, if possible.
+    pub fn compact_unprefixed(&self, url: &Url) -> Option<UnprefixedIdent> {
+        let ontology = self.ontology.as_ref()?;
+        url.as_str()
+            .strip_prefix(OBO_PURL)
+            .and_then(|s| s.strip_prefix(ontology.as_str()))
+            .and_then(|s| s.strip_prefix('#'))
+            .map(UnprefixedIdent::new)
+    }
+
+    /// Compact an IRI into a prefixed or an unprefixed identifier, if possible.
+    pub fn compact(&self, url: &Url) -> Option<Ident> {
+        self.compact_unprefixed(url)
+            .map(Ident::from)
+            .or_else(|| self.compact_prefixed(url).map(Ident::from))
+    }
+}
+
+impl From<&HeaderFrame> for PrefixMap {
+    fn from(header: &HeaderFrame) -> Self {
+        let mut prefixes = PrefixMap::new();
+        for clause in header.iter() {
+            match clause {
+                HeaderClause::Idspace(prefix, url, _) => {
+                    prefixes.insert(prefix.as_ref().clone(), url.as_ref().clone());
+                }
+                HeaderClause::Ontology(ont) => {
+                    prefixes.set_ontology(ont.as_ref().clone());
+                }
+                _ => (),
+            }
+        }
+        prefixes
+    }
+}
+
+impl From<&OboDoc> for PrefixMap {
+    fn from(doc: &OboDoc) -> Self {
+        Self::from(doc.header())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+
+    fn prefixes() -> PrefixMap {
+        PrefixMap::from(
+            &HeaderFrame::from_str(
+                "ontology: go
+                idspace: PMC https://www.ncbi.nlm.nih.gov/pmc/articles/PMC
+                idspace: NCBI https://www.ncbi.nlm.nih.gov/
+                ",
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn expand() {
+        let prefixes = prefixes();
+
+        let id = Ident::from(PrefixedIdent::new("PMC", "2823822"));
+        let url = Url::from_str("https://www.ncbi.nlm.nih.gov/pmc/articles/PMC2823822").unwrap();
+        assert_eq!(prefixes.expand(&id), Some(url));
+
+        let id = Ident::from(PrefixedIdent::new("GO", "0005623"));
+        let url = Url::from_str("http://purl.obolibrary.org/obo/GO_0005623").unwrap();
+        assert_eq!(prefixes.expand(&id), Some(url));
+
+        let id = Ident::from(UnprefixedIdent::new("part_of"));
+        let url = Url::from_str("http://purl.obolibrary.org/obo/go#part_of").unwrap();
+        assert_eq!(prefixes.expand(&id), Some(url));
+        assert_eq!(PrefixMap::new().expand(&id), None);
+    }
+
+    #[test]
+    fn compact() {
+        let prefixes = prefixes();
+
+        let url = Url::from_str("https://www.ncbi.nlm.nih.gov/pmc/articles/PMC2823822").unwrap();
+        let id = Ident::from(PrefixedIdent::new("PMC", "2823822"));
+        assert_eq!(prefixes.compact(&url), Some(id));
+
+        let url = Url::from_str("https://www.ncbi.nlm.nih.gov/gene/7157").unwrap();
+        let id = Ident::from(PrefixedIdent::new("NCBI", "gene/7157"));
+        assert_eq!(prefixes.compact(&url), Some(id));
+
+        let url = Url::from_str("http://purl.obolibrary.org/obo/go#part_of").unwrap();
+        let id = Ident::from(UnprefixedIdent::new("part_of"));
+        assert_eq!(prefixes.compact(&url), Some(id));
+
+        let url = Url::from_str("http://purl.obolibrary.org/obo/PMC_2823822").unwrap();
+        assert_eq!(prefixes.compact(&url), None);
+
+        let url = Url::from_str("http://example.com/test").unwrap();
+        assert_eq!(prefixes.compact(&url), None);
+    }
+}
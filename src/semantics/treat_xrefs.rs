@@ -7,7 +7,7 @@ use crate::ast::*;
 use super::Identified;
 
 /// Apply a single `treat-xrefs-as-equivalent` macro to the whole document.
-pub fn as_equivalent(entities: &mut Vec<EntityFrame>, prefix: &IdentPrefix) {
+pub fn as_equivalent(entities: &mut [EntityFrame], prefix: &IdentPrefix) {
     // Macro to reduce code duplication
     macro_rules! process {
         ($frame:ident, $clause:ident) => {{
@@ -42,7 +42,7 @@ pub fn as_equivalent(entities: &mut Vec<EntityFrame>, prefix: &IdentPrefix) {
 }
 
 /// Apply a single `treat-xrefs-as-is_a` macro to the whole document.
-pub fn as_is_a(entities: &mut Vec<EntityFrame>, prefix: &IdentPrefix) {
+pub fn as_is_a(entities: &mut [EntityFrame], prefix: &IdentPrefix) {
     // Macro to reduce code duplication
     macro_rules! process {
         ($frame:ident, $clause:ident) => {{
@@ -76,7 +76,7 @@ pub fn as_is_a(entities: &mut Vec<EntityFrame>, prefix: &IdentPrefix) {
 }
 
 /// Apply a single `treat-xrefs-as-is_a` macro to the whole document.
-pub fn as_has_subclass(entities: &mut Vec<EntityFrame>, prefix: &IdentPrefix) {
+pub fn as_has_subclass(entities: &mut [EntityFrame], prefix: &IdentPrefix) {
     // Collect subclass info into a mapping where `key is_a value`
     macro_rules! collect {
         () => {
@@ -140,7 +140,7 @@ pub fn as_has_subclass(entities: &mut Vec<EntityFrame>, prefix: &IdentPrefix) {
 
 /// Apply a single `treat-xrefs-as-genus-differentia` macro to the whole document.
 pub fn as_genus_differentia(
-    entities: &mut Vec<EntityFrame>,
+    entities: &mut [EntityFrame],
     prefix: &IdentPrefix,
     relid: &RelationIdent,
     classid: &ClassIdent,
@@ -186,7 +186,7 @@ pub fn as_genus_differentia(
 
 /// Apply a single `treat-xrefs-as-reverse-genus-differentia` macro to the whole document.
 pub fn as_reverse_genus_differentia(
-    entities: &mut Vec<EntityFrame>,
+    entities: &mut [EntityFrame],
     prefix: &IdentPrefix,
     relid: &RelationIdent,
     classid: &ClassIdent,
@@ -257,11 +257,7 @@ pub fn as_reverse_genus_differentia(
 }
 
 /// Apply a single `treat-xrefs-as-relationship` macro to the whole document.
-pub fn as_relationship(
-    entities: &mut Vec<EntityFrame>,
-    prefix: &IdentPrefix,
-    relid: &RelationIdent,
-) {
+pub fn as_relationship(entities: &mut [EntityFrame], prefix: &IdentPrefix, relid: &RelationIdent) {
     // Macro to reduce code duplication
     macro_rules! process {
         ($frame:ident, $clause:ident) => {{
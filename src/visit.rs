@@ -48,12 +48,23 @@
 //!   in [rust-unofficial/patterns](https://github.com/rust-unofficial/patterns).
 //!
 
+use std::collections::BTreeSet;
 use std::collections::HashMap;
-use std::str::FromStr;
+#[cfg(feature = "threading")]
+use std::num::NonZeroUsize;
+#[cfg(feature = "threading")]
+use std::sync::atomic::AtomicUsize;
+#[cfg(feature = "threading")]
+use std::sync::atomic::Ordering;
 
 use blanket::blanket;
+#[cfg(feature = "threading")]
+use lazy_static::lazy_static;
 
 use crate::ast::*;
+use crate::error::LiteralError;
+use crate::semantics::OboClause;
+use crate::semantics::PrefixMap;
 
 // ---------------------------------------------------------------------------
 
@@ -81,6 +92,8 @@ pub trait Visit<'ast> {
     fn visit_namespace_ident(&mut self, id: &'ast NamespaceIdent);
     fn visit_property_value(&mut self, pv: &'ast PropertyValue);
     fn visit_prefixed_ident(&mut self, id: &'ast PrefixedIdent);
+    fn visit_qualifier(&mut self, qualifier: &'ast Qualifier);
+    fn visit_qualifier_list(&mut self, qualifiers: &'ast QualifierList);
     fn visit_quoted_string(&mut self, string: &'ast QuotedString);
     fn visit_relation_ident(&mut self, id: &'ast RelationIdent);
     fn visit_resource_property_value(&mut self, id: &'ast ResourcePropertyValue);
@@ -259,8 +272,11 @@ pub mod visit {
         frame: &'ast InstanceFrame,
     ) {
         visitor.visit_instance_ident(frame.id());
-        for clause in frame.iter() {
-            visitor.visit_instance_clause(clause);
+        for line in frame.iter() {
+            visitor.visit_instance_clause(line.as_inner());
+            if let Some(qualifiers) = line.qualifiers() {
+                visitor.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -316,6 +332,23 @@ pub mod visit {
     ) {
     }
 
+    pub fn visit_qualifier<'ast, V: Visit<'ast> + ?Sized>(
+        visitor: &mut V,
+        qualifier: &'ast Qualifier,
+    ) {
+        visitor.visit_relation_ident(qualifier.key());
+        visitor.visit_quoted_string(qualifier.value());
+    }
+
+    pub fn visit_qualifier_list<'ast, V: Visit<'ast> + ?Sized>(
+        visitor: &mut V,
+        qualifiers: &'ast QualifierList,
+    ) {
+        for qualifier in qualifiers.iter() {
+            visitor.visit_qualifier(qualifier)
+        }
+    }
+
     #[allow(unused_variables)]
     pub fn visit_quoted_string<'ast, V: Visit<'ast> + ?Sized>(
         visitor: &mut V,
@@ -348,7 +381,7 @@ pub mod visit {
     pub fn visit_synonym<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, syn: &'ast Synonym) {
         visitor.visit_quoted_string(syn.description());
         visitor.visit_synonym_scope(syn.scope());
-        if let Some(ref id) = syn.ty() {
+        if let Some(id) = syn.ty() {
             visitor.visit_synonymtype_ident(id);
         }
         visitor.visit_xref_list(syn.xrefs())
@@ -412,8 +445,11 @@ pub mod visit {
         frame: &'ast TermFrame,
     ) {
         visitor.visit_class_ident(frame.id());
-        for clause in frame.iter() {
-            visitor.visit_term_clause(clause);
+        for line in frame.iter() {
+            visitor.visit_term_clause(line.as_inner());
+            if let Some(qualifiers) = line.qualifiers() {
+                visitor.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -477,8 +513,11 @@ pub mod visit {
         frame: &'ast TypedefFrame,
     ) {
         visitor.visit_relation_ident(frame.id());
-        for clause in frame.iter() {
-            visitor.visit_typedef_clause(clause);
+        for line in frame.iter() {
+            visitor.visit_typedef_clause(line.as_inner());
+            if let Some(qualifiers) = line.qualifiers() {
+                visitor.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -501,7 +540,7 @@ pub mod visit {
 
     pub fn visit_xref<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, xref: &'ast Xref) {
         visitor.visit_ident(xref.id());
-        if let Some(ref d) = xref.description() {
+        if let Some(d) = xref.description() {
             visitor.visit_quoted_string(d);
         }
     }
@@ -539,6 +578,8 @@ pub trait VisitMut {
     fn visit_namespace_ident(&mut self, id: &mut NamespaceIdent);
     fn visit_property_value(&mut self, pv: &mut PropertyValue);
     fn visit_prefixed_ident(&mut self, id: &mut PrefixedIdent);
+    fn visit_qualifier(&mut self, qualifier: &mut Qualifier);
+    fn visit_qualifier_list(&mut self, qualifiers: &mut QualifierList);
     fn visit_quoted_string(&mut self, string: &mut QuotedString);
     fn visit_relation_ident(&mut self, id: &mut RelationIdent);
     fn visit_resource_property_value(&mut self, id: &mut ResourcePropertyValue);
@@ -701,8 +742,11 @@ pub mod visit_mut {
 
     pub fn visit_instance_frame<V: VisitMut + ?Sized>(visitor: &mut V, frame: &mut InstanceFrame) {
         visitor.visit_instance_ident(frame.id_mut());
-        for clause in frame.clauses_mut().iter_mut() {
-            visitor.visit_instance_clause(clause);
+        for line in frame.clauses_mut().iter_mut() {
+            visitor.visit_instance_clause(line.as_inner_mut());
+            if let Some(qualifiers) = line.qualifiers_mut() {
+                visitor.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -740,6 +784,20 @@ pub mod visit_mut {
     #[allow(unused_variables)]
     pub fn visit_prefixed_ident<V: VisitMut + ?Sized>(visitor: &mut V, id: &mut PrefixedIdent) {}
 
+    pub fn visit_qualifier<V: VisitMut + ?Sized>(visitor: &mut V, qualifier: &mut Qualifier) {
+        visitor.visit_relation_ident(qualifier.key_mut());
+        visitor.visit_quoted_string(qualifier.value_mut());
+    }
+
+    pub fn visit_qualifier_list<V: VisitMut + ?Sized>(
+        visitor: &mut V,
+        qualifiers: &mut QualifierList,
+    ) {
+        for qualifier in qualifiers.iter_mut() {
+            visitor.visit_qualifier(qualifier)
+        }
+    }
+
     #[allow(unused_variables)]
     pub fn visit_quoted_string<V: VisitMut + ?Sized>(visitor: &mut V, string: &mut QuotedString) {}
 
@@ -818,8 +876,11 @@ pub mod visit_mut {
         {
             visitor.visit_class_ident(frame.id_mut());
         }
-        for clause in frame.iter_mut() {
-            visitor.visit_term_clause(clause);
+        for line in frame.iter_mut() {
+            visitor.visit_term_clause(line.as_inner_mut());
+            if let Some(qualifiers) = line.qualifiers_mut() {
+                visitor.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -879,8 +940,11 @@ pub mod visit_mut {
         {
             visitor.visit_relation_ident(frame.id_mut());
         }
-        for clause in frame.iter_mut() {
-            visitor.visit_typedef_clause(clause);
+        for line in frame.iter_mut() {
+            visitor.visit_typedef_clause(line.as_inner_mut());
+            if let Some(qualifiers) = line.qualifiers_mut() {
+                visitor.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -917,260 +981,1789 @@ pub mod visit_mut {
 
 // ---------------------------------------------------------------------------
 
-/// A visitor that will compact identifiers in an OBO document.
+/// Syntax tree transformation taking ownership of an OBO syntax tree.
 ///
-/// # Usage
-/// The compactor will follow the rules from the OBO specification:
-/// * if the document declares an IDSpace prefix `p` that maps to an Url `u`,
-///   URL identifiers that can be factorized as `{u}{v}` will be replaced
-///   by Prefixed identifiers `{p}:{v}`
-/// * if the document does not declare an IDSpace `p'`, URL identifiers that
-///   can be factorized as `http://purl.obolibrary.org/obo/{p'}_{id}`
-///   will be replaced by Prefixed identifiers `{p'}:{id}`.
+/// Each method consumes a node and returns the node that replaces it. Methods
+/// folding the elements of a collection return an `Option` or a `Vec`, so
+/// that a node can be removed from its parent, or replaced by several nodes.
+///
+/// Default implementations of this trait methods can be found in the
+/// [`fold`](./fold/index.html) submodule for easy composition: they rebuild
+/// the tree from the folded children of each node.
 ///
 /// # Example
+/// The following folder will remove all the `xref` clauses from the terms
+/// of an OBO document, and drop the terms left without any clause:
 /// ```rust
 /// # extern crate fastobo;
-/// # use std::str::FromStr;
-/// # use std::string::ToString;
-/// # use fastobo::visit::*;
-/// let mut doc = fastobo::from_str(
+/// # use fastobo::ast::*;
+/// # use fastobo::visit::Fold;
+/// # use fastobo::visit::fold;
+/// struct XrefRemover;
+///
+/// impl Fold for XrefRemover {
+///     fn fold_term_frame(&mut self, frame: TermFrame) -> Option<TermFrame> {
+///         fold::fold_term_frame(self, frame).filter(|f| !f.clauses().is_empty())
+///     }
+///
+///     fn fold_term_line(&mut self, line: Line<TermClause>) -> Vec<Line<TermClause>> {
+///         match line.as_inner() {
+///             TermClause::Xref(_) => Vec::new(),
+///             _ => fold::fold_term_line(self, line),
+///         }
+///     }
+/// }
+///
+/// let doc = fastobo::from_str(
 /// "[Term]
-/// id: http://purl.obolibrary.org/obo/BFO_0000055
+/// id: TST:001
+/// xref: TST:002
+///
+/// [Term]
+/// id: TST:003
+/// name: test
+/// xref: TST:004
 /// ").unwrap();
 ///
-/// IdCompactor::new().visit_doc(&mut doc);
-/// assert_eq!(doc.to_string(),
+/// assert_eq!(XrefRemover.fold_doc(doc).to_string(),
 /// "[Term]
-/// id: BFO:0000055
+/// id: TST:003
+/// name: test
 /// ");
 /// ```
-///
-/// # See also
-/// * [IdDecompactor](./struct.IdDecompactor.html) that does the opposite.
-#[derive(Clone, Debug, Default)]
-pub struct IdCompactor {
-    idspaces: HashMap<IdentPrefix, Url>,
+#[blanket(default = "fold", derive(Mut, Box))]
+pub trait Fold {
+    fn fold_class_ident(&mut self, id: ClassIdent) -> ClassIdent;
+    fn fold_definition(&mut self, def: Definition) -> Definition;
+    fn fold_doc(&mut self, doc: OboDoc) -> OboDoc;
+    fn fold_entity_frame(&mut self, frame: EntityFrame) -> Option<EntityFrame>;
+    fn fold_header_clause(&mut self, clause: HeaderClause) -> Vec<HeaderClause>;
+    fn fold_header_frame(&mut self, header: HeaderFrame) -> HeaderFrame;
+    fn fold_ident(&mut self, id: Ident) -> Ident;
+    fn fold_ident_prefix(&mut self, prefix: IdentPrefix) -> IdentPrefix;
+    fn fold_import(&mut self, import: Import) -> Import;
+    fn fold_instance_clause(&mut self, clause: InstanceClause) -> InstanceClause;
+    fn fold_instance_frame(&mut self, frame: InstanceFrame) -> Option<InstanceFrame>;
+    fn fold_instance_ident(&mut self, id: InstanceIdent) -> InstanceIdent;
+    fn fold_instance_line(&mut self, line: Line<InstanceClause>) -> Vec<Line<InstanceClause>>;
+    fn fold_iso_date(&mut self, date: IsoDateTime) -> IsoDateTime;
+    fn fold_literal_property_value(&mut self, pv: LiteralPropertyValue) -> LiteralPropertyValue;
+    fn fold_naive_date(&mut self, date: NaiveDateTime) -> NaiveDateTime;
+    fn fold_namespace_ident(&mut self, id: NamespaceIdent) -> NamespaceIdent;
+    fn fold_property_value(&mut self, pv: PropertyValue) -> PropertyValue;
+    fn fold_prefixed_ident(&mut self, id: PrefixedIdent) -> PrefixedIdent;
+    fn fold_qualifier(&mut self, qualifier: Qualifier) -> Option<Qualifier>;
+    fn fold_qualifier_list(&mut self, qualifiers: QualifierList) -> QualifierList;
+    fn fold_quoted_string(&mut self, string: QuotedString) -> QuotedString;
+    fn fold_relation_ident(&mut self, id: RelationIdent) -> RelationIdent;
+    fn fold_resource_property_value(&mut self, pv: ResourcePropertyValue) -> ResourcePropertyValue;
+    fn fold_subset_ident(&mut self, id: SubsetIdent) -> SubsetIdent;
+    fn fold_synonym(&mut self, syn: Synonym) -> Synonym;
+    fn fold_synonym_scope(&mut self, scope: SynonymScope) -> SynonymScope;
+    fn fold_synonymtype_ident(&mut self, id: SynonymTypeIdent) -> SynonymTypeIdent;
+    fn fold_term_clause(&mut self, clause: TermClause) -> TermClause;
+    fn fold_term_frame(&mut self, frame: TermFrame) -> Option<TermFrame>;
+    fn fold_term_line(&mut self, line: Line<TermClause>) -> Vec<Line<TermClause>>;
+    fn fold_typedef_clause(&mut self, clause: TypedefClause) -> TypedefClause;
+    fn fold_typedef_frame(&mut self, frame: TypedefFrame) -> Option<TypedefFrame>;
+    fn fold_typedef_line(&mut self, line: Line<TypedefClause>) -> Vec<Line<TypedefClause>>;
+    fn fold_unprefixed_ident(&mut self, id: UnprefixedIdent) -> UnprefixedIdent;
+    fn fold_unquoted_string(&mut self, string: UnquotedString) -> UnquotedString;
+    fn fold_url(&mut self, url: Url) -> Url;
+    fn fold_xref(&mut self, xref: Xref) -> Xref;
+    fn fold_xref_list(&mut self, xrefs: XrefList) -> XrefList;
 }
 
-impl IdCompactor {
-    pub fn new() -> Self {
-        Self {
-            idspaces: HashMap::new(),
-        }
+/// Default implementation of `Fold` trait methods.
+pub mod fold {
+
+    use std::mem;
+
+    use super::*;
+
+    /// Fold the qualifiers of a line, removing the qualifier list once empty.
+    fn fold_line_qualifiers<F: Fold + ?Sized, T>(folder: &mut F, line: &mut Line<T>) {
+        let qualifiers = line
+            .qualifiers_mut()
+            .map(mem::take)
+            .map(|qualifiers| folder.fold_qualifier_list(qualifiers))
+            .filter(|qualifiers| !qualifiers.is_empty());
+        line.set_qualifiers(qualifiers);
     }
-}
 
-impl VisitMut for IdCompactor {
-    fn visit_header_frame(&mut self, header: &mut HeaderFrame) {
-        // collect all IDSpaces before processing the header
-        for clause in header.iter() {
-            if let HeaderClause::Idspace(prefix, url, _) = clause {
-                self.idspaces
-                    .insert(prefix.as_ref().clone(), (**url).clone());
-            }
-        }
+    pub fn fold_class_ident<F: Fold + ?Sized>(folder: &mut F, id: ClassIdent) -> ClassIdent {
+        ClassIdent::from(folder.fold_ident(Ident::from(id)))
+    }
 
-        // process the header as normal
-        visit_mut::visit_header_frame(self, header);
+    pub fn fold_definition<F: Fold + ?Sized>(folder: &mut F, def: Definition) -> Definition {
+        let (text, xrefs) = def.into_parts();
+        Definition::with_xrefs(
+            folder.fold_quoted_string(text),
+            folder.fold_xref_list(xrefs),
+        )
     }
 
-    fn visit_ident(&mut self, id: &mut Ident) {
-        // the compacted id.
-        let mut new: Option<PrefixedIdent> = None;
+    pub fn fold_doc<F: Fold + ?Sized>(folder: &mut F, doc: OboDoc) -> OboDoc {
+        let (header, entities) = doc.into_parts();
+        OboDoc::with_header(folder.fold_header_frame(header)).and_entities(
+            entities
+                .into_iter()
+                .filter_map(|frame| folder.fold_entity_frame(frame))
+                .collect(),
+        )
+    }
 
-        if let Ident::Url(ref u) = id {
-            // find a prefix from the idspaces declared in the document
-            for (prefix, url) in self.idspaces.iter() {
-                if u.as_str().starts_with(url.as_str()) {
-                    let local = &u.as_str()[url.as_str().len()..];
-                    new = Some(PrefixedIdent::new(prefix.as_str(), local));
-                }
-            }
-            // if none found, attempt to use the OBO factorisation
-            const OBO_URL: &str = "http://purl.obolibrary.org/obo/";
-            if new.is_none() && u.as_str().starts_with(OBO_URL) {
-                let raw_id = &u.as_str()[OBO_URL.len()..];
-                if let Some(i) = raw_id.find('_') {
-                    // check we are not using a declared prefix (otherwise
-                    // the compaction/expansion would not roundtrip!)
-                    let prefix = IdentPrefix::new(&raw_id[..i]);
-                    if self.idspaces.get(&prefix).is_none() {
-                        new = Some(PrefixedIdent::new(prefix.as_str(), &raw_id[i + 1..]));
-                    }
-                }
-            }
+    pub fn fold_entity_frame<F: Fold + ?Sized>(
+        folder: &mut F,
+        frame: EntityFrame,
+    ) -> Option<EntityFrame> {
+        use self::EntityFrame::*;
+        match frame {
+            Term(t) => folder.fold_term_frame(*t).map(From::from),
+            Typedef(t) => folder.fold_typedef_frame(*t).map(From::from),
+            Instance(i) => folder.fold_instance_frame(*i).map(From::from),
         }
+    }
 
-        if let Some(new_id) = new {
-            *id = Ident::Prefixed(Box::new(new_id));
+    pub fn fold_header_clause<F: Fold + ?Sized>(
+        folder: &mut F,
+        clause: HeaderClause,
+    ) -> Vec<HeaderClause> {
+        use self::HeaderClause::*;
+        let clause = match clause {
+            FormatVersion(s) => FormatVersion(Box::new(folder.fold_unquoted_string(*s))),
+            DataVersion(s) => DataVersion(Box::new(folder.fold_unquoted_string(*s))),
+            Date(date) => Date(Box::new(folder.fold_naive_date(*date))),
+            SavedBy(s) => SavedBy(Box::new(folder.fold_unquoted_string(*s))),
+            AutoGeneratedBy(s) => AutoGeneratedBy(Box::new(folder.fold_unquoted_string(*s))),
+            Import(i) => Import(Box::new(folder.fold_import(*i))),
+            Subsetdef(id, s) => Subsetdef(
+                Box::new(folder.fold_subset_ident(*id)),
+                Box::new(folder.fold_quoted_string(*s)),
+            ),
+            SynonymTypedef(ty, s, sc) => SynonymTypedef(
+                Box::new(folder.fold_synonymtype_ident(*ty)),
+                Box::new(folder.fold_quoted_string(*s)),
+                sc.map(|scope| Box::new(folder.fold_synonym_scope(*scope))),
+            ),
+            DefaultNamespace(ns) => DefaultNamespace(Box::new(folder.fold_namespace_ident(*ns))),
+            NamespaceIdRule(r) => NamespaceIdRule(Box::new(folder.fold_unquoted_string(*r))),
+            Idspace(id, url, d) => Idspace(
+                Box::new(folder.fold_ident_prefix(*id)),
+                Box::new(folder.fold_url(*url)),
+                d.map(|desc| Box::new(folder.fold_quoted_string(*desc))),
+            ),
+            TreatXrefsAsEquivalent(pref) => {
+                TreatXrefsAsEquivalent(Box::new(folder.fold_ident_prefix(*pref)))
+            }
+            TreatXrefsAsGenusDifferentia(pref, rid, cid) => TreatXrefsAsGenusDifferentia(
+                Box::new(folder.fold_ident_prefix(*pref)),
+                Box::new(folder.fold_relation_ident(*rid)),
+                Box::new(folder.fold_class_ident(*cid)),
+            ),
+            TreatXrefsAsReverseGenusDifferentia(pref, rid, cid) => {
+                TreatXrefsAsReverseGenusDifferentia(
+                    Box::new(folder.fold_ident_prefix(*pref)),
+                    Box::new(folder.fold_relation_ident(*rid)),
+                    Box::new(folder.fold_class_ident(*cid)),
+                )
+            }
+            TreatXrefsAsRelationship(pref, rid) => TreatXrefsAsRelationship(
+                Box::new(folder.fold_ident_prefix(*pref)),
+                Box::new(folder.fold_relation_ident(*rid)),
+            ),
+            TreatXrefsAsIsA(pref) => TreatXrefsAsIsA(Box::new(folder.fold_ident_prefix(*pref))),
+            TreatXrefsAsHasSubclass(pref) => {
+                TreatXrefsAsHasSubclass(Box::new(folder.fold_ident_prefix(*pref)))
+            }
+            PropertyValue(pv) => PropertyValue(Box::new(folder.fold_property_value(*pv))),
+            Remark(s) => Remark(Box::new(folder.fold_unquoted_string(*s))),
+            Ontology(s) => Ontology(Box::new(folder.fold_unquoted_string(*s))),
+            OwlAxioms(s) => OwlAxioms(Box::new(folder.fold_unquoted_string(*s))),
+            Unreserved(tag, value) => Unreserved(
+                Box::new(folder.fold_unquoted_string(*tag)),
+                Box::new(folder.fold_unquoted_string(*value)),
+            ),
+        };
+        vec![clause]
+    }
+
+    pub fn fold_header_frame<F: Fold + ?Sized>(folder: &mut F, header: HeaderFrame) -> HeaderFrame {
+        header
+            .into_iter()
+            .flat_map(|clause| folder.fold_header_clause(clause))
+            .collect()
+    }
+
+    pub fn fold_ident<F: Fold + ?Sized>(folder: &mut F, id: Ident) -> Ident {
+        use self::Ident::*;
+        match id {
+            Prefixed(p) => Ident::from(folder.fold_prefixed_ident(*p)),
+            Unprefixed(u) => Ident::from(folder.fold_unprefixed_ident(*u)),
+            Url(u) => Ident::from(folder.fold_url(*u)),
         }
     }
-}
 
-/// A visitor that will decompact identifiers in an OBO document.
-///
-/// # Usage
-/// The decompactor will follow the rules from the OBO specification:
-/// * if the document declares an IDSpace prefix `p` that maps to an Url `u`,
-///   Prefixed identifiers `{p}:{id}` will be replaced by URLs identifiers
-///   `{u}{id}`.
-/// * if the document does not declare an IDSpace `p'`, Prefixed identifiers
-///   `{p}:{id}` with be replaced by URLs identifiers
-///   `http://purl.obolibrary.org/obo/{p'}_{id}`.
-///
-/// # See also
-/// * [IdCompactor](./struct.IdCompactor.html) that does the opposite.
-#[derive(Clone, Debug, Default)]
-pub struct IdDecompactor {
-    idspaces: HashMap<IdentPrefix, Url>,
-}
+    #[allow(unused_variables)]
+    pub fn fold_ident_prefix<F: Fold + ?Sized>(folder: &mut F, prefix: IdentPrefix) -> IdentPrefix {
+        prefix
+    }
 
-impl IdDecompactor {
-    pub fn new() -> Self {
-        Self {
-            idspaces: HashMap::new(),
+    pub fn fold_import<F: Fold + ?Sized>(folder: &mut F, import: Import) -> Import {
+        use self::Import::*;
+        match import {
+            Url(url) => Url(Box::new(folder.fold_url(*url))),
+            Abbreviated(id) => Abbreviated(Box::new(folder.fold_ident(*id))),
         }
     }
-}
 
-impl VisitMut for IdDecompactor {
-    fn visit_header_frame(&mut self, header: &mut HeaderFrame) {
-        // collect all IDSpaces before processing the header
-        for clause in header.iter() {
-            if let HeaderClause::Idspace(prefix, url, _) = clause {
-                self.idspaces
-                    .insert(prefix.as_ref().clone(), (**url).clone());
-            }
+    pub fn fold_instance_clause<F: Fold + ?Sized>(
+        folder: &mut F,
+        clause: InstanceClause,
+    ) -> InstanceClause {
+        use self::InstanceClause::*;
+        match clause {
+            IsAnonymous(b) => IsAnonymous(b),
+            Name(s) => Name(Box::new(folder.fold_unquoted_string(*s))),
+            Namespace(id) => Namespace(Box::new(folder.fold_namespace_ident(*id))),
+            AltId(id) => AltId(Box::new(folder.fold_ident(*id))),
+            Def(def) => Def(Box::new(folder.fold_definition(*def))),
+            Comment(s) => Comment(Box::new(folder.fold_unquoted_string(*s))),
+            Subset(id) => Subset(Box::new(folder.fold_subset_ident(*id))),
+            Synonym(s) => Synonym(Box::new(folder.fold_synonym(*s))),
+            Xref(x) => Xref(Box::new(folder.fold_xref(*x))),
+            PropertyValue(pv) => PropertyValue(Box::new(folder.fold_property_value(*pv))),
+            InstanceOf(id) => InstanceOf(Box::new(folder.fold_class_ident(*id))),
+            Relationship(r, id) => Relationship(
+                Box::new(folder.fold_relation_ident(*r)),
+                Box::new(folder.fold_ident(*id)),
+            ),
+            CreatedBy(s) => CreatedBy(Box::new(folder.fold_unquoted_string(*s))),
+            CreationDate(dt) => CreationDate(Box::new(folder.fold_iso_date(*dt))),
+            IsObsolete(b) => IsObsolete(b),
+            ReplacedBy(id) => ReplacedBy(Box::new(folder.fold_instance_ident(*id))),
+            Consider(id) => Consider(Box::new(folder.fold_ident(*id))),
         }
+    }
 
-        // process the header as normal
-        visit_mut::visit_header_frame(self, header)
+    pub fn fold_instance_frame<F: Fold + ?Sized>(
+        folder: &mut F,
+        frame: InstanceFrame,
+    ) -> Option<InstanceFrame> {
+        let (id, clauses) = frame.into_parts();
+        let id = id.map(|id| folder.fold_instance_ident(id));
+        let clauses = clauses
+            .into_iter()
+            .flat_map(|line| folder.fold_instance_line(line))
+            .collect();
+        Some(InstanceFrame::with_clauses(id, clauses))
     }
 
-    fn visit_ident(&mut self, id: &mut Ident) {
-        // the compacted id.
-        let mut new: Option<Url> = None;
-        if let Ident::Prefixed(p) = id {
-            if let Some(baseurl) = self.idspaces.get(p.prefix()) {
-                let newurl = format!("{}{}", baseurl, p.local());
-                new = Some(Url::from_str(&newurl).expect("invalid URL"));
-            } else {
-                let newurl = format!(
-                    "http://purl.obolibrary.org/obo/{}_{}",
-                    p.prefix(),
-                    p.local()
-                );
-                new = Some(Url::from_str(&newurl).expect("invalid URL"));
-            }
-        }
+    pub fn fold_instance_ident<F: Fold + ?Sized>(
+        folder: &mut F,
+        id: InstanceIdent,
+    ) -> InstanceIdent {
+        InstanceIdent::from(folder.fold_ident(Ident::from(id)))
+    }
 
-        if let Some(new_url) = new {
-            *id = Ident::Url(Box::new(new_url));
-        }
+    pub fn fold_instance_line<F: Fold + ?Sized>(
+        folder: &mut F,
+        mut line: Line<InstanceClause>,
+    ) -> Vec<Line<InstanceClause>> {
+        fold_line_qualifiers(folder, &mut line);
+        vec![line.map(|clause| folder.fold_instance_clause(clause))]
     }
-}
 
-// ---------------------------------------------------------------------------
+    #[allow(unused_variables)]
+    pub fn fold_iso_date<F: Fold + ?Sized>(folder: &mut F, date: IsoDateTime) -> IsoDateTime {
+        date
+    }
 
-#[cfg(test)]
-mod tests {
+    pub fn fold_literal_property_value<F: Fold + ?Sized>(
+        folder: &mut F,
+        pv: LiteralPropertyValue,
+    ) -> LiteralPropertyValue {
+        let (property, literal, datatype) = pv.into_parts();
+        LiteralPropertyValue::new(
+            folder.fold_relation_ident(property),
+            folder.fold_quoted_string(literal),
+            folder.fold_ident(datatype),
+        )
+    }
 
-    use super::*;
+    #[allow(unused_variables)]
+    pub fn fold_naive_date<F: Fold + ?Sized>(folder: &mut F, date: NaiveDateTime) -> NaiveDateTime {
+        date
+    }
 
-    mod id_decompactor {
-        use pretty_assertions::assert_eq;
-        use std::str::FromStr;
+    pub fn fold_namespace_ident<F: Fold + ?Sized>(
+        folder: &mut F,
+        id: NamespaceIdent,
+    ) -> NamespaceIdent {
+        NamespaceIdent::from(folder.fold_ident(Ident::from(id)))
+    }
 
-        use crate::ast::EntityFrame;
-        use crate::ast::Ident;
-        use crate::ast::OboDoc;
+    pub fn fold_property_value<F: Fold + ?Sized>(
+        folder: &mut F,
+        pv: PropertyValue,
+    ) -> PropertyValue {
+        use self::PropertyValue::*;
+        match pv {
+            Resource(pv) => Resource(Box::new(folder.fold_resource_property_value(*pv))),
+            Literal(pv) => Literal(Box::new(folder.fold_literal_property_value(*pv))),
+        }
+    }
 
-        use crate::ast::TermClause;
-        use crate::ast::Url;
-        use crate::ast::Xref;
+    #[allow(unused_variables)]
+    pub fn fold_prefixed_ident<F: Fold + ?Sized>(
+        folder: &mut F,
+        id: PrefixedIdent,
+    ) -> PrefixedIdent {
+        id
+    }
+
+    pub fn fold_qualifier<F: Fold + ?Sized>(
+        folder: &mut F,
+        qualifier: Qualifier,
+    ) -> Option<Qualifier> {
+        let (key, value) = qualifier.into_parts();
+        Some(Qualifier::new(
+            folder.fold_relation_ident(key),
+            folder.fold_quoted_string(value),
+        ))
+    }
+
+    pub fn fold_qualifier_list<F: Fold + ?Sized>(
+        folder: &mut F,
+        qualifiers: QualifierList,
+    ) -> QualifierList {
+        qualifiers
+            .into_iter()
+            .filter_map(|qualifier| folder.fold_qualifier(qualifier))
+            .collect()
+    }
 
-        use super::IdDecompactor;
-        use super::VisitMut;
+    #[allow(unused_variables)]
+    pub fn fold_quoted_string<F: Fold + ?Sized>(
+        folder: &mut F,
+        string: QuotedString,
+    ) -> QuotedString {
+        string
+    }
 
-        #[test]
-        fn decompact_idspace() {
-            let mut doc = OboDoc::from_str(
-                "idspace: Wikipedia http://en.wikipedia.org/wiki/
+    pub fn fold_relation_ident<F: Fold + ?Sized>(
+        folder: &mut F,
+        id: RelationIdent,
+    ) -> RelationIdent {
+        RelationIdent::from(folder.fold_ident(Ident::from(id)))
+    }
 
-                [Term]
-                id: TST:001
-                name: Pupil
-                xref: Wikipedia:Pupil
-                ",
-            )
-            .unwrap();
-            IdDecompactor::new().visit_doc(&mut doc);
+    pub fn fold_resource_property_value<F: Fold + ?Sized>(
+        folder: &mut F,
+        pv: ResourcePropertyValue,
+    ) -> ResourcePropertyValue {
+        let (property, target) = pv.into_parts();
+        ResourcePropertyValue::new(
+            folder.fold_relation_ident(property),
+            folder.fold_ident(target),
+        )
+    }
 
-            if let Some(EntityFrame::Term(term)) = doc.entities().get(0) {
-                let url = Url::from_str("http://en.wikipedia.org/wiki/Pupil").unwrap();
-                assert_eq!(
-                    term.clauses()[1].as_inner(),
-                    &TermClause::Xref(Box::new(Xref::new(url))),
-                );
-            } else {
-                unreachable!()
-            }
+    pub fn fold_subset_ident<F: Fold + ?Sized>(folder: &mut F, id: SubsetIdent) -> SubsetIdent {
+        SubsetIdent::from(folder.fold_ident(Ident::from(id)))
+    }
+
+    pub fn fold_synonym<F: Fold + ?Sized>(folder: &mut F, syn: Synonym) -> Synonym {
+        let (desc, scope, ty, xrefs) = syn.into_parts();
+        Synonym::with_type_and_xrefs(
+            folder.fold_quoted_string(desc),
+            folder.fold_synonym_scope(scope),
+            ty.map(|id| folder.fold_synonymtype_ident(id)),
+            folder.fold_xref_list(xrefs),
+        )
+    }
+
+    #[allow(unused_variables)]
+    pub fn fold_synonym_scope<F: Fold + ?Sized>(
+        folder: &mut F,
+        scope: SynonymScope,
+    ) -> SynonymScope {
+        scope
+    }
+
+    pub fn fold_synonymtype_ident<F: Fold + ?Sized>(
+        folder: &mut F,
+        id: SynonymTypeIdent,
+    ) -> SynonymTypeIdent {
+        SynonymTypeIdent::from(folder.fold_ident(Ident::from(id)))
+    }
+
+    pub fn fold_term_clause<F: Fold + ?Sized>(folder: &mut F, clause: TermClause) -> TermClause {
+        use self::TermClause::*;
+        match clause {
+            IsAnonymous(b) => IsAnonymous(b),
+            Name(s) => Name(Box::new(folder.fold_unquoted_string(*s))),
+            Namespace(ns) => Namespace(Box::new(folder.fold_namespace_ident(*ns))),
+            AltId(id) => AltId(Box::new(folder.fold_ident(*id))),
+            Def(def) => Def(Box::new(folder.fold_definition(*def))),
+            Comment(s) => Comment(Box::new(folder.fold_unquoted_string(*s))),
+            Subset(id) => Subset(Box::new(folder.fold_subset_ident(*id))),
+            Synonym(s) => Synonym(Box::new(folder.fold_synonym(*s))),
+            Xref(x) => Xref(Box::new(folder.fold_xref(*x))),
+            Builtin(b) => Builtin(b),
+            PropertyValue(pv) => PropertyValue(Box::new(folder.fold_property_value(*pv))),
+            IsA(id) => IsA(Box::new(folder.fold_class_ident(*id))),
+            IntersectionOf(rid, cid) => IntersectionOf(
+                rid.map(|rel| Box::new(folder.fold_relation_ident(*rel))),
+                Box::new(folder.fold_class_ident(*cid)),
+            ),
+            UnionOf(id) => UnionOf(Box::new(folder.fold_class_ident(*id))),
+            EquivalentTo(id) => EquivalentTo(Box::new(folder.fold_class_ident(*id))),
+            DisjointFrom(id) => DisjointFrom(Box::new(folder.fold_class_ident(*id))),
+            Relationship(rid, cid) => Relationship(
+                Box::new(folder.fold_relation_ident(*rid)),
+                Box::new(folder.fold_class_ident(*cid)),
+            ),
+            IsObsolete(b) => IsObsolete(b),
+            ReplacedBy(id) => ReplacedBy(Box::new(folder.fold_class_ident(*id))),
+            Consider(id) => Consider(Box::new(folder.fold_class_ident(*id))),
+            CreatedBy(s) => CreatedBy(Box::new(folder.fold_unquoted_string(*s))),
+            CreationDate(dt) => CreationDate(Box::new(folder.fold_iso_date(*dt))),
         }
+    }
 
-        #[test]
-        fn decompact_obolibrary() {
-            let mut doc = OboDoc::from_str(
-                "[Term]
-                id: MS:1000031
-                ",
-            )
-            .unwrap();
-            IdDecompactor::new().visit_doc(&mut doc);
+    pub fn fold_term_frame<F: Fold + ?Sized>(
+        folder: &mut F,
+        frame: TermFrame,
+    ) -> Option<TermFrame> {
+        let (id, clauses) = frame.into_parts();
+        let id = id.map(|id| folder.fold_class_ident(id));
+        let clauses = clauses
+            .into_iter()
+            .flat_map(|line| folder.fold_term_line(line))
+            .collect();
+        Some(TermFrame::with_clauses(id, clauses))
+    }
 
-            if let Some(EntityFrame::Term(term)) = doc.entities().get(0) {
-                let url = Url::from_str("http://purl.obolibrary.org/obo/MS_1000031").unwrap();
-                assert_eq!(term.id().as_inner().as_ref(), &Ident::from(url),);
-            } else {
-                unreachable!()
-            }
+    pub fn fold_term_line<F: Fold + ?Sized>(
+        folder: &mut F,
+        mut line: Line<TermClause>,
+    ) -> Vec<Line<TermClause>> {
+        fold_line_qualifiers(folder, &mut line);
+        vec![line.map(|clause| folder.fold_term_clause(clause))]
+    }
+
+    pub fn fold_typedef_clause<F: Fold + ?Sized>(
+        folder: &mut F,
+        clause: TypedefClause,
+    ) -> TypedefClause {
+        use self::TypedefClause::*;
+        match clause {
+            IsAnonymous(b) => IsAnonymous(b),
+            Name(s) => Name(Box::new(folder.fold_unquoted_string(*s))),
+            Namespace(ns) => Namespace(Box::new(folder.fold_namespace_ident(*ns))),
+            AltId(id) => AltId(Box::new(folder.fold_ident(*id))),
+            Def(def) => Def(Box::new(folder.fold_definition(*def))),
+            Comment(s) => Comment(Box::new(folder.fold_unquoted_string(*s))),
+            Subset(id) => Subset(Box::new(folder.fold_subset_ident(*id))),
+            Synonym(s) => Synonym(Box::new(folder.fold_synonym(*s))),
+            Xref(x) => Xref(Box::new(folder.fold_xref(*x))),
+            PropertyValue(pv) => PropertyValue(Box::new(folder.fold_property_value(*pv))),
+            Domain(id) => Domain(Box::new(folder.fold_class_ident(*id))),
+            Range(id) => Range(Box::new(folder.fold_class_ident(*id))),
+            Builtin(b) => Builtin(b),
+
+            IsAntiSymmetric(b) => IsAntiSymmetric(b),
+            IsCyclic(b) => IsCyclic(b),
+            IsReflexive(b) => IsReflexive(b),
+            IsSymmetric(b) => IsSymmetric(b),
+            IsAsymmetric(b) => IsAsymmetric(b),
+            IsTransitive(b) => IsTransitive(b),
+            IsFunctional(b) => IsFunctional(b),
+            IsInverseFunctional(b) => IsInverseFunctional(b),
+
+            IsA(id) => IsA(Box::new(folder.fold_relation_ident(*id))),
+            IntersectionOf(id) => IntersectionOf(Box::new(folder.fold_relation_ident(*id))),
+            UnionOf(id) => UnionOf(Box::new(folder.fold_relation_ident(*id))),
+            EquivalentTo(id) => EquivalentTo(Box::new(folder.fold_relation_ident(*id))),
+            DisjointFrom(id) => DisjointFrom(Box::new(folder.fold_relation_ident(*id))),
+            InverseOf(id) => InverseOf(Box::new(folder.fold_relation_ident(*id))),
+            TransitiveOver(id) => TransitiveOver(Box::new(folder.fold_relation_ident(*id))),
+            EquivalentToChain(r1, r2) => EquivalentToChain(
+                Box::new(folder.fold_relation_ident(*r1)),
+                Box::new(folder.fold_relation_ident(*r2)),
+            ),
+            HoldsOverChain(r1, r2) => HoldsOverChain(
+                Box::new(folder.fold_relation_ident(*r1)),
+                Box::new(folder.fold_relation_ident(*r2)),
+            ),
+            Relationship(r1, r2) => Relationship(
+                Box::new(folder.fold_relation_ident(*r1)),
+                Box::new(folder.fold_relation_ident(*r2)),
+            ),
+            DisjointOver(id) => DisjointOver(Box::new(folder.fold_relation_ident(*id))),
+            IsObsolete(b) => IsObsolete(b),
+            ReplacedBy(id) => ReplacedBy(Box::new(folder.fold_relation_ident(*id))),
+            Consider(id) => Consider(Box::new(folder.fold_ident(*id))),
+            CreatedBy(s) => CreatedBy(Box::new(folder.fold_unquoted_string(*s))),
+            CreationDate(dt) => CreationDate(Box::new(folder.fold_iso_date(*dt))),
+            ExpandAssertionTo(s, xrefs) => ExpandAssertionTo(
+                Box::new(folder.fold_quoted_string(*s)),
+                Box::new(folder.fold_xref_list(*xrefs)),
+            ),
+            ExpandExpressionTo(s, xrefs) => ExpandExpressionTo(
+                Box::new(folder.fold_quoted_string(*s)),
+                Box::new(folder.fold_xref_list(*xrefs)),
+            ),
+
+            IsMetadataTag(b) => IsMetadataTag(b),
+            IsClassLevel(b) => IsClassLevel(b),
         }
     }
 
-    mod id_compactor {
+    pub fn fold_typedef_frame<F: Fold + ?Sized>(
+        folder: &mut F,
+        frame: TypedefFrame,
+    ) -> Option<TypedefFrame> {
+        let (id, clauses) = frame.into_parts();
+        let id = id.map(|id| folder.fold_relation_ident(id));
+        let clauses = clauses
+            .into_iter()
+            .flat_map(|line| folder.fold_typedef_line(line))
+            .collect();
+        Some(TypedefFrame::with_clauses(id, clauses))
+    }
 
-        use pretty_assertions::assert_eq;
-        use std::str::FromStr;
+    pub fn fold_typedef_line<F: Fold + ?Sized>(
+        folder: &mut F,
+        mut line: Line<TypedefClause>,
+    ) -> Vec<Line<TypedefClause>> {
+        fold_line_qualifiers(folder, &mut line);
+        vec![line.map(|clause| folder.fold_typedef_clause(clause))]
+    }
 
-        use crate::ast::EntityFrame;
-        use crate::ast::Ident;
-        use crate::ast::OboDoc;
-        use crate::ast::PrefixedIdent;
-        use crate::ast::TermClause;
-        use crate::ast::Url;
-        use crate::ast::Xref;
+    #[allow(unused_variables)]
+    pub fn fold_unprefixed_ident<F: Fold + ?Sized>(
+        folder: &mut F,
+        id: UnprefixedIdent,
+    ) -> UnprefixedIdent {
+        id
+    }
 
-        use super::IdCompactor;
-        use super::VisitMut;
+    #[allow(unused_variables)]
+    pub fn fold_unquoted_string<F: Fold + ?Sized>(
+        folder: &mut F,
+        string: UnquotedString,
+    ) -> UnquotedString {
+        string
+    }
 
-        #[test]
-        fn compact_idspace() {
-            let mut doc = OboDoc::from_str(
-                "idspace: Wikipedia http://en.wikipedia.org/wiki/
+    #[allow(unused_variables)]
+    pub fn fold_url<F: Fold + ?Sized>(folder: &mut F, url: Url) -> Url {
+        url
+    }
 
-                [Term]
-                id: TST:001
-                name: Pupil
-                xref: http://en.wikipedia.org/wiki/Pupil
-                ",
-            )
-            .unwrap();
-            IdCompactor::new().visit_doc(&mut doc);
+    pub fn fold_xref<F: Fold + ?Sized>(folder: &mut F, xref: Xref) -> Xref {
+        let (id, desc) = xref.into_parts();
+        Xref::with_desc(
+            folder.fold_ident(id),
+            desc.map(|d| folder.fold_quoted_string(d)),
+        )
+    }
 
-            if let Some(EntityFrame::Term(term)) = doc.entities().get(0) {
-                assert_eq!(
-                    term.clauses()[1].as_inner(),
-                    &TermClause::Xref(Box::new(Xref::new(PrefixedIdent::new(
+    pub fn fold_xref_list<F: Fold + ?Sized>(folder: &mut F, xrefs: XrefList) -> XrefList {
+        xrefs
+            .into_iter()
+            .map(|xref| folder.fold_xref(xref))
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// The result of a `VisitControl` method, deciding how the traversal goes on.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VisitFlow<B = ()> {
+    /// Visit the children of the current node, then its siblings.
+    Continue,
+    /// Skip the children of the current node, and go on with its siblings.
+    SkipChildren,
+    /// Stop the traversal, returning the given value.
+    Break(B),
+}
+
+/// The position of a node in the syntax tree traversed by `VisitControl`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VisitContext<'ast> {
+    entity: Option<&'ast Ident>,
+    clause: Option<&'ast str>,
+}
+
+impl<'ast> VisitContext<'ast> {
+    /// Get the identifier of the enclosing entity frame, if any.
+    ///
+    /// The identifier is set for the entity frame itself, and all of its
+    /// descendants, and is `None` while visiting the header frame.
+    pub fn entity(&self) -> Option<&'ast Ident> {
+        self.entity
+    }
+
+    /// Get the tag of the enclosing clause, if any.
+    ///
+    /// The tag is set for the clause itself, all of its descendants, and
+    /// the qualifiers of the line declaring the clause.
+    pub fn clause(&self) -> Option<&'ast str> {
+        self.clause
+    }
+}
+
+/// Syntax tree traversal with early exit and knowledge of the current position.
+///
+/// Each method is called before the children of a node are visited, and
+/// returns a `VisitFlow` to decide whether to visit them, to skip them, or
+/// to stop the traversal altogether. Default implementations continue the
+/// traversal, so that only the methods of interest need to be implemented.
+///
+/// The traversal order is the same as the default `Visit` implementation.
+/// The `VisitContext` passed to each method gives the identifier of the
+/// enclosing entity frame and the tag of the enclosing clause.
+///
+/// # Example
+/// The following visitor will find the first clause of a document which
+/// references an identifier with a given prefix, skipping the xrefs:
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// # use fastobo::visit::*;
+/// struct PrefixFinder(&'static str);
+///
+/// impl<'ast> VisitControl<'ast> for PrefixFinder {
+///     type Break = (&'ast Ident, &'ast str);
+///
+///     fn visit_xref(&mut self, _ctx: &VisitContext<'ast>, _xref: &'ast Xref) -> VisitFlow<Self::Break> {
+///         VisitFlow::SkipChildren
+///     }
+///
+///     fn visit_prefixed_ident(
+///         &mut self,
+///         ctx: &VisitContext<'ast>,
+///         id: &'ast PrefixedIdent,
+///     ) -> VisitFlow<Self::Break> {
+///         match (ctx.entity(), ctx.clause()) {
+///             (Some(entity), Some(tag)) if id.prefix() == self.0 => VisitFlow::Break((entity, tag)),
+///             _ => VisitFlow::Continue,
+///         }
+///     }
+/// }
+///
+/// let doc = fastobo::from_str(
+/// "[Term]
+/// id: TST:001
+/// xref: UBERON:0000001
+/// relationship: part_of UBERON:0000002
+/// ").unwrap();
+///
+/// let (entity, tag) = PrefixFinder("UBERON").walk_doc(&doc).unwrap();
+/// assert_eq!(entity.to_string(), "TST:001");
+/// assert_eq!(tag, "relationship");
+/// ```
+#[allow(unused_variables)]
+pub trait VisitControl<'ast> {
+    /// The type of the value returned when the traversal is stopped.
+    type Break;
+
+    /// Traverse a whole OBO document, returning a value if stopped early.
+    fn walk_doc(&mut self, doc: &'ast OboDoc) -> Option<Self::Break> {
+        let mut walker = Walker::new(self);
+        walker.visit_doc(doc);
+        walker.result
+    }
+
+    /// Traverse an OBO header frame, returning a value if stopped early.
+    fn walk_header_frame(&mut self, header: &'ast HeaderFrame) -> Option<Self::Break> {
+        let mut walker = Walker::new(self);
+        walker.visit_header_frame(header);
+        walker.result
+    }
+
+    /// Traverse an OBO entity frame, returning a value if stopped early.
+    fn walk_entity_frame(&mut self, frame: &'ast EntityFrame) -> Option<Self::Break> {
+        let mut walker = Walker::new(self);
+        walker.visit_entity_frame(frame);
+        walker.result
+    }
+
+    fn visit_class_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast ClassIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_definition(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        def: &'ast Definition,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_doc(&mut self, ctx: &VisitContext<'ast>, doc: &'ast OboDoc) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_entity_frame(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        frame: &'ast EntityFrame,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_header_clause(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        clause: &'ast HeaderClause,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_header_frame(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        header: &'ast HeaderFrame,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_ident(&mut self, ctx: &VisitContext<'ast>, id: &'ast Ident) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_ident_prefix(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        prefix: &'ast IdentPrefix,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_import(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        import: &'ast Import,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_instance_clause(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        clause: &'ast InstanceClause,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_instance_frame(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        frame: &'ast InstanceFrame,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_instance_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast InstanceIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_iso_date(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        date: &'ast IsoDateTime,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_literal_property_value(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        pv: &'ast LiteralPropertyValue,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_naive_date(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        date: &'ast NaiveDateTime,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_namespace_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast NamespaceIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_property_value(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        pv: &'ast PropertyValue,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_prefixed_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast PrefixedIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_qualifier(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        qualifier: &'ast Qualifier,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_qualifier_list(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        qualifiers: &'ast QualifierList,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_quoted_string(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        string: &'ast QuotedString,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_relation_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast RelationIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_resource_property_value(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        pv: &'ast ResourcePropertyValue,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_subset_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast SubsetIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_synonym(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        syn: &'ast Synonym,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_synonym_scope(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        scope: &'ast SynonymScope,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_synonymtype_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast SynonymTypeIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_term_clause(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        clause: &'ast TermClause,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_term_frame(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        frame: &'ast TermFrame,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_typedef_clause(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        clause: &'ast TypedefClause,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_typedef_frame(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        frame: &'ast TypedefFrame,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_unprefixed_ident(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        id: &'ast UnprefixedIdent,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_unquoted_string(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        string: &'ast UnquotedString,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_url(&mut self, ctx: &VisitContext<'ast>, url: &'ast Url) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_xref(&mut self, ctx: &VisitContext<'ast>, xref: &'ast Xref) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+
+    fn visit_xref_list(
+        &mut self,
+        ctx: &VisitContext<'ast>,
+        xrefs: &'ast XrefList,
+    ) -> VisitFlow<Self::Break> {
+        VisitFlow::Continue
+    }
+}
+
+/// A `Visit` implementation driving a `VisitControl` visitor.
+struct Walker<'v, 'ast, V: VisitControl<'ast> + ?Sized> {
+    visitor: &'v mut V,
+    context: VisitContext<'ast>,
+    result: Option<V::Break>,
+}
+
+impl<'v, 'ast, V: VisitControl<'ast> + ?Sized> Walker<'v, 'ast, V> {
+    fn new(visitor: &'v mut V) -> Self {
+        Self {
+            visitor,
+            context: VisitContext::default(),
+            result: None,
+        }
+    }
+
+    /// Call a visitor method, returning whether the children should be visited.
+    fn enter<F>(&mut self, method: F) -> bool
+    where
+        F: FnOnce(&mut V, &VisitContext<'ast>) -> VisitFlow<V::Break>,
+    {
+        if self.result.is_some() {
+            return false;
+        }
+        match method(self.visitor, &self.context) {
+            VisitFlow::Continue => true,
+            VisitFlow::SkipChildren => false,
+            VisitFlow::Break(value) => {
+                self.result = Some(value);
+                false
+            }
+        }
+    }
+
+    /// Visit an entity clause and its qualifiers within the clause context.
+    fn visit_line<T: OboClause>(&mut self, line: &'ast Line<T>, method: fn(&mut Self, &'ast T)) {
+        self.context.clause = Some(line.as_inner().tag());
+        method(self, line.as_inner());
+        if let Some(qualifiers) = line.qualifiers() {
+            self.visit_qualifier_list(qualifiers);
+        }
+        self.context.clause = None;
+    }
+}
+
+macro_rules! walk {
+    ($($method:ident($ty:ty);)*) => {$(
+        fn $method(&mut self, node: &'ast $ty) {
+            if self.enter(|v, ctx| v.$method(ctx, node)) {
+                visit::$method(self, node)
+            }
+        }
+    )*};
+}
+
+impl<'v, 'ast, V: VisitControl<'ast> + ?Sized> Visit<'ast> for Walker<'v, 'ast, V> {
+    walk! {
+        visit_class_ident(ClassIdent);
+        visit_definition(Definition);
+        visit_doc(OboDoc);
+        visit_entity_frame(EntityFrame);
+        visit_header_frame(HeaderFrame);
+        visit_ident(Ident);
+        visit_ident_prefix(IdentPrefix);
+        visit_import(Import);
+        visit_instance_clause(InstanceClause);
+        visit_instance_ident(InstanceIdent);
+        visit_iso_date(IsoDateTime);
+        visit_literal_property_value(LiteralPropertyValue);
+        visit_naive_date(NaiveDateTime);
+        visit_namespace_ident(NamespaceIdent);
+        visit_property_value(PropertyValue);
+        visit_prefixed_ident(PrefixedIdent);
+        visit_qualifier(Qualifier);
+        visit_qualifier_list(QualifierList);
+        visit_quoted_string(QuotedString);
+        visit_relation_ident(RelationIdent);
+        visit_resource_property_value(ResourcePropertyValue);
+        visit_subset_ident(SubsetIdent);
+        visit_synonym(Synonym);
+        visit_synonym_scope(SynonymScope);
+        visit_synonymtype_ident(SynonymTypeIdent);
+        visit_term_clause(TermClause);
+        visit_typedef_clause(TypedefClause);
+        visit_unprefixed_ident(UnprefixedIdent);
+        visit_unquoted_string(UnquotedString);
+        visit_url(Url);
+        visit_xref(Xref);
+        visit_xref_list(XrefList);
+    }
+
+    fn visit_header_clause(&mut self, clause: &'ast HeaderClause) {
+        self.context.clause = Some(clause.tag());
+        if self.enter(|v, ctx| v.visit_header_clause(ctx, clause)) {
+            visit::visit_header_clause(self, clause);
+        }
+        self.context.clause = None;
+    }
+
+    fn visit_instance_frame(&mut self, frame: &'ast InstanceFrame) {
+        self.context.entity = Some(frame.id().as_inner().as_ref());
+        if self.enter(|v, ctx| v.visit_instance_frame(ctx, frame)) {
+            self.visit_instance_ident(frame.id().as_inner());
+            for line in frame.clauses() {
+                self.visit_line(line, Self::visit_instance_clause);
+            }
+        }
+        self.context.entity = None;
+    }
+
+    fn visit_term_frame(&mut self, frame: &'ast TermFrame) {
+        self.context.entity = Some(frame.id().as_inner().as_ref());
+        if self.enter(|v, ctx| v.visit_term_frame(ctx, frame)) {
+            self.visit_class_ident(frame.id().as_inner());
+            for line in frame.clauses() {
+                self.visit_line(line, Self::visit_term_clause);
+            }
+        }
+        self.context.entity = None;
+    }
+
+    fn visit_typedef_frame(&mut self, frame: &'ast TypedefFrame) {
+        self.context.entity = Some(frame.id().as_inner().as_ref());
+        if self.enter(|v, ctx| v.visit_typedef_frame(ctx, frame)) {
+            self.visit_relation_ident(frame.id().as_inner());
+            for line in frame.clauses() {
+                self.visit_line(line, Self::visit_typedef_clause);
+            }
+        }
+        self.context.entity = None;
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A visitor that will compact identifiers in an OBO document.
+///
+/// # Usage
+/// The compactor will follow the rules from the OBO specification:
+/// * if the document declares an IDSpace prefix `p` that maps to an Url `u`,
+///   URL identifiers that can be factorized as `{u}{v}` will be replaced
+///   by Prefixed identifiers `{p}:{v}`
+/// * if the document does not declare an IDSpace `p'`, URL identifiers that
+///   can be factorized as `http://purl.obolibrary.org/obo/{p'}_{id}`
+///   will be replaced by Prefixed identifiers `{p'}:{id}`.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use std::str::FromStr;
+/// # use std::string::ToString;
+/// # use fastobo::visit::*;
+/// let mut doc = fastobo::from_str(
+/// "[Term]
+/// id: http://purl.obolibrary.org/obo/BFO_0000055
+/// ").unwrap();
+///
+/// IdCompactor::new().visit_doc(&mut doc);
+/// assert_eq!(doc.to_string(),
+/// "[Term]
+/// id: BFO:0000055
+/// ");
+/// ```
+///
+/// # See also
+/// * [IdDecompactor](./struct.IdDecompactor.html) that does the opposite.
+/// * [PrefixMap](../semantics/struct.PrefixMap.html) to compact a single URL.
+#[derive(Clone, Debug, Default)]
+pub struct IdCompactor {
+    prefixes: PrefixMap,
+}
+
+impl IdCompactor {
+    pub fn new() -> Self {
+        Self {
+            prefixes: PrefixMap::new(),
+        }
+    }
+}
+
+impl VisitMut for IdCompactor {
+    fn visit_header_frame(&mut self, header: &mut HeaderFrame) {
+        // collect all IDSpaces before processing the header
+        self.prefixes = PrefixMap::from(&*header);
+
+        // process the header as normal
+        visit_mut::visit_header_frame(self, header);
+    }
+
+    fn visit_ident(&mut self, id: &mut Ident) {
+        // the compacted id.
+        let mut new: Option<PrefixedIdent> = None;
+        if let Ident::Url(ref u) = id {
+            new = self.prefixes.compact_prefixed(u);
+        }
+
+        if let Some(new_id) = new {
+            *id = Ident::Prefixed(Box::new(new_id));
+        }
+    }
+}
+
+/// A visitor that will decompact identifiers in an OBO document.
+///
+/// # Usage
+/// The decompactor will follow the rules from the OBO specification:
+/// * if the document declares an IDSpace prefix `p` that maps to an Url `u`,
+///   Prefixed identifiers `{p}:{id}` will be replaced by URLs identifiers
+///   `{u}{id}`.
+/// * if the document does not declare an IDSpace `p'`, Prefixed identifiers
+///   `{p}:{id}` with be replaced by URLs identifiers
+///   `http://purl.obolibrary.org/obo/{p'}_{id}`.
+///
+/// # See also
+/// * [IdCompactor](./struct.IdCompactor.html) that does the opposite.
+/// * [PrefixMap](../semantics/struct.PrefixMap.html) to expand a single identifier.
+#[derive(Clone, Debug, Default)]
+pub struct IdDecompactor {
+    prefixes: PrefixMap,
+}
+
+impl IdDecompactor {
+    pub fn new() -> Self {
+        Self {
+            prefixes: PrefixMap::new(),
+        }
+    }
+}
+
+impl VisitMut for IdDecompactor {
+    fn visit_header_frame(&mut self, header: &mut HeaderFrame) {
+        // collect all IDSpaces before processing the header
+        self.prefixes = PrefixMap::from(&*header);
+
+        // process the header as normal
+        visit_mut::visit_header_frame(self, header)
+    }
+
+    fn visit_ident(&mut self, id: &mut Ident) {
+        // the decompacted id.
+        let mut new: Option<Url> = None;
+        if let Ident::Prefixed(p) = id {
+            new = Some(self.prefixes.expand_prefixed(p));
+        }
+
+        if let Some(new_url) = new {
+            *id = Ident::Url(Box::new(new_url));
+        }
+    }
+}
+
+/// A visitor that will remap identifiers in an OBO document.
+///
+/// # Usage
+/// The remapper is driven by an identifier map and a prefix map:
+/// * identifiers found in the identifier map are replaced by their target,
+///   whatever their kind (prefixed, unprefixed or URL).
+/// * otherwise, prefixed identifiers `{p}:{id}` whose prefix `p` is found
+///   in the prefix map are replaced by `{p'}:{id}`, where `p'` is the new
+///   prefix.
+///
+/// Prefixes appearing in the header (in `idspace` and `treat-xrefs-*`
+/// clauses) are also remapped using the prefix map, so that a document
+/// stays consistent after a prefix was renamed. Namespace identifiers are
+/// never remapped.
+///
+/// Entity and xref identifiers that could not be remapped with either map
+/// are recorded, and can be retrieved with the [`unmapped`](#method.unmapped)
+/// method after the visitor was run. Other identifiers, such as qualifier
+/// keys, subsets or literal datatypes, are remapped but never recorded.
+/// Likewise, when a prefix map was given, IDspace prefixes missing from it
+/// can be retrieved with the [`unmapped_prefixes`](#method.unmapped_prefixes)
+/// method.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// # use fastobo::visit::*;
+/// let mut doc = fastobo::from_str(
+/// "idspace: MS http://purl.obolibrary.org/obo/MS_
+///
+/// [Term]
+/// id: MS:1000031
+/// is_a: MS:1000000
+/// xref: PSI:MS
+/// ").unwrap();
+///
+/// let mut remapper = IdRemapper::new().and_prefix("MS", "MSO");
+/// remapper.visit_doc(&mut doc);
+///
+/// assert_eq!(doc.to_string(),
+/// "idspace: MSO http://purl.obolibrary.org/obo/MS_
+///
+/// [Term]
+/// id: MSO:1000031
+/// is_a: MSO:1000000
+/// xref: PSI:MS
+/// ");
+///
+/// let xref = Ident::from(PrefixedIdent::new("PSI", "MS"));
+/// assert!(remapper.unmapped().contains(&xref));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IdRemapper {
+    ids: HashMap<Ident, Ident>,
+    prefixes: HashMap<IdentPrefix, IdentPrefix>,
+    unmapped: BTreeSet<Ident>,
+    unmapped_prefixes: BTreeSet<IdentPrefix>,
+    tracking: bool,
+}
+
+impl IdRemapper {
+    /// Create a new remapper with empty identifier and prefix maps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new remapper using the given identifier map.
+    pub fn with_ids(ids: HashMap<Ident, Ident>) -> Self {
+        Self {
+            ids,
+            ..Default::default()
+        }
+    }
+
+    /// Create a new remapper using the given prefix map.
+    pub fn with_prefixes(prefixes: HashMap<IdentPrefix, IdentPrefix>) -> Self {
+        Self {
+            prefixes,
+            ..Default::default()
+        }
+    }
+
+    /// Add a mapping from an identifier to another one.
+    pub fn and_id<F, T>(mut self, from: F, to: T) -> Self
+    where
+        F: Into<Ident>,
+        T: Into<Ident>,
+    {
+        self.ids.insert(from.into(), to.into());
+        self
+    }
+
+    /// Add a mapping from an identifier prefix to another one.
+    pub fn and_prefix<F, T>(mut self, from: F, to: T) -> Self
+    where
+        F: Into<IdentPrefix>,
+        T: Into<IdentPrefix>,
+    {
+        self.prefixes.insert(from.into(), to.into());
+        self
+    }
+
+    /// Get the identifiers that could not be remapped so far.
+    pub fn unmapped(&self) -> &BTreeSet<Ident> {
+        &self.unmapped
+    }
+
+    /// Extract the identifiers that could not be remapped so far.
+    pub fn into_unmapped(self) -> BTreeSet<Ident> {
+        self.unmapped
+    }
+
+    /// Get the IDspace prefixes that could not be remapped so far.
+    ///
+    /// Prefixes are only recorded if the prefix map is not empty, so that a
+    /// remapper only renaming identifiers does not report every IDspace.
+    pub fn unmapped_prefixes(&self) -> &BTreeSet<IdentPrefix> {
+        &self.unmapped_prefixes
+    }
+
+    /// Remap an entity or xref identifier, recording it if it is unmapped.
+    fn visit_reference(&mut self, id: &mut Ident) {
+        self.tracking = true;
+        self.visit_ident(id);
+        self.tracking = false;
+    }
+}
+
+impl VisitMut for IdRemapper {
+    fn visit_ident(&mut self, id: &mut Ident) {
+        if let Some(new_id) = self.ids.get(id) {
+            *id = new_id.clone();
+            return;
+        }
+
+        if let Ident::Prefixed(p) = id {
+            if let Some(new_prefix) = self.prefixes.get(p.prefix()) {
+                **p = PrefixedIdent::new(new_prefix.as_str(), p.local());
+                return;
+            }
+        }
+
+        if self.tracking && !self.unmapped.contains(id) {
+            self.unmapped.insert(id.clone());
+        }
+    }
+
+    fn visit_ident_prefix(&mut self, prefix: &mut IdentPrefix) {
+        match self.prefixes.get(prefix.as_str()) {
+            Some(new_prefix) => *prefix = new_prefix.clone(),
+            None if !self.prefixes.is_empty() => {
+                self.unmapped_prefixes.insert(prefix.clone());
+            }
+            None => (),
+        }
+    }
+
+    fn visit_class_ident(&mut self, id: &mut ClassIdent) {
+        self.visit_reference(id.as_mut())
+    }
+
+    fn visit_instance_ident(&mut self, id: &mut InstanceIdent) {
+        self.visit_reference(id.as_mut())
+    }
+
+    fn visit_relation_ident(&mut self, id: &mut RelationIdent) {
+        self.visit_reference(id.as_mut())
+    }
+
+    fn visit_qualifier(&mut self, qualifier: &mut Qualifier) {
+        self.visit_ident(qualifier.key_mut().as_mut())
+    }
+
+    fn visit_resource_property_value(&mut self, pv: &mut ResourcePropertyValue) {
+        self.visit_relation_ident(pv.property_mut());
+        self.visit_reference(pv.target_mut());
+    }
+
+    fn visit_xref(&mut self, xref: &mut Xref) {
+        self.visit_reference(xref.id_mut())
+    }
+
+    #[allow(unused_variables)]
+    fn visit_namespace_ident(&mut self, id: &mut NamespaceIdent) {}
+}
+
+/// A visitor that will report malformed literals in an OBO document.
+///
+/// Every `property_value` clause with a literal value is checked against
+/// its declared datatype using
+/// [`LiteralPropertyValue::validate`](../ast/struct.LiteralPropertyValue.html#method.validate).
+/// Errors are recorded with the identifier of the entity frame they were
+/// found in, or `None` for the header frame.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// # use fastobo::visit::*;
+/// let doc = fastobo::from_str(
+/// r#"[Term]
+/// id: TST:001
+/// property_value: shoe_size "8" xsd:positiveInteger
+/// property_value: has_kids "maybe" xsd:boolean
+/// "#).unwrap();
+///
+/// let mut validator = LiteralValidator::new();
+/// validator.visit_doc(&doc);
+///
+/// let errors = validator.errors();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0.unwrap().to_string(), "TST:001");
+/// ```
+#[derive(Debug, Default)]
+pub struct LiteralValidator<'ast> {
+    frame: Option<&'ast Ident>,
+    errors: Vec<(Option<&'ast Ident>, LiteralError)>,
+}
+
+impl<'ast> LiteralValidator<'ast> {
+    /// Create a new validator without any recorded error.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the errors found so far, with the identifier of their frame.
+    pub fn errors(&self) -> &[(Option<&'ast Ident>, LiteralError)] {
+        &self.errors
+    }
+
+    /// Extract the errors found so far, with the identifier of their frame.
+    pub fn into_errors(self) -> Vec<(Option<&'ast Ident>, LiteralError)> {
+        self.errors
+    }
+}
+
+impl<'ast> Extend<(Option<&'ast Ident>, LiteralError)> for LiteralValidator<'ast> {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (Option<&'ast Ident>, LiteralError)>,
+    {
+        self.errors.extend(iter)
+    }
+}
+
+impl<'ast> IntoIterator for LiteralValidator<'ast> {
+    type Item = (Option<&'ast Ident>, LiteralError);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'ast> Visit<'ast> for LiteralValidator<'ast> {
+    fn visit_header_frame(&mut self, header: &'ast HeaderFrame) {
+        self.frame = None;
+        visit::visit_header_frame(self, header);
+    }
+
+    fn visit_term_frame(&mut self, frame: &'ast TermFrame) {
+        self.frame = Some(frame.id().as_inner().as_ref());
+        visit::visit_term_frame(self, frame);
+    }
+
+    fn visit_typedef_frame(&mut self, frame: &'ast TypedefFrame) {
+        self.frame = Some(frame.id().as_inner().as_ref());
+        visit::visit_typedef_frame(self, frame);
+    }
+
+    fn visit_instance_frame(&mut self, frame: &'ast InstanceFrame) {
+        self.frame = Some(frame.id().as_inner().as_ref());
+        visit::visit_instance_frame(self, frame);
+    }
+
+    fn visit_literal_property_value(&mut self, pv: &'ast LiteralPropertyValue) {
+        if let Err(error) = pv.validate() {
+            self.errors.push((self.frame, error));
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A helper running a `Visit` implementation over entity frames in parallel.
+///
+/// Entity frames are split in fixed-size chunks, which are distributed to a
+/// pool of worker threads. Each chunk is visited in order by a new visitor
+/// obtained from the `factory` function, and the visitors of all the chunks
+/// are then combined with the `merge` function, in the order of the frames.
+///
+/// Since chunks do not depend on the number of threads or on the order in
+/// which they are scheduled, the result is deterministic. It is the same as
+/// visiting all the frames with a single visitor provided `merge` combines
+/// the state of two visitors as if the second one had continued the
+/// traversal of the first.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// # use fastobo::visit::*;
+/// let doc = fastobo::from_str(
+/// r#"[Term]
+/// id: TST:001
+/// property_value: shoe_size "8" xsd:positiveInteger
+///
+/// [Term]
+/// id: TST:002
+/// property_value: has_kids "maybe" xsd:boolean
+/// "#).unwrap();
+///
+/// fn merge<'ast>(mut a: LiteralValidator<'ast>, b: LiteralValidator<'ast>) -> LiteralValidator<'ast> {
+///     a.extend(b);
+///     a
+/// }
+///
+/// let validator = ParallelVisit::new(LiteralValidator::new, merge).visit_doc(&doc);
+///
+/// let errors = validator.errors();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0.unwrap().to_string(), "TST:002");
+/// ```
+#[cfg(feature = "threading")]
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "threading")))]
+#[derive(Clone, Debug)]
+pub struct ParallelVisit<F, M> {
+    factory: F,
+    merge: M,
+    threads: NonZeroUsize,
+}
+
+#[cfg(feature = "threading")]
+impl<F, M> ParallelVisit<F, M> {
+    /// The number of entity frames visited by the same visitor.
+    const CHUNK_SIZE: usize = 64;
+
+    /// Create a new helper using as many threads as there are CPUs.
+    pub fn new(factory: F, merge: M) -> Self {
+        lazy_static! {
+            static ref THREADS: usize = num_cpus::get();
+        }
+        let threads = NonZeroUsize::new(*THREADS).unwrap_or(NonZeroUsize::MIN);
+        Self::with_threads(factory, merge, threads)
+    }
+
+    /// Create a new helper using the given number of threads.
+    pub fn with_threads(factory: F, merge: M, threads: NonZeroUsize) -> Self {
+        Self {
+            factory,
+            merge,
+            threads,
+        }
+    }
+
+    /// Visit a whole OBO document, returning the merged visitor.
+    ///
+    /// The header frame is visited on the current thread, by the visitor
+    /// placed first in the merge order.
+    ///
+    /// # Panics
+    /// Panics if a visitor panics in one of the worker threads.
+    pub fn visit_doc<'ast, V>(&self, doc: &'ast OboDoc) -> V
+    where
+        F: Fn() -> V + Sync,
+        M: Fn(V, V) -> V + Sync,
+        V: Visit<'ast> + Send,
+    {
+        let mut visitor = (self.factory)();
+        visitor.visit_header_frame(doc.header());
+        (self.merge)(visitor, self.visit_entities(doc.entities()))
+    }
+
+    /// Visit a sequence of entity frames, returning the merged visitor.
+    ///
+    /// # Panics
+    /// Panics if a visitor panics in one of the worker threads.
+    pub fn visit_entities<'ast, V>(&self, frames: &'ast [EntityFrame]) -> V
+    where
+        F: Fn() -> V + Sync,
+        M: Fn(V, V) -> V + Sync,
+        V: Visit<'ast> + Send,
+    {
+        let chunks: Vec<&'ast [EntityFrame]> = frames.chunks(Self::CHUNK_SIZE).collect();
+        let next = AtomicUsize::new(0);
+        let workers = self.threads.get().min(chunks.len()).max(1);
+
+        let mut results: Vec<(usize, V)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let chunk = match chunks.get(index) {
+                                Some(chunk) => chunk,
+                                None => break done,
+                            };
+                            let mut visitor = (self.factory)();
+                            for frame in chunk.iter() {
+                                visitor.visit_entity_frame(frame);
+                            }
+                            done.push((index, visitor));
+                        }
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| match handle.join() {
+                    Ok(done) => done,
+                    Err(payload) => std::panic::resume_unwind(payload),
+                })
+                .collect()
+        });
+
+        results.sort_unstable_by_key(|(index, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, visitor)| visitor)
+            .reduce(|a, b| (self.merge)(a, b))
+            .unwrap_or_else(|| (self.factory)())
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    mod id_decompactor {
+        use pretty_assertions::assert_eq;
+        use std::str::FromStr;
+
+        use crate::ast::EntityFrame;
+        use crate::ast::Ident;
+        use crate::ast::OboDoc;
+
+        use crate::ast::TermClause;
+        use crate::ast::Url;
+        use crate::ast::Xref;
+
+        use super::IdDecompactor;
+        use super::VisitMut;
+
+        #[test]
+        fn decompact_idspace() {
+            let mut doc = OboDoc::from_str(
+                "idspace: Wikipedia http://en.wikipedia.org/wiki/
+
+                [Term]
+                id: TST:001
+                name: Pupil
+                xref: Wikipedia:Pupil
+                ",
+            )
+            .unwrap();
+            IdDecompactor::new().visit_doc(&mut doc);
+
+            if let Some(EntityFrame::Term(term)) = doc.entities().first() {
+                let url = Url::from_str("http://en.wikipedia.org/wiki/Pupil").unwrap();
+                assert_eq!(
+                    term.clauses()[1].as_inner(),
+                    &TermClause::Xref(Box::new(Xref::new(url))),
+                );
+            } else {
+                unreachable!()
+            }
+        }
+
+        #[test]
+        fn decompact_obolibrary() {
+            let mut doc = OboDoc::from_str(
+                "[Term]
+                id: MS:1000031
+                ",
+            )
+            .unwrap();
+            IdDecompactor::new().visit_doc(&mut doc);
+
+            if let Some(EntityFrame::Term(term)) = doc.entities().first() {
+                let url = Url::from_str("http://purl.obolibrary.org/obo/MS_1000031").unwrap();
+                assert_eq!(term.id().as_inner().as_ref(), &Ident::from(url),);
+            } else {
+                unreachable!()
+            }
+        }
+
+        #[test]
+        fn decompact_qualifiers() {
+            let mut doc = OboDoc::from_str(
+                "idspace: Wikipedia http://en.wikipedia.org/wiki/
+
+                [Term]
+                id: TST:001
+                is_a: TST:002 {Wikipedia:Source=\"Pupil\"}
+                ",
+            )
+            .unwrap();
+            IdDecompactor::new().visit_doc(&mut doc);
+
+            if let Some(EntityFrame::Term(term)) = doc.entities().first() {
+                let url = Url::from_str("http://en.wikipedia.org/wiki/Source").unwrap();
+                let qualifiers = term.clauses()[0].qualifiers().unwrap();
+                assert_eq!(qualifiers[0].key().as_ref(), &Ident::from(url));
+            } else {
+                unreachable!()
+            }
+        }
+    }
+
+    mod id_compactor {
+
+        use pretty_assertions::assert_eq;
+        use std::str::FromStr;
+
+        use crate::ast::EntityFrame;
+        use crate::ast::Ident;
+        use crate::ast::OboDoc;
+        use crate::ast::PrefixedIdent;
+        use crate::ast::TermClause;
+        use crate::ast::Url;
+        use crate::ast::Xref;
+
+        use super::IdCompactor;
+        use super::VisitMut;
+
+        #[test]
+        fn compact_idspace() {
+            let mut doc = OboDoc::from_str(
+                "idspace: Wikipedia http://en.wikipedia.org/wiki/
+
+                [Term]
+                id: TST:001
+                name: Pupil
+                xref: http://en.wikipedia.org/wiki/Pupil
+                ",
+            )
+            .unwrap();
+            IdCompactor::new().visit_doc(&mut doc);
+
+            if let Some(EntityFrame::Term(term)) = doc.entities().first() {
+                assert_eq!(
+                    term.clauses()[1].as_inner(),
+                    &TermClause::Xref(Box::new(Xref::new(PrefixedIdent::new(
                         "Wikipedia",
                         "Pupil"
                     )))),
@@ -1190,7 +2783,7 @@ mod tests {
             .unwrap();
             IdCompactor::new().visit_doc(&mut doc);
 
-            if let Some(EntityFrame::Term(term)) = doc.entities().get(0) {
+            if let Some(EntityFrame::Term(term)) = doc.entities().first() {
                 assert_eq!(
                     term.id().as_inner().as_ref(),
                     &Ident::from(PrefixedIdent::new("MS", "1000031")),
@@ -1213,7 +2806,7 @@ mod tests {
             .unwrap();
             IdCompactor::new().visit_doc(&mut doc);
 
-            if let Some(EntityFrame::Term(term)) = doc.entities().get(0) {
+            if let Some(EntityFrame::Term(term)) = doc.entities().first() {
                 let url = Url::from_str("http://purl.obolibrary.org/obo/PMC_2823822").unwrap();
                 assert_eq!(
                     term.clauses()[0].as_inner(),
@@ -1223,5 +2816,484 @@ mod tests {
                 unreachable!()
             }
         }
+
+        #[test]
+        fn compact_qualifiers() {
+            let mut doc = OboDoc::from_str(
+                "idspace: Wikipedia http://en.wikipedia.org/wiki/
+
+                [Term]
+                id: TST:001
+                is_a: TST:002 {http://en.wikipedia.org/wiki/Source=\"Pupil\"}
+                ",
+            )
+            .unwrap();
+            IdCompactor::new().visit_doc(&mut doc);
+
+            if let Some(EntityFrame::Term(term)) = doc.entities().first() {
+                let qualifiers = term.clauses()[0].qualifiers().unwrap();
+                assert_eq!(
+                    qualifiers[0].key().as_ref(),
+                    &Ident::from(PrefixedIdent::new("Wikipedia", "Source")),
+                );
+            } else {
+                unreachable!()
+            }
+        }
+    }
+
+    mod id_remapper {
+
+        use pretty_assertions::assert_eq;
+        use std::str::FromStr;
+
+        use crate::ast::Ident;
+        use crate::ast::OboDoc;
+        use crate::ast::PrefixedIdent;
+        use crate::ast::UnprefixedIdent;
+
+        use super::IdRemapper;
+        use super::VisitMut;
+
+        #[test]
+        fn remap_ids() {
+            let mut doc = OboDoc::from_str(
+                "[Term]
+                id: TST:001
+                is_a: TST:002
+                relationship: part_of TST:002 {source=\"PMID:123\"}
+
+                [Typedef]
+                id: part_of
+                ",
+            )
+            .unwrap();
+
+            let mut remapper = IdRemapper::new()
+                .and_id(
+                    PrefixedIdent::new("TST", "002"),
+                    PrefixedIdent::new("TST", "003"),
+                )
+                .and_id(
+                    UnprefixedIdent::new("part_of"),
+                    PrefixedIdent::new("BFO", "0000050"),
+                );
+            remapper.visit_doc(&mut doc);
+
+            assert_eq!(
+                doc.to_string(),
+                "[Term]
+                id: TST:001
+                is_a: TST:003
+                relationship: BFO:0000050 TST:003 {source=\"PMID:123\"}
+
+                [Typedef]
+                id: BFO:0000050
+                "
+                .replace("                ", "")
+            );
+
+            let unmapped = remapper.into_unmapped();
+            assert!(unmapped.contains(&Ident::from(PrefixedIdent::new("TST", "001"))));
+            assert_eq!(unmapped.len(), 1);
+        }
+
+        #[test]
+        fn remap_prefixes() {
+            let mut doc = OboDoc::from_str(
+                "idspace: MS http://purl.obolibrary.org/obo/MS_
+                treat-xrefs-as-equivalent: MS
+                subsetdef: goslim \"GO slim\"
+
+                [Term]
+                id: MS:1000031
+                subset: goslim
+                xref: MS:1000032
+                property_value: MS:1000033 \"1\" xsd:integer
+                ",
+            )
+            .unwrap();
+
+            let mut remapper = IdRemapper::new().and_prefix("MS", "MSO");
+            remapper.visit_doc(&mut doc);
+
+            assert_eq!(
+                doc.to_string(),
+                "idspace: MSO http://purl.obolibrary.org/obo/MS_
+                treat-xrefs-as-equivalent: MSO
+                subsetdef: goslim \"GO slim\"
+
+                [Term]
+                id: MSO:1000031
+                subset: goslim
+                xref: MSO:1000032
+                property_value: MSO:1000033 \"1\" xsd:integer
+                "
+                .replace("                ", "")
+            );
+
+            assert!(remapper.unmapped().is_empty());
+            assert!(remapper.unmapped_prefixes().is_empty());
+        }
+
+        #[test]
+        fn unmapped_references() {
+            let mut doc = OboDoc::from_str(
+                "idspace: MS http://purl.obolibrary.org/obo/MS_
+                idspace: PSI http://psidev.info/
+
+                [Term]
+                id: MS:1000031
+                is_a: MS:1000000 {source=\"PMID:123\"}
+                xref: PSI:MS
+                synonym: \"spectrum\" EXACT PSI-MS-label []
+                property_value: has_units UO:0000010
+                property_value: MS:1000033 \"1\" xsd:integer
+                ",
+            )
+            .unwrap();
+
+            let mut remapper = IdRemapper::new().and_prefix("MS", "MSO");
+            remapper.visit_doc(&mut doc);
+
+            let unmapped: Vec<String> = remapper.unmapped().iter().map(Ident::to_string).collect();
+            assert_eq!(unmapped, vec!["PSI:MS", "UO:0000010", "has_units"]);
+            let prefixes: Vec<&str> = remapper
+                .unmapped_prefixes()
+                .iter()
+                .map(|p| p.as_str())
+                .collect();
+            assert_eq!(prefixes, vec!["PSI"]);
+        }
+
+        #[test]
+        fn unmapped_prefixes_without_prefix_map() {
+            let mut doc = OboDoc::from_str(
+                "idspace: MS http://purl.obolibrary.org/obo/MS_
+                idspace: PSI http://psidev.info/
+
+                [Term]
+                id: MS:1000031
+                ",
+            )
+            .unwrap();
+
+            let mut remapper = IdRemapper::new().and_id(
+                PrefixedIdent::new("MS", "1000031"),
+                PrefixedIdent::new("MS", "1000032"),
+            );
+            remapper.visit_doc(&mut doc);
+
+            assert!(remapper.unmapped().is_empty());
+            assert!(remapper.unmapped_prefixes().is_empty());
+        }
+    }
+
+    mod fold {
+
+        use pretty_assertions::assert_eq;
+        use std::str::FromStr;
+
+        use crate::ast::*;
+
+        use super::Fold;
+        use crate::visit::fold;
+
+        struct Identity;
+
+        impl Fold for Identity {}
+
+        #[test]
+        fn identity() {
+            let doc = crate::from_file("tests/data/ms.obo").unwrap();
+            assert_eq!(Identity.fold_doc(doc.clone()), doc);
+        }
+
+        #[test]
+        fn entity_kind() {
+            struct TermToInstance;
+
+            impl Fold for TermToInstance {
+                fn fold_entity_frame(&mut self, frame: EntityFrame) -> Option<EntityFrame> {
+                    let term = match frame {
+                        EntityFrame::Term(term) => *term,
+                        other => return fold::fold_entity_frame(self, other),
+                    };
+                    let (id, clauses) = term.into_parts();
+                    let id = id.map(|id| InstanceIdent::from(Ident::from(id)));
+                    let clauses = clauses
+                        .into_iter()
+                        .filter_map(|line| match line.as_inner() {
+                            TermClause::Name(name) => {
+                                let name = InstanceClause::Name(name.clone());
+                                Some(line.map(|_| name))
+                            }
+                            TermClause::IsA(cls) => {
+                                let cls = InstanceClause::InstanceOf(cls.clone());
+                                Some(line.map(|_| cls))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    Some(InstanceFrame::with_clauses(id, clauses).into())
+                }
+            }
+
+            let doc = OboDoc::from_str(
+                "[Term]
+                id: TST:001
+                name: test
+                is_a: TST:002 ! a comment
+                is_obsolete: true
+
+                [Typedef]
+                id: part_of
+                ",
+            )
+            .unwrap();
+
+            assert_eq!(
+                TermToInstance.fold_doc(doc).to_string(),
+                "[Instance]
+                id: TST:001
+                name: test
+                instance_of: TST:002 ! a comment
+
+                [Typedef]
+                id: part_of
+                "
+                .replace("                ", "")
+            );
+        }
+
+        #[test]
+        fn split_lines() {
+            struct SplitSynonymXrefs;
+
+            impl Fold for SplitSynonymXrefs {
+                fn fold_term_line(&mut self, line: Line<TermClause>) -> Vec<Line<TermClause>> {
+                    let syn = match line.as_inner() {
+                        TermClause::Synonym(syn) if syn.xrefs().len() > 1 => syn.clone(),
+                        _ => return fold::fold_term_line(self, line),
+                    };
+                    let (desc, scope, ty, xrefs) = syn.into_parts();
+                    xrefs
+                        .into_iter()
+                        .map(|xref| {
+                            let list = XrefList::from(vec![xref]);
+                            let s = Synonym::with_type_and_xrefs(
+                                desc.clone(),
+                                scope.clone(),
+                                ty.clone(),
+                                list,
+                            );
+                            Line::from(TermClause::Synonym(Box::new(s)))
+                        })
+                        .collect()
+                }
+
+                fn fold_qualifier(&mut self, qualifier: Qualifier) -> Option<Qualifier> {
+                    if qualifier.has_key("source") {
+                        None
+                    } else {
+                        fold::fold_qualifier(self, qualifier)
+                    }
+                }
+            }
+
+            let doc = OboDoc::from_str(
+                "[Term]
+                id: TST:001
+                synonym: \"test\" EXACT [TST:002, TST:003]
+                is_a: TST:004 {source=\"PMID:1\"}
+                is_a: TST:005 {source=\"PMID:2\", is_inferred=\"true\"}
+                ",
+            )
+            .unwrap();
+
+            assert_eq!(
+                SplitSynonymXrefs.fold_doc(doc).to_string(),
+                "[Term]
+                id: TST:001
+                synonym: \"test\" EXACT [TST:002]
+                synonym: \"test\" EXACT [TST:003]
+                is_a: TST:004
+                is_a: TST:005 {is_inferred=\"true\"}
+                "
+                .replace("                ", "")
+            );
+        }
+    }
+
+    mod visit_control {
+
+        use pretty_assertions::assert_eq;
+        use std::str::FromStr;
+
+        use crate::ast::*;
+
+        use super::VisitContext;
+        use super::VisitControl;
+        use super::VisitFlow;
+
+        const DOC: &str = "subsetdef: slim \"TST slim\"
+
+        [Term]
+        id: TST:001
+        is_a: TST:002 {TST:003=\"true\"}
+        xref: TST:004
+
+        [Term]
+        id: TST:005
+        is_obsolete: true
+        is_a: TST:006
+
+        [Typedef]
+        id: TST:007
+        ";
+
+        #[derive(Default)]
+        struct IdCollector {
+            ids: Vec<(Option<String>, Option<String>, String)>,
+        }
+
+        impl<'ast> VisitControl<'ast> for IdCollector {
+            type Break = ();
+
+            fn visit_term_frame(
+                &mut self,
+                _ctx: &VisitContext<'ast>,
+                frame: &'ast TermFrame,
+            ) -> VisitFlow {
+                if frame.is_obsolete() {
+                    VisitFlow::SkipChildren
+                } else {
+                    VisitFlow::Continue
+                }
+            }
+
+            fn visit_prefixed_ident(
+                &mut self,
+                ctx: &VisitContext<'ast>,
+                id: &'ast PrefixedIdent,
+            ) -> VisitFlow {
+                self.ids.push((
+                    ctx.entity().map(ToString::to_string),
+                    ctx.clause().map(String::from),
+                    id.to_string(),
+                ));
+                VisitFlow::Continue
+            }
+        }
+
+        #[test]
+        fn context() {
+            let doc = OboDoc::from_str(&DOC.replace("        ", "")).unwrap();
+            let mut collector = IdCollector::default();
+            assert_eq!(collector.walk_doc(&doc), None);
+
+            let s = |x: &str| Some(x.to_string());
+            assert_eq!(
+                collector.ids,
+                vec![
+                    (s("TST:001"), None, "TST:001".to_string()),
+                    (s("TST:001"), s("is_a"), "TST:002".to_string()),
+                    (s("TST:001"), s("is_a"), "TST:003".to_string()),
+                    (s("TST:001"), s("xref"), "TST:004".to_string()),
+                    (s("TST:007"), None, "TST:007".to_string()),
+                ]
+            );
+        }
+
+        #[test]
+        fn early_exit() {
+            struct FirstSubset;
+
+            impl<'ast> VisitControl<'ast> for FirstSubset {
+                type Break = (Option<&'ast str>, &'ast SubsetIdent);
+
+                fn visit_subset_ident(
+                    &mut self,
+                    ctx: &VisitContext<'ast>,
+                    id: &'ast SubsetIdent,
+                ) -> VisitFlow<Self::Break> {
+                    VisitFlow::Break((ctx.clause(), id))
+                }
+
+                fn visit_term_frame(
+                    &mut self,
+                    _ctx: &VisitContext<'ast>,
+                    _frame: &'ast TermFrame,
+                ) -> VisitFlow<Self::Break> {
+                    unreachable!("traversal should have stopped in the header")
+                }
+            }
+
+            let doc = OboDoc::from_str(&DOC.replace("        ", "")).unwrap();
+            let (tag, id) = FirstSubset.walk_doc(&doc).unwrap();
+            assert_eq!(tag, Some("subsetdef"));
+            assert_eq!(id.to_string(), "slim");
+        }
+
+        #[test]
+        fn walk_entity_frame() {
+            let doc = OboDoc::from_str(&DOC.replace("        ", "")).unwrap();
+            let mut collector = IdCollector::default();
+            collector.walk_entity_frame(&doc.entities()[1]);
+            assert!(collector.ids.is_empty());
+            collector.walk_entity_frame(&doc.entities()[2]);
+            assert_eq!(collector.ids.len(), 1);
+        }
+    }
+
+    #[cfg(feature = "threading")]
+    mod parallel_visit {
+
+        use std::num::NonZeroUsize;
+
+        use pretty_assertions::assert_eq;
+
+        use crate::ast::*;
+
+        use super::visit;
+        use super::ParallelVisit;
+        use super::Visit;
+
+        #[derive(Default)]
+        struct IdCollector<'ast> {
+            ids: Vec<&'ast Ident>,
+        }
+
+        impl<'ast> Visit<'ast> for IdCollector<'ast> {
+            fn visit_ident(&mut self, id: &'ast Ident) {
+                self.ids.push(id);
+                visit::visit_ident(self, id);
+            }
+        }
+
+        fn merge<'ast>(mut a: IdCollector<'ast>, b: IdCollector<'ast>) -> IdCollector<'ast> {
+            a.ids.extend(b.ids);
+            a
+        }
+
+        #[test]
+        fn deterministic() {
+            let doc = crate::from_file("tests/data/ms.obo").unwrap();
+            let mut expected = IdCollector::default();
+            expected.visit_doc(&doc);
+
+            for threads in [1, 3, 8].iter() {
+                let threads = NonZeroUsize::new(*threads).unwrap();
+                let collector = ParallelVisit::with_threads(IdCollector::default, merge, threads)
+                    .visit_doc(&doc);
+                assert_eq!(collector.ids, expected.ids);
+            }
+        }
+
+        #[test]
+        fn empty() {
+            let collector = ParallelVisit::new(IdCollector::default, merge).visit_entities(&[]);
+            assert!(collector.ids.is_empty());
+        }
     }
 }
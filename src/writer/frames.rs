@@ -0,0 +1,555 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs::DirBuilder;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering as AtomicOrdering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::ast::*;
+use crate::error::Error;
+use crate::error::Result;
+use crate::error::WriterError;
+use crate::parser::Parser;
+use crate::parser::SequentialParser;
+use crate::semantics::cmp_entities;
+
+use super::names::NameCommenter;
+use super::options::WriterOptions;
+use super::serializer::Serializer;
+
+/// The number of spill directories created by this process so far.
+static SPILL_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+/// The maximum number of runs merged at once.
+const MAX_FAN_IN: usize = 64;
+
+// ---------------------------------------------------------------------------
+
+/// A private directory holding the runs of a sorter, removed when dropped.
+///
+/// Runs are not created directly in the shared temporary directory, where
+/// their names could be guessed and replaced with symbolic links by other
+/// users: the directory is created with a fresh name, only accessible to
+/// the current user on Unix.
+#[derive(Debug)]
+struct SpillDir {
+    path: PathBuf,
+    runs: usize,
+}
+
+impl SpillDir {
+    /// Create a new private directory in `parent`.
+    fn create(parent: &Path) -> Result<Self> {
+        let mut builder = DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        let mut attempts = 0;
+        loop {
+            let n = SPILL_DIRS.fetch_add(1, AtomicOrdering::SeqCst);
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or_default();
+            let name = format!("fastobo-{}-{}-{:08x}", std::process::id(), n, nanos);
+            let path = parent.join(name);
+            match builder.create(&path) {
+                Ok(()) => return Ok(SpillDir { path, runs: 0 }),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists && attempts < 16 => attempts += 1,
+                Err(e) => return Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Write the given (sorted) entity frames to a new run.
+    fn run<I>(&mut self, entities: I) -> Result<Run>
+    where
+        I: IntoIterator<Item = Result<EntityFrame>>,
+    {
+        let run = Run {
+            path: self.path.join(format!("run-{}.obo", self.runs)),
+        };
+        self.runs += 1;
+
+        let options = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&run.path)?;
+        let mut file = BufWriter::new(options);
+        for entity in entities {
+            writeln!(file, "{}", entity?)?;
+        }
+        file.flush()?;
+
+        Ok(run)
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A sorted sequence of entity frames spilled to a temporary file.
+#[derive(Debug)]
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    /// Open the run to read its entity frames back.
+    fn open(&self) -> Result<impl Iterator<Item = Result<EntityFrame>>> {
+        let parser = SequentialParser::new(BufReader::new(File::open(&self.path)?));
+        Ok(parser.filter_map(|res| match res {
+            Ok(frame) => frame.into_entity_frame().map(Ok),
+            Err(e) => Some(Err(e)),
+        }))
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The next entity frame of a run, ranked for a k-way merge.
+struct Head {
+    entity: EntityFrame,
+    run: usize,
+}
+
+impl PartialEq for Head {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Head {}
+
+impl PartialOrd for Head {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Head {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // NB: reversed, since `BinaryHeap` is a max-heap.
+        cmp_entities(&other.entity, &self.entity).then_with(|| other.run.cmp(&self.run))
+    }
+}
+
+/// An iterator merging sorted runs into a single sorted sequence.
+struct Merge<I> {
+    iters: Vec<I>,
+    heap: BinaryHeap<Head>,
+}
+
+impl<I: Iterator<Item = Result<EntityFrame>>> Merge<I> {
+    fn new(iters: Vec<I>) -> Result<Self> {
+        let mut merge = Merge {
+            heap: BinaryHeap::with_capacity(iters.len()),
+            iters,
+        };
+        for run in 0..merge.iters.len() {
+            merge.advance(run)?;
+        }
+        Ok(merge)
+    }
+
+    fn advance(&mut self, run: usize) -> Result<()> {
+        if let Some(entity) = self.iters[run].next().transpose()? {
+            self.heap.push(Head { entity, run });
+        }
+        Ok(())
+    }
+}
+
+impl<I: Iterator<Item = Result<EntityFrame>>> Iterator for Merge<I> {
+    type Item = Result<EntityFrame>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let Head { entity, run } = self.heap.pop()?;
+        match self.advance(run) {
+            Ok(()) => Some(Ok(entity)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// A buffer sorting entity frames in bounded memory.
+#[derive(Debug)]
+struct Sorter {
+    capacity: usize,
+    fan_in: usize,
+    dir: PathBuf,
+    buffer: Vec<EntityFrame>,
+    runs: Vec<Run>,
+    spill_dir: Option<SpillDir>,
+}
+
+impl Sorter {
+    fn push(&mut self, entity: EntityFrame) -> Result<()> {
+        self.buffer.push(entity);
+        if self.buffer.len() >= self.capacity {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill_dir(&mut self) -> Result<&mut SpillDir> {
+        if self.spill_dir.is_none() {
+            self.spill_dir = Some(SpillDir::create(&self.dir)?);
+        }
+        Ok(self.spill_dir.as_mut().unwrap())
+    }
+
+    fn spill(&mut self) -> Result<()> {
+        self.buffer.sort_by(cmp_entities);
+        let entities = std::mem::take(&mut self.buffer);
+        let run = self.spill_dir()?.run(entities.into_iter().map(Ok))?;
+        self.runs.push(run);
+        Ok(())
+    }
+
+    /// Merge the given runs into an iterator over their entity frames.
+    fn merge(runs: &[Run]) -> Result<Merge<impl Iterator<Item = Result<EntityFrame>>>> {
+        Merge::new(runs.iter().map(Run::open).collect::<Result<Vec<_>>>()?)
+    }
+
+    /// Write all the buffered entity frames in order.
+    fn drain<W: Write>(&mut self, serializer: &mut Serializer<W>) -> Result<()> {
+        if self.runs.is_empty() {
+            self.buffer.sort_by(cmp_entities);
+            for entity in self.buffer.drain(..) {
+                serializer.write_entity(&entity)?;
+            }
+            return Ok(());
+        }
+
+        if !self.buffer.is_empty() {
+            self.spill()?;
+        }
+
+        // NB: merge the oldest runs together until few enough files are
+        //     left to be opened at once.
+        while self.runs.len() > self.fan_in {
+            let n = self.fan_in.min(self.runs.len() - self.fan_in + 1);
+            let runs: Vec<Run> = self.runs.drain(..n).collect();
+            let merged = Self::merge(&runs)?;
+            let run = self.spill_dir()?.run(merged)?;
+            self.runs.push(run);
+        }
+
+        for entity in Self::merge(&self.runs)? {
+            serializer.write_entity(&entity?)?;
+        }
+
+        self.runs.clear();
+        self.spill_dir = None;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A streaming writer serializing OBO frames as they are produced.
+///
+/// The header frame must be written first, followed by any number of
+/// entity frames. When the [`WriterOptions`] enable sorting, entity frames
+/// are buffered and written in serialization order when the writer is
+/// finished: at most [`capacity`] frames are kept in memory, and sorted
+/// runs are spilled to temporary files which are merged at the end.
+///
+/// Since frames are written independently, name comments can only refer
+/// to the entities written so far, unless sorting is enabled, in which
+/// case the names of all entities are known before any frame is written.
+/// Names of entities declared in other documents can be added with
+/// [`names_mut`].
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::parser::*;
+/// # use fastobo::writer::*;
+/// let txt = "ontology: tst\n\n[Term]\nid: TST:002\n\n[Term]\nid: TST:001\n";
+/// let parser = DefaultParser::new(std::io::Cursor::new(txt));
+///
+/// let options = WriterOptions::new().with_sorting(true);
+/// let mut writer = FrameWriter::new(Vec::new(), options);
+/// for frame in parser {
+///     writer.write_frame(frame.unwrap()).unwrap();
+/// }
+///
+/// let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+/// assert_eq!(out, "ontology: tst\n\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\n");
+/// ```
+///
+/// [`WriterOptions`]: ./struct.WriterOptions.html
+/// [`capacity`]: #method.with_capacity
+/// [`names_mut`]: #method.names_mut
+#[derive(Debug)]
+pub struct FrameWriter<W: Write> {
+    serializer: Serializer<W>,
+    header: bool,
+    sorter: Option<Sorter>,
+}
+
+impl<W: Write> FrameWriter<W> {
+    /// The default number of entity frames buffered in memory when sorting.
+    pub const DEFAULT_CAPACITY: usize = 100_000;
+
+    /// Create a new frame writer with the given options.
+    pub fn new(writer: W, options: WriterOptions) -> Self {
+        let sorter = if options.sorting() {
+            Some(Sorter {
+                capacity: Self::DEFAULT_CAPACITY,
+                fan_in: MAX_FAN_IN,
+                dir: std::env::temp_dir(),
+                buffer: Vec::new(),
+                runs: Vec::new(),
+                spill_dir: None,
+            })
+        } else {
+            None
+        };
+        Self {
+            serializer: Serializer::with_options(writer, options),
+            header: false,
+            sorter,
+        }
+    }
+
+    /// Set the maximum number of entity frames buffered in memory when sorting.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        if let Some(sorter) = self.sorter.as_mut() {
+            sorter.capacity = capacity.max(1);
+        }
+        self
+    }
+
+    /// Set the directory where to spill sorted runs of entity frames.
+    ///
+    /// Runs are written to a private subdirectory created on demand, and
+    /// removed when the writer is finished or dropped. By default, the
+    /// temporary directory of the system is used.
+    pub fn with_temp_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        if let Some(sorter) = self.sorter.as_mut() {
+            sorter.dir = dir.into();
+        }
+        self
+    }
+
+    /// Get a reference to the options of the writer.
+    pub fn options(&self) -> &WriterOptions {
+        self.serializer.options()
+    }
+
+    /// Get a mutable reference to the names used to comment references.
+    pub fn names_mut(&mut self) -> &mut NameCommenter {
+        self.serializer.names_mut()
+    }
+
+    /// Write the header frame.
+    ///
+    /// # Errors
+    /// Returns `WriterError::UnexpectedHeader` if any frame was already written.
+    pub fn write_header(&mut self, header: &HeaderFrame) -> Result<()> {
+        if self.header {
+            return Err(Error::from(WriterError::UnexpectedHeader));
+        }
+        self.header = true;
+        self.serializer.write_header(header)
+    }
+
+    /// Write an entity frame, or buffer it if sorting is enabled.
+    ///
+    /// # Errors
+    /// Returns `WriterError::MissingHeader` if the header frame was not
+    /// written yet.
+    pub fn write_entity(&mut self, entity: EntityFrame) -> Result<()> {
+        if !self.header {
+            return Err(Error::from(WriterError::MissingHeader));
+        }
+        if self.serializer.options().name_comments() {
+            self.serializer.names_mut().add_entity(&entity);
+        }
+        match self.sorter.as_mut() {
+            Some(sorter) => sorter.push(entity),
+            None => self.serializer.write_entity(&entity),
+        }
+    }
+
+    /// Write a frame, dispatching on its kind.
+    pub fn write_frame(&mut self, frame: Frame) -> Result<()> {
+        match frame {
+            Frame::Header(header) => self.write_header(&header),
+            Frame::Term(term) => self.write_entity(EntityFrame::Term(term)),
+            Frame::Typedef(typedef) => self.write_entity(EntityFrame::Typedef(typedef)),
+            Frame::Instance(instance) => self.write_entity(EntityFrame::Instance(instance)),
+        }
+    }
+
+    /// Write any buffered entity frame and return the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        if let Some(sorter) = self.sorter.as_mut() {
+            sorter.drain(&mut self.serializer)?;
+        }
+        let mut writer = self.serializer.into_inner();
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use textwrap_macros::dedent;
+
+    fn document() -> OboDoc {
+        OboDoc::from_str(dedent!(
+            r#"
+            ontology: tst
+
+            [Term]
+            id: TST:003
+            is_a: TST:001
+
+            [Typedef]
+            id: part_of
+            name: part of
+
+            [Term]
+            id: TST:001
+            name: first
+
+            [Instance]
+            id: TST:005
+            instance_of: TST:003
+
+            [Term]
+            id: TST:004
+            relationship: part_of TST:003
+
+            [Term]
+            id: TST:002
+            "#
+        ))
+        .unwrap()
+    }
+
+    fn stream(doc: &OboDoc, writer: &mut FrameWriter<Vec<u8>>) {
+        writer.write_header(doc.header()).unwrap();
+        for entity in doc.entities() {
+            writer.write_entity(entity.clone()).unwrap();
+        }
+    }
+
+    #[test]
+    fn unsorted() {
+        let doc = document();
+        let mut writer = FrameWriter::new(Vec::new(), WriterOptions::new());
+        stream(&doc, &mut writer);
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(out, doc.to_string());
+    }
+
+    #[test]
+    fn sorted_in_memory() {
+        let doc = document();
+        let options = WriterOptions::canonical();
+        let mut writer = FrameWriter::new(Vec::new(), options.clone());
+        stream(&doc, &mut writer);
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        let mut expected = Vec::new();
+        super::super::to_writer(&mut expected, &doc, &options).unwrap();
+        assert_eq!(out, String::from_utf8(expected).unwrap());
+    }
+
+    #[test]
+    fn sorted_spilled() {
+        let dir = std::env::temp_dir().join(format!("fastobo-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let doc = document();
+        let options = WriterOptions::canonical();
+        let mut writer = FrameWriter::new(Vec::new(), options.clone())
+            .with_capacity(2)
+            .with_temp_dir(&dir);
+        stream(&doc, &mut writer);
+        let spill_dirs: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(spill_dirs.len(), 1);
+        let spill_dir = spill_dirs[0].as_ref().unwrap().path();
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 3);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = std::fs::metadata(&spill_dir).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o700);
+        }
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        std::fs::remove_dir(&dir).unwrap();
+
+        let mut expected = Vec::new();
+        super::super::to_writer(&mut expected, &doc, &options).unwrap();
+        assert_eq!(out, String::from_utf8(expected).unwrap());
+    }
+
+    #[test]
+    fn sorted_bounded_fan_in() {
+        let doc = document();
+        let options = WriterOptions::canonical();
+        let mut writer = FrameWriter::new(Vec::new(), options.clone()).with_capacity(1);
+        writer.sorter.as_mut().unwrap().fan_in = 2;
+        stream(&doc, &mut writer);
+        assert_eq!(
+            writer.sorter.as_ref().unwrap().runs.len(),
+            doc.entities().len()
+        );
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        let mut expected = Vec::new();
+        super::super::to_writer(&mut expected, &doc, &options).unwrap();
+        assert_eq!(out, String::from_utf8(expected).unwrap());
+    }
+
+    #[test]
+    fn ordering_errors() {
+        let doc = document();
+
+        let mut writer = FrameWriter::new(Vec::new(), WriterOptions::new());
+        let err = writer.write_entity(doc.entities()[0].clone()).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::WriterError {
+                error: WriterError::MissingHeader
+            }
+        ));
+
+        writer.write_header(doc.header()).unwrap();
+        let err = writer.write_header(doc.header()).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::WriterError {
+                error: WriterError::UnexpectedHeader
+            }
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+//! Configurable serialization of OBO documents.
+//!
+//! The `Display` implementations of the syntax tree always write OBO
+//! documents in the same style. This module provides a [`Serializer`]
+//! whose output can be controlled with [`WriterOptions`], e.g. to drop
+//! line qualifiers and comments, or to follow the serializer conventions
+//! of the OBO 1.4 specification. The [`FrameWriter`] uses the same options
+//! to write documents frame by frame, without loading them in memory.
+//!
+//! # See also
+//! - The [Serializer conventions](https://owlcollab.github.io/oboformat/doc/GO.format.obo-1_4.html#S.3.5)
+//!   section of the OBO 1.4 syntax and semantics specification.
+//!
+//! [`Serializer`]: ./struct.Serializer.html
+//! [`WriterOptions`]: ./struct.WriterOptions.html
+//! [`FrameWriter`]: ./struct.FrameWriter.html
+
+mod frames;
+mod names;
+mod options;
+mod serializer;
+
+use std::io::Write;
+
+use crate::ast::OboDoc;
+use crate::error::Result;
+
+pub use self::frames::FrameWriter;
+pub use self::names::NameCommenter;
+pub use self::options::BlankLines;
+pub use self::options::EscapeStyle;
+pub use self::options::WriterOptions;
+pub use self::serializer::Serializer;
+
+/// Write an OBO document to a `Write` implementor with the given options.
+pub fn to_writer<W: Write>(writer: W, doc: &OboDoc, options: &WriterOptions) -> Result<()> {
+    Serializer::with_options(writer, options.clone()).write_doc(doc)
+}
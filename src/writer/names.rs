@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::ast::*;
+use crate::semantics::Identified;
+use crate::semantics::OboClause;
+use crate::visit::visit_mut;
+use crate::visit::VisitMut;
+
+/// A clause of an entity frame, as seen by the serializer.
+pub(crate) trait EntityClause: Display + Ord + OboClause {
+    /// Get the unquoted string value of the clause, if any.
+    fn unquoted(&self) -> Option<&UnquotedString>;
+
+    /// Get the identifier of the entity referenced by the clause, if any.
+    fn target(&self) -> Option<&Ident>;
+}
+
+impl EntityClause for TermClause {
+    fn unquoted(&self) -> Option<&UnquotedString> {
+        match self {
+            TermClause::Name(s) | TermClause::Comment(s) | TermClause::CreatedBy(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn target(&self) -> Option<&Ident> {
+        match self {
+            TermClause::IsA(id)
+            | TermClause::IntersectionOf(_, id)
+            | TermClause::UnionOf(id)
+            | TermClause::EquivalentTo(id)
+            | TermClause::DisjointFrom(id)
+            | TermClause::Relationship(_, id)
+            | TermClause::ReplacedBy(id)
+            | TermClause::Consider(id) => Some(id.as_ref().as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl EntityClause for TypedefClause {
+    fn unquoted(&self) -> Option<&UnquotedString> {
+        match self {
+            TypedefClause::Name(s) | TypedefClause::Comment(s) | TypedefClause::CreatedBy(s) => {
+                Some(s)
+            }
+            _ => None,
+        }
+    }
+
+    fn target(&self) -> Option<&Ident> {
+        match self {
+            TypedefClause::Domain(id) | TypedefClause::Range(id) => Some(id.as_ref().as_ref()),
+            TypedefClause::IsA(id)
+            | TypedefClause::IntersectionOf(id)
+            | TypedefClause::UnionOf(id)
+            | TypedefClause::EquivalentTo(id)
+            | TypedefClause::DisjointFrom(id)
+            | TypedefClause::InverseOf(id)
+            | TypedefClause::TransitiveOver(id)
+            | TypedefClause::DisjointOver(id)
+            | TypedefClause::Relationship(_, id)
+            | TypedefClause::ReplacedBy(id) => Some(id.as_ref().as_ref()),
+            TypedefClause::Consider(id) => Some(id.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl EntityClause for InstanceClause {
+    fn unquoted(&self) -> Option<&UnquotedString> {
+        match self {
+            InstanceClause::Name(s) | InstanceClause::Comment(s) | InstanceClause::CreatedBy(s) => {
+                Some(s)
+            }
+            _ => None,
+        }
+    }
+
+    fn target(&self) -> Option<&Ident> {
+        match self {
+            InstanceClause::InstanceOf(id) => Some(id.as_ref().as_ref()),
+            InstanceClause::ReplacedBy(id) => Some(id.as_ref().as_ref()),
+            InstanceClause::Relationship(_, id) | InstanceClause::Consider(id) => Some(id.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A visitor writing the names of referenced entities as line comments.
+///
+/// Files produced by the OBO 1.4 reference serializer annotate clauses
+/// referencing other entities with the name of the target, such as in
+/// `is_a: GO:0008150 ! biological_process`. This visitor writes or
+/// refreshes these comments, so that the serialized document can be
+/// compared with the output of other tools.
+///
+/// Names are collected from the visited document, and can also be collected
+/// from other documents, such as imports, with the `and_doc` method.
+/// Comments of lines referencing an entity without a known name are left
+/// untouched.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::visit::VisitMut;
+/// # use fastobo::writer::NameCommenter;
+/// let import = fastobo::from_str("[Term]\nid: GO:0008150\nname: biological_process\n").unwrap();
+/// let mut doc = fastobo::from_str("[Term]\nid: GO:0000001\nis_a: GO:0008150 ! old\n").unwrap();
+///
+/// NameCommenter::new().and_doc(&import).visit_doc(&mut doc);
+/// assert_eq!(
+///     doc.to_string(),
+///     "[Term]\nid: GO:0000001\nis_a: GO:0008150 ! biological_process\n"
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NameCommenter {
+    names: HashMap<Ident, UnquotedString>,
+}
+
+impl NameCommenter {
+    /// Create a new commenter without any known entity name.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the names of the entities declared in the given document.
+    pub fn and_doc(mut self, doc: &OboDoc) -> Self {
+        self.add_doc(doc);
+        self
+    }
+
+    /// Collect the names of the entities declared in the given document.
+    ///
+    /// Names already known are overwritten by the ones in `doc`.
+    pub fn add_doc(&mut self, doc: &OboDoc) {
+        doc.entities()
+            .iter()
+            .for_each(|entity| self.add_entity(entity))
+    }
+
+    /// Collect the name of the given entity frame, if it declares one.
+    pub fn add_entity(&mut self, entity: &EntityFrame) {
+        if let Ok(name) = entity.name() {
+            self.names.insert(entity.as_id().clone(), name.clone());
+        }
+    }
+
+    /// Get the name of the entity with the given identifier, if known.
+    pub fn name(&self, id: &Ident) -> Option<&UnquotedString> {
+        self.names.get(id)
+    }
+
+    /// Get the comment to write after a clause, if it references a known entity.
+    pub(crate) fn comment<C: EntityClause>(&self, clause: &C) -> Option<Comment> {
+        clause
+            .target()
+            .and_then(|id| self.name(id))
+            // NB: comments span until the end of the line, so line breaks
+            //     in the name must not be written as-is.
+            .map(|name| Comment::new(name.as_str().replace('\n', " ")))
+    }
+
+    fn comment_lines<C: EntityClause>(&self, lines: &mut [Line<C>]) {
+        for line in lines {
+            if let Some(comment) = self.comment(line.as_inner()) {
+                line.set_comment(comment);
+            }
+        }
+    }
+}
+
+impl VisitMut for NameCommenter {
+    fn visit_doc(&mut self, doc: &mut OboDoc) {
+        self.add_doc(doc);
+        visit_mut::visit_doc(self, doc)
+    }
+
+    fn visit_instance_frame(&mut self, frame: &mut InstanceFrame) {
+        self.comment_lines(frame.clauses_mut())
+    }
+
+    fn visit_term_frame(&mut self, frame: &mut TermFrame) {
+        self.comment_lines(frame.clauses_mut())
+    }
+
+    fn visit_typedef_frame(&mut self, frame: &mut TypedefFrame) {
+        self.comment_lines(frame.clauses_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use textwrap_macros::dedent;
+
+    #[test]
+    fn visit_doc() {
+        let mut doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            name: first
+
+            [Term]
+            id: TST:002
+            is_a: TST:001 ! outdated
+            relationship: part_of TST:001 {source="ISBN:1234"}
+            is_a: TST:003 ! unknown
+
+            [Typedef]
+            id: part_of
+            name: part of
+            inverse_of: has_part
+
+            [Instance]
+            id: TST:004
+            instance_of: TST:002
+            "#
+        ))
+        .unwrap();
+
+        NameCommenter::new().visit_doc(&mut doc);
+
+        let expected = dedent!(
+            r#"
+            [Term]
+            id: TST:001
+            name: first
+
+            [Term]
+            id: TST:002
+            is_a: TST:001 ! first
+            relationship: part_of TST:001 {source="ISBN:1234"} ! first
+            is_a: TST:003 ! unknown
+
+            [Typedef]
+            id: part_of
+            name: part of
+            inverse_of: has_part
+
+            [Instance]
+            id: TST:004
+            instance_of: TST:002
+            "#
+        );
+        assert_eq!(doc.to_string(), &expected[1..]);
+    }
+
+    #[test]
+    fn imports() {
+        let import = OboDoc::from_str("[Typedef]\nid: has_part\nname: has part\n").unwrap();
+        let mut doc = OboDoc::from_str("[Typedef]\nid: part_of\ninverse_of: has_part\n").unwrap();
+
+        NameCommenter::new().and_doc(&import).visit_doc(&mut doc);
+        assert_eq!(
+            doc.to_string(),
+            "[Typedef]\nid: part_of\ninverse_of: has_part ! has part\n"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+/// The policy used to separate frames with blank lines.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BlankLines {
+    /// Do not write any blank line between frames.
+    None,
+    /// Write a blank line between two consecutive frames.
+    ///
+    /// This is the style used by the `Display` implementation of `OboDoc`.
+    Between,
+    /// Write a blank line after every frame, including the last one.
+    ///
+    /// This is the style used by the OBO 1.4 reference serializer.
+    After,
+}
+
+/// The escaping style used to write `UnquotedString` values.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EscapeStyle {
+    /// Escape every character with a special meaning in OBO syntax.
+    ///
+    /// This is the style used by the `Display` implementation of
+    /// `UnquotedString`, which escapes line breaks, backslashes, double
+    /// quotes, exclamation marks and curly braces.
+    Full,
+    /// Only escape the characters that would otherwise not be parsed back
+    /// as part of the string: line breaks, backslashes, exclamation marks
+    /// and opening curly braces.
+    Minimal,
+}
+
+/// The options controlling how a [`Serializer`] writes OBO documents.
+///
+/// The default options reproduce the output of the `Display` implementation
+/// of [`OboDoc`], while [`WriterOptions::canonical`] follows the OBO 1.4
+/// serializer conventions.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::writer::*;
+/// let options = WriterOptions::new()
+///     .with_blank_lines(BlankLines::None)
+///     .with_comments(false);
+/// assert_eq!(options.blank_lines(), BlankLines::None);
+/// assert!(!options.comments());
+/// ```
+///
+/// [`Serializer`]: ./struct.Serializer.html
+/// [`OboDoc`]: ../ast/struct.OboDoc.html
+/// [`WriterOptions::canonical`]: #method.canonical
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct WriterOptions {
+    blank_lines: BlankLines,
+    name_comments: bool,
+    qualifiers: bool,
+    comments: bool,
+    escape: EscapeStyle,
+    sort: bool,
+}
+
+impl WriterOptions {
+    /// Create new options reproducing the `Display` output of a document.
+    pub fn new() -> Self {
+        Self {
+            blank_lines: BlankLines::Between,
+            name_comments: false,
+            qualifiers: true,
+            comments: true,
+            escape: EscapeStyle::Full,
+            sort: false,
+        }
+    }
+
+    /// Create new options following the OBO 1.4 serializer conventions.
+    ///
+    /// In canonical mode, the header clauses are sorted, followed by the
+    /// `[Term]`, `[Typedef]` and `[Instance]` frames each sorted by
+    /// identifier, with their clauses and qualifiers in serialization
+    /// order. Every frame is followed by a blank line, and source comments
+    /// are replaced by the names of the referenced entities.
+    pub fn canonical() -> Self {
+        Self {
+            blank_lines: BlankLines::After,
+            name_comments: true,
+            qualifiers: true,
+            comments: false,
+            escape: EscapeStyle::Minimal,
+            sort: true,
+        }
+    }
+
+    /// Set the blank-line policy used to separate frames.
+    pub fn with_blank_lines(mut self, blank_lines: BlankLines) -> Self {
+        self.blank_lines = blank_lines;
+        self
+    }
+
+    /// Set whether to write the names of referenced entities as comments.
+    ///
+    /// When enabled, clauses referencing an entity declared with a `name`
+    /// in the serialized document are written with a trailing comment,
+    /// e.g. `is_a: GO:0008150 ! biological_process`, overriding the
+    /// comment of the source line if any.
+    pub fn with_name_comments(mut self, name_comments: bool) -> Self {
+        self.name_comments = name_comments;
+        self
+    }
+
+    /// Set whether to write the qualifiers of each line.
+    pub fn with_qualifiers(mut self, qualifiers: bool) -> Self {
+        self.qualifiers = qualifiers;
+        self
+    }
+
+    /// Set whether to write the comments of each line.
+    pub fn with_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    /// Set the escaping style used to write `UnquotedString` values.
+    pub fn with_escape(mut self, escape: EscapeStyle) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// Set whether to write frames, clauses and qualifiers in
+    /// serialization order rather than in document order.
+    pub fn with_sorting(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Get the blank-line policy used to separate frames.
+    pub fn blank_lines(&self) -> BlankLines {
+        self.blank_lines
+    }
+
+    /// Check whether the names of referenced entities are written as comments.
+    pub fn name_comments(&self) -> bool {
+        self.name_comments
+    }
+
+    /// Check whether line qualifiers are written.
+    pub fn qualifiers(&self) -> bool {
+        self.qualifiers
+    }
+
+    /// Check whether line comments are written.
+    pub fn comments(&self) -> bool {
+        self.comments
+    }
+
+    /// Get the escaping style used to write `UnquotedString` values.
+    pub fn escape(&self) -> EscapeStyle {
+        self.escape
+    }
+
+    /// Check whether frames and clauses are written in serialization order.
+    pub fn sorting(&self) -> bool {
+        self.sort
+    }
+}
+
+impl Default for WriterOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,440 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::io::Write;
+
+use crate::ast::*;
+use crate::error::Result;
+use crate::semantics::cmp_entities;
+use crate::semantics::OboClause;
+
+use super::names::EntityClause;
+use super::names::NameCommenter;
+use crate::semantics::Orderable;
+
+use super::options::BlankLines;
+use super::options::EscapeStyle;
+use super::options::WriterOptions;
+
+// ---------------------------------------------------------------------------
+
+/// Escape an unquoted string following the given escaping style.
+fn escape(s: &str, style: EscapeStyle) -> Cow<'_, str> {
+    match style {
+        EscapeStyle::Full => Cow::Owned(UnquotedString::new(s).to_string()),
+        EscapeStyle::Minimal if !s.contains(&['\r', '\n', '\\', '!', '{'][..]) => Cow::Borrowed(s),
+        EscapeStyle::Minimal => {
+            let mut escaped = String::with_capacity(s.len() + 1);
+            for c in s.chars() {
+                match c {
+                    '\r' => escaped.push_str("\\r"),
+                    '\n' => escaped.push_str("\\n"),
+                    '\\' => escaped.push_str("\\\\"),
+                    '!' => escaped.push_str("\\!"),
+                    '{' => escaped.push_str("\\{"),
+                    _ => escaped.push(c),
+                }
+            }
+            Cow::Owned(escaped)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A configurable serializer for OBO documents.
+///
+/// Unlike the `Display` implementation of `OboDoc`, which always writes
+/// documents the same way, a `Serializer` follows the
+/// [`WriterOptions`](./struct.WriterOptions.html) it was created with.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::writer::*;
+/// let doc = fastobo::from_str(
+///     "[Term]\nid: TST:002\nis_a: TST:001\n\n[Term]\nid: TST:001\nname: test\n"
+/// ).unwrap();
+///
+/// let mut ser = Serializer::with_options(Vec::new(), WriterOptions::canonical());
+/// ser.write_doc(&doc).unwrap();
+/// assert_eq!(
+///     String::from_utf8(ser.into_inner()).unwrap(),
+///     "[Term]\nid: TST:001\nname: test\n\n[Term]\nid: TST:002\nis_a: TST:001 ! test\n\n"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Serializer<W: Write> {
+    writer: W,
+    options: WriterOptions,
+    names: NameCommenter,
+    frames: usize,
+}
+
+impl<W: Write> Serializer<W> {
+    /// Create a new serializer with the default options.
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, WriterOptions::new())
+    }
+
+    /// Create a new serializer with the given options.
+    pub fn with_options(writer: W, options: WriterOptions) -> Self {
+        Self {
+            writer,
+            options,
+            names: NameCommenter::new(),
+            frames: 0,
+        }
+    }
+
+    /// Get a reference to the options of the serializer.
+    pub fn options(&self) -> &WriterOptions {
+        &self.options
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Get a mutable reference to the names used to comment references.
+    ///
+    /// Use this to record the names declared in imported documents, so that
+    /// references to imported entities are commented as well.
+    pub fn names_mut(&mut self) -> &mut NameCommenter {
+        &mut self.names
+    }
+
+    /// Consume the serializer and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write a complete OBO document.
+    ///
+    /// When name comments are enabled, the names declared in the document
+    /// are recorded before any frame is written, so that forward references
+    /// are commented as well.
+    pub fn write_doc(&mut self, doc: &OboDoc) -> Result<()> {
+        if self.options.name_comments() {
+            self.names.add_doc(doc);
+        }
+
+        self.write_header(doc.header())?;
+        if self.options.sorting() {
+            let mut entities: Vec<&EntityFrame> = doc.entities().iter().collect();
+            entities.sort_by(|x, y| cmp_entities(x, y));
+            entities.into_iter().try_for_each(|e| self.write_entity(e))
+        } else {
+            doc.entities().iter().try_for_each(|e| self.write_entity(e))
+        }
+    }
+
+    /// Write a header frame.
+    ///
+    /// Nothing is written if the header frame is empty.
+    pub fn write_header(&mut self, header: &HeaderFrame) -> Result<()> {
+        if header.is_empty() {
+            return Ok(());
+        }
+
+        let mut clauses: Vec<&HeaderClause> = header.iter().collect();
+        if self.options.sorting() {
+            // NB: owl-axioms are compared as equal so that the stable sort
+            //     keeps them in source order, since they may depend on the
+            //     prefixes or declarations of a previous clause.
+            clauses.sort_by(|x, y| match (x, y) {
+                (HeaderClause::OwlAxioms(_), HeaderClause::OwlAxioms(_)) => Ordering::Equal,
+                _ => x.cmp(y),
+            });
+        }
+
+        self.begin_frame()?;
+        for clause in clauses {
+            self.write_header_clause(clause)?;
+        }
+        self.end_frame()
+    }
+
+    /// Write an entity frame.
+    pub fn write_entity(&mut self, entity: &EntityFrame) -> Result<()> {
+        if self.options.sorting() && !entity.is_sorted() {
+            let mut sorted = entity.clone();
+            sorted.sort();
+            return self.write_entity_unchecked(&sorted);
+        }
+        self.write_entity_unchecked(entity)
+    }
+
+    fn write_entity_unchecked(&mut self, entity: &EntityFrame) -> Result<()> {
+        match entity {
+            EntityFrame::Term(frame) => self.write_frame("Term", frame.id(), frame.clauses()),
+            EntityFrame::Typedef(frame) => self.write_frame("Typedef", frame.id(), frame.clauses()),
+            EntityFrame::Instance(frame) => {
+                self.write_frame("Instance", frame.id(), frame.clauses())
+            }
+        }
+    }
+
+    fn begin_frame(&mut self) -> Result<()> {
+        if self.options.blank_lines() == BlankLines::Between && self.frames > 0 {
+            self.writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    fn end_frame(&mut self) -> Result<()> {
+        self.frames += 1;
+        if self.options.blank_lines() == BlankLines::After {
+            self.writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    fn write_header_clause(&mut self, clause: &HeaderClause) -> Result<()> {
+        let style = self.options.escape();
+        match clause {
+            HeaderClause::Unreserved(tag, value) => writeln!(
+                self.writer,
+                "{}: {}",
+                escape(tag.as_str(), style),
+                escape(value.as_str(), style)
+            )?,
+            HeaderClause::FormatVersion(s)
+            | HeaderClause::DataVersion(s)
+            | HeaderClause::SavedBy(s)
+            | HeaderClause::AutoGeneratedBy(s)
+            | HeaderClause::NamespaceIdRule(s)
+            | HeaderClause::Remark(s)
+            | HeaderClause::Ontology(s)
+            | HeaderClause::OwlAxioms(s) => writeln!(
+                self.writer,
+                "{}: {}",
+                clause.tag(),
+                escape(s.as_str(), style)
+            )?,
+            other => writeln!(self.writer, "{}", other)?,
+        }
+        Ok(())
+    }
+
+    fn write_frame<I, C>(&mut self, kind: &str, id: &Line<I>, clauses: &[Line<C>]) -> Result<()>
+    where
+        I: Display,
+        C: EntityClause,
+    {
+        self.begin_frame()?;
+        write!(self.writer, "[{}]\nid: {}", kind, id.as_inner())?;
+        self.write_eol(id.qualifiers(), id.comment(), None)?;
+
+        for line in clauses {
+            let clause = line.as_inner();
+            match clause.unquoted() {
+                Some(s) if self.options.escape() != EscapeStyle::Full => {
+                    let value = escape(s.as_str(), self.options.escape());
+                    write!(self.writer, "{}: {}", clause.tag(), value)?;
+                }
+                _ => write!(self.writer, "{}", clause)?,
+            }
+            let name = Some(clause)
+                .filter(|_| self.options.name_comments())
+                .and_then(|c| self.names.comment(c));
+            self.write_eol(line.qualifiers(), line.comment(), name)?;
+        }
+
+        self.end_frame()
+    }
+
+    fn write_eol(
+        &mut self,
+        qualifiers: Option<&QualifierList>,
+        comment: Option<&Comment>,
+        name: Option<Comment>,
+    ) -> Result<()> {
+        if let Some(qualifiers) = qualifiers.filter(|_| self.options.qualifiers()) {
+            write!(self.writer, " {}", qualifiers)?;
+        }
+
+        if let Some(name) = name {
+            write!(self.writer, " {}", name)?;
+        } else if let Some(comment) = comment.filter(|_| self.options.comments()) {
+            write!(self.writer, " {}", comment)?;
+        }
+
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use textwrap_macros::dedent;
+
+    fn serialize(doc: &OboDoc, options: WriterOptions) -> String {
+        let mut ser = Serializer::with_options(Vec::new(), options);
+        ser.write_doc(doc).unwrap();
+        String::from_utf8(ser.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn default_options() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            format-version: 1.4
+            remark: a \{braced\} "remark"
+
+            [Term]
+            id: TST:001 ! first
+            name: test\! term
+            is_a: TST:002 {source="ISBN:1234"} ! second
+
+            [Typedef]
+            id: part_of
+            "#
+        ))
+        .unwrap();
+        assert_eq!(serialize(&doc, WriterOptions::default()), doc.to_string());
+    }
+
+    #[test]
+    fn sorting_owl_axioms() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            owl-axioms: Prefix(ex:=<http://example.com/>)
+            ontology: tst
+            owl-axioms: Declaration(Class(ex:A))
+            "#
+        ))
+        .unwrap();
+        let options = WriterOptions::default().with_sorting(true);
+        assert_eq!(
+            serialize(&doc, options),
+            dedent!(
+                r#"
+                ontology: tst
+                owl-axioms: Prefix(ex:=<http://example.com/>)
+                owl-axioms: Declaration(Class(ex:A))
+                "#
+            )[1..]
+        );
+    }
+
+    #[test]
+    fn blank_lines() {
+        let doc = OboDoc::from_str("ontology: tst\n\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\n")
+            .unwrap();
+
+        let options = WriterOptions::new().with_blank_lines(BlankLines::None);
+        assert_eq!(
+            serialize(&doc, options),
+            "ontology: tst\n[Term]\nid: TST:001\n[Term]\nid: TST:002\n"
+        );
+
+        let options = WriterOptions::new().with_blank_lines(BlankLines::After);
+        assert_eq!(
+            serialize(&doc, options),
+            "ontology: tst\n\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\n\n"
+        );
+    }
+
+    #[test]
+    fn drop_qualifiers_and_comments() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            [Term]
+            id: TST:001 ! first
+            is_a: TST:002 {source="ISBN:1234"} ! second
+            "#
+        ))
+        .unwrap();
+
+        let options = WriterOptions::new().with_qualifiers(false);
+        assert_eq!(
+            serialize(&doc, options),
+            "[Term]\nid: TST:001 ! first\nis_a: TST:002 ! second\n"
+        );
+
+        let options = WriterOptions::new().with_comments(false);
+        assert_eq!(
+            serialize(&doc, options),
+            "[Term]\nid: TST:001\nis_a: TST:002 {source=\"ISBN:1234\"}\n"
+        );
+    }
+
+    #[test]
+    fn escape_style() {
+        let mut frame = TermFrame::new(ClassIdent::from_str("TST:001").unwrap());
+        let name = UnquotedString::new("a \"quoted\" {name}!");
+        frame.push(Line::from(TermClause::Name(Box::new(name))));
+        let doc = OboDoc::with_entities(vec![frame.into()]);
+
+        let options = WriterOptions::new().with_escape(EscapeStyle::Full);
+        let actual = serialize(&doc, options);
+        assert_eq!(
+            actual,
+            "[Term]\nid: TST:001\nname: a \\\"quoted\\\" \\{name\\}\\!\n"
+        );
+        assert_eq!(OboDoc::from_str(&actual).unwrap(), doc);
+
+        let options = WriterOptions::new().with_escape(EscapeStyle::Minimal);
+        let actual = serialize(&doc, options);
+        assert_eq!(
+            actual,
+            "[Term]\nid: TST:001\nname: a \"quoted\" \\{name}\\!\n"
+        );
+        assert_eq!(OboDoc::from_str(&actual).unwrap(), doc);
+    }
+
+    #[test]
+    fn canonical() {
+        let doc = OboDoc::from_str(dedent!(
+            r#"
+            ontology: tst
+            format-version: 1.4
+
+            [Typedef]
+            id: part_of
+            name: part of
+
+            [Term]
+            id: TST:002
+            relationship: part_of TST:001 {source="b", comment="a"}
+            is_a: TST:003 ! outdated comment
+            name: second
+
+            [Term]
+            id: TST:001 ! not kept
+            name: first
+            "#
+        ))
+        .unwrap();
+
+        let expected = dedent!(
+            r#"
+            format-version: 1.4
+            ontology: tst
+
+            [Term]
+            id: TST:001
+            name: first
+
+            [Term]
+            id: TST:002
+            name: second
+            is_a: TST:003
+            relationship: part_of TST:001 {comment="a", source="b"} ! first
+
+            [Typedef]
+            id: part_of
+            name: part of
+
+            "#
+        );
+        assert_eq!(serialize(&doc, WriterOptions::canonical()), &expected[1..]);
+    }
+}
@@ -3,6 +3,7 @@ extern crate pretty_assertions;
 
 use std::path::PathBuf;
 
+use fastobo::semantics::Orderable;
 use pretty_assertions::assert_eq;
 
 macro_rules! canonicalizetest {
@@ -21,9 +22,9 @@ macro_rules! canonicalizetest {
             let output_path = dir.join(format!("{}.output.obo", stringify!($name)));
 
             let mut doc = fastobo::from_file(&input_path).unwrap();
-            doc.header_mut().sort_unstable();
-
-            println!("{}", doc.header());
+            assert!(!doc.is_sorted());
+            doc.sort();
+            assert!(doc.is_sorted());
 
             let output = std::fs::read_to_string(&output_path).unwrap();
             assert_eq!(doc.to_string(), output);
@@ -31,4 +32,8 @@ macro_rules! canonicalizetest {
     };
 }
 
+canonicalizetest!(document);
 canonicalizetest!(header);
+canonicalizetest!(instance);
+canonicalizetest!(term);
+canonicalizetest!(typedef);